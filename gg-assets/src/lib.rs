@@ -21,10 +21,10 @@ pub use self::assets::Assets;
 pub use self::event::{Event, EventKind, EventReceiver};
 pub use self::handle::{Handle, WeakHandle};
 pub use self::id::Id;
-pub use self::loader::{AssetLoader, BytesAssetLoader, Input, JsonAssetLoader};
+pub use self::loader::{AssetLoader, BytesAssetLoader, Input, JsonAssetLoader, TomlAssetLoader};
 pub use self::loader_ctx::LoaderCtx;
 pub use self::loaders::LoaderRegistry;
-pub use self::source::{DirSource, Source};
+pub use self::source::{DirSource, MemorySource, Source, ZipSource};
 
 pub trait Asset: Send + Sync + 'static {
     fn register_loaders(registry: &mut LoaderRegistry) {
@@ -45,6 +45,18 @@ where
 
 pub struct JsonAssetLoader<A>(PhantomData<fn() -> A>);
 
+impl<A> JsonAssetLoader<A> {
+    pub fn new() -> JsonAssetLoader<A> {
+        JsonAssetLoader(PhantomData)
+    }
+}
+
+impl<A> Default for JsonAssetLoader<A> {
+    fn default() -> JsonAssetLoader<A> {
+        JsonAssetLoader::new()
+    }
+}
+
 #[async_trait]
 impl<A> BytesAssetLoader<A> for JsonAssetLoader<A>
 where
@@ -55,6 +67,30 @@ where
     }
 }
 
+pub struct TomlAssetLoader<A>(PhantomData<fn() -> A>);
+
+impl<A> TomlAssetLoader<A> {
+    pub fn new() -> TomlAssetLoader<A> {
+        TomlAssetLoader(PhantomData)
+    }
+}
+
+impl<A> Default for TomlAssetLoader<A> {
+    fn default() -> TomlAssetLoader<A> {
+        TomlAssetLoader::new()
+    }
+}
+
+#[async_trait]
+impl<A> BytesAssetLoader<A> for TomlAssetLoader<A>
+where
+    A: Asset + DeserializeOwned,
+{
+    async fn load(&self, _ctx: &mut LoaderCtx, data: Vec<u8>) -> Result<A> {
+        Ok(toml::from_slice(&data)?)
+    }
+}
+
 pub trait Input: Send + Sync + 'static {}
 
 impl<T: Send + Sync + 'static> Input for T {}
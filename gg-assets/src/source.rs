@@ -1,15 +1,19 @@
+use std::borrow::Cow;
 use std::fmt::{self, Debug};
 use std::fs::File;
-use std::io::Read;
-use std::path::{Path, PathBuf};
+use std::io::{self, Read, Seek};
+use std::path::{Component, Path, PathBuf};
 use std::sync::mpsc;
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
+use gg_util::ahash::AHashMap;
 use gg_util::eyre::{Result, WrapErr};
 use gg_util::parking_lot::Mutex;
 use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
-use tracing::error;
+use tracing::{error, warn};
+use zip::result::ZipError;
+use zip::ZipArchive;
 
 pub trait Source: Send + Sync + Debug + 'static {
     fn read_bytes(&self, path: &Path) -> Result<Vec<u8>>;
@@ -59,10 +63,19 @@ impl DirSource {
             .name("asset-watcher".into())
             .spawn(move || {
                 for event in rx.iter() {
-                    if let DebouncedEvent::Create(path) | DebouncedEvent::Write(path) = event {
-                        if let Ok(suffix) = path.strip_prefix(&root) {
-                            callback(suffix);
+                    match event {
+                        // editors often save by renaming a temporary file
+                        DebouncedEvent::Create(path)
+                        | DebouncedEvent::Write(path)
+                        | DebouncedEvent::Rename(_, path) => {
+                            if let Ok(suffix) = path.strip_prefix(&root) {
+                                callback(suffix);
+                            }
                         }
+                        DebouncedEvent::Remove(path) => {
+                            warn!(path = %path.display(), "asset removed, keeping old value");
+                        }
+                        _ => {}
                     }
                 }
             })?;
@@ -97,3 +110,122 @@ impl Source for DirSource {
         };
     }
 }
+
+// files kept in memory, e.g. embedded with `include_bytes!`
+#[derive(Debug, Default)]
+pub struct MemorySource {
+    files: AHashMap<PathBuf, Cow<'static, [u8]>>,
+}
+
+impl MemorySource {
+    pub fn new() -> MemorySource {
+        MemorySource::default()
+    }
+
+    pub fn insert(&mut self, path: impl AsRef<Path>, bytes: impl Into<Cow<'static, [u8]>>) {
+        self.files
+            .insert(normalize_path(path.as_ref()), bytes.into());
+    }
+
+    pub fn with(
+        mut self,
+        path: impl AsRef<Path>,
+        bytes: impl Into<Cow<'static, [u8]>>,
+    ) -> MemorySource {
+        self.insert(path, bytes);
+        self
+    }
+}
+
+impl Source for MemorySource {
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        match self.files.get(&normalize_path(path)) {
+            Some(bytes) => Ok(bytes.to_vec()),
+            None => not_found(path),
+        }
+    }
+}
+
+// the entry sizes come from the archive, so they only serve as a hint
+const MAX_PREALLOC: u64 = 16 * 1024 * 1024;
+
+pub struct ZipSource<R> {
+    archive: Mutex<ZipArchive<R>>,
+}
+
+impl<R: Read + Seek> Debug for ZipSource<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZipSource")
+            .field("num_files", &self.archive.lock().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: Read + Seek> ZipSource<R> {
+    pub fn new(reader: R) -> Result<ZipSource<R>> {
+        let archive = ZipArchive::new(reader).wrap_err("invalid zip archive")?;
+        Ok(ZipSource {
+            archive: Mutex::new(archive),
+        })
+    }
+}
+
+impl ZipSource<File> {
+    pub fn open(path: impl AsRef<Path>) -> Result<ZipSource<File>> {
+        let path = path.as_ref();
+        let file = File::open(path).wrap_err_with(|| format!("cannot open {}", path.display()))?;
+        ZipSource::new(file)
+    }
+}
+
+impl<R> Source for ZipSource<R>
+where
+    R: Read + Seek + Send + 'static,
+{
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>> {
+        // entries are always separated with forward slashes
+        let name = normalize_path(path)
+            .components()
+            .map(|v| v.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let mut archive = self.archive.lock();
+        let mut file = match archive.by_name(&name) {
+            Ok(v) => v,
+            Err(ZipError::FileNotFound) => return not_found(path),
+            Err(error) => {
+                return Err(error).wrap_err_with(|| format!("cannot open {}", path.display()))
+            }
+        };
+
+        let capacity = file.size().min(MAX_PREALLOC);
+        let mut buf = Vec::with_capacity(usize::try_from(capacity).unwrap_or(0));
+        file.read_to_end(&mut buf)
+            .wrap_err_with(|| format!("cannot read {}", path.display()))?;
+
+        Ok(buf)
+    }
+}
+
+// resolves `.` and `..` like the filesystem would for `DirSource`
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut res = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(v) => res.push(v),
+            Component::ParentDir => {
+                res.pop();
+            }
+            _ => {}
+        }
+    }
+
+    res
+}
+
+// same error as `DirSource` gives for missing files
+fn not_found<T>(path: &Path) -> Result<T> {
+    Err(io::Error::from(io::ErrorKind::NotFound))
+        .wrap_err_with(|| format!("cannot open {}", path.display()))
+}
@@ -240,7 +240,18 @@ impl Task {
             input_type = ?TypeId::from(input.type_id())
         );
 
-        let (deps, asset) = self.load(shared.clone(), &*input, &loader).await?;
+        let (deps, asset) = match self.load(shared.clone(), &*input, &loader).await {
+            Ok(v) => v,
+            Err(error) => {
+                // the last good value stays, later changes can still reload it
+                if self.reload {
+                    let mut meta_storage = shared.metadata.write();
+                    meta_storage.get_or_insert(&self.handle).loader_input = Some(input);
+                }
+
+                return Err(error);
+            }
+        };
 
         let mut meta_storage = shared.metadata.write();
 
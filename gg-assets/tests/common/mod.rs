@@ -0,0 +1,55 @@
+// each test binary uses only some of the helpers
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use std::{fs, thread};
+
+use gg_assets::{Asset, Assets, BytesAssetLoader, LoaderCtx, LoaderRegistry};
+use gg_util::async_trait;
+use gg_util::eyre::Result;
+
+#[derive(Debug)]
+pub struct Text(pub String);
+
+impl Asset for Text {
+    fn register_loaders(registry: &mut LoaderRegistry) {
+        registry.add(TextLoader);
+    }
+}
+
+pub struct TextLoader;
+
+#[async_trait]
+impl BytesAssetLoader<Text> for TextLoader {
+    async fn load(&self, _ctx: &mut LoaderCtx, bytes: Vec<u8>) -> Result<Text> {
+        Ok(Text(String::from_utf8(bytes)?))
+    }
+}
+
+// an empty directory unique to the test and the process
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gg-assets-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// runs `maintain` until the predicate holds or the time runs out
+pub fn wait_until(
+    assets: &mut Assets,
+    timeout: Duration,
+    mut f: impl FnMut(&Assets) -> bool,
+) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        assets.maintain();
+        if f(assets) {
+            return true;
+        }
+
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    false
+}
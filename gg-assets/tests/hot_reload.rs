@@ -0,0 +1,113 @@
+mod common;
+
+use std::path::Path;
+use std::time::Duration;
+use std::{fs, thread};
+
+use gg_assets::{Assets, DirSource, EventKind, Handle};
+
+use self::common::{temp_dir, wait_until, Text};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn load(assets: &mut Assets, path: &str) -> Handle<Text> {
+    let handle = assets.load::<Text, _>(path);
+    assert!(wait_until(assets, TIMEOUT, |assets| assets.contains(&handle)));
+    handle
+}
+
+// writes the file and waits for the reload, by then the watcher has handled
+// every earlier change
+fn sync_watcher(assets: &mut Assets, dir: &Path, handle: &Handle<Text>, value: &str) {
+    fs::write(dir.join("fence.txt"), value).unwrap();
+    assert!(wait_until(assets, TIMEOUT, |assets| assets[handle].0 == value));
+}
+
+#[test]
+fn hot_reload() {
+    let dir = temp_dir("hot-reload");
+    let file = dir.join("greeting.txt");
+    fs::write(&file, "hello").unwrap();
+
+    let mut assets = Assets::new(DirSource::new(&dir).unwrap());
+    let events = assets.subscribe::<Text>();
+
+    let handle = load(&mut assets, "greeting.txt");
+    assert_eq!(assets[&handle].0, "hello");
+
+    // give the watcher some time to start
+    thread::sleep(Duration::from_millis(200));
+    fs::write(&file, "goodbye").unwrap();
+
+    let reloaded = wait_until(&mut assets, TIMEOUT, |assets| {
+        assets[&handle].0 == "goodbye"
+    });
+    assert!(reloaded);
+    assert!(events.try_iter().any(|v| v.kind == EventKind::Updated));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn removed_and_renamed() {
+    let dir = temp_dir("hot-reload-removed");
+    let file = dir.join("greeting.txt");
+    fs::write(&file, "hello").unwrap();
+    fs::write(dir.join("fence.txt"), "0").unwrap();
+
+    let mut assets = Assets::new(DirSource::new(&dir).unwrap());
+    let events = assets.subscribe::<Text>();
+
+    let handle = load(&mut assets, "greeting.txt");
+    let fence = load(&mut assets, "fence.txt");
+    thread::sleep(Duration::from_millis(200));
+
+    // removed files keep the last loaded value
+    fs::remove_file(&file).unwrap();
+    thread::sleep(Duration::from_millis(100));
+    sync_watcher(&mut assets, &dir, &fence, "1");
+    assert_eq!(assets[&handle].0, "hello");
+    assert!(events
+        .try_iter()
+        .all(|v| v.asset != handle.id() || v.kind != EventKind::Removed));
+
+    // editors often save to a temporary file and rename it over the asset
+    let temp = dir.join(".greeting.txt.tmp");
+    fs::write(&temp, "restored").unwrap();
+    thread::sleep(Duration::from_millis(200));
+    fs::rename(&temp, &file).unwrap();
+
+    let reloaded = wait_until(&mut assets, TIMEOUT, |assets| {
+        assets[&handle].0 == "restored"
+    });
+    assert!(reloaded);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn failed_reload() {
+    let dir = temp_dir("hot-reload-failed");
+    let file = dir.join("greeting.txt");
+    fs::write(&file, "hello").unwrap();
+    fs::write(dir.join("fence.txt"), "0").unwrap();
+
+    let mut assets = Assets::new(DirSource::new(&dir).unwrap());
+
+    let handle = load(&mut assets, "greeting.txt");
+    let fence = load(&mut assets, "fence.txt");
+    thread::sleep(Duration::from_millis(200));
+
+    // invalid utf-8 fails to load and the last good value stays
+    fs::write(&file, [0xff, 0xfe]).unwrap();
+    thread::sleep(Duration::from_millis(100));
+    sync_watcher(&mut assets, &dir, &fence, "1");
+    assert_eq!(assets[&handle].0, "hello");
+
+    // and the asset can still be reloaded after fixing the file
+    fs::write(&file, "fixed").unwrap();
+    let reloaded = wait_until(&mut assets, TIMEOUT, |assets| assets[&handle].0 == "fixed");
+    assert!(reloaded);
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use std::io::{self, Cursor, Write};
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use std::{fs, thread};
+
+use gg_assets::{Assets, DirSource, MemorySource, Source, ZipSource};
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+use self::common::{temp_dir, Text};
+
+fn zip_archive() -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer
+        .start_file("hello.txt", FileOptions::default())
+        .unwrap();
+    writer.write_all(b"hello").unwrap();
+    writer
+        .add_directory("text", FileOptions::default())
+        .unwrap();
+    writer
+        .start_file("text/bye.txt", FileOptions::default())
+        .unwrap();
+    writer.write_all(b"bye").unwrap();
+    writer.finish().unwrap().into_inner()
+}
+
+fn load_text<S: Source>(source: S, path: &str) -> String {
+    let mut assets = Assets::new(source);
+    let handle = assets.load::<Text, _>(path);
+
+    // loaded assets are inserted by `maintain`, so poll it instead of blocking
+    let start = Instant::now();
+    while !assets.contains(&handle) {
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "{} not loaded",
+            path
+        );
+        thread::sleep(Duration::from_millis(1));
+        assets.maintain();
+    }
+
+    assets[&handle].0.clone()
+}
+
+fn assert_not_found(source: &dyn Source, path: &str) {
+    let error = source.read_bytes(Path::new(path)).unwrap_err();
+    assert!(error.to_string().starts_with("cannot open"), "{}", error);
+
+    let io_error = error.root_cause().downcast_ref::<io::Error>().unwrap();
+    assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn memory_source() {
+    let source = MemorySource::new()
+        .with("hello.txt", &b"hello"[..])
+        .with("text/bye.txt", b"bye".to_vec());
+
+    assert_eq!(
+        source.read_bytes(Path::new("./text/../hello.txt")).unwrap(),
+        b"hello"
+    );
+    assert_not_found(&source, "missing.txt");
+    assert_eq!(load_text(source, "text/bye.txt"), "bye");
+}
+
+#[test]
+fn zip_source() {
+    let source = ZipSource::new(Cursor::new(zip_archive())).unwrap();
+
+    assert_eq!(
+        source.read_bytes(Path::new("./text/bye.txt")).unwrap(),
+        b"bye"
+    );
+    assert_not_found(&source, "missing.txt");
+    assert_eq!(load_text(source, "hello.txt"), "hello");
+}
+
+#[test]
+fn dir_source_not_found() {
+    let dir = temp_dir("source");
+
+    let source = DirSource::new(&dir).unwrap();
+    assert_not_found(&source, "missing.txt");
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn dir_source_watch_rename() {
+    let dir = temp_dir("source-rename");
+    let source = DirSource::new(&dir).unwrap();
+
+    let (sender, receiver) = mpsc::channel();
+    source.start_watching(Box::new(move |path| {
+        let _ = sender.send(path.to_owned());
+    }));
+
+    // wait out the debounce delay, so that the rename isn't merged into a create
+    fs::write(dir.join(".hello.txt.tmp"), "hello").unwrap();
+    thread::sleep(Duration::from_millis(200));
+    fs::rename(dir.join(".hello.txt.tmp"), dir.join("hello.txt")).unwrap();
+
+    let start = Instant::now();
+    loop {
+        let timeout = Duration::from_secs(5).saturating_sub(start.elapsed());
+        let path = receiver.recv_timeout(timeout).expect("rename not reported");
+        if path == Path::new("hello.txt") {
+            break;
+        }
+    }
+
+    drop(source);
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,53 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use gg_assets::{Asset, Assets, LoaderRegistry, MemorySource, TomlAssetLoader};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct WindowConfig {
+    title: String,
+    size: [u32; 2],
+    fullscreen: bool,
+    fonts: Vec<String>,
+}
+
+impl Asset for WindowConfig {
+    fn register_loaders(registry: &mut LoaderRegistry) {
+        registry.add(TomlAssetLoader::<WindowConfig>::new());
+    }
+}
+
+#[test]
+fn toml_loader() {
+    let source = MemorySource::new().with(
+        "window.toml",
+        &br#"
+title = "gg"
+size = [1280, 720]
+fullscreen = false
+fonts = ["OpenSans-Regular.ttf", "NotoSans-Regular.ttf"]
+"#[..],
+    );
+
+    let mut assets = Assets::new(source);
+    let handle = assets.load::<WindowConfig, _>("window.toml");
+
+    // loaded assets are inserted by `maintain`, so poll it instead of blocking
+    let start = Instant::now();
+    while !assets.contains(&handle) {
+        assert!(start.elapsed() < Duration::from_secs(5));
+        thread::sleep(Duration::from_millis(1));
+        assets.maintain();
+    }
+
+    assert_eq!(
+        assets[&handle],
+        WindowConfig {
+            title: "gg".into(),
+            size: [1280, 720],
+            fullscreen: false,
+            fonts: vec!["OpenSans-Regular.ttf".into(), "NotoSans-Regular.ttf".into()],
+        }
+    );
+}
@@ -1,25 +1,87 @@
-use criterion::{criterion_group, criterion_main, Criterion};
-use gg_expr::{compile_text, Value, Vm};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use criterion::{criterion_group, Criterion};
+use gg_expr::builtins::builtins;
+use gg_expr::{compile_text, Map, Value, Vm};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+// counts allocations, so that they can be reported next to timings
+struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
 
 fn fib(vm: &mut Vm, func: &Value, arg: i32) -> i32 {
     vm.eval(func, &[&arg.into()]).unwrap().as_int().unwrap()
 }
 
+fn nested_closures(depth: usize) -> String {
+    let mut source = String::new();
+
+    for i in 0..depth {
+        source.push_str(&format!("fn(x{}): len([x{}]) + ", i, i));
+    }
+
+    source.push_str("len([])");
+    source
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     let mut vm = Vm::new();
     let source = "let fib = fn(x): if x < 2 then x else fib(x - 2) + fib(x - 1) in fib";
-    let (func, diags) = compile_text(source);
+    let (func, diags) = compile_text(Map::new(), source);
     assert!(diags.is_empty());
     let func = vm.eval(&func.unwrap(), &[]).unwrap();
     c.bench_function("fib 25", |b| b.iter(|| fib(&mut vm, &func, 25)));
 
     let mut vm = Vm::new();
     let source = "let helper = fn(n, a, b): if n == 0 then a else if n == 1 then b else helper(n - 1, b, a + b), fib = fn(n): helper(n, 0, 1) in fib";
-    let (func, diags) = compile_text(source);
+    let (func, diags) = compile_text(Map::new(), source);
     assert!(diags.is_empty());
     let func = vm.eval(&func.unwrap(), &[]).unwrap();
     c.bench_function("fib 46 (TCO)", |b| b.iter(|| fib(&mut vm, &func, 46)));
+
+    let env = builtins();
+    let source = nested_closures(64);
+    c.bench_function("compile nested closures", |b| {
+        b.iter(|| compile_text(env.clone(), &source))
+    });
+}
+
+// compiling is deterministic, so allocations are counted once instead of
+// being sampled like time
+fn count_allocations() {
+    let env = builtins();
+    let source = nested_closures(64);
+
+    let start = ALLOCATIONS.load(Ordering::Relaxed);
+    let _ = compile_text(env, &source);
+    let count = ALLOCATIONS.load(Ordering::Relaxed) - start;
+    println!("compile nested closures: {} allocations", count);
 }
 
 criterion_group!(benches, criterion_benchmark);
-criterion_main!(benches);
+
+fn main() {
+    count_allocations();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+}
@@ -0,0 +1,140 @@
+use std::cmp::Ordering;
+
+use super::{add_func, any_error};
+use crate::{List, Map, Result, Value, VmContext};
+
+fn to_list<'a>(ctx: &VmContext, idx: usize, value: &'a Value) -> Result<&'a List> {
+    value.as_list().map_err(|e| any_error(ctx, idx, e))
+}
+
+fn pair(a: Value, b: Value) -> Value {
+    List::from_iter([a, b]).into()
+}
+
+fn enumerate(ctx: &VmContext, [l]: &[Value; 1]) -> Result<Value> {
+    let l = to_list(ctx, 0, l)?;
+    let res = l
+        .iter()
+        .enumerate()
+        .map(|(i, v)| pair(Value::from(i as i32), v.clone()))
+        .collect::<List>();
+    Ok(res.into())
+}
+
+fn zip(ctx: &VmContext, [a, b]: &[Value; 2]) -> Result<Value> {
+    let a = to_list(ctx, 0, a)?;
+    let b = to_list(ctx, 1, b)?;
+    let res = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| pair(a.clone(), b.clone()))
+        .collect::<List>();
+    Ok(res.into())
+}
+
+fn to_int(ctx: &VmContext, idx: usize, value: &Value) -> Result<i32> {
+    value.as_int().map_err(|e| any_error(ctx, idx, e))
+}
+
+fn map(ctx: &VmContext, [l, f]: &[Value; 2]) -> Result<Value> {
+    let l = to_list(ctx, 0, l)?;
+    let res = l
+        .iter()
+        .map(|v| ctx.call_nested(f, &[v]))
+        .collect::<Result<List>>()?;
+    Ok(res.into())
+}
+
+fn filter(ctx: &VmContext, [l, f]: &[Value; 2]) -> Result<Value> {
+    let l = to_list(ctx, 0, l)?;
+    let mut res = List::new();
+
+    for v in l {
+        if ctx.call_nested(f, &[v])?.is_truthy() {
+            res.push_back(v.clone());
+        }
+    }
+
+    Ok(res.into())
+}
+
+fn fold(ctx: &VmContext, [l, init, f]: &[Value; 3]) -> Result<Value> {
+    let l = to_list(ctx, 0, l)?;
+    l.iter()
+        .try_fold(init.clone(), |acc, v| ctx.call_nested(f, &[&acc, v]))
+}
+
+fn range(ctx: &VmContext, [a, b]: &[Value; 2]) -> Result<Value> {
+    let a = to_int(ctx, 0, a)?;
+    let b = to_int(ctx, 1, b)?;
+    Ok((a..b).map(Value::from).collect::<List>().into())
+}
+
+fn len(ctx: &VmContext, [l]: &[Value; 1]) -> Result<Value> {
+    let l = to_list(ctx, 0, l)?;
+    Ok(Value::from(l.len() as i32))
+}
+
+fn reverse(ctx: &VmContext, [l]: &[Value; 1]) -> Result<Value> {
+    let l = to_list(ctx, 0, l)?;
+    Ok(l.iter().rev().cloned().collect::<List>().into())
+}
+
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    if let (Ok(a), Ok(b)) = (a.as_int(), b.as_int()) {
+        Some(a.cmp(&b))
+    } else if let (Ok(a), Ok(b)) = (a.as_string(), b.as_string()) {
+        Some(a.cmp(b))
+    } else {
+        let a = a
+            .as_float()
+            .or_else(|_| a.as_int().map(|v| v as f32))
+            .ok()?;
+        let b = b
+            .as_float()
+            .or_else(|_| b.as_int().map(|v| v as f32))
+            .ok()?;
+        Some(a.total_cmp(&b))
+    }
+}
+
+fn sort(ctx: &VmContext, [l]: &[Value; 1]) -> Result<Value> {
+    let l = to_list(ctx, 0, l)?;
+    let mut res = l.iter().cloned().collect::<Vec<_>>();
+    let mut valid = true;
+
+    res.sort_by(|a, b| {
+        compare(a, b).unwrap_or_else(|| {
+            valid = false;
+            Ordering::Equal
+        })
+    });
+
+    if !valid {
+        return Err(any_error(ctx, 0, "list elements are not comparable"));
+    }
+
+    Ok(res.into_iter().collect::<List>().into())
+}
+
+fn contains(ctx: &VmContext, [l, x]: &[Value; 2]) -> Result<Value> {
+    let l = to_list(ctx, 0, l)?;
+    Ok(l.contains(x).into())
+}
+
+pub fn module() -> Value {
+    let mut map = Map::new();
+
+    add_func(&mut map, "list.contains", contains);
+    add_func(&mut map, "list.enumerate", enumerate);
+    add_func(&mut map, "list.filter", filter);
+    add_func(&mut map, "list.fold", fold);
+    add_func(&mut map, "list.len", len);
+    add_func(&mut map, "list.map", self::map);
+    add_func(&mut map, "list.range", range);
+    add_func(&mut map, "list.reverse", reverse);
+    add_func(&mut map, "list.sort", sort);
+    add_func(&mut map, "list.zip", zip);
+
+    map.into()
+}
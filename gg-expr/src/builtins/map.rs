@@ -0,0 +1,64 @@
+use super::{add_func, any_error};
+use crate::{List, Map, Result, Value, VmContext};
+
+fn to_map<'a>(ctx: &VmContext, idx: usize, value: &'a Value) -> Result<&'a Map> {
+    value.as_map().map_err(|e| any_error(ctx, idx, e))
+}
+
+fn to_list<'a>(ctx: &VmContext, idx: usize, value: &'a Value) -> Result<&'a List> {
+    value.as_list().map_err(|e| any_error(ctx, idx, e))
+}
+
+fn merge(a: &Map, b: &Map) -> Map {
+    let mut res = a.clone();
+
+    for (key, b_val) in b {
+        let merged = match (res.get(key).map(Value::as_map), b_val.as_map()) {
+            (Some(Ok(a_map)), Ok(b_map)) => merge(a_map, b_map).into(),
+            _ => b_val.clone(),
+        };
+
+        res.insert(key.clone(), merged);
+    }
+
+    res
+}
+
+fn deep_merge(ctx: &VmContext, [a, b]: &[Value; 2]) -> Result<Value> {
+    let a = to_map(ctx, 0, a)?;
+    let b = to_map(ctx, 1, b)?;
+    Ok(merge(a, b).into())
+}
+
+fn get(value: &Value, key: &Value) -> Option<Value> {
+    if let Ok(map) = value.as_map() {
+        return map.get(key).cloned();
+    }
+
+    let list = value.as_list().ok()?;
+    let idx = usize::try_from(key.as_int().ok()?).ok()?;
+    list.get(idx).cloned()
+}
+
+fn get_path(ctx: &VmContext, [value, path]: &[Value; 2]) -> Result<Value> {
+    let path = to_list(ctx, 1, path)?;
+    let mut cur = value.clone();
+
+    for key in path {
+        cur = match get(&cur, key) {
+            Some(v) => v,
+            None => return Ok(Value::null()),
+        };
+    }
+
+    Ok(cur)
+}
+
+pub fn module() -> Value {
+    let mut map = Map::new();
+
+    add_func(&mut map, "map.deep_merge", deep_merge);
+    add_func(&mut map, "map.get_path", get_path);
+
+    map.into()
+}
@@ -1,20 +1,7 @@
 use std::f32::consts;
-use std::fmt::Display;
 
-use crate::diagnostic::{Severity, SourceComponent};
-use crate::{Error, ExtFunc, Map, Result, Value, VmContext};
-
-fn any_error<E: Display>(ctx: &VmContext, idx: usize, error: E) -> Error {
-    let ranges = ctx.cur_ranges();
-    let call_range = ranges.as_ref().and_then(|v| v.get(0)).copied();
-    let arg_range = ranges.as_ref().and_then(|v| v.get(2 + idx)).copied();
-    let message = format!("{}", error);
-    ctx.error(call_range, message, |diag, source| {
-        if let (Some(source), Some(range)) = (source, arg_range) {
-            diag.add_source(SourceComponent::new(source).with_label(Severity::Error, range, ""));
-        }
-    })
-}
+use super::{add_ext_func, add_func, add_value, any_error};
+use crate::{ExtFunc, Map, Result, Value, VmContext};
 
 fn to_float(ctx: &VmContext, idx: usize, value: &Value) -> Result<f32> {
     value.as_float().map_err(|e| any_error(ctx, idx, e))
@@ -56,92 +43,6 @@ fn abs(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
     Ok(x.abs().into())
 }
 
-fn trunc(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.trunc().into())
-}
-
-fn sin(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.sin().into())
-}
-
-fn cos(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.cos().into())
-}
-
-fn tan(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.tan().into())
-}
-
-fn sinh(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.sinh().into())
-}
-
-fn cosh(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.cosh().into())
-}
-
-fn tanh(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.tanh().into())
-}
-
-fn asin(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.asin().into())
-}
-
-fn acos(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.acos().into())
-}
-
-fn atan(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.atan().into())
-}
-
-fn asinh(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.asinh().into())
-}
-
-fn acosh(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.acosh().into())
-}
-
-fn atanh(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.atanh().into())
-}
-
-fn exp(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.exp().into())
-}
-
-fn ln(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
-    let x = to_float(ctx, 0, x)?;
-    Ok(x.ln().into())
-}
-
-fn add_value(map: &mut Map, name: &str, val: impl Into<Value>) {
-    map.insert(name.into(), val.into());
-}
-
-fn add_func<const N: usize, F>(map: &mut Map, name: &str, func: F)
-where
-    F: Fn(&VmContext, &[Value; N]) -> Result<Value> + 'static,
-{
-    add_value(map, name, ExtFunc::new(func));
-}
-
 pub fn module() -> Value {
     let mut map = Map::new();
 
@@ -150,25 +51,25 @@ pub fn module() -> Value {
     add_value(&mut map, "E", consts::E);
     add_value(&mut map, "EPSILON", f32::EPSILON);
 
-    add_func(&mut map, "floor", floor);
-    add_func(&mut map, "ceil", ceil);
-    add_func(&mut map, "round", round);
-    add_func(&mut map, "abs", abs);
-    add_func(&mut map, "trunc", trunc);
-    add_func(&mut map, "sin", sin);
-    add_func(&mut map, "cos", cos);
-    add_func(&mut map, "tan", tan);
-    add_func(&mut map, "sinh", sinh);
-    add_func(&mut map, "cosh", cosh);
-    add_func(&mut map, "tanh", tanh);
-    add_func(&mut map, "asin", asin);
-    add_func(&mut map, "acos", acos);
-    add_func(&mut map, "atan", atan);
-    add_func(&mut map, "asinh", asinh);
-    add_func(&mut map, "acosh", acosh);
-    add_func(&mut map, "atanh", atanh);
-    add_func(&mut map, "exp", exp);
-    add_func(&mut map, "ln", ln);
+    add_func(&mut map, "math.floor", floor);
+    add_func(&mut map, "math.ceil", ceil);
+    add_func(&mut map, "math.round", round);
+    add_func(&mut map, "math.abs", abs);
+    add_ext_func(&mut map, ExtFunc::new_1("math.trunc", f32::trunc));
+    add_ext_func(&mut map, ExtFunc::new_1("math.sin", f32::sin));
+    add_ext_func(&mut map, ExtFunc::new_1("math.cos", f32::cos));
+    add_ext_func(&mut map, ExtFunc::new_1("math.tan", f32::tan));
+    add_ext_func(&mut map, ExtFunc::new_1("math.sinh", f32::sinh));
+    add_ext_func(&mut map, ExtFunc::new_1("math.cosh", f32::cosh));
+    add_ext_func(&mut map, ExtFunc::new_1("math.tanh", f32::tanh));
+    add_ext_func(&mut map, ExtFunc::new_1("math.asin", f32::asin));
+    add_ext_func(&mut map, ExtFunc::new_1("math.acos", f32::acos));
+    add_ext_func(&mut map, ExtFunc::new_1("math.atan", f32::atan));
+    add_ext_func(&mut map, ExtFunc::new_1("math.asinh", f32::asinh));
+    add_ext_func(&mut map, ExtFunc::new_1("math.acosh", f32::acosh));
+    add_ext_func(&mut map, ExtFunc::new_1("math.atanh", f32::atanh));
+    add_ext_func(&mut map, ExtFunc::new_1("math.exp", f32::exp));
+    add_ext_func(&mut map, ExtFunc::new_1("math.ln", f32::ln));
 
     map.into()
 }
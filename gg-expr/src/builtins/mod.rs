@@ -1,9 +1,102 @@
-use crate::Map;
+use std::fmt::{Display, Write};
 
+use crate::diagnostic::{Severity, SourceComponent};
+use crate::{Error, ExtFunc, Map, Result, Thunk, Type, Value, VmContext};
+
+pub mod list;
+pub mod map;
 pub mod math;
+pub mod os;
+pub mod string;
+pub mod time;
 
 pub fn builtins() -> Map {
     let mut map = Map::new();
+    add_func(&mut map, "debug", debug);
+    add_func(&mut map, "force", force);
+    add_func(&mut map, "lazy", lazy);
+    add_func(&mut map, "print", print);
+    map.insert("list".into(), list::module());
+    map.insert("map".into(), map::module());
     map.insert("math".into(), math::module());
+    map.insert("os".into(), os::module());
+    map.insert("string".into(), string::module());
+    map.insert("time".into(), time::module());
     map
 }
+
+fn lazy(ctx: &VmContext, [func]: &[Value; 1]) -> Result<Value> {
+    let arity = match func.as_ext_func() {
+        Ok(func) => func.arity,
+        Err(_) => func.as_func().map_err(|e| any_error(ctx, 0, e))?.arity,
+    };
+
+    if arity != 0 {
+        let msg = format!(
+            "expected a function with no arguments, found {} arguments",
+            arity
+        );
+        return Err(any_error(ctx, 0, msg));
+    }
+
+    Ok(Thunk::new(func.clone()).into())
+}
+
+fn force(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
+    match x.as_thunk() {
+        Ok(thunk) => thunk.force_eval(ctx).cloned(),
+        Err(_) => Ok(x.clone()),
+    }
+}
+
+fn debug(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
+    ctx.debug_sink().write(&format!("{:?}", x));
+    Ok(x.clone())
+}
+
+fn print(ctx: &VmContext, [x]: &[Value; 1]) -> Result<Value> {
+    let mut message = String::new();
+    write_value(&mut message, x);
+    ctx.debug_sink().write(&message);
+    Ok(x.clone())
+}
+
+pub(crate) fn write_value(res: &mut String, value: &Value) {
+    let _ = match value.ty() {
+        Type::String => write!(res, "{}", value.as_string().unwrap()),
+        Type::Int => write!(res, "{}", value.as_int().unwrap()),
+        Type::Float => write!(res, "{}", value.as_float().unwrap()),
+        _ => write!(res, "{:?}", value),
+    };
+}
+
+pub(crate) fn any_error<E: Display>(ctx: &VmContext, idx: usize, error: E) -> Error {
+    let ranges = ctx.cur_ranges();
+    let call_range = ranges.as_ref().and_then(|v| v.first()).copied();
+    let arg_range = ranges.as_ref().and_then(|v| v.get(2 + idx)).copied();
+    let message = format!("{}", error);
+    ctx.error(call_range, message, |diag, source| {
+        if let (Some(source), Some(range)) = (source, arg_range) {
+            diag.add_source(SourceComponent::new(source).with_label(Severity::Error, range, ""));
+        }
+    })
+}
+
+fn add_value(map: &mut Map, name: &str, val: impl Into<Value>) {
+    map.insert(name.into(), val.into());
+}
+
+// `name` is the qualified name (e.g. `string.len`), which is what serialized
+// functions use to refer to builtins; the last segment becomes the key
+fn add_func<const N: usize, F>(map: &mut Map, name: &str, func: F)
+where
+    F: Fn(&VmContext, &[Value; N]) -> Result<Value> + 'static,
+{
+    add_ext_func(map, ExtFunc::new(func).with_name(name));
+}
+
+fn add_ext_func(map: &mut Map, func: ExtFunc) {
+    let name = func.name.clone().unwrap_or_default();
+    let key = name.rsplit('.').next().unwrap_or(&name);
+    add_value(map, key, func);
+}
@@ -0,0 +1,14 @@
+use std::env::consts;
+
+use super::add_value;
+use crate::{Map, Value};
+
+pub fn module() -> Value {
+    let mut map = Map::new();
+
+    add_value(&mut map, "name", consts::OS);
+    add_value(&mut map, "arch", consts::ARCH);
+    add_value(&mut map, "family", consts::FAMILY);
+
+    map.into()
+}
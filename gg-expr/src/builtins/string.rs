@@ -0,0 +1,149 @@
+use super::{add_func, any_error, write_value};
+use crate::{Error, List, Map, Result, Value, VmContext};
+
+fn to_string<'a>(ctx: &VmContext, idx: usize, value: &'a Value) -> Result<&'a str> {
+    value.as_string().map_err(|e| any_error(ctx, idx, e))
+}
+
+fn to_list<'a>(ctx: &VmContext, idx: usize, value: &'a Value) -> Result<&'a List> {
+    value.as_list().map_err(|e| any_error(ctx, idx, e))
+}
+
+fn format(ctx: &VmContext, [template, args]: &[Value; 2]) -> Result<Value> {
+    let template = to_string(ctx, 0, template)?;
+    let args = to_list(ctx, 1, args)?;
+
+    let template_error = |msg: &str| -> Error { any_error(ctx, 0, msg) };
+
+    let mut res = String::with_capacity(template.len());
+    let mut next_idx = 0;
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                res.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                res.push('}');
+            }
+            '{' => {
+                let mut spec = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => spec.push(c),
+                        None => return Err(template_error("unclosed `{` in format string")),
+                    }
+                }
+
+                let idx = if spec.is_empty() {
+                    next_idx += 1;
+                    next_idx - 1
+                } else {
+                    spec.trim().parse::<usize>().map_err(|_| {
+                        template_error(&format!("invalid placeholder `{{{}}}`", spec))
+                    })?
+                };
+
+                let value = args.get(idx).ok_or_else(|| {
+                    let msg = format!(
+                        "placeholder index {} is out of range for {} arguments",
+                        idx,
+                        args.len()
+                    );
+                    any_error(ctx, 1, msg)
+                })?;
+
+                write_value(&mut res, value);
+            }
+            '}' => return Err(template_error("unmatched `}` in format string")),
+            c => res.push(c),
+        }
+    }
+
+    Ok(res.into())
+}
+
+fn len(ctx: &VmContext, [s]: &[Value; 1]) -> Result<Value> {
+    let s = to_string(ctx, 0, s)?;
+    Ok(Value::from(s.chars().count() as i32))
+}
+
+fn upper(ctx: &VmContext, [s]: &[Value; 1]) -> Result<Value> {
+    Ok(to_string(ctx, 0, s)?.to_uppercase().into())
+}
+
+fn lower(ctx: &VmContext, [s]: &[Value; 1]) -> Result<Value> {
+    Ok(to_string(ctx, 0, s)?.to_lowercase().into())
+}
+
+fn trim(ctx: &VmContext, [s]: &[Value; 1]) -> Result<Value> {
+    Ok(to_string(ctx, 0, s)?.trim().into())
+}
+
+fn split(ctx: &VmContext, [s, sep]: &[Value; 2]) -> Result<Value> {
+    let s = to_string(ctx, 0, s)?;
+    let sep = to_string(ctx, 1, sep)?;
+
+    if sep.is_empty() {
+        return Err(any_error(ctx, 1, "separator must not be empty"));
+    }
+
+    let res = s.split(sep).map(Value::from).collect::<List>();
+    Ok(res.into())
+}
+
+fn replace(ctx: &VmContext, [s, from, to]: &[Value; 3]) -> Result<Value> {
+    let s = to_string(ctx, 0, s)?;
+    let from = to_string(ctx, 1, from)?;
+    let to = to_string(ctx, 2, to)?;
+    Ok(s.replace(from, to).into())
+}
+
+fn contains(ctx: &VmContext, [s, pat]: &[Value; 2]) -> Result<Value> {
+    let s = to_string(ctx, 0, s)?;
+    let pat = to_string(ctx, 1, pat)?;
+    Ok(s.contains(pat).into())
+}
+
+fn starts_with(ctx: &VmContext, [s, pat]: &[Value; 2]) -> Result<Value> {
+    let s = to_string(ctx, 0, s)?;
+    let pat = to_string(ctx, 1, pat)?;
+    Ok(s.starts_with(pat).into())
+}
+
+fn ends_with(ctx: &VmContext, [s, pat]: &[Value; 2]) -> Result<Value> {
+    let s = to_string(ctx, 0, s)?;
+    let pat = to_string(ctx, 1, pat)?;
+    Ok(s.ends_with(pat).into())
+}
+
+fn chars(ctx: &VmContext, [s]: &[Value; 1]) -> Result<Value> {
+    let s = to_string(ctx, 0, s)?;
+    let res = s
+        .chars()
+        .map(|c| Value::from(c.to_string()))
+        .collect::<List>();
+    Ok(res.into())
+}
+
+pub fn module() -> Value {
+    let mut map = Map::new();
+
+    add_func(&mut map, "string.chars", chars);
+    add_func(&mut map, "string.contains", contains);
+    add_func(&mut map, "string.ends_with", ends_with);
+    add_func(&mut map, "string.format", format);
+    add_func(&mut map, "string.len", len);
+    add_func(&mut map, "string.lower", lower);
+    add_func(&mut map, "string.replace", replace);
+    add_func(&mut map, "string.split", split);
+    add_func(&mut map, "string.starts_with", starts_with);
+    add_func(&mut map, "string.trim", trim);
+    add_func(&mut map, "string.upper", upper);
+
+    map.into()
+}
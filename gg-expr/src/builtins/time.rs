@@ -0,0 +1,64 @@
+use std::fmt::Write;
+
+use super::{add_func, any_error};
+use crate::{Map, Result, Value, VmContext};
+
+fn to_secs(ctx: &VmContext, idx: usize, value: &Value) -> Result<f64> {
+    if let Ok(v) = value.as_int() {
+        return Ok(v.into());
+    }
+
+    let v = value.as_float().map_err(|e| any_error(ctx, idx, e))?;
+    Ok(v.into())
+}
+
+fn now(ctx: &VmContext, _: &[Value; 0]) -> Result<Value> {
+    Ok((ctx.clock().now() as f32).into())
+}
+
+fn format(ctx: &VmContext, [secs, fmt]: &[Value; 2]) -> Result<Value> {
+    let secs = to_secs(ctx, 0, secs)?;
+    let fmt = fmt.as_string().map_err(|e| any_error(ctx, 1, e))?;
+
+    if !secs.is_finite() || secs < 0.0 {
+        return Err(any_error(ctx, 0, "time must be a non-negative number"));
+    }
+
+    let millis = (secs * 1000.0).round() as u64;
+    let secs = millis / 1000;
+
+    let mut res = String::with_capacity(fmt.len());
+    let mut chars = fmt.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            res.push(c);
+            continue;
+        }
+
+        let _ = match chars.next() {
+            Some('d') => write!(res, "{}", secs / 86400),
+            Some('H') => write!(res, "{:02}", secs / 3600 % 24),
+            Some('M') => write!(res, "{:02}", secs / 60 % 60),
+            Some('S') => write!(res, "{:02}", secs % 60),
+            Some('f') => write!(res, "{:03}", millis % 1000),
+            Some('%') => write!(res, "%"),
+            Some(c) => {
+                let msg = format!("invalid format specifier `%{}`", c);
+                return Err(any_error(ctx, 1, msg));
+            }
+            None => return Err(any_error(ctx, 1, "trailing `%` in format string")),
+        };
+    }
+
+    Ok(res.into())
+}
+
+pub fn module() -> Value {
+    let mut map = Map::new();
+
+    add_func(&mut map, "time.now", now);
+    add_func(&mut map, "time.format", format);
+
+    map.into()
+}
@@ -0,0 +1,135 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::compile_with_importer;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::{syntax, Clock, DebugSink, Map, Source, Value, Vm};
+
+pub struct ResolvedImport {
+    // canonical path, used as the cache key and as the source name
+    pub path: String,
+    pub text: String,
+}
+
+type ResolveFn = dyn Fn(&str, &str) -> Result<ResolvedImport, String>;
+
+// `import "path"` is resolved at compile time: the resolver is called with the
+// name of the importing source and the path, and the resolved module is
+// compiled and evaluated once, its result becomes the value of the expression
+#[derive(Clone)]
+pub struct Importer(Rc<ImporterInner>);
+
+struct ImporterInner {
+    env: Map,
+    resolve: Box<ResolveFn>,
+    cache: RefCell<HashMap<String, Value>>,
+    stack: RefCell<Vec<String>>,
+    // settings of the vms evaluating modules, the fuel is per module
+    fuel: Cell<Option<u64>>,
+    debug_sink: RefCell<DebugSink>,
+    clock: RefCell<Clock>,
+}
+
+impl Importer {
+    pub fn new(
+        env: Map,
+        resolve: impl Fn(&str, &str) -> Result<ResolvedImport, String> + 'static,
+    ) -> Importer {
+        Importer(Rc::new(ImporterInner {
+            env,
+            resolve: Box::new(resolve),
+            cache: Default::default(),
+            stack: Default::default(),
+            fuel: Default::default(),
+            debug_sink: Default::default(),
+            clock: Default::default(),
+        }))
+    }
+
+    pub fn set_fuel(&self, fuel: Option<u64>) {
+        self.0.fuel.set(fuel);
+    }
+
+    pub fn set_debug_sink(&self, sink: DebugSink) {
+        *self.0.debug_sink.borrow_mut() = sink;
+    }
+
+    pub fn set_clock(&self, clock: Clock) {
+        *self.0.clock.borrow_mut() = clock;
+    }
+
+    pub(super) fn import(
+        &self,
+        from: &str,
+        path: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<Value, String> {
+        let resolved = (self.0.resolve)(from, path)?;
+
+        if let Some(value) = self.0.cache.borrow().get(&resolved.path) {
+            return Ok(value.clone());
+        }
+
+        {
+            let mut stack = self.0.stack.borrow_mut();
+            if let Some(pos) = stack.iter().position(|v| *v == resolved.path) {
+                let mut cycle = stack[pos..].to_vec();
+                cycle.push(resolved.path);
+                return Err(format!("cyclic import: {}", cycle.join(" -> ")));
+            }
+
+            stack.push(resolved.path.clone());
+        }
+
+        let res = self.load(&resolved, diagnostics);
+        self.0.stack.borrow_mut().pop();
+
+        let value = res?;
+        self.0
+            .cache
+            .borrow_mut()
+            .insert(resolved.path, value.clone());
+
+        Ok(value)
+    }
+
+    fn load(
+        &self,
+        resolved: &ResolvedImport,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<Value, String> {
+        let failed = || format!("failed to compile `{}`", resolved.path);
+
+        let mut parse_res = syntax::parse(&resolved.text);
+        diagnostics.append(&mut parse_res.diagnostics);
+
+        let expr = parse_res.expr.ok_or_else(failed)?;
+        let source = Arc::new(Source::new(
+            resolved.path.clone(),
+            parse_res.source.text.clone(),
+        ));
+
+        let mut compile_res = compile_with_importer(self.0.env.clone(), source, expr, self.clone());
+        diagnostics.append(&mut compile_res.diagnostics);
+
+        if diagnostics.iter().any(|v| v.severity == Severity::Error) {
+            return Err(failed());
+        }
+
+        let mut vm = Vm::new();
+        vm.set_fuel(self.0.fuel.get());
+        vm.set_debug_sink(self.0.debug_sink.borrow().clone());
+        vm.set_clock(self.0.clock.borrow().clone());
+
+        let func = Value::from(compile_res.func);
+        vm.eval(&func, &[]).map_err(|e| {
+            format!(
+                "failed to evaluate `{}`: {}",
+                resolved.path,
+                e.diagnostic().message
+            )
+        })
+    }
+}
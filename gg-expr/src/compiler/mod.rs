@@ -1,20 +1,23 @@
+mod import;
 mod reg_alloc;
 mod scope;
 
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::iter;
+use std::rc::Rc;
 use std::sync::Arc;
 
+pub use self::import::{Importer, ResolvedImport};
 use self::reg_alloc::RegAlloc;
 use self::scope::{ScopeStack, VarLoc};
 use crate::diagnostic::{Diagnostic, Severity, SourceComponent};
 use crate::syntax::{SyntaxKind as SK, *};
 use crate::vm::*;
-use crate::{DebugInfo, Func, Map, Source, Value};
+use crate::{DebugInfo, Func, List, Map, Source, Value};
 
 pub struct Compiler {
-    env: Map,
+    globals: Rc<HashMap<String, Value>>,
     regs: RegAlloc,
     instrs: Instrs,
     consts: Consts,
@@ -22,34 +25,41 @@ pub struct Compiler {
     scopes: ScopeStack,
     pattern_scope: HashMap<Ident, RegId>,
     sibling_pattern_scope: HashMap<Ident, RegId>,
+    folded: HashMap<Expr, Option<Value>>,
     diagnostics: Vec<Diagnostic>,
     debug_info: DebugInfo,
+    importer: Option<Importer>,
     arity: u16,
+    variadic: bool,
     in_ret_expr: bool,
 }
 
 impl Compiler {
     pub fn new(env: Map, source: Arc<Source>) -> Compiler {
-        let mut scopes = ScopeStack::default();
+        let globals = env
+            .iter()
+            .filter_map(|(k, v)| Some((k.as_string().ok()?.to_owned(), v.clone())))
+            .collect();
 
-        for (k, v) in env.iter() {
-            if let Ok(str) = k.as_string() {
-                scopes.set(Ident::from(str), v.clone());
-            }
-        }
+        Compiler::with_globals(Rc::new(globals), source)
+    }
 
+    fn with_globals(globals: Rc<HashMap<String, Value>>, source: Arc<Source>) -> Compiler {
         Compiler {
-            env,
-            scopes,
+            globals,
+            scopes: Default::default(),
             regs: Default::default(),
             instrs: Default::default(),
             consts: Default::default(),
             upvalues: Default::default(),
             pattern_scope: Default::default(),
             sibling_pattern_scope: Default::default(),
+            folded: Default::default(),
             diagnostics: Default::default(),
             debug_info: DebugInfo::new(source),
+            importer: None,
             arity: 0,
+            variadic: false,
             in_ret_expr: true,
         }
     }
@@ -104,6 +114,89 @@ impl Compiler {
         self.compile_expr_ret(range, dst);
     }
 
+    // results are cached, so that compiling a chain of operators folds each
+    // subexpression only once
+    fn fold_const(&mut self, expr: &Expr) -> Option<Value> {
+        if let Some(value) = self.folded.get(expr) {
+            return value.clone();
+        }
+
+        let value = match expr {
+            Expr::Null(_) => Some(Value::null()),
+            Expr::Bool(expr) => expr.value().map(Value::from),
+            Expr::Int(expr) => expr.value().map(Value::from),
+            Expr::Float(expr) => expr.value().map(Value::from),
+            Expr::String(expr) => expr.value().map(Value::from),
+            Expr::Symbol(expr) => expr.value().map(|v| Value::from_symbol(&v)),
+            Expr::Grouped(expr) => self.fold_const(&expr.expr()?),
+            Expr::List(expr) => expr
+                .exprs()
+                .map(|expr| self.fold_const(&expr))
+                .collect::<Option<List>>()
+                .map(Value::from),
+            Expr::Binary(expr) => self.fold_binary(expr),
+            Expr::Unary(expr) => self.fold_unary(expr),
+            _ => None,
+        };
+
+        self.folded.insert(expr.clone(), value.clone());
+        value
+    }
+
+    fn fold_binary(&mut self, expr: &ExprBinary) -> Option<Value> {
+        let opcode = match expr.op()? {
+            SK::TokLt => Opcode::OpLt,
+            SK::TokLe => Opcode::OpLe,
+            SK::TokEq => Opcode::OpEq,
+            SK::TokNeq => Opcode::OpNeq,
+            SK::TokGe => Opcode::OpGe,
+            SK::TokGt => Opcode::OpGt,
+            SK::TokAdd => Opcode::OpAdd,
+            SK::TokSub => Opcode::OpSub,
+            SK::TokMul => Opcode::OpMul,
+            SK::TokDiv => Opcode::OpDiv,
+            SK::TokRem => Opcode::OpRem,
+            SK::TokPow => Opcode::OpPow,
+            SK::TokAmp => Opcode::OpBitAnd,
+            SK::TokPipe => Opcode::OpBitOr,
+            SK::TokCaret => Opcode::OpBitXor,
+            SK::TokShl => Opcode::OpShl,
+            SK::TokShr => Opcode::OpShr,
+            _ => return None,
+        };
+
+        let lhs = self.fold_const(&expr.lhs()?)?;
+        let rhs = self.fold_const(&expr.rhs()?)?;
+
+        if opcode == Opcode::OpAdd {
+            if let Some(value) = concat(&lhs, &rhs) {
+                return Some(value);
+            }
+        }
+
+        if !is_foldable(&lhs) || !is_foldable(&rhs) {
+            return None;
+        }
+
+        // `"a" * n` may produce an arbitrarily large string
+        if opcode == Opcode::OpMul && (lhs.is_string() || rhs.is_string()) {
+            return None;
+        }
+
+        eval_const_op(opcode, vec![lhs, rhs])
+    }
+
+    fn fold_unary(&mut self, expr: &ExprUnary) -> Option<Value> {
+        let opcode = match expr.op()? {
+            SK::TokSub => Opcode::UnOpNeg,
+            SK::TokNot => Opcode::UnOpNot,
+            _ => return None,
+        };
+
+        let arg = self.fold_const(&expr.expr()?).filter(is_foldable)?;
+        eval_const_op(opcode, vec![arg])
+    }
+
     fn compile_expr(&mut self, expr: Expr, dst: &mut RegId) {
         match expr {
             Expr::Null(expr) => self.compile_expr_null(expr, dst),
@@ -111,6 +204,7 @@ impl Compiler {
             Expr::Int(expr) => self.compile_expr_int(expr, dst),
             Expr::Float(expr) => self.compile_expr_float(expr, dst),
             Expr::String(expr) => self.compile_expr_string(expr, dst),
+            Expr::Symbol(expr) => self.compile_expr_symbol(expr, dst),
             Expr::Binding(expr) => self.compile_expr_binding(expr, dst),
             Expr::Binary(expr) => self.compile_expr_binary(expr, dst),
             Expr::Unary(expr) => self.compile_expr_unary(expr, dst),
@@ -123,6 +217,9 @@ impl Compiler {
             Expr::LetIn(expr) => self.compile_expr_let_in(expr, dst),
             Expr::When(expr) => self.compile_expr_when(expr, dst),
             Expr::Fn(expr) => self.compile_expr_fn(expr, dst),
+            Expr::TryCatch(expr) => self.compile_expr_try_catch(expr, dst),
+            Expr::Interp(expr) => self.compile_expr_interp(expr, dst),
+            Expr::Import(expr) => self.compile_expr_import(expr, dst),
         }
     }
 
@@ -145,10 +242,21 @@ impl Compiler {
     }
 
     fn compile_expr_int(&mut self, expr: ExprInt, dst: &mut RegId) {
-        let value = expr.value().unwrap_or_default();
+        let value = expr.value().unwrap_or_else(|| {
+            self.int_out_of_range(expr.range());
+            0
+        });
         self.compile_const(expr.range(), value, *dst)
     }
 
+    fn int_out_of_range(&mut self, range: TextRange) {
+        self.add_simple_error(
+            range,
+            "integer literal is out of range",
+            "does not fit into a 32-bit signed integer",
+        );
+    }
+
     fn compile_expr_float(&mut self, expr: ExprFloat, dst: &mut RegId) {
         let value = expr.value().unwrap_or_default();
         self.compile_const(expr.range(), value, *dst)
@@ -159,6 +267,59 @@ impl Compiler {
         self.compile_const(expr.range(), value, *dst)
     }
 
+    fn compile_expr_interp(&mut self, expr: ExprInterp, dst: &mut RegId) {
+        let in_ret_expr = self.in_ret_expr;
+        self.in_ret_expr = false;
+
+        let range = expr.range();
+        let part = self.regs.alloc();
+        let mut is_first = true;
+
+        for interp_part in expr.parts() {
+            let target = if is_first { *dst } else { part };
+            let mut part_range = range;
+
+            match interp_part {
+                InterpPart::String(value) => self.compile_const(range, value, target),
+                InterpPart::Expr(expr) => {
+                    part_range = expr.range();
+
+                    let mut val = target;
+                    self.compile_expr(expr, &mut val);
+
+                    let instr = Instr::new(Opcode::ToString)
+                        .with_reg_a(val)
+                        .with_reg_b(target);
+                    self.add_instr_ranged(&[part_range], instr);
+                }
+            }
+
+            if !is_first {
+                let instr = Instr::new(Opcode::OpAdd)
+                    .with_reg_a(*dst)
+                    .with_reg_b(part)
+                    .with_reg_c(*dst);
+                self.add_instr_ranged(&[range, range, part_range], instr);
+            }
+
+            is_first = false;
+        }
+
+        if is_first {
+            self.compile_const(range, "", *dst);
+        }
+
+        self.regs.free(part);
+
+        self.in_ret_expr = in_ret_expr;
+        self.compile_expr_ret(range, *dst);
+    }
+
+    fn compile_expr_symbol(&mut self, expr: ExprSymbol, dst: &mut RegId) {
+        let value = Value::from_symbol(&expr.value().unwrap_or_default());
+        self.compile_const(expr.range(), value, *dst)
+    }
+
     fn compile_var_dst(&mut self, ident: Ident, dst: RegId) {
         let mut tmp = dst;
         self.compile_var(ident, &mut tmp);
@@ -194,25 +355,27 @@ impl Compiler {
                     .with_reg_b(*dst);
                 self.add_instr_ranged(&[range], instr);
             }
-            Some(VarLoc::Value(val)) => {
-                let value = val.clone();
-                self.compile_const(range, value, *dst);
-            }
-            None => {
-                self.no_such_var(ident);
-            }
+            None => match self.globals.get(ident.name()) {
+                Some(value) => {
+                    let value = value.clone();
+                    self.compile_const(range, value, *dst);
+                }
+                None => self.no_such_var(ident),
+            },
         }
 
         self.compile_expr_ret(range, *dst);
     }
 
-    fn vars_in_scope(&self) -> Vec<Ident> {
+    fn vars_in_scope(&self) -> Vec<String> {
         let mut vars = HashSet::new();
 
         for ident in self.scopes.names() {
-            if !vars.contains(&ident) {
-                vars.insert(ident);
-            }
+            vars.insert(ident.name().to_owned());
+        }
+
+        for name in self.globals.keys() {
+            vars.insert(name.clone());
         }
 
         vars.into_iter().collect()
@@ -221,16 +384,16 @@ impl Compiler {
     fn no_such_var(&mut self, ident: Ident) {
         let range = ident.range();
         let mut in_scope = self.vars_in_scope();
-        in_scope.sort_by_cached_key(|v| strsim::damerau_levenshtein(v.name(), ident.name()));
+        in_scope.sort_by_cached_key(|v| strsim::damerau_levenshtein(v, ident.name()));
 
         let mut help = String::from("perhaps you meant ");
 
-        for (i, ident) in in_scope.iter().take(3).enumerate() {
+        for (i, name) in in_scope.iter().take(3).enumerate() {
             if i > 0 {
                 help.push_str(", ");
             }
 
-            let _ = write!(&mut help, "`{}`", ident.name());
+            let _ = write!(&mut help, "`{}`", name);
         }
 
         let message = format!("cannot find variable `{}`", ident.name());
@@ -257,6 +420,16 @@ impl Compiler {
             return self.compile_expr_binary_logic(expr, dst);
         }
 
+        if let Some(value) = self.fold_const(&Expr::Binary(expr.clone())) {
+            return self.compile_const(expr.range(), value, *dst);
+        }
+
+        if expr.op() == Some(SK::TokIn) {
+            if let Some(range) = expr.rhs().and_then(range_expr) {
+                return self.compile_expr_in_range(expr, range, dst);
+            }
+        }
+
         let in_ret_expr = self.in_ret_expr;
         self.in_ret_expr = false;
 
@@ -299,6 +472,14 @@ impl Compiler {
             Some(SK::TokDiv) => Opcode::OpDiv,
             Some(SK::TokRem) => Opcode::OpRem,
             Some(SK::TokPow) => Opcode::OpPow,
+            Some(SK::TokAmp) => Opcode::OpBitAnd,
+            Some(SK::TokPipe) => Opcode::OpBitOr,
+            Some(SK::TokCaret) => Opcode::OpBitXor,
+            Some(SK::TokShl) => Opcode::OpShl,
+            Some(SK::TokShr) => Opcode::OpShr,
+            Some(SK::TokIn) => Opcode::OpIn,
+            Some(SK::TokRange) => Opcode::OpRange,
+            Some(SK::TokRangeInclusive) => Opcode::OpRangeInclusive,
             _ => Opcode::OpAdd,
         };
 
@@ -312,6 +493,33 @@ impl Compiler {
         self.compile_expr_ret(range, *dst);
     }
 
+    fn compile_expr_in_range(&mut self, expr: ExprBinary, range: ExprBinary, dst: &mut RegId) {
+        let in_ret_expr = self.in_ret_expr;
+        self.in_ret_expr = false;
+
+        let seq = self.regs.alloc_seq(3);
+        let [val, start, end] = [0, 1, 2].map(|i| RegId(seq.base.0 + i));
+
+        for (expr, reg) in [(expr.lhs(), val), (range.lhs(), start), (range.rhs(), end)] {
+            if let Some(expr) = expr {
+                self.compile_expr_dst(expr, reg);
+            }
+        }
+
+        let opcode = if range.op() == Some(SK::TokRangeInclusive) {
+            Opcode::OpInRangeInclusive
+        } else {
+            Opcode::OpInRange
+        };
+
+        let instr = Instr::new(opcode).with_reg_seq(seq).with_reg_c(*dst);
+        self.add_instr_ranged(&[range.range()], instr);
+        self.regs.free_seq(seq);
+
+        self.in_ret_expr = in_ret_expr;
+        self.compile_expr_ret(expr.range(), *dst);
+    }
+
     fn compile_expr_binary_logic(&mut self, expr: ExprBinary, dst: &mut RegId) {
         let in_ret_expr = self.in_ret_expr;
         self.in_ret_expr = false;
@@ -345,11 +553,16 @@ impl Compiler {
 
         let hole = self.instrs.add(Instr::new(Opcode::Nop));
 
+        // the rhs of `??` is the whole result when the lhs is null
+        let is_tail = is_coalesce && in_ret_expr;
+
         if let Some(expr) = expr.rhs() {
             let mut rhs = *dst;
+            self.in_ret_expr = is_tail;
             self.compile_expr(expr, &mut rhs);
+            self.in_ret_expr = false;
 
-            if is_coalesce && rhs != *dst {
+            if is_coalesce && !is_tail && rhs != *dst {
                 let instr = Instr::new(Opcode::Copy).with_reg_a(rhs).with_reg_b(*dst);
                 self.instrs.add(instr);
             }
@@ -382,6 +595,10 @@ impl Compiler {
     }
 
     fn compile_expr_unary(&mut self, expr: ExprUnary, dst: &mut RegId) {
+        if let Some(value) = self.fold_const(&Expr::Unary(expr.clone())) {
+            return self.compile_const(expr.range(), value, *dst);
+        }
+
         let in_ret_expr = self.in_ret_expr;
         self.in_ret_expr = false;
 
@@ -440,6 +657,7 @@ impl Compiler {
         self.in_ret_expr = false;
 
         let range = expr.range();
+        self.check_duplicate_keys(&expr);
 
         let len = expr.pairs().count() as u16;
         let seq = self.regs.alloc_seq(len * 2);
@@ -469,7 +687,44 @@ impl Compiler {
         self.compile_expr_ret(range, *dst);
     }
 
+    fn check_duplicate_keys(&mut self, expr: &ExprMap) {
+        let mut keys = HashMap::new();
+
+        for pair in expr.pairs() {
+            let (key, range) = if let Some(ident) = pair.key_ident() {
+                (Value::from(ident.name()), ident.range())
+            } else if let Some(expr) = pair.key_expr() {
+                match self.fold_const(&expr) {
+                    Some(key) => (key, expr.range()),
+                    None => continue,
+                }
+            } else {
+                continue;
+            };
+
+            let first = match keys.get(&key) {
+                Some(&first) => first,
+                None => {
+                    keys.insert(key, range);
+                    continue;
+                }
+            };
+
+            let src = SourceComponent::new(self.debug_info.source.clone())
+                .with_label(Severity::Info, first, "first defined here")
+                .with_label(Severity::Error, range, "duplicate key");
+
+            let msg = format!("duplicate map key {:?}", key);
+            let diag = Diagnostic::new(Severity::Error, msg).with_source(src);
+            self.add_error(diag);
+        }
+    }
+
     fn compile_expr_call(&mut self, expr: ExprCall, dst: &mut RegId) {
+        if let Some(arg) = self.len_call_arg(&expr) {
+            return self.compile_expr_len(expr.range(), arg, dst);
+        }
+
         let in_ret_expr = self.in_ret_expr;
         self.in_ret_expr = false;
 
@@ -500,6 +755,40 @@ impl Compiler {
         self.regs.free_seq(seq);
     }
 
+    // `len(x)` compiles to the `Len` opcode unless `len` is shadowed
+    fn len_call_arg(&self, expr: &ExprCall) -> Option<Expr> {
+        let ident = match expr.func()? {
+            Expr::Binding(binding) => binding.ident()?,
+            _ => return None,
+        };
+
+        if ident.name() != "len"
+            || self.scopes.get(&ident).is_some()
+            || self.globals.contains_key("len")
+        {
+            return None;
+        }
+
+        let mut args = expr.args();
+        let arg = args.next()?;
+        args.next().is_none().then_some(arg)
+    }
+
+    fn compile_expr_len(&mut self, range: TextRange, expr: Expr, dst: &mut RegId) {
+        let in_ret_expr = self.in_ret_expr;
+        self.in_ret_expr = false;
+
+        let arg_range = expr.range();
+        let mut arg = *dst;
+        self.compile_expr(expr, &mut arg);
+
+        let instr = Instr::new(Opcode::Len).with_reg_a(arg).with_reg_b(*dst);
+        self.add_instr_ranged(&[range, arg_range], instr);
+
+        self.in_ret_expr = in_ret_expr;
+        self.compile_expr_ret(range, *dst);
+    }
+
     fn compile_expr_index(&mut self, expr: ExprIndex, dst: &mut RegId) {
         let in_ret_expr = self.in_ret_expr;
         self.in_ret_expr = false;
@@ -610,12 +899,18 @@ impl Compiler {
                 }
             }
 
-            if loc != tmp_reg {
-                self.regs.free(tmp_reg);
-            }
-
             if let Some(ident) = binding.ident() {
+                if loc != tmp_reg {
+                    self.regs.free(tmp_reg);
+                }
+
                 self.scopes.set(ident, loc);
+            } else {
+                if let Some(pat) = binding.pat() {
+                    self.compile_let_pat(binding.range(), pat, loc);
+                }
+
+                self.regs.free(tmp_reg);
             }
         }
 
@@ -628,6 +923,23 @@ impl Compiler {
         self.pop_scope();
     }
 
+    fn compile_let_pat(&mut self, range: TextRange, pat: Pat, val: RegId) {
+        let cond = self.regs.alloc();
+        self.compile_pat_root(pat, val, cond);
+
+        let jump_idx = self.instrs.add(Instr::new(Opcode::Nop));
+        let start_idx = self.instrs.next_idx();
+        self.add_instr_ranged(&[range], Instr::new(Opcode::Panic));
+        let offset = self.instrs.next_idx() - start_idx;
+
+        let instr = Instr::new(Opcode::JumpIfTrue)
+            .with_reg_a(cond)
+            .with_offset(offset);
+        self.instrs.set(jump_idx, instr);
+
+        self.regs.free(cond);
+    }
+
     fn compile_expr_when(&mut self, expr: ExprWhen, dst: &mut RegId) {
         let src_tmp = self.regs.alloc();
         let mut src = src_tmp;
@@ -641,14 +953,23 @@ impl Compiler {
         }
 
         let mut holes = Vec::new();
+        let mut catch_all = None;
 
         for case in expr.cases() {
+            if let Some(catch_all) = catch_all {
+                self.add_unreachable_case(case.range(), catch_all);
+            }
+
             self.push_scope();
 
             let in_ret_expr = self.in_ret_expr;
             self.in_ret_expr = false;
 
             if let Some(pat) = case.pat() {
+                if catch_all.is_none() && is_irrefutable(&pat) {
+                    catch_all = Some(pat.range());
+                }
+
                 self.compile_pat_root(pat.clone(), src, cond);
             }
 
@@ -688,7 +1009,105 @@ impl Compiler {
         self.regs.free(cond);
     }
 
-    fn compile_args(&mut self, args: impl Iterator<Item = Ident>) {
+    fn add_unreachable_case(&mut self, range: TextRange, catch_all: TextRange) {
+        self.add_error(
+            Diagnostic::new(Severity::Warning, "unreachable pattern").with_source(
+                SourceComponent::new(self.debug_info.source.clone())
+                    .with_label(Severity::Warning, range, "this case is never reached")
+                    .with_label(
+                        Severity::Info,
+                        catch_all,
+                        "because this pattern matches any value",
+                    ),
+            ),
+        );
+    }
+
+    fn compile_expr_import(&mut self, expr: ExprImport, dst: &mut RegId) {
+        let range = expr.range();
+
+        let path = match expr.path() {
+            Some(v) => v,
+            None => return,
+        };
+
+        let importer = match self.importer.clone() {
+            Some(v) => v,
+            None => {
+                return self.add_simple_error(
+                    range,
+                    "cannot import modules",
+                    "no import resolver was provided",
+                )
+            }
+        };
+
+        let mut diagnostics = Vec::new();
+        let res = importer.import(&self.debug_info.source.name, &path, &mut diagnostics);
+        self.diagnostics.append(&mut diagnostics);
+
+        match res {
+            Ok(value) => self.compile_const(range, value, *dst),
+            Err(message) => self.add_simple_error(range, &message, "imported here"),
+        }
+    }
+
+    fn compile_expr_try_catch(&mut self, expr: ExprTryCatch, dst: &mut RegId) {
+        let range = expr.range();
+        let error = self.regs.alloc();
+
+        let start = self.instrs.add(Instr::new(Opcode::Nop));
+
+        let in_ret_expr = self.in_ret_expr;
+        self.in_ret_expr = false;
+
+        if let Some(expr) = expr.expr() {
+            self.compile_expr_dst(expr, *dst);
+        }
+
+        self.instrs.add(Instr::new(Opcode::PopCatch));
+
+        self.in_ret_expr = in_ret_expr;
+        self.compile_expr_ret(range, *dst);
+
+        let hole = if self.in_ret_expr {
+            None
+        } else {
+            Some(self.instrs.add(Instr::new(Opcode::Nop)))
+        };
+
+        let handler = self.instrs.next_idx();
+        let instr = Instr::new(Opcode::PushCatch)
+            .with_reg_a(error)
+            .with_offset(handler - start - 1);
+        self.instrs.set(start, instr);
+
+        self.push_scope();
+
+        let ident = expr.ident();
+        let is_bound = ident.is_some();
+        if let Some(ident) = ident {
+            self.scopes.set(ident, error);
+        }
+
+        if let Some(expr) = expr.handler() {
+            self.compile_expr_dst(expr, *dst);
+        }
+
+        self.pop_scope();
+
+        if !is_bound {
+            self.regs.free(error);
+        }
+
+        if let Some(hole) = hole {
+            let end = self.instrs.next_idx();
+            let instr = Instr::new(Opcode::Jump).with_offset(end - hole - 1);
+            self.instrs.set(hole, instr);
+        }
+    }
+
+    fn compile_args(&mut self, args: impl Iterator<Item = Ident>, rest: Option<Ident>) {
         let mut num_args = 0;
         for (i, arg) in args.enumerate() {
             let reg = RegId(i as u16);
@@ -697,10 +1116,16 @@ impl Compiler {
         }
         self.arity = num_args;
         self.regs.advance(num_args);
+
+        if let Some(rest) = rest {
+            self.scopes.set(rest, RegId(num_args));
+            self.variadic = true;
+            self.regs.advance(1);
+        }
     }
 
-    fn compile_fn(&mut self, args: impl Iterator<Item = Ident>, body: Expr) {
-        self.compile_args(args);
+    fn compile_fn(&mut self, args: impl Iterator<Item = Ident>, rest: Option<Ident>, body: Expr) {
+        self.compile_args(args, rest);
         let mut dst = self.regs.alloc();
         self.compile_expr(body, &mut dst);
     }
@@ -712,8 +1137,10 @@ impl Compiler {
     fn compile_expr_fn_named(&mut self, expr: ExprFn, dst: &mut RegId, name: Option<Ident>) {
         let range = expr.range();
 
-        let mut compiler = Compiler::new(self.env.clone(), self.debug_info.source.clone());
+        let mut compiler =
+            Compiler::with_globals(self.globals.clone(), self.debug_info.source.clone());
         compiler.debug_info.range = range;
+        compiler.importer = self.importer.clone();
         compiler.debug_info.name = Some(
             name.clone()
                 .map(|v| v.name().into())
@@ -734,8 +1161,25 @@ impl Compiler {
             compiler.scopes.set(name, UpfnId(0));
         }
 
+        let rest = expr.rest_arg();
+        if let Some(rest) = &rest {
+            if expr
+                .args()
+                .any(|arg| arg.range().start() > rest.range().start())
+            {
+                let src = SourceComponent::new(self.debug_info.source.clone()).with_label(
+                    Severity::Error,
+                    rest.range(),
+                    "rest parameter",
+                );
+                let msg = "rest parameter must be the last parameter";
+                let diag = Diagnostic::new(Severity::Error, msg).with_source(src);
+                self.add_error(diag);
+            }
+        }
+
         if let Some(body) = expr.expr() {
-            compiler.compile_fn(expr.args(), body);
+            compiler.compile_fn(expr.args(), rest, body);
         }
 
         let in_ret_expr = self.in_ret_expr;
@@ -782,10 +1226,13 @@ impl Compiler {
             Pat::Grouped(pat) => self.compile_pat_grouped(pat, val, cond),
             Pat::Or(pat) => self.compile_pat_or(pat, val, cond),
             Pat::List(pat) => self.compile_pat_list(pat, val, cond),
+            Pat::Map(pat) => self.compile_pat_map(pat, val, cond),
             Pat::Null(pat) => self.compile_pat_null(pat, val, cond),
             Pat::Bool(pat) => self.compile_pat_bool(pat, val, cond),
             Pat::Int(pat) => self.compile_pat_int(pat, val, cond),
+            Pat::Float(pat) => self.compile_pat_float(pat, val, cond),
             Pat::String(pat) => self.compile_pat_string(pat, val, cond),
+            Pat::Symbol(pat) => self.compile_pat_symbol(pat, val, cond),
             Pat::Rest(pat) => self.compile_pat_rest(pat, val, cond),
             Pat::Hole(pat) => self.compile_pat_hole(pat, val, cond),
             Pat::Binding(pat) => self.compile_pat_binding(pat, val, cond),
@@ -920,7 +1367,11 @@ impl Compiler {
         let mut idx = if rest_start { expected_len } else { 0 };
 
         for pat in pat.pats() {
-            if let Pat::Rest(_) = pat {
+            if let Pat::Rest(pat) = pat {
+                if rest_start || rest_end {
+                    self.compile_list_rest(pat, val, len_reg, expected_len, rest_start, cond);
+                }
+
                 continue;
             }
 
@@ -963,6 +1414,168 @@ impl Compiler {
         self.regs.free(inner_reg);
     }
 
+    fn compile_pat_map(&mut self, pat: PatMap, val: RegId, cond: RegId) {
+        let range = pat.range();
+        let mut holes = Vec::new();
+
+        let mut has_rest = false;
+        for rest in pat.rests() {
+            if rest.syntax().next_sibling().is_some() || has_rest {
+                self.add_simple_error(
+                    rest.range(),
+                    "invalid pattern",
+                    "`...` invalid in this position",
+                );
+            } else if rest.ident().is_some() {
+                self.add_simple_error(
+                    rest.range(),
+                    "invalid pattern",
+                    "rest of a map cannot be bound",
+                );
+            }
+
+            has_rest = true;
+        }
+
+        let inner_reg = self.regs.alloc();
+        let key_reg = self.regs.alloc();
+
+        let instr = Instr::new(Opcode::IsMap).with_reg_a(val).with_reg_b(cond);
+        self.instrs.add(instr);
+        holes.push(self.instrs.add(Instr::new(Opcode::Nop)));
+
+        // without `...` the map must not have any keys besides the listed ones
+        if !has_rest {
+            let num_pairs = pat.pairs().count() as i32;
+
+            let instr = Instr::new(Opcode::Len)
+                .with_reg_a(val)
+                .with_reg_b(inner_reg);
+            self.instrs.add(instr);
+
+            self.compile_const(range, num_pairs, key_reg);
+
+            let instr = Instr::new(Opcode::OpEq)
+                .with_reg_a(inner_reg)
+                .with_reg_b(key_reg)
+                .with_reg_c(cond);
+            self.instrs.add(instr);
+
+            holes.push(self.instrs.add(Instr::new(Opcode::Nop)));
+        }
+
+        for pair in pat.pairs() {
+            let key = if let Some(ident) = pair.key_ident() {
+                Value::from(ident.name())
+            } else if let Some(expr) = pair.key_expr() {
+                match self.fold_const(&expr) {
+                    Some(v) => v,
+                    None => {
+                        self.add_simple_error(
+                            expr.range(),
+                            "invalid pattern",
+                            "map pattern keys must be constant",
+                        );
+                        // keep compiling the pattern, so its bindings exist
+                        Value::null()
+                    }
+                }
+            } else {
+                continue;
+            };
+
+            self.compile_const(pair.range(), key, key_reg);
+
+            let instr = Instr::new(Opcode::OpIn)
+                .with_reg_a(key_reg)
+                .with_reg_b(val)
+                .with_reg_c(cond);
+            self.instrs.add(instr);
+
+            holes.push(self.instrs.add(Instr::new(Opcode::Nop)));
+
+            let instr = Instr::new(Opcode::OpIndex)
+                .with_reg_a(val)
+                .with_reg_b(key_reg)
+                .with_reg_c(inner_reg);
+            self.instrs.add(instr);
+
+            if let Some(pat) = pair.pat() {
+                self.compile_pat(pat, inner_reg, cond);
+            } else if let Some(ident) = pair.key_ident() {
+                // `{ name }` is a shorthand for `{ name = name }`
+                self.bind_pat_ident(ident, inner_reg, cond);
+            }
+
+            holes.push(self.instrs.add(Instr::new(Opcode::Nop)));
+        }
+
+        let end = self.instrs.last_idx();
+        for hole in holes {
+            if end == hole {
+                continue;
+            }
+
+            let instr = Instr::new(Opcode::JumpIfFalse)
+                .with_reg_a(cond)
+                .with_offset(end - hole);
+            self.instrs.set(hole, instr);
+        }
+
+        self.regs.free(key_reg);
+        self.regs.free(inner_reg);
+    }
+
+    fn compile_list_rest(
+        &mut self,
+        pat: PatRest,
+        val: RegId,
+        len_reg: RegId,
+        num_fixed: i32,
+        at_start: bool,
+        cond: RegId,
+    ) {
+        let ident = match pat.ident() {
+            Some(v) => v,
+            None => return,
+        };
+
+        let range = pat.range();
+        let seq = self.regs.alloc_seq(3);
+        let [list_reg, start_reg, end_reg] = [0, 1, 2].map(|i| RegId(seq.base.0 + i));
+
+        let instr = Instr::new(Opcode::Copy)
+            .with_reg_a(val)
+            .with_reg_b(list_reg);
+        self.instrs.add(instr);
+
+        if at_start {
+            self.compile_const(range, 0, start_reg);
+            self.compile_const(range, num_fixed, end_reg);
+            let instr = Instr::new(Opcode::OpSub)
+                .with_reg_a(len_reg)
+                .with_reg_b(end_reg)
+                .with_reg_c(end_reg);
+            self.instrs.add(instr);
+        } else {
+            self.compile_const(range, num_fixed, start_reg);
+            let instr = Instr::new(Opcode::Copy)
+                .with_reg_a(len_reg)
+                .with_reg_b(end_reg);
+            self.instrs.add(instr);
+        }
+
+        let rest_reg = self.regs.alloc();
+        let instr = Instr::new(Opcode::Slice)
+            .with_reg_seq(seq)
+            .with_reg_c(rest_reg);
+        self.instrs.add(instr);
+        self.regs.free_seq(seq);
+
+        self.bind_pat_ident(ident, rest_reg, cond);
+        self.regs.free(rest_reg);
+    }
+
     fn compile_pat_const_eq(
         &mut self,
         range: TextRange,
@@ -990,6 +1603,15 @@ impl Compiler {
     }
 
     fn compile_pat_int(&mut self, pat: PatInt, val: RegId, cond: RegId) {
+        match pat.value() {
+            Some(value) => self.compile_pat_const_eq(pat.range(), value, val, cond),
+            None => self.int_out_of_range(pat.range()),
+        }
+    }
+
+    // float patterns use the same equality as `Value::eq`: `2.0` doesn't match
+    // the int `2`, and since a literal is never NaN, NaN only matches bindings
+    fn compile_pat_float(&mut self, pat: PatFloat, val: RegId, cond: RegId) {
         if let Some(value) = pat.value() {
             self.compile_pat_const_eq(pat.range(), value, val, cond);
         }
@@ -1001,6 +1623,12 @@ impl Compiler {
         }
     }
 
+    fn compile_pat_symbol(&mut self, pat: PatSymbol, val: RegId, cond: RegId) {
+        if let Some(value) = pat.value() {
+            self.compile_pat_const_eq(pat.range(), Value::from_symbol(&value), val, cond);
+        }
+    }
+
     fn compile_pat_rest(&mut self, pat: PatRest, _val: RegId, _cond: RegId) {
         self.add_simple_error(
             pat.range(),
@@ -1021,33 +1649,38 @@ impl Compiler {
         }
 
         if let Some(ident) = pat.ident() {
-            let loc = if self.pattern_scope.contains_key(&ident) {
-                let msg = format!(
-                    "identifier `{}` is bound more than once in a pattern",
-                    ident.name()
-                );
-                self.add_simple_error(ident.range(), &msg, "already bound");
-                self.regs.alloc()
-            } else if let Some(&reg) = self.sibling_pattern_scope.get(&ident) {
-                reg
-            } else {
-                self.regs.alloc()
-            };
+            self.bind_pat_ident(ident, val, cond);
+        }
+    }
 
-            self.pattern_scope.insert(ident, loc);
+    fn bind_pat_ident(&mut self, ident: Ident, val: RegId, cond: RegId) {
+        let loc = if self.pattern_scope.contains_key(&ident) {
+            let msg = format!(
+                "identifier `{}` is bound more than once in a pattern",
+                ident.name()
+            );
+            self.add_simple_error(ident.range(), &msg, "already bound");
+            self.regs.alloc()
+        } else if let Some(&reg) = self.sibling_pattern_scope.get(&ident) {
+            reg
+        } else {
+            self.regs.alloc()
+        };
 
-            let instr = Instr::new(Opcode::CopyIfTrue)
-                .with_reg_a(val)
-                .with_reg_b(loc)
-                .with_reg_c(cond);
-            self.instrs.add(instr);
-        }
+        self.pattern_scope.insert(ident, loc);
+
+        let instr = Instr::new(Opcode::CopyIfTrue)
+            .with_reg_a(val)
+            .with_reg_b(loc)
+            .with_reg_c(cond);
+        self.instrs.add(instr);
     }
 
     fn finish(self) -> CompileResult {
         CompileResult {
             func: Func {
                 arity: self.arity,
+                variadic: self.variadic,
                 slots: self.regs.slots(),
                 instrs: self.instrs.compile(),
                 consts: self.consts.compile(),
@@ -1059,11 +1692,95 @@ impl Compiler {
     }
 }
 
+fn is_irrefutable(pat: &Pat) -> bool {
+    match pat {
+        Pat::Hole(_) => true,
+        Pat::Binding(pat) => pat.pat().map_or(true, |pat| is_irrefutable(&pat)),
+        Pat::Grouped(pat) => pat.pat().map_or(false, |pat| is_irrefutable(&pat)),
+        Pat::Or(pat) => pat.pats().any(|pat| is_irrefutable(&pat)),
+        _ => false,
+    }
+}
+
+fn concat(lhs: &Value, rhs: &Value) -> Option<Value> {
+    if let (Ok(x), Ok(y)) = (lhs.as_string(), rhs.as_string()) {
+        Some(format!("{}{}", x, y).into())
+    } else if let (Ok(x), Ok(y)) = (lhs.as_list(), rhs.as_list()) {
+        Some((x + y).into())
+    } else {
+        None
+    }
+}
+
+fn is_foldable(value: &Value) -> bool {
+    value.is_int() || value.is_float() || value.is_bool() || value.is_string() || value.is_null()
+}
+
+// evaluates the operator with the vm itself, so that folded results (including
+// int overflow to float) always match the runtime; errors such as division by
+// zero are left to be reported at runtime
+fn eval_const_op(opcode: Opcode, args: Vec<Value>) -> Option<Value> {
+    let num_args = args.len() as u16;
+
+    let mut instrs = (0..num_args)
+        .map(|i| {
+            Instr::new(Opcode::LoadConst)
+                .with_const_id(ConstId(i))
+                .with_reg_b(RegId(i))
+        })
+        .collect::<Vec<_>>();
+
+    let dst = RegId(num_args);
+    let instr = Instr::new(opcode).with_reg_a(RegId(0));
+    instrs.push(if num_args == 2 {
+        instr.with_reg_b(RegId(1)).with_reg_c(dst)
+    } else {
+        instr.with_reg_b(dst)
+    });
+    instrs.push(Instr::new(Opcode::Ret).with_reg_a(dst));
+
+    let func = Func {
+        arity: 0,
+        variadic: false,
+        slots: num_args + 1,
+        instrs: CompiledInstrs(instrs.into()),
+        consts: CompiledConsts(args.into()),
+        upvalues: Upvalues(Box::new([])),
+        debug_info: None,
+    };
+
+    Vm::new().eval(&func.into(), &[]).ok()
+}
+
+fn range_expr(expr: Expr) -> Option<ExprBinary> {
+    match expr {
+        Expr::Grouped(expr) => expr.expr().and_then(range_expr),
+        Expr::Binary(expr) if matches!(expr.op(), Some(SK::TokRange | SK::TokRangeInclusive)) => {
+            Some(expr)
+        }
+        _ => None,
+    }
+}
+
 pub fn compile(env: Map, source: Arc<Source>, expr: Expr) -> CompileResult {
+    compile_main(Compiler::new(env, source), expr)
+}
+
+pub fn compile_with_importer(
+    env: Map,
+    source: Arc<Source>,
+    expr: Expr,
+    importer: Importer,
+) -> CompileResult {
     let mut compiler = Compiler::new(env, source);
+    compiler.importer = Some(importer);
+    compile_main(compiler, expr)
+}
+
+fn compile_main(mut compiler: Compiler, expr: Expr) -> CompileResult {
     compiler.debug_info.name = Some("<main>".into());
     compiler.debug_info.range = expr.range();
-    compiler.compile_fn(iter::empty(), expr);
+    compiler.compile_fn(iter::empty(), None, expr);
     compiler.finish()
 }
 
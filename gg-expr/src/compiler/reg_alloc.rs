@@ -29,27 +29,23 @@ impl RegAlloc {
     }
 
     pub fn alloc_seq(&mut self, len: u16) -> RegSeq {
-        if self.free.len() >= usize::from(len) {
-            self.free.sort_unstable();
+        let len_usize = usize::from(len);
 
-            let mut seq_len = 0;
-            let mut seq_start = None;
+        if len > 0 && self.free.len() >= len_usize {
+            self.free.sort_unstable();
 
-            for window in self.free.windows(2) {
-                let (a, b) = (window[0].0, window[1].0);
-                if b == a + 1 {
-                    seq_len += 1;
+            let mut start = 0;
 
-                    let start = seq_start.unwrap_or(a);
-                    seq_start = Some(start);
+            for i in 0..self.free.len() {
+                if i > start && self.free[i].0 != self.free[i - 1].0 + 1 {
+                    start = i;
+                }
 
-                    if seq_len == len {
-                        let base = RegId(start);
-                        return RegSeq { base, len };
-                    }
-                } else {
-                    seq_len = 0;
-                    seq_start = None;
+                // the registers are taken, so they must leave the free list
+                if i + 1 - start == len_usize {
+                    let base = self.free[start];
+                    self.free.drain(start..=i);
+                    return RegSeq { base, len };
                 }
             }
         }
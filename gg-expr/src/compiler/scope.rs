@@ -2,7 +2,6 @@ use std::collections::HashMap;
 
 use crate::syntax::Ident;
 use crate::vm::{RegId, UpfnId, UpvalueId};
-use crate::Value;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum VarLoc {
@@ -10,7 +9,6 @@ pub enum VarLoc {
     Upvalue(UpvalueId),
     PossibleUpvalue,
     Upfn(UpfnId),
-    Value(Value),
 }
 
 impl From<RegId> for VarLoc {
@@ -31,12 +29,6 @@ impl From<UpfnId> for VarLoc {
     }
 }
 
-impl From<Value> for VarLoc {
-    fn from(v: Value) -> VarLoc {
-        VarLoc::Value(v)
-    }
-}
-
 #[derive(Clone, Debug, Default)]
 pub struct Scope {
     vars: HashMap<Ident, VarLoc>,
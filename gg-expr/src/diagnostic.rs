@@ -113,6 +113,7 @@ impl Display for Component {
 pub struct SourceComponent {
     pub source: Arc<Source>,
     pub labels: Vec<Label>,
+    pub context_lines: u32,
 }
 
 impl SourceComponent {
@@ -120,9 +121,15 @@ impl SourceComponent {
         SourceComponent {
             source,
             labels: Vec::new(),
+            context_lines: 1,
         }
     }
 
+    pub fn with_context_lines(mut self, context_lines: u32) -> SourceComponent {
+        self.context_lines = context_lines;
+        self
+    }
+
     pub fn add_label(&mut self, severity: Severity, range: TextRange, message: impl Into<String>) {
         self.labels.push(Label {
             severity,
@@ -153,7 +160,10 @@ impl Display for SourceComponent {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let max_range = max_range(self.labels.iter().map(|l| l.range));
 
-        let lines = self.source.text.lines_in_range(max_range, 1);
+        let lines = self
+            .source
+            .text
+            .lines_in_range(max_range, self.context_lines);
         if lines.is_empty() {
             return Ok(());
         }
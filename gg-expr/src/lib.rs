@@ -8,19 +8,40 @@ pub mod vm;
 
 use diagnostic::Severity;
 
-pub use self::compiler::{compile, Compiler};
+pub use self::compiler::{compile, compile_with_importer, Compiler, Importer, ResolvedImport};
 pub use self::source::{LineColPos, LineColRange, Source, SourceText};
-pub use self::value::{DebugInfo, ExtFunc, Func, FuncValue, List, Map, Type, Value};
-pub use self::vm::{Error, Result, Vm, VmContext};
+pub use self::value::{
+    DebugInfo, ExtFunc, FromValueError, Func, FuncValue, List, Map, Thunk, ToJsonError, Type, Value,
+};
+pub use self::vm::{Clock, DebugSink, Error, Result, Vm, VmContext};
 use crate::diagnostic::Diagnostic;
 
 pub fn compile_text(env: Map, text: &str) -> (Option<Value>, Vec<Diagnostic>) {
+    compile_text_impl(env, text, None)
+}
+
+pub fn compile_text_with_importer(
+    env: Map,
+    text: &str,
+    importer: Importer,
+) -> (Option<Value>, Vec<Diagnostic>) {
+    compile_text_impl(env, text, Some(importer))
+}
+
+fn compile_text_impl(
+    env: Map,
+    text: &str,
+    importer: Option<Importer>,
+) -> (Option<Value>, Vec<Diagnostic>) {
     let parse_res = syntax::parse(text);
 
     let mut diagnostics = parse_res.diagnostics;
 
     let value = parse_res.expr.map(|e| {
-        let mut compile_res = compile(env, parse_res.source, e);
+        let mut compile_res = match importer {
+            Some(importer) => compile_with_importer(env, parse_res.source, e, importer),
+            None => compile(env, parse_res.source, e),
+        };
         diagnostics.append(&mut compile_res.diagnostics);
         compile_res.func.into()
     });
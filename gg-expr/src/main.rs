@@ -1,6 +1,6 @@
 use eyre::{bail, Result};
 use gg_expr::builtins::builtins;
-use gg_expr::{compile_text, Map, Vm};
+use gg_expr::{compile_text, DebugSink, Map, Vm};
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
 
@@ -68,16 +68,19 @@ impl Context {
         }
 
         let func = match value {
-            Some(v) => v.try_into().unwrap(),
+            Some(v) => v,
             None => return,
         };
 
         if self.show_bytecode {
-            println!("{:?}", func);
-            println!();
+            if let Ok(func) = func.as_func() {
+                println!("{}", func.disassemble());
+            }
         }
 
+        // the repl has no tracing subscriber, print debug output directly
         let mut vm = Vm::new();
+        vm.set_debug_sink(DebugSink::new(|message| eprintln!("{}", message)));
         let t = std::time::Instant::now();
 
         match vm.eval(&func, &[]) {
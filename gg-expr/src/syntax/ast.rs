@@ -173,6 +173,7 @@ define_terms![
     ExprInt,
     ExprFloat,
     ExprString,
+    ExprSymbol,
     ExprBinding,
     ExprBinary,
     ExprUnary,
@@ -185,17 +186,24 @@ define_terms![
     ExprLetIn,
     ExprWhen,
     ExprFn,
+    ExprTryCatch,
+    ExprInterp,
+    ExprImport,
     PatGrouped,
     PatOr,
     PatList,
+    PatMap,
     PatNull,
     PatBool,
     PatInt,
+    PatFloat,
     PatString,
+    PatSymbol,
     PatRest,
     PatHole,
     PatBinding,
     MapPair,
+    PatMapPair,
     LetBinding,
     WhenCase,
 ];
@@ -206,6 +214,7 @@ define_enum!(Expr {
     Int(ExprInt),
     Float(ExprFloat),
     String(ExprString),
+    Symbol(ExprSymbol),
     Binding(ExprBinding),
     Binary(ExprBinary),
     Unary(ExprUnary),
@@ -218,16 +227,22 @@ define_enum!(Expr {
     LetIn(ExprLetIn),
     When(ExprWhen),
     Fn(ExprFn),
+    TryCatch(ExprTryCatch),
+    Interp(ExprInterp),
+    Import(ExprImport),
 });
 
 define_enum!(Pat {
     Grouped(PatGrouped),
     Or(PatOr),
     List(PatList),
+    Map(PatMap),
     Null(PatNull),
     Bool(PatBool),
     Int(PatInt),
+    Float(PatFloat),
     String(PatString),
+    Symbol(PatSymbol),
     Rest(PatRest),
     Hole(PatHole),
     Binding(PatBinding),
@@ -241,6 +256,8 @@ define_single_children! {
     ExprFn: expr -> Expr,
     PatGrouped: pat  -> Pat,
     PatBinding: pat -> Pat,
+    PatMapPair: pat -> Pat,
+    LetBinding: pat -> Pat,
     LetBinding: expr -> Expr,
     WhenCase: pat -> Pat,
     WhenCase: expr -> Expr,
@@ -253,6 +270,8 @@ define_multi_children! {
     ExprWhen: cases -> WhenCase,
     PatOr: pats -> Pat,
     PatList: pats -> Pat,
+    PatMap: pairs -> PatMapPair,
+    PatMap: rests -> PatRest,
 }
 
 impl ExprBool {
@@ -283,6 +302,20 @@ impl ExprString {
     }
 }
 
+impl ExprImport {
+    pub fn path(&self) -> Option<String> {
+        let token = self.nontrivial_tokens().nth(1)?;
+        (token.kind() == SyntaxKind::TokString).then(|| parser::string_value(token.text()))
+    }
+}
+
+impl ExprSymbol {
+    pub fn value(&self) -> Option<String> {
+        let token = self.nontrivial_tokens().next()?;
+        Some(parser::symbol_value(token.text()))
+    }
+}
+
 impl ExprBinding {
     pub fn ident(&self) -> Option<Ident> {
         let token = self.nontrivial_tokens().next()?;
@@ -331,11 +364,42 @@ impl ExprIndex {
 
 impl ExprCall {
     pub fn func(&self) -> Option<Expr> {
-        self.syntax.first_child().and_then(Expr::cast)
+        match self.pipeline_rhs() {
+            Some(Expr::Call(call)) => call.func(),
+            Some(rhs) => Some(rhs),
+            None if self.is_pipeline() => None,
+            None => self.syntax.first_child().and_then(Expr::cast),
+        }
     }
 
     pub fn args(&self) -> impl Iterator<Item = Expr> {
-        self.syntax.children().skip(1).flat_map(Expr::cast)
+        let (lhs, rest) = if self.is_pipeline() {
+            let rest = match self.pipeline_rhs() {
+                Some(Expr::Call(call)) => call.args().collect(),
+                _ => Vec::new(),
+            };
+
+            (self.syntax.first_child().and_then(Expr::cast), rest)
+        } else {
+            let rest = self.syntax.children().skip(1).flat_map(Expr::cast);
+            (None, rest.collect())
+        };
+
+        lhs.into_iter().chain(rest)
+    }
+
+    // `a |> f(b)` is parsed as a call node with `a`, `|>` and `f(b)` as its
+    // children, and is treated as `f(a, b)`
+    pub fn is_pipeline(&self) -> bool {
+        self.tokens().any(|t| t.kind() == SyntaxKind::TokPipeline)
+    }
+
+    fn pipeline_rhs(&self) -> Option<Expr> {
+        if !self.is_pipeline() {
+            return None;
+        }
+
+        self.syntax.children().nth(1).and_then(Expr::cast)
     }
 }
 
@@ -353,6 +417,45 @@ impl ExprIfElse {
     }
 }
 
+impl ExprTryCatch {
+    pub fn expr(&self) -> Option<Expr> {
+        self.syntax.first_child().and_then(Expr::cast)
+    }
+
+    pub fn ident(&self) -> Option<Ident> {
+        self.nontrivial_tokens().find_map(Ident::cast)
+    }
+
+    pub fn handler(&self) -> Option<Expr> {
+        self.syntax.children().nth(1).and_then(Expr::cast)
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum InterpPart {
+    String(String),
+    Expr(Expr),
+}
+
+impl ExprInterp {
+    pub fn parts(&self) -> impl Iterator<Item = InterpPart> {
+        self.syntax.children_with_tokens().flat_map(|el| match el {
+            SyntaxElement::Node(node) => Expr::cast(node).map(InterpPart::Expr),
+            SyntaxElement::Token(token) => {
+                use SyntaxKind::{TokInterpEnd, TokInterpMid, TokInterpStart};
+
+                let kind = token.kind();
+                if !matches!(kind, TokInterpStart | TokInterpMid | TokInterpEnd) {
+                    return None;
+                }
+
+                let value = parser::interp_piece_value(kind, token.text());
+                (!value.is_empty()).then(|| InterpPart::String(value))
+            }
+        })
+    }
+}
+
 impl MapPair {
     pub fn key_expr(&self) -> Option<Expr> {
         if self.key_ident().is_some() {
@@ -372,16 +475,50 @@ impl MapPair {
     }
 }
 
-impl LetBinding {
-    pub fn ident(&self) -> Option<Ident> {
+impl PatMapPair {
+    pub fn key_expr(&self) -> Option<Expr> {
+        if self.key_ident().is_some() {
+            return None;
+        }
+
+        self.syntax.first_child().and_then(Expr::cast)
+    }
+
+    pub fn key_ident(&self) -> Option<Ident> {
         let token = self.nontrivial_tokens().next()?;
         Ident::cast(token)
     }
 }
 
+impl LetBinding {
+    pub fn ident(&self) -> Option<Ident> {
+        match self.pat()? {
+            Pat::Binding(pat) if pat.pat().is_none() => pat.ident(),
+            _ => None,
+        }
+    }
+}
+
 impl ExprFn {
     pub fn args(&self) -> impl Iterator<Item = Ident> {
-        self.nontrivial_tokens().flat_map(Ident::cast)
+        self.arg_tokens()
+            .filter(|&(is_rest, _)| !is_rest)
+            .map(|(_, ident)| ident)
+    }
+
+    pub fn rest_arg(&self) -> Option<Ident> {
+        self.arg_tokens()
+            .find(|&(is_rest, _)| is_rest)
+            .map(|(_, ident)| ident)
+    }
+
+    fn arg_tokens(&self) -> impl Iterator<Item = (bool, Ident)> {
+        let mut prev = None;
+        self.nontrivial_tokens().flat_map(move |token| {
+            let is_rest = prev == Some(SyntaxKind::TokRest);
+            prev = Some(token.kind());
+            Ident::cast(token).map(|ident| (is_rest, ident))
+        })
     }
 }
 
@@ -399,6 +536,13 @@ impl PatInt {
     }
 }
 
+impl PatFloat {
+    pub fn value(&self) -> Option<f32> {
+        let token = self.nontrivial_tokens().next()?;
+        parser::float_value(token.text())
+    }
+}
+
 impl PatString {
     pub fn value(&self) -> Option<String> {
         let token = self.nontrivial_tokens().next()?;
@@ -406,6 +550,20 @@ impl PatString {
     }
 }
 
+impl PatSymbol {
+    pub fn value(&self) -> Option<String> {
+        let token = self.nontrivial_tokens().next()?;
+        Some(parser::symbol_value(token.text()))
+    }
+}
+
+impl PatRest {
+    pub fn ident(&self) -> Option<Ident> {
+        let token = self.nontrivial_tokens().last()?;
+        Ident::cast(token)
+    }
+}
+
 impl PatBinding {
     pub fn ident(&self) -> Option<Ident> {
         let token = self.nontrivial_tokens().last()?;
@@ -3,12 +3,13 @@ use rowan::Language;
 
 #[repr(u16)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Logos)]
-#[logos(subpattern decimal = r"[0-9](?:_*[0-9])*")]
 pub enum SyntaxKind {
     #[regex(r"[ \t\n\f]+")]
     TokWhitespace,
-    #[regex(r"//[^\n]*", logos::skip)]
+    #[regex(r"//[^\n]*")]
+    #[token("/*", block_comment)]
     TokComment,
+    TokUnterminatedComment,
     #[token("+")]
     TokAdd,
     #[token("-")]
@@ -27,6 +28,16 @@ pub enum SyntaxKind {
     TokOr,
     #[token("|")]
     TokPipe,
+    #[token("|>")]
+    TokPipeline,
+    #[token("&")]
+    TokAmp,
+    #[token("^")]
+    TokCaret,
+    #[token("<<")]
+    TokShl,
+    #[token(">>")]
+    TokShr,
     #[token("??")]
     TokCoalesce,
     #[token("!")]
@@ -67,6 +78,10 @@ pub enum SyntaxKind {
     TokComma,
     #[token(":")]
     TokColon,
+    #[token("..")]
+    TokRange,
+    #[token("..=")]
+    TokRangeInclusive,
     #[token("...")]
     TokRest,
     #[token("_")]
@@ -97,13 +112,25 @@ pub enum SyntaxKind {
     TokWhen,
     #[token("is")]
     TokIs,
-    #[regex(r"(?&decimal)", priority = 2)]
-    #[regex(r"0x[0-9a-fA-F](?:_*[0-9a-fA-F])*")]
+    #[token("try")]
+    TokTry,
+    #[token("catch")]
+    TokCatch,
+    #[token("import")]
+    TokImport,
+    // every number is lexed as an int and classified afterwards by
+    // `classify_number`, logos can't back out of `0.` in `0..10`
+    #[regex(r"[0-9][0-9a-zA-Z_]*", number)]
     TokInt,
-    #[regex(r"(?&decimal)(?:\.(?&decimal))?(?:_*[eE][+-]?(?&decimal))?")]
+    TokMalformedInt,
     TokFloat,
-    #[regex(r#""(?:[^"]|\\")*""#)]
+    #[token("\"", string)]
     TokString,
+    TokInterpStart,
+    TokInterpMid,
+    TokInterpEnd,
+    #[regex(r":[_a-zA-Z][_0-9a-zA-Z]*")]
+    TokSymbol,
     #[regex(r"[_a-zA-Z][_0-9a-zA-Z]*")]
     TokIdent,
 
@@ -114,6 +141,7 @@ pub enum SyntaxKind {
     ExprBool,
     ExprFloat,
     ExprString,
+    ExprSymbol,
     ExprBinding,
     ExprBinary,
     ExprUnary,
@@ -126,19 +154,26 @@ pub enum SyntaxKind {
     ExprLetIn,
     ExprWhen,
     ExprFn,
+    ExprTryCatch,
+    ExprInterp,
+    ExprImport,
 
     PatGrouped,
     PatOr,
     PatList,
+    PatMap,
     PatNull,
     PatBool,
     PatInt,
+    PatFloat,
     PatString,
+    PatSymbol,
     PatRest,
     PatHole,
     PatBinding,
 
     MapPair,
+    PatMapPair,
     LetBinding,
     WhenCase,
 
@@ -150,7 +185,10 @@ pub enum SyntaxKind {
 
 impl SyntaxKind {
     pub fn is_trivia(self) -> bool {
-        matches!(self, SyntaxKind::TokWhitespace | SyntaxKind::TokComment)
+        matches!(
+            self,
+            SyntaxKind::TokWhitespace | SyntaxKind::TokComment | SyntaxKind::TokUnterminatedComment
+        )
     }
 
     pub fn explain(self) -> &'static str {
@@ -166,6 +204,11 @@ impl SyntaxKind {
             TokAnd => "`&&`",
             TokOr => "`||`",
             TokPipe => "`|`",
+            TokPipeline => "`|>`",
+            TokAmp => "`&`",
+            TokCaret => "`^`",
+            TokShl => "`<<`",
+            TokShr => "`>>`",
             TokCoalesce => "`??`",
             TokNot => "`!`",
             TokAssign => "`=`",
@@ -186,6 +229,8 @@ impl SyntaxKind {
             TokQuestionDot => "`?.`",
             TokComma => "`,`",
             TokColon => "`:`",
+            TokRange => "`..`",
+            TokRangeInclusive => "`..=`",
             TokRest => "`...`",
             TokHole => "`_`",
             TokArrow => "`->`",
@@ -201,16 +246,193 @@ impl SyntaxKind {
             TokFn => "`fn`",
             TokWhen => "`when`",
             TokIs => "`is`",
+            TokTry => "`try`",
+            TokCatch => "`catch`",
+            TokImport => "`import`",
             TokInt => "int",
+            TokMalformedInt => "malformed integer",
             TokFloat => "float",
-            TokString => "string",
+            TokString | TokInterpStart => "string",
+            TokInterpMid | TokInterpEnd => "`}`",
+            TokSymbol => "symbol",
             TokIdent => "identifier",
+            TokUnterminatedComment => "unterminated block comment",
             TokError => "unrecognized character",
             _ => "?",
         }
     }
 }
 
+fn string(lexer: &mut logos::Lexer<SyntaxKind>) -> bool {
+    match scan_string(lexer.remainder().as_bytes()) {
+        Some(len) => {
+            lexer.bump(len);
+            true
+        }
+        None => {
+            lexer.bump(lexer.remainder().len());
+            false
+        }
+    }
+}
+
+// returns the length of a string literal (without the opening quote),
+// skipping over escapes and `${...}` interpolations
+pub(super) fn scan_string(bytes: &[u8]) -> Option<usize> {
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i + 1),
+            b'$' if bytes.get(i + 1) == Some(&b'{') => i += 2 + scan_interp(&bytes[i + 2..])?,
+            _ => i += 1,
+        }
+    }
+
+    None
+}
+
+// returns the length of an interpolated expression including the closing `}`
+pub(super) fn scan_interp(bytes: &[u8]) -> Option<usize> {
+    let mut depth = 1;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            b'"' => {
+                i += 1 + scan_string(&bytes[i + 1..])?;
+                continue;
+            }
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+// extends a number over its fractional part and a signed exponent
+fn number(lexer: &mut logos::Lexer<SyntaxKind>) {
+    let rest = lexer.remainder().as_bytes();
+    let mut len = 0;
+
+    if rest.first() == Some(&b'.') && rest.get(1).map_or(false, u8::is_ascii_digit) {
+        len = 1 + alnum_len(&rest[1..]);
+    }
+
+    let last = match len {
+        0 => lexer.slice().as_bytes().last().copied(),
+        _ => Some(rest[len - 1]),
+    };
+
+    if matches!(last, Some(b'e' | b'E'))
+        && !lexer.slice().starts_with("0x")
+        && matches!(rest.get(len), Some(b'+' | b'-'))
+        && rest.get(len + 1).map_or(false, u8::is_ascii_digit)
+    {
+        len += 1 + alnum_len(&rest[len + 1..]);
+    }
+
+    lexer.bump(len);
+}
+
+fn alnum_len(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .take_while(|&&c| c.is_ascii_alphanumeric() || c == b'_')
+        .count()
+}
+
+pub(super) fn classify_number(text: &str) -> SyntaxKind {
+    let radix = match text.get(..2) {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ => 10,
+    };
+
+    if radix != 10 {
+        return if is_digits(&text[2..], radix) {
+            SyntaxKind::TokInt
+        } else {
+            SyntaxKind::TokMalformedInt
+        };
+    }
+
+    if is_digits(text, 10) {
+        return SyntaxKind::TokInt;
+    }
+
+    let (mantissa, exponent) = match text.find(['e', 'E']) {
+        Some(i) => {
+            let exponent = &text[i + 1..];
+            let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            (text[..i].trim_end_matches('_'), Some(exponent))
+        }
+        None => (text, None),
+    };
+
+    let valid_mantissa = match mantissa.split_once('.') {
+        Some((int, frac)) => is_digits(int, 10) && is_digits(frac, 10),
+        None => is_digits(mantissa, 10),
+    };
+
+    let valid_exponent = match exponent {
+        Some(exponent) => is_digits(exponent, 10),
+        None => mantissa.contains('.'),
+    };
+
+    if valid_mantissa && valid_exponent {
+        SyntaxKind::TokFloat
+    } else {
+        SyntaxKind::TokMalformedInt
+    }
+}
+
+// digits separated by single underscores
+fn is_digits(text: &str, radix: u32) -> bool {
+    !text.is_empty()
+        && !text.starts_with('_')
+        && !text.ends_with('_')
+        && !text.contains("__")
+        && text.chars().all(|c| c == '_' || c.is_digit(radix))
+}
+
+fn block_comment(lexer: &mut logos::Lexer<SyntaxKind>) -> bool {
+    let rest = lexer.remainder().as_bytes();
+    let mut depth = 1;
+    let mut i = 0;
+
+    while i < rest.len() {
+        if rest[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if rest[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+
+            if depth == 0 {
+                lexer.bump(i);
+                return true;
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    lexer.bump(rest.len());
+    false
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ExprLang;
 
@@ -1,13 +1,60 @@
+use std::collections::VecDeque;
+
+use logos::Logos;
+
+use super::kind::{classify_number, scan_interp};
 use super::SyntaxKind;
 
 pub struct Lexer<'s> {
     lexer: logos::Lexer<'s, SyntaxKind>,
+    prev: Option<SyntaxKind>,
+    pending: VecDeque<(&'s str, SyntaxKind)>,
 }
 
 impl Lexer<'_> {
     pub fn new(source: &str) -> Lexer<'_> {
         Lexer {
             lexer: logos::Lexer::new(source),
+            prev: None,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl<'s> Lexer<'s> {
+    // splits `"a ${x} b"` into `"a ${`, tokens of `x` and `} b"`
+    fn split_interp(&mut self, slice: &'s str) {
+        let bytes = slice.as_bytes();
+        let mut start = 0;
+        let mut i = 1;
+
+        while i + 1 < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'$' if bytes[i + 1] == b'{' => {
+                    let kind = if start == 0 {
+                        SyntaxKind::TokInterpStart
+                    } else {
+                        SyntaxKind::TokInterpMid
+                    };
+                    self.pending.push_back((&slice[start..i + 2], kind));
+
+                    let len = scan_interp(&bytes[i + 2..]).unwrap_or(bytes.len() - i - 2);
+                    let end = i + 1 + len;
+                    self.pending.extend(Lexer::new(&slice[i + 2..end]));
+
+                    start = end;
+                    i = end + 1;
+                }
+                _ => i += 1,
+            }
+        }
+
+        if start == 0 {
+            self.pending.push_back((slice, SyntaxKind::TokString));
+        } else {
+            self.pending
+                .push_back((&slice[start..], SyntaxKind::TokInterpEnd));
         }
     }
 }
@@ -16,8 +63,50 @@ impl<'s> Iterator for Lexer<'s> {
     type Item = (&'s str, SyntaxKind);
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some((slice, token)) = self.pending.pop_front() {
+            if !token.is_trivia() {
+                self.prev = Some(token);
+            }
+            return Some((slice, token));
+        }
+
         let token = self.lexer.next()?;
         let slice = self.lexer.slice();
+
+        let token = match token {
+            SyntaxKind::TokInt => classify_number(slice),
+            _ => token,
+        };
+
+        // `fn(x):x` is a colon followed by the body, not a symbol
+        if token == SyntaxKind::TokSymbol && self.prev == Some(SyntaxKind::TokRParen) {
+            let rest = &slice[1..];
+            let kind = SyntaxKind::lexer(rest)
+                .next()
+                .unwrap_or(SyntaxKind::TokError);
+            self.pending.push_back((rest, kind));
+            self.prev = Some(SyntaxKind::TokColon);
+            return Some((&slice[..1], SyntaxKind::TokColon));
+        }
+
+        // an unterminated block comment is reported at its opening `/*`
+        if token == SyntaxKind::TokError && slice.starts_with("/*") {
+            if slice.len() > 2 {
+                self.pending
+                    .push_back((&slice[2..], SyntaxKind::TokComment));
+            }
+            return Some((&slice[..2], SyntaxKind::TokUnterminatedComment));
+        }
+
+        if token == SyntaxKind::TokString && slice.contains("${") {
+            self.split_interp(slice);
+            return self.next();
+        }
+
+        if !token.is_trivia() {
+            self.prev = Some(token);
+        }
+
         Some((slice, token))
     }
 }
@@ -2,6 +2,7 @@
 
 use std::collections::HashMap;
 use std::iter::Peekable;
+use std::ops::Range;
 use std::sync::Arc;
 
 use rowan::{Checkpoint, GreenNodeBuilder, TextRange, TextSize};
@@ -16,6 +17,9 @@ pub struct Parser<'s> {
     builder: GreenNodeBuilder<'static>,
     recovery_set: HashMap<SyntaxKind, u32>,
     errors: Vec<String>,
+    escape_errors: Vec<(String, TextRange)>,
+    offset: TextSize,
+    no_in: bool,
 }
 
 impl Parser<'_> {
@@ -25,6 +29,9 @@ impl Parser<'_> {
             builder: GreenNodeBuilder::new(),
             recovery_set: HashMap::default(),
             errors: Vec::new(),
+            escape_errors: Vec::new(),
+            offset: TextSize::from(0),
+            no_in: false,
         }
     }
 
@@ -43,10 +50,13 @@ impl Parser<'_> {
             }
         });
 
+        let escape_errors = self.escape_errors.into_iter();
+
         let errors = self
             .errors
             .into_iter()
             .zip(error_ranges)
+            .chain(escape_errors)
             .map(|(error, mut range)| {
                 let one = TextSize::from(1);
 
@@ -73,8 +83,13 @@ impl Parser<'_> {
 
     fn skip_trivia(&mut self) {
         while let Some(&(text, token)) = self.lexer.peek() {
-            if token.is_trivia() {
-                self.builder.token(token.into(), text);
+            if token == TokUnterminatedComment {
+                self.start_error("unterminated block comment");
+                self.token(token, text);
+                self.finish_node();
+                self.lexer.next();
+            } else if token.is_trivia() {
+                self.token(token, text);
                 self.lexer.next();
             } else {
                 break;
@@ -89,10 +104,29 @@ impl Parser<'_> {
 
     fn bump(&mut self) {
         if let Some((text, token)) = self.lexer.next() {
-            self.builder.token(token.into(), text);
+            self.token(token, text);
         }
     }
 
+    fn token(&mut self, token: SyntaxKind, text: &str) {
+        if matches!(
+            token,
+            TokString | TokInterpStart | TokInterpMid | TokInterpEnd
+        ) {
+            let start = self.offset + TextSize::from(1);
+            unescape_with(string_contents(token, text), |range, message| {
+                let range = TextRange::new(
+                    TextSize::try_from(range.start).unwrap(),
+                    TextSize::try_from(range.end).unwrap(),
+                );
+                self.escape_errors.push((message.into(), range + start));
+            });
+        }
+
+        self.offset += TextSize::of(text);
+        self.builder.token(token.into(), text);
+    }
+
     fn checkpoint(&self) -> Checkpoint {
         self.builder.checkpoint()
     }
@@ -153,6 +187,24 @@ impl Parser<'_> {
         self.finish_node();
     }
 
+    fn error_malformed_int(&mut self) {
+        let text = self.lexer.peek().map(|&(text, _)| text).unwrap_or_default();
+
+        let message = if matches!(text, "0x" | "0o" | "0b") {
+            format!("expected digits after `{}`", text)
+        } else if text.contains("__") {
+            "consecutive underscores in integer literal".into()
+        } else if text.ends_with('_') {
+            "trailing underscore in integer literal".into()
+        } else {
+            format!("invalid digit in integer literal `{}`", text)
+        };
+
+        self.start_error(message);
+        self.bump();
+        self.finish_node();
+    }
+
     fn expect_one_of(&mut self, expected: &[SyntaxKind]) {
         if let Some(token) = self.peek() {
             if expected.contains(&token) {
@@ -217,6 +269,18 @@ impl Parser<'_> {
         self.expr_bp(0)
     }
 
+    fn expr_delimited(&mut self) {
+        let no_in = std::mem::replace(&mut self.no_in, false);
+        self.expr();
+        self.no_in = no_in;
+    }
+
+    fn expr_no_in(&mut self) {
+        let no_in = std::mem::replace(&mut self.no_in, true);
+        self.expr();
+        self.no_in = no_in;
+    }
+
     fn expr_bp(&mut self, min_bp: u8) {
         let root = self.checkpoint();
 
@@ -240,11 +304,18 @@ impl Parser<'_> {
             }
 
             if let Some((l_bp, r_bp)) = infix_bp(token) {
-                if l_bp < min_bp {
+                if l_bp < min_bp || (token == TokIn && self.no_in) {
                     break;
                 }
 
-                self.start_node_at(root, ExprBinary);
+                // `a |> f(b)` is kept as a call node, see `ExprCall::args`
+                let kind = if token == TokPipeline {
+                    ExprCall
+                } else {
+                    ExprBinary
+                };
+
+                self.start_node_at(root, kind);
                 self.bump();
                 self.expr_bp(r_bp);
                 self.finish_node();
@@ -272,11 +343,16 @@ impl Parser<'_> {
             Some(TokLet) => self.expr_let_in(root),
             Some(TokIf) => self.expr_if_else(root),
             Some(TokWhen) => self.expr_when(root),
+            Some(TokTry) => self.expr_try_catch(root),
+            Some(TokImport) => self.expr_import(root),
             Some(TokNull) => self.expr_null(root),
             Some(TokTrue | TokFalse) => self.expr_bool(root),
             Some(TokInt) => self.expr_int(root),
+            Some(TokMalformedInt) => self.error_malformed_int(),
             Some(TokFloat) => self.expr_float(root),
             Some(TokString) => self.expr_string(root),
+            Some(TokInterpStart) => self.expr_interp(root),
+            Some(TokSymbol) => self.expr_symbol(root),
             Some(TokIdent) => self.expr_binding(root),
             _ => self.error_unexpected_token("expression"),
         }
@@ -286,7 +362,7 @@ impl Parser<'_> {
         self.start_node_at(root, ExprGrouped);
         self.expect(TokLParen);
         self.push_recovery(&[TokRParen]);
-        self.expr();
+        self.expr_delimited();
         self.pop_recovery();
         self.expect(TokRParen);
         self.finish_node();
@@ -295,7 +371,7 @@ impl Parser<'_> {
     fn expr_list(&mut self, root: Checkpoint) {
         self.start_node_at(root, ExprList);
         self.expect(TokLBracket);
-        self.comma_separated(TokRBracket, |s| s.expr());
+        self.comma_separated(TokRBracket, |s| s.expr_delimited());
         self.expect(TokRBracket);
         self.finish_node();
     }
@@ -317,10 +393,14 @@ impl Parser<'_> {
                     s.expr_string(s.checkpoint());
                     false
                 }
+                Some(TokSymbol) => {
+                    s.expr_symbol(s.checkpoint());
+                    false
+                }
                 Some(TokLBracket) => {
                     s.bump();
                     s.push_recovery(&[TokRBracket]);
-                    s.expr();
+                    s.expr_delimited();
                     s.pop_recovery();
                     s.expect(TokRBracket);
                     false
@@ -335,7 +415,7 @@ impl Parser<'_> {
 
             if s.peek() == Some(TokAssign) || !is_ident {
                 s.expect(TokAssign);
-                s.expr();
+                s.expr_delimited();
             }
 
             s.finish_node();
@@ -351,7 +431,13 @@ impl Parser<'_> {
         self.push_recovery(&[TokColon]);
 
         self.expect(TokLParen);
-        self.comma_separated(TokRParen, |s| s.expect(TokIdent));
+        self.comma_separated(TokRParen, |s| {
+            if s.peek() == Some(TokRest) {
+                s.bump();
+            }
+
+            s.expect(TokIdent);
+        });
         self.expect(TokRParen);
 
         self.pop_recovery();
@@ -366,9 +452,9 @@ impl Parser<'_> {
 
         self.comma_separated(TokIn, |s| {
             s.start_node(LetBinding);
-            s.expect(TokIdent);
+            s.pat();
             s.expect(TokAssign);
-            s.expr();
+            s.expr_no_in();
             s.finish_node();
         });
 
@@ -421,10 +507,25 @@ impl Parser<'_> {
         self.finish_node();
     }
 
+    fn expr_try_catch(&mut self, root: Checkpoint) {
+        self.start_node_at(root, ExprTryCatch);
+        self.expect(TokTry);
+        self.push_recovery(&[TokCatch]);
+        self.expr();
+        self.pop_recovery();
+        self.expect(TokCatch);
+        self.push_recovery(&[TokArrow]);
+        self.expect(TokIdent);
+        self.pop_recovery();
+        self.expect(TokArrow);
+        self.expr();
+        self.finish_node();
+    }
+
     fn expr_call(&mut self, root: Checkpoint) {
         self.start_node_at(root, ExprCall);
         self.expect(TokLParen);
-        self.comma_separated(TokRParen, |s| s.expr());
+        self.comma_separated(TokRParen, |s| s.expr_delimited());
         self.expect(TokRParen);
         self.finish_node();
     }
@@ -447,7 +548,7 @@ impl Parser<'_> {
             self.expect(TokIdent);
         } else {
             self.push_recovery(&[TokRBracket]);
-            self.expr();
+            self.expr_delimited();
             self.pop_recovery();
             self.expect(TokRBracket);
         }
@@ -455,6 +556,13 @@ impl Parser<'_> {
         self.finish_node();
     }
 
+    fn expr_import(&mut self, root: Checkpoint) {
+        self.start_node_at(root, ExprImport);
+        self.expect(TokImport);
+        self.expect(TokString);
+        self.finish_node();
+    }
+
     fn expr_null(&mut self, root: Checkpoint) {
         self.start_node_at(root, ExprNull);
         self.expect(TokNull);
@@ -485,6 +593,31 @@ impl Parser<'_> {
         self.finish_node();
     }
 
+    fn expr_interp(&mut self, root: Checkpoint) {
+        self.start_node_at(root, ExprInterp);
+        self.expect(TokInterpStart);
+
+        loop {
+            self.push_recovery(&[TokInterpMid, TokInterpEnd]);
+            self.expr_delimited();
+            self.pop_recovery();
+
+            match self.peek() {
+                Some(TokInterpMid) => self.bump(),
+                _ => break,
+            }
+        }
+
+        self.expect(TokInterpEnd);
+        self.finish_node();
+    }
+
+    fn expr_symbol(&mut self, root: Checkpoint) {
+        self.start_node_at(root, ExprSymbol);
+        self.expect(TokSymbol);
+        self.finish_node();
+    }
+
     fn expr_binding(&mut self, root: Checkpoint) {
         self.start_node_at(root, ExprBinding);
         self.expect(TokIdent);
@@ -515,11 +648,15 @@ impl Parser<'_> {
         match self.peek() {
             Some(TokLParen) => self.pat_grouped(),
             Some(TokLBracket) => self.pat_list(),
+            Some(TokLBrace) => self.pat_map(),
             Some(TokRest) => self.pat_rest(),
             Some(TokNull) => self.pat_null(),
             Some(TokTrue | TokFalse) => self.pat_bool(),
             Some(TokInt) => self.pat_int(),
+            Some(TokFloat) => self.pat_float(),
+            Some(TokMalformedInt) => self.error_malformed_int(),
             Some(TokString) => self.pat_string(),
+            Some(TokSymbol) => self.pat_symbol(),
             Some(TokIdent) => self.pat_binding(),
             Some(TokHole) => self.pat_hole(),
             _ => self.error_unexpected_token("pattern"),
@@ -553,9 +690,67 @@ impl Parser<'_> {
         self.finish_node();
     }
 
+    fn pat_map(&mut self) {
+        self.start_node(PatMap);
+        self.expect(TokLBrace);
+        self.push_recovery(&[TokRBrace]);
+
+        self.comma_separated(TokRBrace, |s| {
+            if s.peek() == Some(TokRest) {
+                s.pat_rest();
+                return;
+            }
+
+            s.start_node(PatMapPair);
+            s.push_recovery(&[TokAssign]);
+
+            let is_ident = match s.peek() {
+                Some(TokIdent) => {
+                    s.bump();
+                    true
+                }
+                Some(TokString) => {
+                    s.expr_string(s.checkpoint());
+                    false
+                }
+                Some(TokSymbol) => {
+                    s.expr_symbol(s.checkpoint());
+                    false
+                }
+                // rejected by the compiler, keys must be constant
+                Some(TokInterpStart) => {
+                    s.expr_interp(s.checkpoint());
+                    false
+                }
+                _ => {
+                    s.error_unexpected_token("map key");
+                    false
+                }
+            };
+
+            s.pop_recovery();
+
+            if s.peek() == Some(TokAssign) || !is_ident {
+                s.expect(TokAssign);
+                s.pat();
+            }
+
+            s.finish_node();
+        });
+
+        self.pop_recovery();
+        self.expect(TokRBrace);
+        self.finish_node();
+    }
+
     fn pat_rest(&mut self) {
         self.start_node(PatRest);
         self.expect(TokRest);
+
+        if self.peek() == Some(TokIdent) {
+            self.bump();
+        }
+
         self.finish_node();
     }
 
@@ -577,12 +772,24 @@ impl Parser<'_> {
         self.finish_node();
     }
 
+    fn pat_float(&mut self) {
+        self.start_node(PatFloat);
+        self.expect(TokFloat);
+        self.finish_node();
+    }
+
     fn pat_string(&mut self) {
         self.start_node(PatString);
         self.expect(TokString);
         self.finish_node();
     }
 
+    fn pat_symbol(&mut self) {
+        self.start_node(PatSymbol);
+        self.expect(TokSymbol);
+        self.finish_node();
+    }
+
     fn pat_binding(&mut self) {
         self.start_node(PatBinding);
         self.expect(TokIdent);
@@ -606,44 +813,143 @@ pub struct ParseResult {
 
 fn prefix_bp(token: SyntaxKind) -> Option<u8> {
     Some(match token {
-        TokSub | TokNot => 14,
+        TokSub | TokNot => 26,
         _ => return None,
     })
 }
 
 fn infix_bp(token: SyntaxKind) -> Option<(u8, u8)> {
     Some(match token {
-        TokOr | TokCoalesce => (1, 2),
-        TokAnd => (3, 4),
-        TokEq | TokNeq => (5, 6),
-        TokLt | TokLe | TokGe | TokGt => (7, 8),
-        TokAdd | TokSub => (9, 10),
-        TokMul | TokDiv | TokRem => (11, 12),
-        TokPow => (15, 16),
+        TokPipeline => (1, 2),
+        TokOr | TokCoalesce => (3, 4),
+        TokAnd => (5, 6),
+        TokEq | TokNeq => (7, 8),
+        TokLt | TokLe | TokGe | TokGt | TokIn => (9, 10),
+        TokRange | TokRangeInclusive => (11, 12),
+        TokPipe => (13, 14),
+        TokCaret => (15, 16),
+        TokAmp => (17, 18),
+        TokShl | TokShr => (19, 20),
+        TokAdd | TokSub => (21, 22),
+        TokMul | TokDiv | TokRem => (23, 24),
+        TokPow => (27, 28),
         _ => return None,
     })
 }
 
 fn postfix_bp(token: SyntaxKind) -> Option<u8> {
     Some(match token {
-        TokLParen | TokLBracket | TokQuestionLBracket | TokDot | TokQuestionDot => 17,
+        TokLParen | TokLBracket | TokQuestionLBracket | TokDot | TokQuestionDot => 29,
         _ => return None,
     })
 }
 
 pub fn int_value(text: &str) -> Option<i32> {
-    text.parse().ok()
+    let text = text.replace('_', "");
+    let (digits, radix) = match text.get(..2) {
+        Some("0x") => (&text[2..], 16),
+        Some("0o") => (&text[2..], 8),
+        Some("0b") => (&text[2..], 2),
+        _ => (&text[..], 10),
+    };
+
+    i32::from_str_radix(digits, radix).ok()
 }
 
 pub fn float_value(text: &str) -> Option<f32> {
-    text.parse().ok()
+    text.replace('_', "").parse().ok()
 }
 
 pub fn string_value(text: &str) -> String {
-    text[1..text.len() - 1]
-        .replace("\\\\", "\\")
-        .replace("\\\"", "\"")
-        .replace("\\r", "\r")
-        .replace("\\n", "\n")
-        .replace("\\t", "\t")
+    interp_piece_value(TokString, text)
+}
+
+pub fn interp_piece_value(kind: SyntaxKind, text: &str) -> String {
+    unescape_with(string_contents(kind, text), |_, _| {})
+}
+
+fn string_contents(kind: SyntaxKind, text: &str) -> &str {
+    match kind {
+        TokInterpStart | TokInterpMid => &text[1..text.len() - 2],
+        _ => &text[1..text.len() - 1],
+    }
+}
+
+// invalid escapes are kept verbatim after being reported to `on_error`
+fn unescape_with(text: &str, mut on_error: impl FnMut(Range<usize>, &str)) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+
+        let escaped = match chars.next() {
+            Some((_, '\\')) => '\\',
+            Some((_, '"')) => '"',
+            Some((_, '$')) => '$',
+            Some((_, 'r')) => '\r',
+            Some((_, 'n')) => '\n',
+            Some((_, 't')) => '\t',
+            Some((_, '0')) => '\0',
+            Some((_, 'u')) => {
+                let mut end = start + 2;
+                let mut digits = None;
+
+                if chars.next_if(|&(_, c)| c == '{').is_some() {
+                    end += 1;
+                    let digits_start = end;
+
+                    while let Some((i, c)) = chars.next_if(|&(_, c)| c.is_ascii_alphanumeric()) {
+                        end = i + c.len_utf8();
+                    }
+
+                    if let Some((i, _)) = chars.next_if(|&(_, c)| c == '}') {
+                        digits = Some(&text[digits_start..i]);
+                        end = i + 1;
+                    }
+                }
+
+                let range = start..end;
+                let code = match digits {
+                    Some(digits) if (1..=6).contains(&digits.len()) => {
+                        u32::from_str_radix(digits, 16).ok()
+                    }
+                    _ => None,
+                };
+
+                match code.map(char::from_u32) {
+                    Some(Some(c)) => c,
+                    Some(None) => {
+                        on_error(range.clone(), "invalid unicode codepoint");
+                        res.push_str(&text[range]);
+                        continue;
+                    }
+                    None => {
+                        on_error(
+                            range.clone(),
+                            "invalid unicode escape, expected `\\u{XXXX}`",
+                        );
+                        res.push_str(&text[range]);
+                        continue;
+                    }
+                }
+            }
+            Some((_, c)) => {
+                res.push('\\');
+                c
+            }
+            None => '\\',
+        };
+
+        res.push(escaped);
+    }
+
+    res
+}
+
+pub fn symbol_value(text: &str) -> String {
+    text[1..].into()
 }
@@ -0,0 +1,301 @@
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::vm::{CompiledConsts, CompiledInstrs, Instr, Opcode, Upvalues};
+use crate::{Error, Func, List, Map, Result, Thunk, Type, Value};
+
+const MAGIC: &[u8; 4] = b"GGBC";
+const VERSION: u16 = 4;
+const MAX_DEPTH: usize = 128;
+
+pub fn encode(func: &Func) -> Vec<u8> {
+    let mut encoder = Encoder { buf: Vec::new() };
+    encoder.buf.extend_from_slice(MAGIC);
+    encoder.u16(VERSION);
+    encoder.func(func);
+    encoder.buf
+}
+
+pub fn decode(bytes: &[u8], env: &Map) -> Result<Func> {
+    let mut decoder = Decoder {
+        bytes,
+        env,
+        depth: 0,
+    };
+
+    if decoder.take(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+        return Err(error("not a compiled function"));
+    }
+
+    let version = decoder.u16()?;
+    if version != VERSION {
+        let message = format!(
+            "unsupported bytecode version {} (expected {})",
+            version, VERSION
+        );
+        return Err(error(message));
+    }
+
+    let func = decoder.func()?;
+
+    if !decoder.bytes.is_empty() {
+        return Err(error("unexpected data after compiled function"));
+    }
+
+    Ok(func)
+}
+
+fn error(message: impl Into<String>) -> Error {
+    Error::new(Diagnostic::new(Severity::Error, message))
+}
+
+struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    fn u16(&mut self, v: u16) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        self.u32(len as u32);
+    }
+
+    fn str(&mut self, v: &str) {
+        self.len(v.len());
+        self.buf.extend_from_slice(v.as_bytes());
+    }
+
+    fn func(&mut self, func: &Func) {
+        self.u16(func.arity);
+        self.u8(func.variadic as u8);
+        self.u16(func.slots);
+
+        self.len(func.instrs.0.len());
+        for instr in func.instrs.0.iter() {
+            self.u8(instr.opcode as u8);
+            for operand in instr.operands {
+                self.u16(operand);
+            }
+        }
+
+        self.len(func.consts.0.len());
+        for value in func.consts.0.iter() {
+            self.value(value);
+        }
+
+        self.len(func.upvalues.0.len());
+        for value in func.upvalues.0.iter() {
+            self.value(value);
+        }
+    }
+
+    fn value(&mut self, value: &Value) {
+        self.u8(value.ty() as u8);
+
+        match value.ty() {
+            Type::Null => {}
+            Type::Int => self.u32(value.as_int().unwrap() as u32),
+            Type::Float => self.u32(value.as_float().unwrap().to_bits()),
+            Type::Bool => self.u8(value.as_bool().unwrap() as u8),
+            Type::String => self.str(value.as_string().unwrap()),
+            Type::Func => self.func(value.as_func().unwrap()),
+            Type::ExtFunc => {
+                let func = value.as_ext_func().unwrap();
+                self.str(func.name.as_deref().unwrap_or_default());
+                self.u16(func.arity);
+            }
+            Type::List => {
+                let list = value.as_list().unwrap();
+                self.len(list.len());
+                for value in list {
+                    self.value(value);
+                }
+            }
+            Type::Map => {
+                let map = value.as_map().unwrap();
+                self.len(map.len());
+                for (key, value) in map {
+                    self.value(key);
+                    self.value(value);
+                }
+            }
+            Type::Symbol => self.str(value.as_symbol().unwrap()),
+            Type::Thunk => self.value(&value.as_thunk().unwrap().func),
+        }
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    env: &'a Map,
+    depth: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(error("unexpected end of compiled function"));
+        }
+
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    // `min_size` is the smallest encoding of an element, which keeps the
+    // preallocated capacity proportional to the remaining input
+    fn len(&mut self, min_size: usize) -> Result<usize> {
+        let len = self.u32()? as usize;
+
+        if len.saturating_mul(min_size) > self.bytes.len() {
+            return Err(error("unexpected end of compiled function"));
+        }
+
+        Ok(len)
+    }
+
+    fn nested<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.depth == MAX_DEPTH {
+            return Err(error("compiled function is nested too deeply"));
+        }
+
+        self.depth += 1;
+        let res = f(self);
+        self.depth -= 1;
+        res
+    }
+
+    fn str(&mut self) -> Result<&'a str> {
+        let len = self.len(1)?;
+        std::str::from_utf8(self.take(len)?).map_err(|_| error("invalid utf-8 in string"))
+    }
+
+    fn func(&mut self) -> Result<Func> {
+        let arity = self.u16()?;
+        let variadic = self.u8()? != 0;
+        let slots = self.u16()?;
+
+        // an opcode and three operands
+        let num_instrs = self.len(7)?;
+        let mut instrs = Vec::with_capacity(num_instrs);
+        for _ in 0..num_instrs {
+            let opcode = self.u8()?;
+            let opcode = Opcode::from_u8(opcode)
+                .ok_or_else(|| error(format!("invalid opcode {}", opcode)))?;
+            let operands = [self.u16()?, self.u16()?, self.u16()?];
+            instrs.push(Instr { opcode, operands });
+        }
+
+        let num_consts = self.len(1)?;
+        let consts = (0..num_consts)
+            .map(|_| self.value())
+            .collect::<Result<Vec<_>>>()?;
+
+        let num_upvalues = self.len(1)?;
+        let upvalues = (0..num_upvalues)
+            .map(|_| self.value())
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Func {
+            arity,
+            variadic,
+            slots,
+            instrs: CompiledInstrs(instrs.into()),
+            consts: CompiledConsts(consts.into()),
+            upvalues: Upvalues(upvalues.into()),
+            debug_info: None,
+        })
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        let tag = self.u8()?;
+        let ty = Type::VALUES
+            .get(usize::from(tag))
+            .copied()
+            .ok_or_else(|| error(format!("invalid value tag {}", tag)))?;
+
+        Ok(match ty {
+            Type::Null => Value::null(),
+            Type::Int => Value::from(self.u32()? as i32),
+            Type::Float => Value::from(f32::from_bits(self.u32()?)),
+            Type::Bool => Value::from(self.u8()? != 0),
+            Type::String => Value::from(self.str()?),
+            Type::Func => Value::from(self.nested(Self::func)?),
+            Type::ExtFunc => {
+                let name = self.str()?;
+                let arity = self.u16()?;
+                self.ext_func(name, arity)?
+            }
+            Type::List => {
+                let len = self.len(1)?;
+                let list =
+                    self.nested(|s| (0..len).map(|_| s.value()).collect::<Result<List>>())?;
+                Value::from(list)
+            }
+            Type::Map => {
+                let len = self.len(2)?;
+                let map = self.nested(|s| {
+                    (0..len)
+                        .map(|_| Ok((s.value()?, s.value()?)))
+                        .collect::<Result<Map>>()
+                })?;
+                Value::from(map)
+            }
+            Type::Symbol => Value::from_symbol(self.str()?),
+            Type::Thunk => Value::from(Thunk::new(self.nested(Self::value)?)),
+        })
+    }
+
+    // external functions are stored by their qualified name and looked up in
+    // the environment, e.g. `string.len` is `env["string"]["len"]`
+    fn ext_func(&self, name: &str, arity: u16) -> Result<Value> {
+        let not_found = || error(format!("cannot find external function `{}`", name));
+
+        if name.is_empty() {
+            return Err(error("cannot load an unnamed external function"));
+        }
+
+        let mut segments = name.split('.');
+        let first = segments.next().unwrap_or_default();
+        let mut value = self.env.get(&Value::from(first)).ok_or_else(not_found)?;
+
+        for segment in segments {
+            let map = value.as_map().map_err(|_| not_found())?;
+            value = map.get(&Value::from(segment)).ok_or_else(not_found)?;
+        }
+
+        match value.as_ext_func() {
+            Ok(func) if func.arity == arity => Ok(value.clone()),
+            Ok(func) => {
+                let message = format!(
+                    "external function `{}` takes {} arguments, expected {}",
+                    name, func.arity, arity
+                );
+                Err(error(message))
+            }
+            Err(_) => Err(not_found()),
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use std::fmt::{self, Debug};
 use std::hash::{Hash, Hasher};
 
-use crate::{Result, Value, VmContext};
+use super::FromValueError;
+use crate::builtins::any_error;
+use crate::{Error, Result, Value, VmContext};
 
 pub struct ExtFunc {
     pub arity: u16,
@@ -18,11 +20,61 @@ impl ExtFunc {
             arity: N as u16,
             name: None,
             func: Box::new(move |ctx, args| {
-                let args = <&[Value; N]>::try_from(args).unwrap();
+                let args =
+                    <&[Value; N]>::try_from(args).map_err(|_| arity_error(ctx, N, args.len()))?;
                 func(ctx, args)
             }),
         }
     }
+
+    // typed constructors, arguments are converted with `TryFrom<&Value>` and
+    // a conversion failure is reported at the offending argument
+    pub fn new_1<A, R, F>(name: &str, func: F) -> ExtFunc
+    where
+        A: for<'a> TryFrom<&'a Value, Error = FromValueError>,
+        R: Into<Value>,
+        F: Fn(A) -> R + 'static,
+    {
+        ExtFunc::new(move |ctx, [a]: &[Value; 1]| {
+            let a = convert_arg(ctx, 0, a)?;
+            Ok(func(a).into())
+        })
+        .with_name(name)
+    }
+
+    pub fn new_2<A, B, R, F>(name: &str, func: F) -> ExtFunc
+    where
+        A: for<'a> TryFrom<&'a Value, Error = FromValueError>,
+        B: for<'a> TryFrom<&'a Value, Error = FromValueError>,
+        R: Into<Value>,
+        F: Fn(A, B) -> R + 'static,
+    {
+        ExtFunc::new(move |ctx, [a, b]: &[Value; 2]| {
+            let a = convert_arg(ctx, 0, a)?;
+            let b = convert_arg(ctx, 1, b)?;
+            Ok(func(a, b).into())
+        })
+        .with_name(name)
+    }
+
+    pub fn with_name(mut self, name: &str) -> ExtFunc {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+fn convert_arg<A>(ctx: &VmContext, idx: usize, value: &Value) -> Result<A>
+where
+    A: for<'a> TryFrom<&'a Value, Error = FromValueError>,
+{
+    A::try_from(value).map_err(|e| any_error(ctx, idx, e))
+}
+
+#[cold]
+fn arity_error(ctx: &VmContext, expected: usize, found: usize) -> Error {
+    let range = ctx.cur_ranges().and_then(|v| v.first().copied());
+    let message = format!("expected {} arguments, got {}", expected, found);
+    ctx.error(range, message, |_, _| ())
 }
 
 type DynFn = dyn Fn(&VmContext, &[Value]) -> Result<Value>;
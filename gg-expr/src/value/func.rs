@@ -5,13 +5,16 @@ use std::sync::Arc;
 
 use indenter::indented;
 
+use super::bytecode;
+use crate::builtins::builtins;
 use crate::syntax::TextRange;
-use crate::vm::{CompiledConsts, CompiledInstrs, InstrIdx, Upvalues};
-use crate::Source;
+use crate::vm::{CompiledConsts, CompiledInstrs, InstrIdx, Opcode, Operand, Upvalues};
+use crate::{Map, Result, Source};
 
 #[derive(Clone)]
 pub struct Func {
     pub arity: u16,
+    pub variadic: bool,
     pub slots: u16,
     pub instrs: CompiledInstrs,
     pub consts: CompiledConsts,
@@ -19,6 +22,127 @@ pub struct Func {
     pub debug_info: Option<Arc<DebugInfo>>,
 }
 
+impl Func {
+    // debug info is not serialized
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bytecode::encode(self)
+    }
+
+    // external functions are resolved against the builtins
+    pub fn from_bytes(bytes: &[u8]) -> Result<Func> {
+        Func::from_bytes_with_env(bytes, &builtins())
+    }
+
+    pub fn from_bytes_with_env(bytes: &[u8], env: &Map) -> Result<Func> {
+        bytecode::decode(bytes, env)
+    }
+
+    fn location(&self) -> Option<(&str, TextRange, Option<&str>)> {
+        let di = self.debug_info.as_ref()?;
+        Some((&di.source.name, di.range, di.name.as_deref()))
+    }
+
+    // like `Hash`, but with constants and captured values hashed by content,
+    // see `Value::content_hash`
+    pub(super) fn hash_content<H: Hasher>(&self, state: &mut H) {
+        self.slots.hash(state);
+        self.instrs.hash(state);
+
+        self.consts.0.len().hash(state);
+        for val in self.consts.0.iter() {
+            val.hash_content(state);
+        }
+
+        self.upvalues.0.len().hash(state);
+        for val in self.upvalues.0.iter() {
+            val.hash_content(state);
+        }
+
+        self.location().hash(state);
+    }
+
+    pub fn disassemble(&self) -> String {
+        let mut out = String::new();
+        let _ = self.write_disassembly(&mut out);
+        out
+    }
+
+    fn write_disassembly(&self, f: &mut dyn Write) -> fmt::Result {
+        write!(f, "fn")?;
+
+        if let Some(name) = self.debug_info.as_ref().and_then(|di| di.name.as_ref()) {
+            write!(f, " {}", name)?;
+        }
+
+        writeln!(f, "({} args, {} slots) {{", self.arity, self.slots)?;
+
+        {
+            let mut f = indented(f);
+
+            for (i, val) in self.consts.0.iter().enumerate() {
+                write!(f, "c{} = ", i)?;
+                match val.as_func() {
+                    Ok(func) => func.write_disassembly(&mut f)?,
+                    Err(_) => writeln!(f, "{:?}", val)?,
+                }
+            }
+
+            if !self.consts.0.is_empty() {
+                writeln!(f)?;
+            }
+
+            for (i, instr) in self.instrs.0.iter().enumerate() {
+                write!(f, "{}: {:?}", i, instr.opcode)?;
+
+                for (j, operand) in instr.opcode.operands().into_iter().enumerate() {
+                    if operand == Operand::None {
+                        break;
+                    }
+
+                    write!(f, "{}", if j == 0 { " " } else { ", " })?;
+
+                    match operand {
+                        Operand::ConstId => {
+                            let id = instr.const_id();
+                            write!(f, "{:?}", id)?;
+                            match self.consts.get(id) {
+                                Some(val) if val.is_func() => write!(f, " (fn)")?,
+                                Some(val) => write!(f, " ({:?})", val)?,
+                                None => write!(f, " (?)")?,
+                            }
+                        }
+                        Operand::UpvalueId if instr.opcode == Opcode::LoadUpfn => {
+                            write!(f, "{:?}", instr.upfn_id())?
+                        }
+                        Operand::UpvalueId => write!(f, "{:?}", instr.upvalue_id())?,
+                        Operand::RegA => write!(f, "{:?}", instr.reg_a())?,
+                        Operand::RegB => write!(f, "{:?}", instr.reg_b())?,
+                        Operand::RegC => write!(f, "{:?}", instr.reg_c())?,
+                        Operand::RegSeq if instr.reg_seq().len == 0 => write!(f, "()")?,
+                        Operand::RegSeq => write!(f, "{:?}", instr.reg_seq())?,
+                        Operand::Offset => {
+                            let target = InstrIdx(i as u32 + 1) + instr.offset();
+                            write!(f, "-> {}", target.0)?
+                        }
+                        Operand::None => {}
+                    }
+                }
+
+                if let Some(di) = &self.debug_info {
+                    let ranges = di.instruction_ranges.get(&InstrIdx(i as u32));
+                    if let Some(range) = ranges.and_then(|v| v.first()) {
+                        write!(f, " @ {}", di.source.text.range_to_line_col(*range))?;
+                    }
+                }
+
+                writeln!(f)?;
+            }
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
 impl Debug for Func {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(name) = self.debug_info.as_ref().and_then(|di| di.name.as_ref()) {
@@ -77,9 +201,14 @@ impl Debug for Func {
     }
 }
 
+// functions with the same code from different places are kept apart, so that
+// the constant pool doesn't merge them and lose their debug info
 impl PartialEq for Func {
     fn eq(&self, other: &Self) -> bool {
-        self.slots == other.slots && self.instrs == other.instrs && self.consts == other.consts
+        self.slots == other.slots
+            && self.instrs == other.instrs
+            && self.consts == other.consts
+            && self.location() == other.location()
     }
 }
 
@@ -90,6 +219,7 @@ impl Hash for Func {
         self.slots.hash(state);
         self.instrs.hash(state);
         self.consts.hash(state);
+        self.location().hash(state);
     }
 }
 
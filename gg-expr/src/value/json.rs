@@ -0,0 +1,69 @@
+use serde_json::{Map as JsonMap, Number, Value as Json};
+
+use super::{List, Map, Type, Value};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ToJsonError {
+    #[error("cannot convert {0:?} to JSON")]
+    Unsupported(Type),
+    #[error("JSON object keys must be strings or symbols, found {0:?}")]
+    NonStringKey(Type),
+    #[error("cannot convert a non-finite float to JSON")]
+    NonFiniteFloat,
+}
+
+impl Value {
+    // symbols become plain strings, so they don't survive a round trip
+    pub fn to_json(&self) -> Result<Json, ToJsonError> {
+        Ok(match self.ty() {
+            Type::Null => Json::Null,
+            Type::Int => Json::from(self.as_int().unwrap()),
+            Type::Float => {
+                let v = f64::from(self.as_float().unwrap());
+                Json::Number(Number::from_f64(v).ok_or(ToJsonError::NonFiniteFloat)?)
+            }
+            Type::Bool => Json::Bool(self.as_bool().unwrap()),
+            Type::String => Json::String(self.as_string().unwrap().into()),
+            Type::Symbol => Json::String(self.as_symbol().unwrap().into()),
+            Type::List => {
+                let list = self.as_list().unwrap();
+                Json::Array(list.iter().map(Value::to_json).collect::<Result<_, _>>()?)
+            }
+            Type::Map => {
+                let mut object = JsonMap::new();
+                for (key, value) in self.as_map().unwrap() {
+                    let key = key
+                        .as_string()
+                        .or_else(|_| key.as_symbol())
+                        .map_err(|_| ToJsonError::NonStringKey(key.ty()))?;
+                    object.insert(key.into(), value.to_json()?);
+                }
+                Json::Object(object)
+            }
+            ty @ (Type::Func | Type::ExtFunc | Type::Thunk) => {
+                return Err(ToJsonError::Unsupported(ty))
+            }
+        })
+    }
+
+    // integers that don't fit into an `i32` become floats
+    pub fn from_json(json: &Json) -> Value {
+        match json {
+            Json::Null => Value::null(),
+            Json::Bool(v) => Value::from(*v),
+            Json::Number(v) => match v.as_i64().and_then(|v| i32::try_from(v).ok()) {
+                Some(v) => Value::from(v),
+                None => Value::from(v.as_f64().unwrap_or(f64::NAN) as f32),
+            },
+            Json::String(v) => Value::from(v.as_str()),
+            Json::Array(v) => Value::from(v.iter().map(Value::from_json).collect::<List>()),
+            Json::Object(v) => {
+                let map = v
+                    .iter()
+                    .map(|(k, v)| (Value::from(k.as_str()), Value::from_json(v)))
+                    .collect::<Map>();
+                Value::from(map)
+            }
+        }
+    }
+}
@@ -1,6 +1,11 @@
+mod bytecode;
 mod ext_func;
 mod func;
+mod json;
+mod symbol;
+mod thunk;
 
+use std::collections::hash_map::DefaultHasher;
 use std::fmt::{self, Debug};
 use std::hash::{Hash, Hasher};
 use std::hint::unreachable_unchecked;
@@ -11,6 +16,9 @@ use std::sync::atomic::Ordering::{Acquire, Release};
 
 pub use self::ext_func::ExtFunc;
 pub use self::func::{DebugInfo, Func};
+pub use self::json::ToJsonError;
+pub use self::thunk::Thunk;
+use crate::Vm;
 
 pub type List = im::Vector<Value>;
 pub type Map = im::HashMap<Value, Value>;
@@ -26,10 +34,12 @@ pub enum Type {
     ExtFunc = 6,
     List = 7,
     Map = 8,
+    Symbol = 9,
+    Thunk = 10,
 }
 
 impl Type {
-    pub const VALUES: [Type; 9] = [
+    pub const VALUES: [Type; 11] = [
         Type::Null,
         Type::Int,
         Type::Float,
@@ -39,11 +49,13 @@ impl Type {
         Type::ExtFunc,
         Type::List,
         Type::Map,
+        Type::Symbol,
+        Type::Thunk,
     ];
 
     fn is_heap(&self) -> bool {
         use Type::*;
-        matches!(self, String | Func | ExtFunc | List | Map)
+        matches!(self, String | Func | ExtFunc | List | Map | Thunk)
     }
 }
 
@@ -59,6 +71,8 @@ impl Debug for Type {
             Type::ExtFunc => "ext_func",
             Type::List => "list",
             Type::Map => "map",
+            Type::Symbol => "symbol",
+            Type::Thunk => "thunk",
         })
     }
 }
@@ -84,6 +98,7 @@ union HeapPayload {
     ext_func: ManuallyDrop<ExtFunc>,
     list: ManuallyDrop<List>,
     map: ManuallyDrop<Map>,
+    thunk: ManuallyDrop<Thunk>,
 }
 
 impl Value {
@@ -106,6 +121,8 @@ impl Value {
             6 => Type::ExtFunc,
             7 => Type::List,
             8 => Type::Map,
+            9 => Type::Symbol,
+            10 => Type::Thunk,
             _ => unsafe { unreachable_unchecked() },
         }
     }
@@ -175,6 +192,51 @@ impl Value {
         }
     }
 
+    pub fn from_symbol(name: &str) -> Value {
+        Value {
+            u64: u64::from(symbol::intern(name)) << 32 | (Type::Symbol as u64),
+        }
+    }
+
+    pub fn is_symbol(&self) -> bool {
+        self.ty() == Type::Symbol
+    }
+
+    pub fn as_symbol(&self) -> Result<&'static str, FromValueError> {
+        if self.is_symbol() {
+            unsafe { Ok(symbol::name((self.u64 >> 32) as u32)) }
+        } else {
+            Err(FromValueError {
+                expected: &[Type::Symbol],
+                found: self.ty(),
+            })
+        }
+    }
+
+    pub fn len(&self) -> Result<usize, FromValueError> {
+        match self.ty() {
+            Type::String => Ok(self.as_string().unwrap().chars().count()),
+            Type::List => Ok(self.as_list().unwrap().len()),
+            Type::Map => Ok(self.as_map().unwrap().len()),
+            found => Err(FromValueError {
+                expected: &[Type::String, Type::List, Type::Map],
+                found,
+            }),
+        }
+    }
+
+    pub fn is_empty(&self) -> Result<bool, FromValueError> {
+        match self.ty() {
+            Type::String => Ok(self.as_string().unwrap().is_empty()),
+            Type::List => Ok(self.as_list().unwrap().is_empty()),
+            Type::Map => Ok(self.as_map().unwrap().is_empty()),
+            found => Err(FromValueError {
+                expected: &[Type::String, Type::List, Type::Map],
+                found,
+            }),
+        }
+    }
+
     pub fn is_truthy(&self) -> bool {
         !self.is_null() && self.as_bool() != Ok(false)
     }
@@ -339,6 +401,33 @@ impl Value {
             })
         }
     }
+
+    pub fn from_thunk(thunk: Thunk) -> Value {
+        Value::from_heap(
+            Type::Thunk,
+            HeapValue {
+                refcount: AtomicUsize::new(1),
+                payload: HeapPayload {
+                    thunk: ManuallyDrop::new(thunk),
+                },
+            },
+        )
+    }
+
+    pub fn is_thunk(&self) -> bool {
+        self.ty() == Type::Thunk
+    }
+
+    pub fn as_thunk(&self) -> Result<&Thunk, FromValueError> {
+        if self.is_thunk() {
+            unsafe { Ok(&self.get_heap().payload.thunk) }
+        } else {
+            Err(FromValueError {
+                expected: &[Type::Thunk],
+                found: self.ty(),
+            })
+        }
+    }
 }
 
 impl Clone for Value {
@@ -372,12 +461,13 @@ unsafe fn drop_slow(value: &mut Value) {
     let ty = value.ty();
     let payload = &mut value.get_heap_mut().payload;
     match ty {
-        Type::Null | Type::Int | Type::Float | Type::Bool => unreachable_unchecked(),
+        Type::Null | Type::Int | Type::Float | Type::Bool | Type::Symbol => unreachable_unchecked(),
         Type::String => ManuallyDrop::drop(&mut payload.string),
         Type::Func => ManuallyDrop::drop(&mut payload.func),
         Type::ExtFunc => ManuallyDrop::drop(&mut payload.ext_func),
         Type::List => ManuallyDrop::drop(&mut payload.list),
         Type::Map => ManuallyDrop::drop(&mut payload.map),
+        Type::Thunk => ManuallyDrop::drop(&mut payload.thunk),
     }
 }
 
@@ -393,6 +483,8 @@ impl Debug for Value {
             Type::ExtFunc => self.as_ext_func().unwrap().fmt(f),
             Type::List => self.as_list().unwrap().fmt(f),
             Type::Map => fmt_map(self.as_map().unwrap(), f),
+            Type::Symbol => write!(f, ":{}", self.as_symbol().unwrap()),
+            Type::Thunk => self.as_thunk().unwrap().fmt(f),
         }
     }
 }
@@ -420,6 +512,10 @@ impl PartialEq for Value {
             return false;
         }
 
+        if self.is_heap() && unsafe { self.u64 == other.u64 } {
+            return true;
+        }
+
         match self.ty() {
             Type::Null => true,
             Type::Int => self.as_int() == other.as_int(),
@@ -435,8 +531,16 @@ impl PartialEq for Value {
             Type::String => self.as_string() == other.as_string(),
             Type::Func => self.as_func() == other.as_func(),
             Type::ExtFunc => self.as_ext_func() == other.as_ext_func(),
-            Type::List => self.as_list() == other.as_list(),
-            Type::Map => self.as_map() == other.as_map(),
+            Type::List => {
+                let (a, b) = (self.as_list().unwrap(), other.as_list().unwrap());
+                a.ptr_eq(b) || a == b
+            }
+            Type::Map => {
+                let (a, b) = (self.as_map().unwrap(), other.as_map().unwrap());
+                a.ptr_eq(b) || a == b
+            }
+            Type::Symbol => unsafe { self.u64 == other.u64 },
+            Type::Thunk => self.as_thunk() == other.as_thunk(),
         }
     }
 }
@@ -472,6 +576,97 @@ impl Hash for Value {
             Type::Map => {
                 self.as_map().unwrap().hash(state);
             }
+            Type::Symbol => {
+                unsafe { (self.u64 >> 32) as u32 }.hash(state);
+            }
+            Type::Thunk => {
+                self.as_thunk().unwrap().hash(state);
+            }
+        }
+    }
+}
+
+impl Value {
+    /// Hash of the value's contents that doesn't depend on map layout or
+    /// symbol interning order, suitable for detecting changes across runs.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash_content(&mut hasher);
+        hasher.finish()
+    }
+
+    fn hash_content<H: Hasher>(&self, state: &mut H) {
+        self.ty().hash(state);
+
+        match self.ty() {
+            Type::Float => {
+                let x = self.as_float().unwrap();
+                let x = if x.is_nan() {
+                    f32::NAN
+                } else if x == 0.0 {
+                    0.0
+                } else {
+                    x
+                };
+                x.to_bits().hash(state);
+            }
+            Type::Func => {
+                self.as_func().unwrap().hash_content(state);
+            }
+            Type::ExtFunc => {
+                let func = self.as_ext_func().unwrap();
+                func.name.hash(state);
+                func.arity.hash(state);
+            }
+            Type::List => {
+                let list = self.as_list().unwrap();
+                list.len().hash(state);
+                for val in list {
+                    val.hash_content(state);
+                }
+            }
+            Type::Map => {
+                let map = self.as_map().unwrap();
+                let sum = map.iter().fold(0u64, |acc, (key, val)| {
+                    let mut hasher = DefaultHasher::new();
+                    key.hash_content(&mut hasher);
+                    val.hash_content(&mut hasher);
+                    acc.wrapping_add(hasher.finish())
+                });
+                map.len().hash(state);
+                sum.hash(state);
+            }
+            Type::Symbol => {
+                self.as_symbol().unwrap().hash(state);
+            }
+            Type::Thunk => {
+                self.as_thunk().unwrap().func.hash_content(state);
+            }
+            _ => self.hash(state),
+        }
+    }
+
+    pub fn deep_eq(&self, other: &Value) -> bool {
+        if self.ty() != other.ty() {
+            return false;
+        }
+
+        match self.ty() {
+            Type::List => {
+                let (a, b) = (self.as_list().unwrap(), other.as_list().unwrap());
+                a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.deep_eq(y))
+            }
+            Type::Map => {
+                let (a, b) = (self.as_map().unwrap(), other.as_map().unwrap());
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(key, x)| b.get(key).map_or(false, |y| x.deep_eq(y)))
+            }
+            Type::Thunk => {
+                let (a, b) = (self.as_thunk().unwrap(), other.as_thunk().unwrap());
+                a.func.deep_eq(&b.func)
+            }
+            _ => self == other,
         }
     }
 }
@@ -530,6 +725,12 @@ impl From<Map> for Value {
     }
 }
 
+impl From<Thunk> for Value {
+    fn from(v: Thunk) -> Value {
+        Value::from_thunk(v)
+    }
+}
+
 impl TryFrom<&Value> for i32 {
     type Error = FromValueError;
     fn try_from(v: &Value) -> Result<i32, FromValueError> {
@@ -597,6 +798,12 @@ impl Deref for FuncValue {
     }
 }
 
+impl FuncValue {
+    pub fn call(&self, vm: &mut Vm, args: &[Value]) -> crate::Result<Value> {
+        vm.call(&self.0, args)
+    }
+}
+
 impl From<FuncValue> for Value {
     fn from(v: FuncValue) -> Self {
         v.0
@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+
+static SYMBOLS: Lazy<RwLock<Symbols>> = Lazy::new(Default::default);
+
+#[derive(Default)]
+struct Symbols {
+    ids: HashMap<&'static str, u32>,
+    names: Vec<&'static str>,
+}
+
+pub fn intern(name: &str) -> u32 {
+    if let Some(&id) = SYMBOLS.read().unwrap().ids.get(name) {
+        return id;
+    }
+
+    let mut symbols = SYMBOLS.write().unwrap();
+    if let Some(&id) = symbols.ids.get(name) {
+        return id;
+    }
+
+    let name: &'static str = Box::leak(name.into());
+    let id = symbols.names.len() as u32;
+    symbols.names.push(name);
+    symbols.ids.insert(name, id);
+    id
+}
+
+pub fn name(id: u32) -> &'static str {
+    SYMBOLS.read().unwrap().names[id as usize]
+}
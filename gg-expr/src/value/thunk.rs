@@ -1,31 +1,26 @@
 use std::fmt::{self, Debug};
 use std::hash::{Hash, Hasher};
-use std::sync::Arc;
 
 use once_cell::sync::OnceCell;
 
-use crate::{Error, Value};
+use crate::{Result, Value, VmContext};
 
-#[derive(Clone)]
 pub struct Thunk {
     pub func: Value,
-    pub value: Arc<OnceCell<Value>>,
+    pub value: OnceCell<Value>,
 }
 
 impl Thunk {
     pub fn new(func: Value) -> Thunk {
         Thunk {
             func,
-            value: Arc::new(OnceCell::new()),
+            value: OnceCell::new(),
         }
     }
 
-    pub fn force_eval(&self) -> Result<&Value, Error> {
-        // self.value.get_or_try_init(|| {
-        //     let mut vm = Vm::new();
-        //     vm.eval(&self.func, &[])
-        // })
-        todo!()
+    pub fn force_eval(&self, ctx: &VmContext) -> Result<&Value> {
+        self.value
+            .get_or_try_init(|| ctx.call_nested(&self.func, &[]))
     }
 }
 
@@ -34,7 +29,7 @@ impl Debug for Thunk {
         if let Some(val) = self.value.get() {
             val.fmt(f)
         } else {
-            writeln!(f, "thunk: {:?}", self.func)
+            write!(f, "thunk: {:?}", self.func)
         }
     }
 }
@@ -32,6 +32,10 @@ impl Consts {
         self.0.len() as u16
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub fn compile(self) -> CompiledConsts {
         let mut pairs = self.0.into_iter().collect::<Vec<_>>();
         pairs.sort_unstable_by_key(|(_, id)| *id);
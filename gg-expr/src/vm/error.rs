@@ -111,7 +111,7 @@ impl Display for StackFrame {
         let di = match &self.func.debug_info {
             Some(v) => v,
             None => {
-                return write!(f, "{}", Paint::new("unknown").dimmed());
+                return writeln!(f, "{}", Paint::new("unknown").dimmed());
             }
         };
 
@@ -131,8 +131,9 @@ impl Display for StackFrame {
         }
 
         if let Some(range) = self.range {
-            let comp =
-                SourceComponent::new(di.source.clone()).with_label(Severity::Error, range, "");
+            let comp = SourceComponent::new(di.source.clone())
+                .with_context_lines(0)
+                .with_label(Severity::Error, range, "");
             write!(f, "{}", comp)?;
         }
 
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use super::reg::{RegId, RegSeq};
 use super::{ConstId, UpfnId, UpvalueId};
 
+#[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub enum Opcode {
     Nop,
@@ -29,11 +30,17 @@ pub enum Opcode {
     TailCall,
     Ret,
 
+    PushCatch,
+    PopCatch,
+
     IsList,
+    IsMap,
     Len,
+    Slice,
 
     IsTruthy,
     IsNull,
+    ToString,
 
     OpLt,
     OpLe,
@@ -47,8 +54,18 @@ pub enum Opcode {
     OpDiv,
     OpRem,
     OpPow,
+    OpBitAnd,
+    OpBitOr,
+    OpBitXor,
+    OpShl,
+    OpShr,
     OpIndex,
     OpIndexNullable,
+    OpIn,
+    OpRange,
+    OpRangeInclusive,
+    OpInRange,
+    OpInRangeInclusive,
 
     UnOpNeg,
     UnOpNot,
@@ -67,6 +84,65 @@ pub enum Operand {
 }
 
 impl Opcode {
+    // in declaration order, so that `ALL[op as usize] == op`
+    const ALL: [Opcode; 51] = [
+        Opcode::Nop,
+        Opcode::Panic,
+        Opcode::LoadConst,
+        Opcode::LoadUpvalue,
+        Opcode::LoadUpfn,
+        Opcode::Copy,
+        Opcode::CopyIfTrue,
+        Opcode::NewList,
+        Opcode::NewMap,
+        Opcode::NewFunc,
+        Opcode::Jump,
+        Opcode::JumpIfTrue,
+        Opcode::JumpIfFalse,
+        Opcode::Call,
+        Opcode::TailCall,
+        Opcode::Ret,
+        Opcode::PushCatch,
+        Opcode::PopCatch,
+        Opcode::IsList,
+        Opcode::IsMap,
+        Opcode::Len,
+        Opcode::Slice,
+        Opcode::IsTruthy,
+        Opcode::IsNull,
+        Opcode::ToString,
+        Opcode::OpLt,
+        Opcode::OpLe,
+        Opcode::OpEq,
+        Opcode::OpNeq,
+        Opcode::OpGe,
+        Opcode::OpGt,
+        Opcode::OpAdd,
+        Opcode::OpSub,
+        Opcode::OpMul,
+        Opcode::OpDiv,
+        Opcode::OpRem,
+        Opcode::OpPow,
+        Opcode::OpBitAnd,
+        Opcode::OpBitOr,
+        Opcode::OpBitXor,
+        Opcode::OpShl,
+        Opcode::OpShr,
+        Opcode::OpIndex,
+        Opcode::OpIndexNullable,
+        Opcode::OpIn,
+        Opcode::OpRange,
+        Opcode::OpRangeInclusive,
+        Opcode::OpInRange,
+        Opcode::OpInRangeInclusive,
+        Opcode::UnOpNeg,
+        Opcode::UnOpNot,
+    ];
+
+    pub fn from_u8(v: u8) -> Option<Opcode> {
+        Opcode::ALL.get(usize::from(v)).copied()
+    }
+
     pub fn operator(self) -> &'static str {
         use Opcode::*;
 
@@ -83,14 +159,41 @@ impl Opcode {
             OpDiv => "/",
             OpRem => "%",
             OpPow => "**",
+            OpBitAnd => "&",
+            OpBitOr => "|",
+            OpBitXor => "^",
+            OpShl => "<<",
+            OpShr => ">>",
             OpIndex => "[]",
             OpIndexNullable => "?[]",
+            OpIn | OpInRange | OpInRangeInclusive => "in",
+            OpRange => "..",
+            OpRangeInclusive => "..=",
+            Len => "len",
             UnOpNeg => "-",
             UnOpNot => "!",
             _ => "?",
         }
     }
 
+    pub fn metamethod(self) -> Option<&'static str> {
+        use Opcode::*;
+
+        Some(match self {
+            OpLt => "__lt",
+            OpLe => "__le",
+            OpGe => "__ge",
+            OpGt => "__gt",
+            OpAdd => "__add",
+            OpSub => "__sub",
+            OpMul => "__mul",
+            OpDiv => "__div",
+            OpRem => "__rem",
+            OpPow => "__pow",
+            _ => return None,
+        })
+    }
+
     pub fn operands(self) -> [Operand; 3] {
         use Opcode::*;
         use Operand::*;
@@ -107,12 +210,19 @@ impl Opcode {
             Call => [RegSeq, RegC, None],
             TailCall => [RegSeq, None, None],
             Ret => [RegA, None, None],
+            PushCatch => [RegA, Offset, None],
+            PopCatch => [None; 3],
             IsList => [RegA, RegB, None],
+            IsMap => [RegA, RegB, None],
             Len => [RegA, RegB, None],
+            Slice => [RegSeq, RegC, None],
             IsTruthy => [RegA, RegB, None],
             IsNull => [RegA, RegB, None],
+            ToString => [RegA, RegB, None],
             OpLt | OpLe | OpEq | OpNeq | OpGe | OpGt | OpAdd | OpSub | OpMul | OpDiv | OpRem
-            | OpPow | OpIndex | OpIndexNullable => [RegA, RegB, RegC],
+            | OpPow | OpBitAnd | OpBitOr | OpBitXor | OpShl | OpShr | OpIndex | OpIndexNullable
+            | OpIn | OpRange | OpRangeInclusive => [RegA, RegB, RegC],
+            OpInRange | OpInRangeInclusive => [RegSeq, RegC, None],
             UnOpNeg | UnOpNot => [RegA, RegB, None],
         }
     }
@@ -4,12 +4,14 @@ mod instr;
 mod reg;
 mod upvalues;
 
-use std::fmt::Write;
+use std::cell::Cell;
+use std::fmt::{self, Debug, Write};
 use std::sync::Arc;
+use std::time::Instant;
 
 pub use self::consts::{CompiledConsts, ConstId, Consts};
 pub use self::error::{Error, Result, StackFrame, StackTrace};
-pub use self::instr::{CompiledInstrs, Instr, InstrIdx, InstrOffset, Instrs, Opcode};
+pub use self::instr::{CompiledInstrs, Instr, InstrIdx, InstrOffset, Instrs, Opcode, Operand};
 pub use self::reg::{RegId, RegSeq, RegSeqIter};
 pub use self::upvalues::{UpfnId, UpvalueId, UpvalueNames, Upvalues};
 use crate::diagnostic::{Diagnostic, Severity, SourceComponent};
@@ -20,6 +22,11 @@ use crate::{Func, FuncValue, List, Map, Source, Value};
 pub struct Vm {
     frames: Vec<Frame>,
     stack: Vec<Value>,
+    debug_sink: DebugSink,
+    clock: Clock,
+    fuel: Option<u64>,
+    // number of vms this one is nested in, see `call_nested`
+    nesting: usize,
 }
 
 #[derive(Debug)]
@@ -27,6 +34,66 @@ pub struct VmContext {
     frame: Frame,
     frames: Vec<Frame>,
     stack: Vec<Value>,
+    catches: Vec<Catch>,
+    debug_sink: DebugSink,
+    clock: Clock,
+    fuel: Cell<Option<u64>>,
+    nesting: usize,
+}
+
+#[derive(Clone)]
+pub struct DebugSink(Arc<dyn Fn(&str) + Send + Sync>);
+
+impl DebugSink {
+    pub fn new(sink: impl Fn(&str) + Send + Sync + 'static) -> DebugSink {
+        DebugSink(Arc::new(sink))
+    }
+
+    pub fn write(&self, message: &str) {
+        (self.0)(message)
+    }
+}
+
+impl Default for DebugSink {
+    fn default() -> DebugSink {
+        DebugSink::new(|message| tracing::debug!("{}", message))
+    }
+}
+
+impl Debug for DebugSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DebugSink")
+    }
+}
+
+#[derive(Clone)]
+pub struct Clock(Arc<dyn Fn() -> f64 + Send + Sync>);
+
+impl Clock {
+    pub fn new(clock: impl Fn() -> f64 + Send + Sync + 'static) -> Clock {
+        Clock(Arc::new(clock))
+    }
+
+    pub fn fixed(secs: f64) -> Clock {
+        Clock::new(move || secs)
+    }
+
+    pub fn now(&self) -> f64 {
+        (self.0)()
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Clock {
+        let start = Instant::now();
+        Clock::new(move || start.elapsed().as_secs_f64())
+    }
+}
+
+impl Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Clock")
+    }
 }
 
 #[derive(Debug)]
@@ -37,20 +104,99 @@ struct Frame {
     dst: usize,
 }
 
+#[derive(Debug)]
+struct Catch {
+    ip: InstrIdx,
+    depth: usize,
+    stack_len: usize,
+    dst: usize,
+}
+
 impl Vm {
     pub fn new() -> Vm {
         Vm::default()
     }
 
+    pub fn with_fuel(fuel: u64) -> Vm {
+        Vm {
+            fuel: Some(fuel),
+            ..Vm::default()
+        }
+    }
+
+    // `None` means the number of executed instructions is unlimited
+    pub fn set_fuel(&mut self, fuel: Option<u64>) {
+        self.fuel = fuel;
+    }
+
+    pub fn fuel(&self) -> Option<u64> {
+        self.fuel
+    }
+
+    pub fn set_debug_sink(&mut self, sink: DebugSink) {
+        self.debug_sink = sink;
+    }
+
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    // unlike `eval`, checks the number of arguments instead of padding
+    // missing ones with nulls
+    pub fn call(&mut self, func: &Value, args: &[Value]) -> Result<Value> {
+        let func_ref = func
+            .as_func()
+            .map_err(|e| Error::new(Diagnostic::new(Severity::Error, e.to_string())))?;
+
+        let arity = usize::from(func_ref.arity);
+        let arity_matches = if func_ref.variadic {
+            args.len() >= arity
+        } else {
+            args.len() == arity
+        };
+
+        if !arity_matches {
+            let message = format!(
+                "expected {}{} arguments, got {}",
+                if func_ref.variadic { "at least " } else { "" },
+                arity,
+                args.len()
+            );
+            return Err(Error::new(Diagnostic::new(Severity::Error, message)));
+        }
+
+        let args = args.iter().collect::<Vec<_>>();
+        self.eval(func, &args)
+    }
+
     pub fn eval(&mut self, func: &Value, args: &[&Value]) -> Result<Value> {
-        let mut rem_slots = func.as_func().unwrap().slots;
+        let func_ref = func.as_func().unwrap();
+        let mut rem_slots = usize::from(func_ref.slots);
 
         self.stack.push(Value::null());
         self.stack.push(func.clone());
 
+        let arity = usize::from(func_ref.arity);
+        let (args, rest) = if func_ref.variadic && args.len() > arity {
+            args.split_at(arity)
+        } else {
+            (args, &[][..])
+        };
+
         for &arg in args {
             self.stack.push(arg.clone());
-            rem_slots -= 1;
+            rem_slots = rem_slots.saturating_sub(1);
+        }
+
+        if func_ref.variadic {
+            let padding = arity.saturating_sub(args.len());
+            for _ in 0..padding {
+                self.stack.push(Value::null());
+            }
+
+            let rest = rest.iter().map(|&v| v.clone()).collect::<List>();
+            self.stack.push(rest.into());
+            rem_slots = rem_slots.saturating_sub(padding + 1);
         }
 
         for _ in 0..rem_slots {
@@ -78,13 +224,24 @@ impl Vm {
             frame,
             frames: std::mem::take(&mut self.frames),
             stack: std::mem::take(&mut self.stack),
+            catches: Vec::new(),
+            debug_sink: self.debug_sink.clone(),
+            clock: self.clock.clone(),
+            fuel: Cell::new(self.fuel),
+            nesting: self.nesting,
         };
 
         while ctx.frame.ip != InstrIdx(u32::MAX) {
-            let instr = ctx.fetch()?;
-            ctx.dispatch(instr)?;
+            let res = ctx.fetch().and_then(|instr| ctx.dispatch(instr));
+            if let Err(error) = res {
+                if let Err(error) = ctx.unwind(error) {
+                    self.fuel = ctx.fuel.get();
+                    return Err(error);
+                }
+            }
         }
 
+        self.fuel = ctx.fuel.get();
         self.frames = ctx.frames;
         self.stack = ctx.stack;
 
@@ -118,6 +275,14 @@ impl VmContext {
         StackTrace { frames }
     }
 
+    pub fn debug_sink(&self) -> &DebugSink {
+        &self.debug_sink
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
     pub fn cur_ranges(&self) -> Option<Vec<TextRange>> {
         if let Some(di) = &self.cur_func().ok()?.debug_info {
             let prev_ip = &(self.frame.ip + InstrOffset(-1));
@@ -222,6 +387,14 @@ impl VmContext {
     }
 
     fn fetch(&mut self) -> Result<Instr> {
+        if let Some(fuel) = self.fuel.get() {
+            if fuel == 0 {
+                return Err(self.error_out_of_fuel());
+            }
+
+            self.fuel.set(Some(fuel - 1));
+        }
+
         let func = self.cur_func()?;
         let instrs = &func.instrs.0;
         let instr = instrs
@@ -237,6 +410,28 @@ impl VmContext {
         self.error_simple("code overrun")
     }
 
+    #[inline(never)]
+    fn error_out_of_fuel(&self) -> Error {
+        self.error_simple("instruction budget exceeded")
+    }
+
+    fn unwind(&mut self, error: Error) -> Result<()> {
+        let catch = match self.catches.pop() {
+            Some(v) => v,
+            None => return Err(error),
+        };
+
+        while self.frames.len() > catch.depth {
+            self.frame = self.frames.pop().unwrap();
+        }
+
+        self.stack.truncate(catch.stack_len);
+        self.stack[catch.dst] = error.diagnostic().message.clone().into();
+        self.frame.ip = catch.ip;
+
+        Ok(())
+    }
+
     #[inline(always)]
     fn dispatch(&mut self, instr: Instr) -> Result<()> {
         match instr.opcode {
@@ -256,10 +451,15 @@ impl VmContext {
             Opcode::Call => self.instr_call(instr),
             Opcode::TailCall => self.instr_tail_call(instr),
             Opcode::Ret => self.instr_ret(instr),
+            Opcode::PushCatch => self.instr_push_catch(instr),
+            Opcode::PopCatch => self.instr_pop_catch(instr),
             Opcode::IsList => self.instr_is_list(instr),
+            Opcode::IsMap => self.instr_is_map(instr),
             Opcode::Len => self.instr_len(instr),
+            Opcode::Slice => self.instr_slice(instr),
             Opcode::IsTruthy => self.instr_is_truthy(instr),
             Opcode::IsNull => self.instr_is_null(instr),
+            Opcode::ToString => self.instr_to_string(instr),
             Opcode::OpLt => self.instr_op_lt(instr),
             Opcode::OpLe => self.instr_op_le(instr),
             Opcode::OpEq => self.instr_op_eq(instr),
@@ -272,8 +472,18 @@ impl VmContext {
             Opcode::OpDiv => self.instr_op_div(instr),
             Opcode::OpRem => self.instr_op_rem(instr),
             Opcode::OpPow => self.instr_op_pow(instr),
+            Opcode::OpBitAnd => self.instr_op_bitwise(instr, |x, y| x & y),
+            Opcode::OpBitOr => self.instr_op_bitwise(instr, |x, y| x | y),
+            Opcode::OpBitXor => self.instr_op_bitwise(instr, |x, y| x ^ y),
+            Opcode::OpShl => self.instr_op_shift(instr),
+            Opcode::OpShr => self.instr_op_shift(instr),
             Opcode::OpIndex => self.instr_op_index(instr),
             Opcode::OpIndexNullable => self.instr_op_index_nullable(instr),
+            Opcode::OpIn => self.instr_op_in(instr),
+            Opcode::OpRange => self.instr_op_range(instr, false),
+            Opcode::OpRangeInclusive => self.instr_op_range(instr, true),
+            Opcode::OpInRange => self.instr_op_in_range(instr, false),
+            Opcode::OpInRangeInclusive => self.instr_op_in_range(instr, true),
             Opcode::UnOpNeg => self.instr_un_op_neg(instr),
             Opcode::UnOpNot => self.instr_un_op_not(instr),
         }
@@ -367,6 +577,7 @@ impl VmContext {
 
         let func = Func {
             arity: func.arity,
+            variadic: func.variadic,
             slots: func.slots,
             instrs: func.instrs.clone(),
             consts: func.consts.clone(),
@@ -402,6 +613,9 @@ impl VmContext {
 
     const MAX_DEPTH: usize = 1024;
 
+    // nested vms recurse on the native stack, which is much smaller than ours
+    const MAX_NESTING: usize = 128;
+
     fn instr_call(&mut self, instr: Instr) -> Result<()> {
         if self.frames.len() == Self::MAX_DEPTH {
             return Err(self.error_stack_overflow());
@@ -418,11 +632,12 @@ impl VmContext {
         }
 
         let func = func_val.as_func().map_err(|_| self.error_bad_fn())?;
+        let (slots, rest) = (usize::from(func.slots), func.variadic.then(|| func.arity));
 
         let old_base = self.frame.base;
         let new_base = self.stack.len();
 
-        self.push_nulls(usize::from(func.slots));
+        self.push_nulls(slots.max(usize::from(arg_regs.len)));
 
         for (i, arg) in arg_regs.into_iter().enumerate() {
             let src = old_base + usize::from(arg.0);
@@ -430,6 +645,10 @@ impl VmContext {
             self.stack.swap(src, dst);
         }
 
+        if let Some(arity) = rest {
+            self.gather_rest(new_base, usize::from(arity), usize::from(arg_regs.len));
+        }
+
         let new_frame = Frame {
             ip: InstrIdx(0),
             base: new_base,
@@ -467,11 +686,12 @@ impl VmContext {
         let func_val = self.reg_write(func_reg, Value::null())?;
 
         let func = func_val.as_func().map_err(|_| self.error_bad_fn())?;
+        let rest = func.variadic.then(|| func.arity);
 
         let base = self.frame.base;
 
         let cur_slots = self.stack.len() - base;
-        let req_slots = usize::from(func.slots) + 1;
+        let req_slots = usize::from(func.slots).max(usize::from(arg_regs.len)) + 1;
         if cur_slots < req_slots {
             self.push_nulls(req_slots - cur_slots);
         }
@@ -482,6 +702,10 @@ impl VmContext {
             self.stack.swap(src, dst);
         }
 
+        if let Some(arity) = rest {
+            self.gather_rest(base, usize::from(arity), usize::from(arg_regs.len));
+        }
+
         self.frame.ip = InstrIdx(0);
         self.frame.func = self.stack.len() - 1;
         self.stack[self.frame.func] = func_val;
@@ -489,6 +713,13 @@ impl VmContext {
         Ok(())
     }
 
+    fn gather_rest(&mut self, base: usize, arity: usize, num_args: usize) {
+        let rest = (arity..num_args)
+            .map(|i| std::mem::replace(&mut self.stack[base + i], Value::null()))
+            .collect::<List>();
+        self.stack[base + arity] = rest.into();
+    }
+
     fn instr_call_ext(&mut self, seq: RegSeq, dst: usize) -> Result<()> {
         let (func_reg, arg_regs) = seq.split_first();
 
@@ -539,19 +770,60 @@ impl VmContext {
         Ok(())
     }
 
+    fn instr_push_catch(&mut self, instr: Instr) -> Result<()> {
+        let dst = self.reg_offset(instr.reg_a())?;
+        self.catches.push(Catch {
+            ip: self.frame.ip + instr.offset(),
+            depth: self.frames.len(),
+            stack_len: self.stack.len(),
+            dst,
+        });
+        Ok(())
+    }
+
+    fn instr_pop_catch(&mut self, _instr: Instr) -> Result<()> {
+        self.catches.pop();
+        Ok(())
+    }
+
     fn instr_is_list(&mut self, instr: Instr) -> Result<()> {
         let val = self.reg_read(instr.reg_a())?;
         self.reg_write(instr.reg_b(), Value::from(val.is_list()))?;
         Ok(())
     }
 
+    fn instr_is_map(&mut self, instr: Instr) -> Result<()> {
+        let val = self.reg_read(instr.reg_a())?;
+        self.reg_write(instr.reg_b(), Value::from(val.is_map()))?;
+        Ok(())
+    }
+
     fn instr_len(&mut self, instr: Instr) -> Result<()> {
         let val = self.reg_read(instr.reg_a())?;
-        let len = val.as_list().unwrap().len(); // TODO
+        let len = val.len().map_err(|_| self.error_un_op(instr))?;
         self.reg_write(instr.reg_b(), Value::from(len as i32))?;
         Ok(())
     }
 
+    fn instr_slice(&mut self, instr: Instr) -> Result<()> {
+        let base = instr.reg_seq().base.0;
+        let list = self.reg_read(RegId(base))?;
+        let start = self.reg_read(RegId(base + 1))?;
+        let end = self.reg_read(RegId(base + 2))?;
+
+        let (list, start, end) = match (list.as_list(), start.as_int(), end.as_int()) {
+            (Ok(list), Ok(start), Ok(end)) => (list, start, end),
+            _ => return Err(self.error_simple("invalid slice")),
+        };
+
+        let end = usize::try_from(end).unwrap_or(0).min(list.len());
+        let start = usize::try_from(start).unwrap_or(0).min(end);
+        let slice = list.clone().slice(start..end);
+
+        self.reg_write(instr.reg_c(), slice.into())?;
+        Ok(())
+    }
+
     fn instr_is_truthy(&mut self, instr: Instr) -> Result<()> {
         let val = self.reg_read(instr.reg_a())?;
         let res = val.is_truthy();
@@ -566,6 +838,19 @@ impl VmContext {
         Ok(())
     }
 
+    fn instr_to_string(&mut self, instr: Instr) -> Result<()> {
+        let val = self.reg_read(instr.reg_a())?;
+        let res = if val.is_string() {
+            val.clone()
+        } else {
+            let mut res = String::new();
+            crate::builtins::write_value(&mut res, val);
+            res.into()
+        };
+        self.reg_write(instr.reg_b(), res)?;
+        Ok(())
+    }
+
     fn instr_bin_op(
         &mut self,
         instr: Instr,
@@ -589,6 +874,66 @@ impl VmContext {
         Ok(())
     }
 
+    pub(crate) fn call_nested(&self, func: &Value, args: &[&Value]) -> Result<Value> {
+        let (arity, variadic) = match (func.as_ext_func(), func.as_func()) {
+            (Ok(func), _) => (func.arity, false),
+            (_, Ok(func)) => (func.arity, func.variadic),
+            _ => return Err(self.error_bad_fn()),
+        };
+
+        let arity_matches = if variadic {
+            args.len() >= usize::from(arity)
+        } else {
+            args.len() == usize::from(arity)
+        };
+
+        if !arity_matches {
+            let message = format!(
+                "expected a function with {}{} arguments, found {} arguments",
+                if variadic { "at least " } else { "" },
+                arity,
+                args.len()
+            );
+            return Err(self.error_simple(&message));
+        }
+
+        if let Ok(func) = func.as_ext_func() {
+            let args = args.iter().map(|&v| v.clone()).collect::<Vec<_>>();
+            return (func.func)(self, &args);
+        }
+
+        if self.nesting == Self::MAX_NESTING {
+            return Err(self.error_stack_overflow());
+        }
+
+        let mut vm = Vm::new();
+        vm.set_debug_sink(self.debug_sink.clone());
+        vm.set_clock(self.clock.clone());
+        vm.set_fuel(self.fuel.get());
+        vm.nesting = self.nesting + 1;
+        let res = vm.eval(func, args);
+        self.fuel.set(vm.fuel());
+        res
+    }
+
+    #[inline(never)]
+    fn bin_op_meta(&self, instr: Instr, lhs: &Value, rhs: &Value) -> Result<Value> {
+        let key = match instr.opcode.metamethod() {
+            Some(v) => Value::from(v),
+            None => return Err(self.error_bin_op(instr)),
+        };
+
+        let func = [lhs, rhs]
+            .into_iter()
+            .flat_map(|v| v.as_map().ok())
+            .find_map(|map| map.get(&key));
+
+        match func {
+            Some(func) => self.call_nested(func, &[lhs, rhs]),
+            None => Err(self.error_bin_op(instr)),
+        }
+    }
+
     #[inline(never)]
     fn error_bin_op(&self, instr: Instr) -> Error {
         let lhs = self.reg_read(instr.reg_a()).unwrap();
@@ -775,7 +1120,7 @@ macro_rules! op_cmp {
             } else if let (Ok(x), Ok(y)) = (x.as_string(), y.as_string()) {
                 x $op y
             } else {
-                return Err(s.error_bin_op($instr))
+                return s.bin_op_meta($instr, x, y)
             };
 
             Ok(res.into())
@@ -800,6 +1145,93 @@ impl VmContext {
         op_cmp!(self, instr, >)
     }
 
+    fn instr_op_in(&mut self, instr: Instr) -> Result<()> {
+        self.instr_bin_op(instr, |s, x, y| {
+            let res = if let Ok(list) = y.as_list() {
+                list.contains(x)
+            } else if let Ok(map) = y.as_map() {
+                map.contains_key(x)
+            } else if let (Ok(x), Ok(y)) = (x.as_string(), y.as_string()) {
+                y.contains(x)
+            } else {
+                return Err(s.error_bin_op(instr));
+            };
+
+            Ok(res.into())
+        })
+    }
+
+    const MAX_RANGE_LEN: u64 = 1 << 24;
+
+    fn instr_op_range(&mut self, instr: Instr, inclusive: bool) -> Result<()> {
+        self.instr_bin_op(instr, |s, x, y| {
+            let (start, end) = match (x.as_int(), y.as_int()) {
+                (Ok(x), Ok(y)) => (i64::from(x), i64::from(y) + i64::from(inclusive)),
+                _ => return Err(s.error_bin_op(instr)),
+            };
+
+            // every element costs as much as an instruction
+            let len = u64::try_from(end - start).unwrap_or(0);
+            if len > Self::MAX_RANGE_LEN {
+                return Err(s.error_simple("range is too long"));
+            }
+
+            if let Some(fuel) = s.fuel.get() {
+                if fuel < len {
+                    return Err(s.error_out_of_fuel());
+                }
+
+                s.fuel.set(Some(fuel - len));
+            }
+
+            let list = (start..end)
+                .map(|v| Value::from(v as i32))
+                .collect::<List>();
+            Ok(list.into())
+        })
+    }
+
+    // `x in a..b` without building the list, so only ints are members
+    fn instr_op_in_range(&mut self, instr: Instr, inclusive: bool) -> Result<()> {
+        let base = instr.reg_seq().base.0;
+        let val = self.reg_read(RegId(base))?;
+        let start = self.reg_read(RegId(base + 1))?;
+        let end = self.reg_read(RegId(base + 2))?;
+
+        let (start, end) = match (start.as_int(), end.as_int()) {
+            (Ok(start), Ok(end)) => (start, end),
+            _ => return Err(self.error_range_bounds(instr, start, end)),
+        };
+
+        let res = match val.as_int() {
+            Ok(val) if inclusive => (start..=end).contains(&val),
+            Ok(val) => (start..end).contains(&val),
+            Err(_) => false,
+        };
+
+        self.reg_write(instr.reg_c(), res.into())?;
+        Ok(())
+    }
+
+    #[cold]
+    fn error_range_bounds(&self, instr: Instr, start: &Value, end: &Value) -> Error {
+        let op = if instr.opcode == Opcode::OpInRangeInclusive {
+            Opcode::OpRangeInclusive
+        } else {
+            Opcode::OpRange
+        };
+
+        let message = format!(
+            "operator `{}` cannot be applied to `{:?}` and `{:?}`",
+            op.operator(),
+            start.ty(),
+            end.ty()
+        );
+
+        let range = self.cur_ranges().map(|v| v[0]);
+        self.error(range, message, |_, _| ())
+    }
+
     fn instr_op_eq(&mut self, instr: Instr) -> Result<()> {
         self.instr_bin_op(instr, |_, x, y| Ok(Value::from(x == y)))
     }
@@ -822,7 +1254,7 @@ macro_rules! op_arith {
             } else if let (Ok(x), Ok(y)) = (x.as_float(), y.as_float()) {
                 (x $op y).into()
             } else {
-                return Err(s.error_bin_op($instr))
+                return s.bin_op_meta($instr, x, y)
             };
 
             Ok(res)
@@ -851,7 +1283,7 @@ impl VmContext {
             } else if let (Ok(x), Ok(y)) = (x.as_list(), y.as_list()) {
                 (x + y).into()
             } else {
-                return Err(s.error_bin_op(instr));
+                return s.bin_op_meta(instr, x, y);
             };
 
             Ok(res)
@@ -887,7 +1319,7 @@ impl VmContext {
                 }
                 res.into()
             } else {
-                return Err(s.error_bin_op(instr));
+                return s.bin_op_meta(instr, x, y);
             };
 
             Ok(res)
@@ -895,13 +1327,50 @@ impl VmContext {
     }
 
     fn instr_op_div(&mut self, instr: Instr) -> Result<()> {
+        self.check_div_by_zero(instr)?;
         op_arith!(self, instr, checked_div, /)
     }
 
     fn instr_op_rem(&mut self, instr: Instr) -> Result<()> {
+        self.check_div_by_zero(instr)?;
         op_arith!(self, instr, checked_rem, %)
     }
 
+    fn check_div_by_zero(&self, instr: Instr) -> Result<()> {
+        let lhs = self.reg_read(instr.reg_a())?;
+        let rhs = self.reg_read(instr.reg_b())?;
+
+        if lhs.is_int() && matches!(rhs.as_int(), Ok(0)) {
+            return Err(self.error_div_by_zero(instr));
+        }
+
+        Ok(())
+    }
+
+    #[cold]
+    fn error_div_by_zero(&self, instr: Instr) -> Error {
+        let lhs = self.reg_read(instr.reg_a()).unwrap();
+
+        let message = if instr.opcode == Opcode::OpRem {
+            "attempt to calculate the remainder with a divisor of zero"
+        } else {
+            "attempt to divide by zero"
+        };
+
+        let ranges = self.cur_ranges();
+        let main_range = ranges.as_ref().map(|v| v[0]);
+
+        self.error(main_range, message, |diag, source| {
+            if let (Some(source), Some(ranges)) = (source, ranges) {
+                diag.add_source(
+                    SourceComponent::new(source)
+                        .with_label(Severity::Info, ranges[1], format!("{:?}", lhs))
+                        .with_label(Severity::Error, ranges[2], "divisor is zero"),
+                );
+            }
+        })
+    }
+
     fn instr_op_pow(&mut self, instr: Instr) -> Result<()> {
         self.instr_bin_op(instr, |s, x, y| {
             let res = if let (Ok(x), Ok(y)) = (x.as_int(), y.as_int()) {
@@ -919,13 +1388,63 @@ impl VmContext {
             } else if let (Ok(x), Ok(y)) = (x.as_float(), y.as_float()) {
                 x.powf(y).into()
             } else {
-                return Err(s.error_bin_op(instr));
+                return s.bin_op_meta(instr, x, y);
             };
 
             Ok(res)
         })
     }
 
+    fn instr_op_bitwise(&mut self, instr: Instr, op: impl FnOnce(i32, i32) -> i32) -> Result<()> {
+        self.instr_bin_op(instr, |s, x, y| match (x.as_int(), y.as_int()) {
+            (Ok(x), Ok(y)) => Ok(op(x, y).into()),
+            _ => Err(s.error_bin_op(instr)),
+        })
+    }
+
+    fn instr_op_shift(&mut self, instr: Instr) -> Result<()> {
+        self.instr_bin_op(instr, |s, x, y| {
+            let (x, y) = match (x.as_int(), y.as_int()) {
+                (Ok(x), Ok(y)) => (x, y),
+                _ => return Err(s.error_bin_op(instr)),
+            };
+
+            if !(0..32).contains(&y) {
+                return Err(s.error_bad_shift(instr, y));
+            }
+
+            let res = if instr.opcode == Opcode::OpShl {
+                x << y
+            } else {
+                x >> y
+            };
+
+            Ok(res.into())
+        })
+    }
+
+    #[cold]
+    fn error_bad_shift(&self, instr: Instr, amount: i32) -> Error {
+        let message = format!(
+            "operator `{}` cannot shift by {} bits",
+            instr.opcode.operator(),
+            amount
+        );
+
+        let ranges = self.cur_ranges();
+        let main_range = ranges.as_ref().map(|v| v[0]);
+
+        self.error(main_range, message, |diag, source| {
+            if let (Some(source), Some(ranges)) = (source, ranges) {
+                diag.add_source(SourceComponent::new(source).with_label(
+                    Severity::Error,
+                    ranges[2],
+                    "shift amount must be in [0, 32)",
+                ));
+            }
+        })
+    }
+
     fn instr_un_op_neg(&mut self, instr: Instr) -> Result<()> {
         self.instr_un_op(instr, |s, x| {
             let res = if let Ok(x) = x.as_int() {
@@ -1,13 +1,22 @@
-use gg_expr::{eval, ExtFunc, Value, Vm};
+use std::sync::{Arc, Mutex};
+
+use gg_expr::builtins::builtins;
+use gg_expr::diagnostic::{Component, Severity};
+use gg_expr::syntax::{Expr, Lexer, SyntaxKind, TextRange};
+use gg_expr::vm::Opcode;
+use gg_expr::{
+    compile_text, compile_text_with_importer, eval, Clock, DebugSink, ExtFunc, Func, FuncValue,
+    Importer, List, Map, ResolvedImport, ToJsonError, Type, Value, Vm,
+};
 
 fn check(code: &str, expected: impl Into<Value>) {
-    let (res, diagnostics) = eval(code);
+    let (res, diagnostics) = eval(builtins(), code);
     assert!(diagnostics.is_empty());
     assert_eq!(res.unwrap(), expected.into());
 }
 
 fn check_func(code: &str, args: &[&Value], expected: impl Into<Value>) {
-    let (func, diagnostics) = eval(code);
+    let (func, diagnostics) = eval(Map::new(), code);
     let func = func.unwrap();
     assert!(diagnostics.is_empty());
     let mut vm = Vm::new();
@@ -22,6 +31,1271 @@ fn test_math() {
 
 #[test]
 fn test_ext_func() {
-    let func = Value::from(ExtFunc::new(|[x]| Value::from(x.as_int().unwrap() * 2)));
+    let func = Value::from(ExtFunc::new(|_, [x]| {
+        Ok(Value::from(x.as_int().unwrap() * 2))
+    }));
     check_func("fn(foo): foo(10)", &[&func], 20);
 }
+
+#[test]
+fn test_typed_ext_func() {
+    let add = Value::from(ExtFunc::new_2("add", |a: i32, b: i32| a + b));
+    check_func("fn(add): add(1, 2)", &[&add], 3);
+
+    let mut vm = Vm::new();
+    let run = |vm: &mut Vm, code: &str| {
+        let (func, diagnostics) = eval(Map::new(), code);
+        assert!(diagnostics.is_empty());
+        vm.eval(&func.unwrap(), &[&add])
+    };
+
+    let error = run(&mut vm, r#"fn(add): add(1, "2")"#).unwrap_err();
+    assert_eq!(error.diagnostic().message, "expected int, found string");
+
+    let error = run(&mut vm, "fn(add): add(1.5, 2)").unwrap_err();
+    assert_eq!(error.diagnostic().message, "expected int, found float");
+
+    let error = run(&mut vm, "fn(add): add(1)").unwrap_err();
+    assert_eq!(error.diagnostic().message, "expected 2 arguments, got 1");
+
+    let neg = Value::from(ExtFunc::new_1("neg", |a: f32| -a));
+    check_func("fn(neg): neg(2)", &[&neg], -2.0);
+    check("math.sin(0)", 0.0);
+}
+
+#[test]
+fn test_try_catch() {
+    check("try ([][0]) catch e -> -1", -1);
+    check("try [1][0] catch e -> -1", 1);
+    check("let f = fn(x): x.foo in try f({}) catch e -> 2", 2);
+}
+
+#[test]
+fn test_collection_eq() {
+    let list = (0..100_000).map(Value::from).collect::<List>();
+    let a = Value::from(list.clone());
+    assert_eq!(a, a.clone());
+
+    // a new value with the same root is compared by pointer
+    let b = Value::from(list.clone());
+    assert!(a.as_list().unwrap().ptr_eq(b.as_list().unwrap()));
+    assert_eq!(a, b);
+
+    let mut other = list;
+    other.set(50_000, Value::null());
+    let other = Value::from(other);
+    assert!(!a.as_list().unwrap().ptr_eq(other.as_list().unwrap()));
+    assert_ne!(a, other);
+
+    let mut map = Map::new();
+    map.insert("foo".into(), 1.into());
+    let b = Value::from(map.clone());
+    assert!(b
+        .as_map()
+        .unwrap()
+        .ptr_eq(Value::from(map.clone()).as_map().unwrap()));
+    assert_eq!(b, b.clone());
+
+    map.insert("foo".into(), 2.into());
+    assert_ne!(b, Value::from(map));
+
+    check("let a = [1, 2, 3] in a == a && a != [1, 2]", true);
+}
+
+#[test]
+fn test_len() {
+    check("len(\"abc\")", 3);
+    check("len(\"ёж\")", 2);
+    check("len([1, 2])", 2);
+    check("len({a = 1})", 1);
+
+    check("let len = fn(x): 0 in len([1, 2])", 0);
+    assert_eq!(count_opcodes("len([1, 2])", Opcode::Len), 1);
+
+    let (res, diagnostics) = eval(builtins(), "len(5)");
+    assert!(diagnostics.is_empty());
+    assert_eq!(
+        res.unwrap_err().diagnostic().message,
+        "unary operator `len` cannot be applied to `int`"
+    );
+}
+
+fn count_opcodes(code: &str, opcode: Opcode) -> usize {
+    let (func, diagnostics) = compile_text(Map::new(), code);
+    assert!(diagnostics.is_empty());
+    let func = func.unwrap();
+    let func = func.as_func().unwrap();
+    func.instrs.0.iter().filter(|v| v.opcode == opcode).count()
+}
+
+#[test]
+fn test_in_operator() {
+    check("5 in 0..10", true);
+    check("10 in 0..10", false);
+    check("10 in 0..=10", true);
+    check("-1 in (0..10)", false);
+    check(r#""a" in {a = 1}"#, true);
+    check(r#""b" in {a = 1}"#, false);
+    check("2 in [1, 2, 3]", true);
+    check("4 in [1, 2, 3]", false);
+    check(r#""ell" in "hello""#, true);
+    check("let x = 3 in x in 0..5", true);
+    check("let r = 1..4 in 2 in r", true);
+    check("1..=3", List::from_iter([1.into(), 2.into(), 3.into()]));
+    check("len(0..10)", 10);
+    check("1.5 in 0..10", false);
+    check("let r = 0..10 in 1.5 in r", false);
+
+    for code in ["1 in 0.5..2", "let r = 0.5..2 in 1 in r"] {
+        let (res, _) = eval(builtins(), code);
+        let message = res.unwrap_err().diagnostic().message.clone();
+        assert_eq!(
+            message,
+            "operator `..` cannot be applied to `float` and `int`"
+        );
+    }
+
+    let (res, _) = eval(builtins(), "0..1000000000");
+    assert_eq!(res.unwrap_err().diagnostic().message, "range is too long");
+
+    let opcodes = func_opcodes("fn(x): x in 0..10");
+    assert!(opcodes.contains(&Opcode::OpInRange));
+    assert!(!opcodes.contains(&Opcode::OpIn));
+    assert!(!opcodes.contains(&Opcode::OpRange));
+}
+
+fn func_opcodes(code: &str) -> Vec<Opcode> {
+    let (func, diagnostics) = eval(Map::new(), code);
+    assert!(diagnostics.is_empty());
+    let func = func.unwrap();
+    let func = func.as_func().unwrap();
+    func.instrs.0.iter().map(|v| v.opcode).collect()
+}
+
+#[test]
+fn test_tail_position() {
+    let tail = [
+        "fn(f, x): f(x)",
+        "fn(f, x): (f(x))",
+        "fn(f, x): let y = x in (f(y))",
+        "fn(f, x): if x then f(x) else (f(null))",
+        "fn(f, x): x ?? f(x)",
+    ];
+
+    for code in tail {
+        let opcodes = func_opcodes(code);
+        assert!(opcodes.contains(&Opcode::TailCall), "{}", code);
+        assert!(!opcodes.contains(&Opcode::Call), "{}", code);
+    }
+
+    let non_tail = [
+        "fn(f, x): {result = f(x)}",
+        "fn(f, x): [f(x), x]",
+        "fn(f, x): f(x) + 1",
+        "fn(f, x): try f(x) catch e -> null",
+    ];
+
+    for code in non_tail {
+        let opcodes = func_opcodes(code);
+        assert!(opcodes.contains(&Opcode::Call), "{}", code);
+        assert!(!opcodes.contains(&Opcode::TailCall), "{}", code);
+    }
+
+    check(
+        "let g = fn(x): x ?? len([1, 2]) in [g(null), g(5)]",
+        List::from_iter([2.into(), 5.into()]),
+    );
+}
+
+#[test]
+fn test_shared_globals() {
+    let code = "let f = fn(xs): fn(ys): len(xs) + len(ys) in f([1, 2])";
+    let (func, diagnostics) = eval(builtins(), code);
+    assert!(diagnostics.is_empty());
+    let func = func.unwrap();
+    assert_eq!(func.as_func().unwrap().upvalues.0.len(), 1);
+
+    let mut vm = Vm::new();
+    let res = vm.eval(&func, &[&List::unit(3.into()).into()]);
+    assert_eq!(res.unwrap(), 3.into());
+
+    let mut env = Map::new();
+    env.insert("g".into(), 10.into());
+
+    // the inner function loads the global as a constant instead of capturing it
+    let (func, diagnostics) = compile_text(env, "fn(x): fn(y): g + x + y");
+    assert!(diagnostics.is_empty());
+    let expected = "\
+fn <main>(0 args, 1 slots) {
+    c0 = fn <anon>(1 args, 4 slots) {
+        c0 = fn <anon>(1 args, 3 slots) {
+            c0 = 10
+
+            0: LoadConst c0 (10), r1 @ 1:15-1:15
+            1: LoadUpvalue u0, r2 @ 1:19-1:19
+            2: OpAdd r1, r2, r1 @ 1:15-1:19
+            3: OpAdd r1, r0, r1 @ 1:15-1:23
+            4: Ret r1 @ 1:15-1:23
+        }
+
+        0: Copy r0, r3
+        1: LoadConst c0 (fn), r2 @ 1:8-1:23
+        2: NewFunc r2:3, r1 @ 1:8-1:23
+        3: Ret r1 @ 1:8-1:23
+    }
+
+    0: LoadConst c0 (fn), r0 @ 1:1-1:23
+    1: Ret r0 @ 1:1-1:23
+}
+";
+    assert_eq!(func.unwrap().as_func().unwrap().disassemble(), expected);
+}
+
+fn disassemble(code: &str) -> String {
+    let (func, diagnostics) = eval(Map::new(), code);
+    assert!(diagnostics.is_empty());
+    func.unwrap().as_func().unwrap().disassemble()
+}
+
+#[test]
+fn test_disassemble() {
+    let expected = "\
+fn <anon>(1 args, 3 slots) {
+    c0 = 2
+    c1 = 1
+
+    0: JumpIfTrue r0, -> 3
+    1: LoadConst c0 (2), r2 @ 1:26-1:26
+    2: Jump -> 4
+    3: LoadConst c1 (1), r2 @ 1:19-1:19
+    4: NewList r2:2, r1 @ 1:8-1:27
+    5: Ret r1 @ 1:8-1:27
+}
+";
+    assert_eq!(disassemble("fn(x): [if x then 1 else 2]"), expected);
+
+    let expected = "\
+fn <anon>(1 args, 2 slots) {
+    c0 = fn <anon>(0 args, 1 slots) {
+        c0 = 1
+
+        0: LoadConst c0 (1), r0 @ 1:14-1:14
+        1: Ret r0 @ 1:14-1:14
+    }
+
+    0: LoadConst c0 (fn), r1 @ 1:8-1:14
+    1: Ret r1 @ 1:8-1:14
+}
+";
+    assert_eq!(disassemble("fn(x): fn(): 1"), expected);
+
+    let (func, _) = eval(Map::new(), "fn(x): fn(): 1");
+    let bytes = func.unwrap().as_func().unwrap().to_bytes();
+    let loaded = Func::from_bytes(&bytes).unwrap();
+    assert!(!loaded.disassemble().contains(" @ "));
+}
+
+#[test]
+fn test_symbols() {
+    check(":foo == :foo", true);
+    check(":foo == :bar", false);
+    check(r#":foo == "foo""#, false);
+    check(
+        "let m = {:kind = 1, [:other] = 2} in m[:kind] + m[:other]",
+        3,
+    );
+    check("when :b is :a -> 1, :b -> 2, _ -> 3", 2);
+    check("when [:a, 1] is [:a, x] -> x, _ -> 0", 1);
+
+    // symbols and strings are different keys
+    let (res, _) = eval(builtins(), r#"let m = {:kind = 1} in m["kind"]"#);
+    let error = res.unwrap_err();
+    assert_eq!(
+        error.diagnostic().message,
+        r#"key not present in map: "kind""#
+    );
+
+    let (a, _) = eval(Map::new(), ":player");
+    let (b, _) = eval(Map::new(), "let x = :player in x");
+    let (a, b) = (a.unwrap(), b.unwrap());
+    assert_eq!(a, Value::from_symbol("player"));
+    assert_eq!(format!("{:?}", a), ":player");
+    assert!(std::ptr::eq(a.as_symbol().unwrap(), b.as_symbol().unwrap()));
+}
+
+#[test]
+fn test_concat_folding() {
+    let code = r#""a" + ("b" + "c") + "d""#;
+    assert_eq!(count_opcodes(code, Opcode::LoadConst), 1);
+    assert_eq!(count_opcodes(code, Opcode::OpAdd), 0);
+    check(code, "abcd");
+
+    let code = "[1, 2] + [[3]]";
+    assert_eq!(count_opcodes(code, Opcode::LoadConst), 1);
+    check(
+        code,
+        List::from_iter([1.into(), 2.into(), List::unit(3.into()).into()]),
+    );
+
+    let code = r#"let x = "b" in "a" + x + "c""#;
+    assert_eq!(count_opcodes(code, Opcode::OpAdd), 2);
+    check(code, "abc");
+
+    // every subexpression is folded once, long chains compile quickly
+    let code = format!(r#"let x = "" in x{}"#, r#" + "a""#.repeat(200));
+    assert_eq!(count_opcodes(&code, Opcode::OpAdd), 200);
+    check(&code, "a".repeat(200));
+
+    let code = format!("0{}", " + 1".repeat(200));
+    assert_eq!(count_opcodes(&code, Opcode::OpAdd), 0);
+    check(&code, 200);
+}
+
+#[test]
+fn test_const_folding() {
+    let num_instrs = |code: &str| {
+        let (func, diagnostics) = compile_text(builtins(), code);
+        assert!(diagnostics.is_empty());
+        func.unwrap().as_func().unwrap().instrs.0.len()
+    };
+
+    for (folded, runtime) in [
+        ("2 + 3 * 4", "let a = 2, b = 3 in a + b * 4"),
+        ("2147483647 + 1", "let x = 2147483647 in x + 1"),
+        ("2 ** 40", "let x = 2 in x ** 40"),
+        ("-(7 % 3) / 2.0", "let x = 7 in -(x % 3) / 2.0"),
+        ("7 / 2 - 0.5", "let x = 7 in x / 2 - 0.5"),
+        (
+            "1 < 2 == !(2.5 > 3)",
+            "let x = 1 in x < 2 == !(2.5 > 3 * x)",
+        ),
+        (r#""a" < "b""#, r#"let x = "a" in x < "b""#),
+    ] {
+        assert_eq!(num_instrs(folded), 2, "{}", folded);
+        assert!(num_instrs(folded) < num_instrs(runtime), "{}", folded);
+
+        let (a, _) = eval(builtins(), folded);
+        let (b, _) = eval(builtins(), runtime);
+        assert_eq!(a.unwrap(), b.unwrap(), "{}", folded);
+    }
+
+    assert_eq!(count_opcodes("1 / 0", Opcode::OpDiv), 1);
+    assert_eq!(count_opcodes("1 % 0", Opcode::OpRem), 1);
+    assert_eq!(count_opcodes("1 + null", Opcode::OpAdd), 1);
+    assert_eq!(count_opcodes("-true", Opcode::UnOpNeg), 1);
+}
+
+#[test]
+fn test_let_destructuring() {
+    check("let [a, b] = [1, 2] in a + b", 3);
+    check(
+        "let [x, ...] = [1, 2, 3], y = x * 2 in [x, y]",
+        List::from_iter([1.into(), 2.into()]),
+    );
+    check("let [[a], b] = [[1], 2] in a + b", 3);
+
+    let (res, _) = eval(builtins(), "let [a, b] = [1] in a");
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_rest_binding() {
+    check(
+        "when [1, 2, 3] is [h, ...t] -> t",
+        List::from_iter([2.into(), 3.into()]),
+    );
+    check("when [1] is [h, ...t] -> t", List::new());
+    check(
+        "when [1, 2, 3] is [...init, l] -> [init, l]",
+        List::from_iter([List::from_iter([1.into(), 2.into()]).into(), 3.into()]),
+    );
+    check("when [] is [h, ...t] -> t, _ -> null", Value::null());
+    check("let [a, ...rest] = [1, 2] in rest", List::unit(2.into()));
+}
+
+#[test]
+fn test_map_patterns() {
+    let pat = "{name = n, age = _} -> n, _ -> null";
+    check(&format!(r#"when {{name = "a", age = 3}} is {}"#, pat), "a");
+    check(&format!(r#"when {{name = "a"}} is {}"#, pat), Value::null());
+    check(&format!("when [1, 2] is {}", pat), Value::null());
+    check(
+        &format!(r#"when {{name = "a", age = 3, id = 0}} is {}"#, pat),
+        Value::null(),
+    );
+
+    check(
+        r#"when {name = "a", id = 0} is {name = n, ...} -> n, _ -> null"#,
+        "a",
+    );
+    check(r#"when {name = "a"} is {name} -> name"#, "a");
+    check(
+        r#"when {"a b" = 1, :c = 2} is {"a b" = x, :c = y} -> x + y"#,
+        3,
+    );
+    check("when {x = [1, 2]} is {x = [_, y]} -> y", 2);
+    check("when {} is {} -> true", true);
+    check("let {a, b} = {a = 1, b = 2} in a + b", 3);
+
+    let code = r#"let k = "b" in when {ab = 1} is {"a${k}" = x} -> x, _ -> null"#;
+    let (_, diagnostics) = compile_text(Map::new(), code);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "invalid pattern");
+}
+
+#[test]
+fn test_float_patterns() {
+    check("when 1.5 is 1.5 -> true, _ -> false", true);
+    check("when 1.5 is 2.5 -> true, _ -> false", false);
+    check("when [0.5, 1] is [0.5, x] -> x, _ -> null", 1);
+    check("when 2 is 2.0 -> true, _ -> false", false);
+    check("when 2.0 is 2 -> true, _ -> false", false);
+    check("when 0.0 / 0.0 is 0.0 -> true, _ -> false", false);
+    check("when 0.0 / 0.0 is x -> x != 1.0", true);
+}
+
+#[test]
+fn test_pipeline() {
+    let res = gg_expr::syntax::parse("a |> f(b) |> g");
+    assert!(res.diagnostics.is_empty());
+
+    let call = match res.expr {
+        Some(Expr::Call(call)) => call,
+        _ => panic!("expected a call"),
+    };
+    assert_eq!(call.func().unwrap().to_string().trim(), "g");
+
+    let args = call.args().collect::<Vec<_>>();
+    assert_eq!(args.len(), 1);
+
+    let inner = match &args[0] {
+        Expr::Call(call) => call,
+        _ => panic!("expected a call"),
+    };
+    assert_eq!(inner.func().unwrap().to_string().trim(), "f");
+
+    let args = inner.args().map(|v| v.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        args.iter().map(|v| v.trim()).collect::<Vec<_>>(),
+        ["a", "b"]
+    );
+
+    check(r#"" Ab " |> string.upper |> string.trim"#, "AB");
+    check("[3, 1, 2] |> list.sort |> list.map(fn(x): x * 2)", {
+        List::from_iter([2.into(), 4.into(), 6.into()])
+    });
+    check("1 + 2 |> fn(x): x * 10", 30);
+    check("let f = fn(a, b): a - b in 5 |> f(3)", 2);
+    check("([1, 2] |> list.len) == 2", true);
+}
+
+#[test]
+fn test_bitwise_operators() {
+    check("12 & 10", 8);
+    check("12 | 10", 14);
+    check("12 ^ 10", 6);
+    check("1 << 4", 16);
+    check("-16 >> 2", -4);
+    check("1 << 31", i32::MIN);
+    check("1 | 2 & 3 << 1", 3);
+    check("1 + 1 << 2", 8);
+    check("6 & 3 == 2", true);
+
+    let (res, _) = eval(builtins(), "1 << 32");
+    let error = res.unwrap_err();
+    assert_eq!(
+        error.diagnostic().message,
+        "operator `<<` cannot shift by 32 bits"
+    );
+
+    let (res, _) = eval(builtins(), "1 >> -1");
+    assert!(res.is_err());
+    let (res, _) = eval(builtins(), "1.0 & 1");
+    assert!(res.is_err());
+    let (res, _) = eval(builtins(), "true | false");
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_json() {
+    let json = serde_json::json!({
+        "name": "gg",
+        "version": 3,
+        "scale": 1.5,
+        "enabled": true,
+        "parent": null,
+        "tags": ["a", ["b", {"c": 1}]],
+    });
+
+    let value = Value::from_json(&json);
+    let expected = eval(
+        builtins(),
+        r#"{name = "gg", version = 3, scale = 1.5, enabled = true, parent = null,
+            tags = ["a", ["b", {c = 1}]]}"#,
+    )
+    .0
+    .unwrap();
+    assert!(value.deep_eq(&expected));
+    assert_eq!(value.to_json().unwrap(), json);
+
+    let big = Value::from_json(&serde_json::json!(1u64 << 40));
+    assert!(big.is_float());
+
+    let (func, _) = eval(Map::new(), "fn(x): x");
+    let error = func.unwrap().to_json().unwrap_err();
+    assert_eq!(error, ToJsonError::Unsupported(Type::Func));
+
+    let (map, _) = eval(Map::new(), "{:kind = :player, [:id] = 1}");
+    let json = serde_json::json!({"kind": "player", "id": 1});
+    assert_eq!(map.unwrap().to_json().unwrap(), json);
+
+    let (map, _) = eval(Map::new(), "{[1] = 2}");
+    let error = map.unwrap().to_json().unwrap_err();
+    assert_eq!(error, ToJsonError::NonStringKey(Type::Int));
+
+    let value = Value::from(List::unit(Value::from(f32::NAN)));
+    assert_eq!(value.to_json(), Err(ToJsonError::NonFiniteFloat));
+}
+
+#[test]
+fn test_unreachable_cases() {
+    let (_, diagnostics) = compile_text(Map::new(), "fn(x): when x is _ -> 1, 2 -> 3");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].message, "unreachable pattern");
+
+    let labels = match &diagnostics[0].components[..] {
+        [Component::Source(src)] => &src.labels,
+        _ => panic!("expected a source component"),
+    };
+    assert_eq!(labels[0].message, "this case is never reached");
+    assert_eq!(labels[0].range, TextRange::new(25.into(), 31.into()));
+    assert_eq!(labels[1].range, TextRange::new(17.into(), 18.into()));
+
+    let warnings = |code: &str| {
+        let (_, diagnostics) = compile_text(Map::new(), code);
+        assert!(diagnostics.iter().all(|v| v.severity == Severity::Warning));
+        diagnostics.len()
+    };
+    assert_eq!(warnings("fn(x): when x is y -> 1, 2 -> 3, _ -> 4"), 2);
+    assert_eq!(warnings("fn(x): when x is 1 | _ -> 1, 2 -> 3"), 1);
+    assert_eq!(warnings("fn(x): when x is (_ as y) -> 1, 2 -> 3"), 1);
+    assert_eq!(warnings("fn(x): when x is 1 -> 1, [_] -> 2, _ -> 3"), 0);
+    assert_eq!(warnings("fn(x): when x is [y] -> 1, _ -> 2"), 0);
+
+    let (res, diagnostics) = eval(builtins(), "when 2 is _ -> 1, 2 -> 3");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(res.unwrap(), 1.into());
+}
+
+fn importer(files: &'static [(&'static str, &'static str)]) -> Importer {
+    Importer::new(builtins(), move |_, path| {
+        let path = path.trim_start_matches("./");
+        match files.iter().find(|(name, _)| *name == path) {
+            Some((name, text)) => Ok(ResolvedImport {
+                path: name.to_string(),
+                text: text.to_string(),
+            }),
+            None => Err(format!("module `{}` not found", path)),
+        }
+    })
+}
+
+#[test]
+fn test_import() {
+    let files = &[
+        ("util.expr", "{double = fn(x): x * 2, name = :util}"),
+        ("a.expr", r#"import "b.expr""#),
+        ("b.expr", r#"import "a.expr""#),
+    ];
+
+    let eval_with_imports = |code: &str| {
+        let (func, diagnostics) = compile_text_with_importer(builtins(), code, importer(files));
+        (func.map(|f| Vm::new().eval(&f, &[])), diagnostics)
+    };
+
+    let (res, diagnostics) =
+        eval_with_imports(r#"let u = import "./util.expr" in [u.double(21), u.name]"#);
+    assert!(diagnostics.is_empty());
+    assert_eq!(
+        res.unwrap().unwrap(),
+        List::from_iter([42.into(), Value::from_symbol("util")]).into()
+    );
+
+    let (_, diagnostics) = eval_with_imports(r#"import "a.expr""#);
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.message == "cyclic import: a.expr -> b.expr -> a.expr"));
+
+    let (_, diagnostics) = eval_with_imports(r#"import "missing.expr""#);
+    assert_eq!(diagnostics[0].message, "module `missing.expr` not found");
+
+    let (_, diagnostics) = compile_text(builtins(), r#"import "util.expr""#);
+    assert_eq!(diagnostics[0].message, "cannot import modules");
+
+    // every load would produce a different value, so equal results mean the
+    // module was only evaluated once
+    let loads = Arc::new(Mutex::new(0));
+    let importer = Importer::new(Map::new(), move |_, path| {
+        let mut loads = loads.lock().unwrap();
+        *loads += 1;
+        Ok(ResolvedImport {
+            path: path.into(),
+            text: loads.to_string(),
+        })
+    });
+
+    let code = r#"[import "x", import "x"]"#;
+    let (func, diagnostics) = compile_text_with_importer(Map::new(), code, importer);
+    assert!(diagnostics.is_empty());
+    assert_eq!(
+        Vm::new().eval(&func.unwrap(), &[]).unwrap(),
+        List::from_iter([1.into(), 1.into()]).into()
+    );
+}
+
+#[test]
+fn test_import_vm_settings() {
+    let files = &[
+        ("loop.expr", "let f = fn(x): f(x + 1) in f(0)"),
+        ("debug.expr", "debug(time.now())"),
+    ];
+
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let importer = importer(files);
+    importer.set_fuel(Some(1000));
+    importer.set_clock(Clock::fixed(1.5));
+    importer.set_debug_sink(DebugSink::new({
+        let output = output.clone();
+        move |message| output.lock().unwrap().push(message.to_owned())
+    }));
+
+    let code = r#"import "debug.expr""#;
+    let (func, diagnostics) = compile_text_with_importer(builtins(), code, importer.clone());
+    assert!(diagnostics.is_empty());
+    assert_eq!(Vm::new().eval(&func.unwrap(), &[]).unwrap(), 1.5.into());
+    assert_eq!(*output.lock().unwrap(), ["1.5"]);
+
+    let code = r#"import "loop.expr""#;
+    let (_, diagnostics) = compile_text_with_importer(builtins(), code, importer);
+    assert_eq!(
+        diagnostics[0].message,
+        "failed to evaluate `loop.expr`: instruction budget exceeded"
+    );
+}
+
+#[test]
+fn test_enumerate_zip() {
+    check(
+        r#"list.enumerate(["a", "b"]) == [[0, "a"], [1, "b"]]"#,
+        true,
+    );
+    check("list.enumerate([]) == []", true);
+    check("list.zip([1, 2, 3], [4, 5]) == [[1, 4], [2, 5]]", true);
+    check("list.zip([], [1]) == []", true);
+}
+
+#[test]
+fn test_list_module() {
+    check("list.map([1, 2, 3], fn(x): x * 2) == [2, 4, 6]", true);
+    check(
+        "let k = 3 in list.map([1, 2], fn(x): x * k) == [3, 6]",
+        true,
+    );
+    check(
+        "list.filter(list.range(0, 6), fn(x): x % 2 == 0) == [0, 2, 4]",
+        true,
+    );
+    check("list.fold([1, 2, 3], 10, fn(acc, x): acc + x)", 16);
+    check("list.range(3, 3) == []", true);
+    check("list.len([1, 2])", 2);
+    check("list.reverse([1, 2, 3]) == [3, 2, 1]", true);
+    check("list.sort([3, 1.5, 2]) == [1.5, 2, 3]", true);
+    check(r#"list.sort(["b", "a"]) == ["a", "b"]"#, true);
+    check("list.contains([1, 2], 2)", true);
+
+    let (res, _) = eval(builtins(), r#"list.sort([1, "a"])"#);
+    assert!(res.is_err());
+    let (res, _) = eval(builtins(), "list.map([1], fn(x, y): x)");
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_deep_merge_get_path() {
+    check(
+        "map.deep_merge({a = {x = 1}}, {a = {y = 2}}) == {a = {x = 1, y = 2}}",
+        true,
+    );
+    check(
+        "map.deep_merge({a = {x = 1}, b = 1}, {a = 2}) == {a = 2, b = 1}",
+        true,
+    );
+    check(
+        "map.deep_merge({a = 1}, {a = {x = 1}}) == {a = {x = 1}}",
+        true,
+    );
+    check(
+        "let m = {a = {x = 1}} in let n = map.deep_merge(m, {a = {x = 2}}) in [m.a.x, n.a.x]",
+        List::from_iter([1.into(), 2.into()]),
+    );
+
+    check(r#"map.get_path({a = {b = {c = 1}}}, ["a", "b", "c"])"#, 1);
+    check(
+        r#"map.get_path({a = {b = {c = 1}}}, ["a", "x", "c"])"#,
+        Value::null(),
+    );
+    check(r#"map.get_path({a = [1, 2]}, ["a", 1])"#, 2);
+    check(r#"map.get_path({a = 1}, ["a", "b"])"#, Value::null());
+    check("map.get_path({a = 1}, [])", Map::unit("a".into(), 1.into()));
+}
+
+#[test]
+fn test_string_format() {
+    check(r#"string.format("{} + {} = {}", [1, 2, 3])"#, "1 + 2 = 3");
+    check(r#"string.format("{1} {0} {1}", ["a", "b"])"#, "b a b");
+    check(r#"string.format("{{literal}}", [])"#, "{literal}");
+    check(r#"string.format("{}", [1.5])"#, "1.5");
+
+    let (res, _) = eval(builtins(), r#"string.format("{2}", [1])"#);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_string_module() {
+    check(r#"string.upper("hi")"#, "HI");
+    check(r#"string.lower("Hi")"#, "hi");
+    check(r#"string.trim("  hi ")"#, "hi");
+    check(r#"string.len("héllo")"#, 5);
+    check(
+        r#"string.split("a,b,,c", ",") == ["a", "b", "", "c"]"#,
+        true,
+    );
+    check(r#"string.replace("a-b-c", "-", "+")"#, "a+b+c");
+    check(r#"string.contains("hello", "ell")"#, true);
+    check(r#"string.starts_with("hello", "he")"#, true);
+    check(r#"string.ends_with("hello", "he")"#, false);
+    check(r#"string.chars("abc") == ["a", "b", "c"]"#, true);
+
+    let (res, _) = eval(builtins(), "string.upper(1)");
+    assert!(res.is_err());
+    let (res, _) = eval(builtins(), r#"string.split("abc", "")"#);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_debug_sink() {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let mut vm = Vm::new();
+    vm.set_debug_sink(DebugSink::new({
+        let output = output.clone();
+        move |message| output.lock().unwrap().push(message.to_owned())
+    }));
+
+    let code = r#"let y = debug([1, "a"]) in print("y") + print(y[1])"#;
+    let (func, diagnostics) = compile_text(builtins(), code);
+    assert!(diagnostics.is_empty());
+    let res = vm.eval(&func.unwrap(), &[]).unwrap();
+
+    assert_eq!(res, Value::from("ya"));
+    assert_eq!(*output.lock().unwrap(), [r#"[1, "a"]"#, "y", "a"]);
+}
+
+#[test]
+fn test_call() {
+    let (func, diagnostics) = eval(Map::new(), "fn(a, b): a * 10 + b");
+    assert!(diagnostics.is_empty());
+    let func = func.unwrap();
+
+    let mut vm = Vm::new();
+    let res = vm.call(&func, &[1.into(), 2.into()]);
+    assert_eq!(res.unwrap(), 12.into());
+
+    let error = vm.call(&func, &[1.into()]).unwrap_err();
+    assert_eq!(error.diagnostic().message, "expected 2 arguments, got 1");
+
+    let error = vm.call(&func, &[1.into(), 2.into(), 3.into()]).unwrap_err();
+    assert_eq!(error.diagnostic().message, "expected 2 arguments, got 3");
+
+    let func = FuncValue::try_from(func).unwrap();
+    assert_eq!(
+        func.call(&mut vm, &[3.into(), 4.into()]).unwrap(),
+        34.into()
+    );
+
+    let (func, _) = eval(Map::new(), "fn(a, ...rest): [a, rest]");
+    let func = FuncValue::try_from(func.unwrap()).unwrap();
+    let error = func.call(&mut vm, &[]).unwrap_err();
+    assert_eq!(
+        error.diagnostic().message,
+        "expected at least 1 arguments, got 0"
+    );
+    assert!(func.call(&mut vm, &[1.into(), 2.into()]).is_ok());
+
+    let error = vm.call(&Value::from(1), &[]).unwrap_err();
+    assert_eq!(error.diagnostic().message, "expected func, found int");
+}
+
+#[test]
+fn test_bytecode() {
+    let code = r#"
+        let
+            add = fn(a, b): a + b,
+            xs = list.map([1, 2, 3], fn(x): add(x, 1)),
+            m = {a = 1.5, b = :sym, c = [true, null]}
+        in
+            [xs, len(xs), m.a, m.b, m.c, string.upper("${xs[0]}x")]
+    "#;
+
+    let (func, diagnostics) = compile_text(builtins(), code);
+    assert!(diagnostics.is_empty());
+    let func = func.unwrap();
+    let func = func.as_func().unwrap();
+
+    let bytes = func.to_bytes();
+    let loaded = Func::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.instrs, func.instrs);
+
+    let mut vm = Vm::new();
+    let expected = vm.eval(&func.clone().into(), &[]).unwrap();
+    let res = vm.eval(&loaded.into(), &[]).unwrap();
+    assert!(res.deep_eq(&expected));
+
+    let mut bad_version = bytes.clone();
+    bad_version[4] = 0xff;
+    let error = Func::from_bytes(&bad_version).unwrap_err();
+    assert!(error
+        .diagnostic()
+        .message
+        .starts_with("unsupported bytecode version"));
+
+    assert!(Func::from_bytes(b"not bytecode").is_err());
+    assert!(Func::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+    let error = Func::from_bytes_with_env(&bytes, &Map::new()).unwrap_err();
+    assert!(error
+        .diagnostic()
+        .message
+        .starts_with("cannot find external function"));
+
+    // a function with a single constant: `depth` nested one-element lists
+    let nested_lists = |depth: usize| {
+        let mut bytes = bytes[..6].to_vec();
+        bytes.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+        for _ in 0..depth {
+            bytes.extend_from_slice(&[Type::List as u8, 1, 0, 0, 0]);
+        }
+        bytes.extend_from_slice(&[Type::Null as u8, 0, 0, 0, 0]);
+        bytes
+    };
+
+    assert!(Func::from_bytes(&nested_lists(100)).is_ok());
+    let error = Func::from_bytes(&nested_lists(100_000)).unwrap_err();
+    assert_eq!(
+        error.diagnostic().message,
+        "compiled function is nested too deeply"
+    );
+
+    let mut huge_len = nested_lists(0);
+    huge_len[11..15].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(Func::from_bytes(&huge_len).is_err());
+}
+
+#[test]
+fn test_fuel() {
+    let (func, diagnostics) = compile_text(builtins(), "let f = fn(n): f(n + 1) in f(0)");
+    assert!(diagnostics.is_empty());
+    let func = func.unwrap();
+
+    let mut vm = Vm::with_fuel(10_000);
+    let error = vm.eval(&func, &[]).unwrap_err();
+    assert_eq!(error.diagnostic().message, "instruction budget exceeded");
+    assert_eq!(vm.fuel(), Some(0));
+
+    let (func, _) = compile_text(
+        builtins(),
+        "try list.map(list.range(0, 1000), fn(x): x * 2) catch e -> e",
+    );
+    let mut vm = Vm::with_fuel(100);
+    let res = vm.eval(&func.unwrap(), &[]).unwrap_err();
+    assert_eq!(res.diagnostic().message, "instruction budget exceeded");
+
+    let (func, _) = compile_text(builtins(), "len(0..1000)");
+    let mut vm = Vm::with_fuel(100);
+    let res = vm.eval(&func.unwrap(), &[]).unwrap_err();
+    assert_eq!(res.diagnostic().message, "instruction budget exceeded");
+
+    let (func, _) = compile_text(builtins(), "1 + 2");
+    let func = func.unwrap();
+    let mut vm = Vm::new();
+    assert_eq!(vm.fuel(), None);
+    assert_eq!(vm.eval(&func, &[]).unwrap(), 3.into());
+
+    vm.set_fuel(Some(100));
+    assert_eq!(vm.eval(&func, &[]).unwrap(), 3.into());
+    let remaining = vm.fuel().unwrap();
+    assert!(remaining > 0 && remaining < 100);
+}
+
+#[test]
+fn test_time() {
+    let mut vm = Vm::new();
+    vm.set_clock(Clock::fixed(93784.25));
+
+    let mut run = |code: &str| {
+        let (func, diagnostics) = compile_text(builtins(), code);
+        assert!(diagnostics.is_empty());
+        vm.eval(&func.unwrap(), &[]).unwrap()
+    };
+
+    assert_eq!(run("time.now()"), Value::from(93784.25));
+    assert_eq!(
+        run(r#"time.format(time.now(), "%d %H:%M:%S.%f")"#),
+        Value::from("1 02:03:04.250")
+    );
+    assert_eq!(
+        run(r#"time.format(90, "%M:%S %%")"#),
+        Value::from("01:30 %")
+    );
+
+    let (res, _) = eval(builtins(), r#"time.format(1, "%x")"#);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_duplicate_map_keys() {
+    let (_, diagnostics) = compile_text(Map::new(), "{a = 1, a = 2}");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("duplicate"));
+
+    let labels = match &diagnostics[0].components[..] {
+        [Component::Source(src)] => &src.labels,
+        _ => panic!("expected a source component"),
+    };
+    assert_eq!(labels.len(), 2);
+    assert_ne!(labels[0].range, labels[1].range);
+
+    let (_, diagnostics) = compile_text(Map::new(), r#"{a = 1, "a" = 2, :a = 3}"#);
+    assert_eq!(diagnostics.len(), 1);
+
+    check("{a = 1, b = 2} == {b = 2, a = 1}", true);
+    check(r#"let k = "a", m = {[k] = 1, a = 2} in m.a"#, 2);
+}
+
+#[test]
+fn test_lazy() {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let mut vm = Vm::new();
+    vm.set_debug_sink(DebugSink::new({
+        let output = output.clone();
+        move |message| output.lock().unwrap().push(message.to_owned())
+    }));
+
+    let code = "let t = lazy(fn(): debug(6 * 7)) in [force(t), force(t)]";
+    let (func, diagnostics) = compile_text(builtins(), code);
+    assert!(diagnostics.is_empty());
+    let res = vm.eval(&func.unwrap(), &[]).unwrap();
+
+    assert_eq!(res, Value::from(List::from_iter([42.into(), 42.into()])));
+    assert_eq!(*output.lock().unwrap(), ["42"]);
+
+    check("let t = lazy(fn(): 1) in 0", 0);
+    check("force(5)", 5);
+
+    let (res, _) = eval(builtins(), "lazy(fn(x): x)");
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_comments() {
+    let tokens = Lexer::new("1 /* a /* b */ c */ + 2 // end")
+        .filter(|(_, kind)| *kind != SyntaxKind::TokWhitespace)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tokens,
+        [
+            ("1", SyntaxKind::TokInt),
+            ("/* a /* b */ c */", SyntaxKind::TokComment),
+            ("+", SyntaxKind::TokAdd),
+            ("2", SyntaxKind::TokInt),
+            ("// end", SyntaxKind::TokComment),
+        ]
+    );
+
+    check("1 /* one */ + /* two */ 2 // three", 3);
+    check("[1, // first\n 2] == [1, 2]", true);
+
+    let (res, diagnostics) = eval(builtins(), "1 + /* two */ null");
+    assert!(diagnostics.is_empty());
+    let error = res.unwrap_err();
+    let labels = match &error.diagnostic().components[..] {
+        [Component::Source(src)] => &src.labels,
+        _ => panic!("expected a source component"),
+    };
+    assert_eq!(labels[1].range, TextRange::new(14.into(), 18.into()));
+
+    let (_, diagnostics) = compile_text(Map::new(), "1 + 2 /* a /* b */");
+    assert_eq!(diagnostics.len(), 1);
+    let labels = match &diagnostics[0].components[..] {
+        [Component::Source(src)] => &src.labels,
+        _ => panic!("expected a source component"),
+    };
+    assert_eq!(labels[0].message, "unterminated block comment");
+    assert_eq!(labels[0].range, TextRange::new(6.into(), 8.into()));
+
+    let (_, diagnostics) = compile_text(Map::new(), "1 /*");
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn test_string_escapes() {
+    check(r#""\u{1F600}""#, "😀");
+    check(r#""smile: \u{1f600}!""#, "smile: 😀!");
+    check(r#""\u{41}\u{e9}""#, "Aé");
+    check(r#""say \"hi\"""#, "say \"hi\"");
+    check(r#""a\0b""#, "a\0b");
+    check(r#""\\ \r\n\t""#, "\\ \r\n\t");
+    check(r#"let x = 1 in "\"${x}\"""#, "\"1\"");
+    check(r#"when "\"" is "\u{22}" -> true, _ -> false"#, true);
+
+    for (code, message, range) in [
+        (r#""\u{110000}""#, "invalid unicode codepoint", (1, 11)),
+        (r#""\u{D800}""#, "invalid unicode codepoint", (1, 9)),
+        (
+            r#""ab\u{zz}""#,
+            "invalid unicode escape, expected `\\u{XXXX}`",
+            (3, 9),
+        ),
+        (
+            r#""\u41""#,
+            "invalid unicode escape, expected `\\u{XXXX}`",
+            (1, 3),
+        ),
+        (
+            r#""\u{1234567}""#,
+            "invalid unicode escape, expected `\\u{XXXX}`",
+            (1, 12),
+        ),
+    ] {
+        let (_, diagnostics) = compile_text(Map::new(), code);
+        assert_eq!(diagnostics.len(), 1, "{}", code);
+        let labels = match &diagnostics[0].components[..] {
+            [Component::Source(src)] => &src.labels,
+            _ => panic!("expected a source component"),
+        };
+        assert_eq!(labels[0].message, message);
+        assert_eq!(
+            labels[0].range,
+            TextRange::new(range.0.into(), range.1.into())
+        );
+    }
+}
+
+#[test]
+fn test_int_literals() {
+    check("0xFF", 255);
+    check("0x7fff_ffff", i32::MAX);
+    check("0o755", 0o755);
+    check("0b1010", 10);
+    check("0b1111_0000", 0xf0);
+    check("1_000_000", 1_000_000);
+    check("1_000.5", 1000.5);
+    check("when 0x10 is 16 -> true, _ -> false", true);
+
+    for (code, message) in [
+        ("0x", "expected digits after `0x`"),
+        ("0b", "expected digits after `0b`"),
+        ("1__0", "consecutive underscores in integer literal"),
+        ("1_", "trailing underscore in integer literal"),
+        ("0b102", "invalid digit in integer literal `0b102`"),
+        ("0xFFFF_FFFF", "does not fit into a 32-bit signed integer"),
+        ("2147483648", "does not fit into a 32-bit signed integer"),
+    ] {
+        let (_, diagnostics) = compile_text(Map::new(), code);
+        assert_eq!(diagnostics.len(), 1, "{}", code);
+        let labels = match &diagnostics[0].components[..] {
+            [Component::Source(src)] => &src.labels,
+            _ => panic!("expected a source component"),
+        };
+        assert_eq!(labels[0].message, message);
+        assert_eq!(
+            labels[0].range,
+            TextRange::up_to((code.len() as u32).into())
+        );
+    }
+}
+
+#[test]
+fn test_string_interpolation() {
+    let tokens = Lexer::new(r#""a ${x} b ${y}""#).collect::<Vec<_>>();
+    assert_eq!(
+        tokens,
+        [
+            (r#""a ${"#, SyntaxKind::TokInterpStart),
+            ("x", SyntaxKind::TokIdent),
+            ("} b ${", SyntaxKind::TokInterpMid),
+            ("y", SyntaxKind::TokIdent),
+            (r#"}""#, SyntaxKind::TokInterpEnd),
+        ]
+    );
+
+    check(r#"let name = "world" in "hello ${name}""#, "hello world");
+    check(r#""${1 + 2} and ${1.5}""#, "3 and 1.5");
+    check(r#""${ {a = 1}["a"] }!""#, "1!");
+    check(r#""outer ${"inner ${"deep"}"}""#, "outer inner deep");
+    check(r#""${"}"}""#, "}");
+    check(r#""\${x}""#, "${x}");
+    check(r#""$x $""#, "$x $");
+
+    let (_, diagnostics) = compile_text(Map::new(), r#""${}""#);
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn test_rest_params() {
+    let sum = "let add = fn(a, b): a + b, sum = fn(...xs): list.fold(xs, 0, add) in";
+    check(&format!("{} sum(1, 2, 3)", sum), 6);
+    check(&format!("{} sum()", sum), 0);
+    check("let f = fn(...xs): xs in f() == []", true);
+    check(
+        "let f = fn(x, ...rest): [x, rest] in f(1, 2, 3) == [1, [2, 3]]",
+        true,
+    );
+    check("let f = fn(x, ...rest): rest in f(1) == []", true);
+    check(
+        "let f = fn(x, ...rest): [x, rest] in f() == [null, []]",
+        true,
+    );
+    check(
+        "let f = fn(...xs): list.len(xs), g = fn(): f(1, 2) in g()",
+        2,
+    );
+    check("list.map([1, 2], fn(...xs): xs) == [[1], [2]]", true);
+    check_func(
+        "fn(x, ...xs): [x, xs]",
+        &[&1.into()],
+        List::from_iter([1.into(), List::new().into()]),
+    );
+
+    let (_, diagnostics) = eval(builtins(), "fn(...xs, y): y");
+    assert!(diagnostics[0].message.contains("rest parameter"));
+}
+
+#[test]
+fn test_content_hash() {
+    let config = |code: &str| eval(builtins(), code).0.unwrap();
+
+    let a = config(r#"{name = "gg", size = [800, 600], scale = 1.5, mode = :windowed}"#);
+    let b = config(r#"{mode = :windowed, scale = 1.5, size = [800, 600], name = "gg"}"#);
+    let c = config(r#"{name = "gg", size = [800, 601], scale = 1.5, mode = :windowed}"#);
+
+    assert!(a.deep_eq(&b));
+    assert_eq!(a.content_hash(), b.content_hash());
+    assert!(!a.deep_eq(&c));
+    assert_ne!(a.content_hash(), c.content_hash());
+
+    let mut map = Map::new();
+    map.insert("scale".into(), 1.5.into());
+    map.insert("name".into(), "gg".into());
+    map.insert("mode".into(), Value::from_symbol("windowed"));
+    map.insert(
+        "size".into(),
+        List::from_iter([800.into(), 600.into()]).into(),
+    );
+    assert_eq!(Value::from(map).content_hash(), a.content_hash());
+
+    assert_eq!(
+        Value::from(0.0).content_hash(),
+        Value::from(-0.0).content_hash()
+    );
+
+    // functions are hashed with their constants and captured values
+    let a = config("let x = [:a, 1] in fn(): x");
+    let b = config("let x = [:a, 1] in fn(): x");
+    let c = config("let x = [:a, 2] in fn(): x");
+    assert_eq!(a.content_hash(), b.content_hash());
+    assert_ne!(a.content_hash(), c.content_hash());
+    assert_ne!(
+        config("fn(): :a").content_hash(),
+        config("fn(): :b").content_hash()
+    );
+}
+
+#[test]
+fn test_div_by_zero() {
+    let (res, _) = eval(builtins(), "3 / 0");
+    let error = res.unwrap_err();
+    assert_eq!(error.diagnostic().message, "attempt to divide by zero");
+
+    let (res, _) = eval(builtins(), "let x = 0 in 3 % x");
+    let error = res.unwrap_err();
+    assert!(error.diagnostic().message.contains("divisor of zero"));
+
+    check("3.0 / 0 == 1.0 / 0.0", true);
+    check("3 / 0.0 > 1000000", true);
+    check("7 / 2", 3);
+}
+
+#[test]
+fn test_metamethods() {
+    let vec = r#"
+        let add = fn(a, b): {x = a.x + b.x, y = a.y + b.y},
+            scale = fn(a, b): {x = a * b.x, y = a * b.y},
+            v1 = {x = 1, y = 2, __add = add},
+            v2 = {x = 3, y = 4, __add = add, __mul = scale}
+    "#;
+
+    check(&format!("{} in v1 + v2 == {{x = 4, y = 6}}", vec), true);
+    check(&format!("{} in 2 * v2 == {{x = 6, y = 8}}", vec), true);
+
+    check("1 + 2", 3);
+    check(r#""a" + "b""#, "ab");
+    check("[1] + [2]", List::from_iter([1.into(), 2.into()]));
+
+    let (res, _) = eval(builtins(), "{x = 1} + {x = 2}");
+    assert!(res.is_err());
+    let (res, _) = eval(builtins(), "{__add = 1} + 2");
+    assert!(res.is_err());
+
+    let (res, _) = eval(builtins(), "{__add = fn(a): a} + 2");
+    assert_eq!(
+        res.unwrap_err().diagnostic().message,
+        "expected a function with 1 arguments, found 2 arguments"
+    );
+
+    let (res, _) = eval(builtins(), "let v = {__add = fn(a, b): a + b} in v + 1");
+    assert_eq!(res.unwrap_err().diagnostic().message, "stack overflow");
+
+    let (res, _) = eval(builtins(), "let f = fn(x): list.map([x], f) in f(1)");
+    assert_eq!(res.unwrap_err().diagnostic().message, "stack overflow");
+}
+
+#[test]
+fn test_stack_trace_snippets() {
+    yansi::Paint::disable();
+
+    let lines = [
+        "let f = fn(x): x + null,",
+        "    g = fn(x): f(x) + 1,",
+        "    h = fn(x): g(x) + 1",
+        "in [h(1)]",
+    ];
+
+    let (res, diagnostics) = eval(builtins(), &lines.join("\n"));
+    assert!(diagnostics.is_empty());
+
+    let error = res.unwrap_err();
+    assert_eq!(error.stack_trace().unwrap().frames.len(), 4);
+
+    let rendered = error.to_string();
+    let trace = &rendered[rendered.find("stack trace:").unwrap()..];
+
+    let mut pos = 0;
+    for (name, line) in ["fn f", "fn g", "fn h", "fn <main>"].iter().zip(lines) {
+        pos += trace[pos..].find(name).unwrap();
+        pos += trace[pos..].find(line).unwrap();
+    }
+
+    for line in lines {
+        assert_eq!(trace.matches(line).count(), 1);
+    }
+}
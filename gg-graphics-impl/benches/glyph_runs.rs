@@ -0,0 +1,64 @@
+// needs a display and a GPU, like the ignored tests sharing the harness
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::path::Path;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use gg_graphics::{Backend, Command, DrawGlyph, FontFace, GraphicsEncoder};
+use gg_math::Vec2;
+
+fn paragraph(h: &mut common::Harness) -> Vec<DrawGlyph> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets/fonts/OpenSans-Regular.ttf");
+    let face = FontFace::new(std::fs::read(path).unwrap().into(), 0).unwrap();
+    let text = "the quick brown fox jumps over the lazy dog ";
+    let glyphs = text
+        .chars()
+        .map(|c| face.lookup_glyph(c))
+        .collect::<Vec<_>>();
+    let font = h.assets.insert(face);
+
+    (0..5000)
+        .map(|i| DrawGlyph {
+            font: font.id(),
+            glyph: glyphs[i % glyphs.len()],
+            size: 10.0,
+            pos: Vec2::new((i % 100) as f32 * 5.0, 10.0 + (i / 100) as f32 * 10.0),
+            color: [1.0, 1.0, 1.0].into(),
+        })
+        .collect()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut h = common::Harness::new();
+    let paragraph = paragraph(&mut h);
+    let canvas = h.backend.create_canvas(Vec2::new(512, 512));
+
+    let mut render = |split_runs: bool| {
+        let mut encoder = GraphicsEncoder::new(&canvas);
+        encoder.clear([0.0, 0.0, 0.0]);
+
+        for glyph in &paragraph {
+            encoder.glyph(*glyph);
+
+            // any other command ends a run
+            if split_runs {
+                encoder.command(Command::ClearScissor);
+            }
+        }
+
+        h.backend.submit(encoder.finish());
+        h.backend.present(&mut h.assets).unwrap();
+    };
+
+    // rasterize the glyphs before measuring
+    render(false);
+
+    c.bench_function("5000 glyphs in one run", |b| b.iter(|| render(false)));
+    c.bench_function("5000 glyphs in single glyph runs", |b| {
+        b.iter(|| render(true))
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
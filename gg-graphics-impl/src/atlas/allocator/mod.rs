@@ -1,9 +1,11 @@
 mod grid;
+mod shelf;
 mod tree;
 
 use gg_math::{Rect, Vec2};
 
 pub use self::grid::GridAllocator;
+pub use self::shelf::ShelfAllocator;
 pub use self::tree::TreeAllocator;
 
 pub trait Allocator: std::fmt::Debug + Send + Sync + 'static {
@@ -35,6 +37,7 @@ pub struct AllocationId(pub u32);
 pub enum AllocatorKind {
     Tree,
     Grid { cell_size: Vec2<u16> },
+    Shelf,
 }
 
 impl AllocatorKind {
@@ -45,6 +48,7 @@ impl AllocatorKind {
                 let grid_size = size.cast().zip_map(cell_size, |a, b| (a + b - 1) / b);
                 GridAllocator::new(grid_size, cell_size).into()
             }
+            AllocatorKind::Shelf => ShelfAllocator::new(size).into(),
         }
     }
 }
@@ -56,6 +60,7 @@ impl AnyAllocator {
             AnyAllocator::Grid(v) => AllocatorKind::Grid {
                 cell_size: v.cell_size(),
             },
+            AnyAllocator::Shelf(_) => AllocatorKind::Shelf,
         }
     }
 }
@@ -112,4 +117,5 @@ macro_rules! any_allocator {
 any_allocator! {
     Tree(TreeAllocator),
     Grid(GridAllocator),
+    Shelf(ShelfAllocator),
 }
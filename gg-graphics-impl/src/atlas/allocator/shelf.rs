@@ -0,0 +1,295 @@
+use std::ops::Range;
+
+use gg_math::{Rect, Vec2};
+
+use super::{Allocation, AllocationId, Allocator};
+
+// shelf heights are rounded up, so that glyphs of slightly different sizes
+// end up on the same shelves
+const SHELF_ALIGN: u32 = 4;
+
+// rects are not put on shelves much taller than themselves
+const MAX_SHELF_WASTE: f32 = 1.5;
+
+#[derive(Debug)]
+pub struct ShelfAllocator {
+    size: Vec2<u32>,
+    shelves: Vec<Shelf>,
+    slots: Vec<Option<Slot>>,
+    free_slots: Vec<u32>,
+}
+
+#[derive(Debug)]
+struct Shelf {
+    y: u32,
+    height: u32,
+    end_x: u32,
+    free_spans: Vec<Range<u32>>,
+    num_allocs: u32,
+}
+
+#[derive(Clone, Debug)]
+struct Slot {
+    shelf: usize,
+    span: Range<u32>,
+}
+
+impl ShelfAllocator {
+    pub fn new(size: Vec2<u32>) -> ShelfAllocator {
+        ShelfAllocator {
+            size,
+            shelves: Vec::new(),
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+        }
+    }
+
+    fn next_y(&self) -> u32 {
+        self.shelves.last().map_or(0, |v| v.y + v.height)
+    }
+
+    fn alloc_span(&mut self, size: Vec2<u32>) -> Option<(usize, Range<u32>)> {
+        let max_height = (size.y as f32 * MAX_SHELF_WASTE).ceil() as u32;
+
+        let mut best = None;
+        for (i, shelf) in self.shelves.iter().enumerate() {
+            let fits = shelf.height >= size.y && (shelf.height <= max_height || shelf.is_empty());
+            if !fits || !shelf.has_space(size.x, self.size.x) {
+                continue;
+            }
+
+            if best.map_or(true, |(_, height)| shelf.height < height) {
+                best = Some((i, shelf.height));
+            }
+        }
+
+        if let Some((i, _)) = best {
+            let span = self.shelves[i].alloc(size.x, self.size.x)?;
+            return Some((i, span));
+        }
+
+        let height = align_up(size.y, SHELF_ALIGN);
+        let y = self.next_y();
+        if size.x > self.size.x || y + height > self.size.y {
+            return None;
+        }
+
+        let mut shelf = Shelf {
+            y,
+            height,
+            end_x: 0,
+            free_spans: Vec::new(),
+            num_allocs: 0,
+        };
+
+        let span = shelf.alloc(size.x, self.size.x)?;
+        self.shelves.push(shelf);
+
+        Some((self.shelves.len() - 1, span))
+    }
+
+    fn insert_slot(&mut self, slot: Slot) -> AllocationId {
+        match self.free_slots.pop() {
+            Some(id) => {
+                self.slots[id as usize] = Some(slot);
+                AllocationId(id)
+            }
+            None => {
+                self.slots.push(Some(slot));
+                AllocationId(self.slots.len() as u32 - 1)
+            }
+        }
+    }
+}
+
+impl Shelf {
+    fn is_empty(&self) -> bool {
+        self.num_allocs == 0
+    }
+
+    fn has_space(&self, width: u32, max_width: u32) -> bool {
+        self.end_x + width <= max_width || self.free_spans.iter().any(|v| v.len() as u32 >= width)
+    }
+
+    fn alloc(&mut self, width: u32, max_width: u32) -> Option<Range<u32>> {
+        // partially filled shelves reuse the smallest freed span that fits
+        let best = self
+            .free_spans
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.len() as u32 >= width)
+            .min_by_key(|(_, v)| v.len())
+            .map(|(i, _)| i);
+
+        let span = if let Some(i) = best {
+            let free = &mut self.free_spans[i];
+            let span = free.start..free.start + width;
+            free.start += width;
+
+            if free.start >= free.end {
+                self.free_spans.swap_remove(i);
+            }
+
+            span
+        } else if self.end_x + width <= max_width {
+            let span = self.end_x..self.end_x + width;
+            self.end_x += width;
+            span
+        } else {
+            return None;
+        };
+
+        self.num_allocs += 1;
+        Some(span)
+    }
+
+    fn free(&mut self, mut span: Range<u32>) {
+        self.num_allocs -= 1;
+
+        if self.num_allocs == 0 {
+            self.free_spans.clear();
+            self.end_x = 0;
+            return;
+        }
+
+        // merge with neighbouring free spans
+        while let Some(i) = self
+            .free_spans
+            .iter()
+            .position(|v| v.end == span.start || v.start == span.end)
+        {
+            let free = self.free_spans.swap_remove(i);
+            span = free.start.min(span.start)..free.end.max(span.end);
+        }
+
+        if span.end == self.end_x {
+            self.end_x = span.start;
+        } else {
+            self.free_spans.push(span);
+        }
+    }
+}
+
+impl Allocator for ShelfAllocator {
+    fn size(&self) -> Vec2<u32> {
+        self.size
+    }
+
+    fn can_grow(&self) -> bool {
+        true
+    }
+
+    fn grow(&mut self, new_size: Vec2<u32>) {
+        self.size = self.size.max(new_size);
+    }
+
+    fn alloc(&mut self, size: Vec2<u32>) -> Option<Allocation> {
+        // empty rects still take a pixel, so that every allocation has a span
+        let (shelf, span) = self.alloc_span(size.max(Vec2::splat(1)))?;
+        let rect = Rect::new(Vec2::new(span.start, self.shelves[shelf].y), size);
+        let id = self.insert_slot(Slot { shelf, span });
+
+        Some(Allocation { id, rect })
+    }
+
+    fn free(&mut self, id: AllocationId) {
+        let slot = match self.slots.get_mut(id.0 as usize).and_then(Option::take) {
+            Some(v) => v,
+            None => return,
+        };
+
+        self.free_slots.push(id.0);
+        self.shelves[slot.shelf].free(slot.span);
+
+        // empty shelves at the bottom give their space back to taller ones
+        while self.shelves.last().map_or(false, Shelf::is_empty) {
+            self.shelves.pop();
+        }
+    }
+}
+
+fn align_up(v: u32, align: u32) -> u32 {
+    (v + align - 1) / align * align
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overlaps(a: &Rect<u32>, b: &Rect<u32>) -> bool {
+        a.min.x < b.max.x && b.min.x < a.max.x && a.min.y < b.max.y && b.min.y < a.max.y
+    }
+
+    #[test]
+    fn packs_same_height_rects() {
+        let mut allocator = ShelfAllocator::new(Vec2::new(256, 64));
+        let size = Vec2::new(16, 16);
+
+        // 16 per shelf, 4 shelves
+        let allocs = (0..64)
+            .map(|_| allocator.alloc(size).unwrap())
+            .collect::<Vec<_>>();
+        assert!(allocator.alloc(size).is_none());
+
+        for (i, a) in allocs.iter().enumerate() {
+            assert_eq!(a.rect.size(), size);
+            assert!(a.rect.max.x <= 256 && a.rect.max.y <= 64);
+            assert!(allocs[i + 1..].iter().all(|b| !overlaps(&a.rect, &b.rect)));
+        }
+    }
+
+    #[test]
+    fn free_reuses_space() {
+        let mut allocator = ShelfAllocator::new(Vec2::new(64, 32));
+        let size = Vec2::new(16, 16);
+
+        let allocs = (0..8)
+            .map(|_| allocator.alloc(size).unwrap())
+            .collect::<Vec<_>>();
+        assert!(allocator.alloc(size).is_none());
+
+        // a hole in a partially filled shelf is reused
+        allocator.free(allocs[1].id);
+        let reused = allocator.alloc(size).unwrap();
+        assert_eq!(reused.rect, allocs[1].rect);
+
+        // adjacent holes merge into a wider span
+        allocator.free(allocs[5].id);
+        allocator.free(allocs[6].id);
+        let wide = allocator.alloc(Vec2::new(32, 16)).unwrap();
+        assert_eq!(wide.rect.min, allocs[5].rect.min);
+        assert!(allocator.alloc(size).is_none());
+    }
+
+    #[test]
+    fn empty_shelves_are_reclaimed() {
+        let mut allocator = ShelfAllocator::new(Vec2::new(64, 32));
+
+        let small = allocator.alloc(Vec2::new(64, 8)).unwrap();
+        let other = allocator.alloc(Vec2::new(64, 8)).unwrap();
+        assert!(allocator.alloc(Vec2::new(64, 24)).is_none());
+
+        allocator.free(other.id);
+        allocator.free(small.id);
+
+        let tall = allocator.alloc(Vec2::new(64, 32)).unwrap();
+        assert_eq!(tall.rect, Rect::new(Vec2::zero(), Vec2::new(64, 32)));
+    }
+
+    #[test]
+    fn grows() {
+        let mut allocator = ShelfAllocator::new(Vec2::new(32, 16));
+        let size = Vec2::new(16, 16);
+
+        allocator.alloc(size).unwrap();
+        allocator.alloc(size).unwrap();
+        assert!(allocator.alloc(size).is_none());
+
+        assert!(allocator.can_grow());
+        allocator.grow(Vec2::new(32, 32));
+        assert_eq!(allocator.size(), Vec2::new(32, 32));
+
+        let alloc = allocator.alloc(size).unwrap();
+        assert_eq!(alloc.rect.min, Vec2::new(0, 16));
+    }
+}
@@ -3,20 +3,29 @@ mod pool;
 mod texture;
 
 use gg_math::{Rect, Vec2};
+use gg_util::ahash::AHashMap;
 use wgpu::{Device, Queue, TextureFormat, TextureView};
 
 pub use self::allocator::{
-    Allocation, AllocationId, Allocator, AllocatorKind, AnyAllocator, GridAllocator, TreeAllocator,
+    Allocation, AllocationId, Allocator, AllocatorKind, AnyAllocator, TreeAllocator,
 };
 pub use self::pool::{AtlasId, AtlasPool, PoolAllocation, PoolAllocationId, PoolConfig, PoolImage};
 pub use self::texture::AtlasTexture;
 
+// an atlas is repacked after a failed allocation only if at least this
+// fraction of it is free
+const REPACK_THRESHOLD: f32 = 0.25;
+
 #[derive(Debug)]
 pub struct Atlas {
     format: TextureFormat,
     texture: Option<AtlasTexture>,
     allocator: AnyAllocator,
     upload_queue: Vec<(Rect<u32>, Vec<u8>)>,
+    allocations: AHashMap<AllocationId, Rect<u32>>,
+    pending_moves: Vec<(Rect<u32>, Vec2<u32>)>,
+    alloc_failed: bool,
+    standalone: bool,
 }
 
 impl Atlas {
@@ -28,9 +37,28 @@ impl Atlas {
             texture: None,
             allocator,
             upload_queue: Vec::new(),
+            allocations: AHashMap::new(),
+            pending_moves: Vec::new(),
+            alloc_failed: false,
+            standalone: false,
         }
     }
 
+    pub fn new_standalone(format: TextureFormat, size: Vec2<u32>) -> Atlas {
+        Atlas {
+            standalone: true,
+            ..Atlas::new(format, TreeAllocator::new(size))
+        }
+    }
+
+    pub fn is_standalone(&self) -> bool {
+        self.standalone
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allocations.is_empty()
+    }
+
     pub fn size(&self) -> Vec2<u32> {
         self.allocator.size()
     }
@@ -69,21 +97,104 @@ impl Atlas {
         let alloc = loop {
             match self.allocator.alloc(size) {
                 Some(alloc) => break alloc,
-                None => {
-                    let next_size = self.next_size(max_size).ok_or(NoSpaceError)?;
-                    self.allocator.grow(next_size);
-                }
+                None => match self.next_size(max_size) {
+                    Some(next_size) => self.allocator.grow(next_size),
+                    None => {
+                        self.alloc_failed = true;
+                        return Err(NoSpaceError);
+                    }
+                },
             };
         };
 
         let data = std::mem::take(data);
         self.upload_queue.push((alloc.rect, data));
+        self.allocations.insert(alloc.id, alloc.rect);
 
         Ok(alloc)
     }
 
     pub fn free(&mut self, id: AllocationId) {
         self.allocator.free(id);
+        self.allocations.remove(&id);
+    }
+
+    // fraction of the atlas area not covered by live allocations
+    pub fn fragmentation(&self) -> f32 {
+        let total = self.size().cast::<f32>().product();
+        let used = self.allocations.values().map(|v| v.area()).sum::<u32>();
+        1.0 - used as f32 / total
+    }
+
+    // repacking only helps when an allocation failed for lack of a large
+    // enough hole, while the atlas as a whole still has plenty of free space
+    pub fn should_repack(&self) -> bool {
+        !self.standalone && self.alloc_failed && self.fragmentation() >= REPACK_THRESHOLD
+    }
+
+    // reallocates all live allocations in a fresh allocator, largest first.
+    // Returns new allocations by their old ids, which are no longer valid.
+    // If the allocations don't fit, the atlas is left as it was
+    pub fn repack(&mut self) -> Vec<(AllocationId, Allocation)> {
+        self.alloc_failed = false;
+
+        let mut live = self
+            .allocations
+            .iter()
+            .map(|(&id, &rect)| (id, rect))
+            .collect::<Vec<_>>();
+        live.sort_by_key(|(_, rect)| std::cmp::Reverse((rect.height(), rect.width())));
+
+        let mut allocator = self.allocator.kind().new_allocator(self.allocator.size());
+        let mut remap = Vec::with_capacity(live.len());
+
+        for &(id, rect) in &live {
+            match allocator.alloc(rect.size()) {
+                Some(alloc) => remap.push((id, alloc)),
+                None => return Vec::new(),
+            }
+        }
+
+        let moves = live
+            .iter()
+            .zip(&remap)
+            .map(|(&(_, old), (_, new))| (old, new.rect))
+            .collect::<Vec<_>>();
+
+        // pending uploads are written straight to the new place, while stale
+        // uploads of freed allocations are dropped
+        for (rect, data) in std::mem::take(&mut self.upload_queue) {
+            if let Some(&(_, new)) = moves.iter().find(|(old, _)| *old == rect) {
+                self.upload_queue.push((new, data));
+            }
+        }
+
+        // regions already in the texture are moved when uploading. If the
+        // previous repack wasn't uploaded yet, moves start from its sources
+        let previous = std::mem::take(&mut self.pending_moves);
+        if self.texture.is_some() {
+            for &(old, new) in &moves {
+                let src = match previous.is_empty() {
+                    true => Some(old),
+                    false => previous
+                        .iter()
+                        .find(|(_, dst)| *dst == old.min)
+                        .map(|&(src, _)| src),
+                };
+
+                if let Some(src) = src {
+                    self.pending_moves.push((src, new.min));
+                }
+            }
+        }
+
+        self.allocator = allocator;
+        self.allocations = remap
+            .iter()
+            .map(|(_, alloc)| (alloc.id, alloc.rect))
+            .collect();
+
+        remap
     }
 
     pub fn upload(&mut self, device: &Device, queue: &Queue) {
@@ -94,6 +205,11 @@ impl Atlas {
 
         texture.resize(device, queue, size, self.format);
 
+        if !self.pending_moves.is_empty() {
+            texture.move_regions(device, queue, &self.pending_moves, self.format);
+            self.pending_moves.clear();
+        }
+
         for (rect, data) in self.upload_queue.drain(..) {
             texture.upload(queue, rect, &data);
         }
@@ -110,3 +226,70 @@ fn double_size(size: Vec2<u32>) -> Vec2<u32> {
         Vec2::new(size.x, size.y * 2)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overlaps(a: &Rect<u32>, b: &Rect<u32>) -> bool {
+        a.min.x < b.max.x && b.min.x < a.max.x && a.min.y < b.max.y && b.min.y < a.max.y
+    }
+
+    #[test]
+    fn repack_fragmented_atlas() {
+        let size = Vec2::splat(64);
+        let mut atlas = Atlas::new(TextureFormat::R8Unorm, TreeAllocator::new(size));
+
+        let cell = Vec2::splat(16);
+        let allocs = (0..16)
+            .map(|_| atlas.alloc(size, cell, &mut vec![0; 256]).unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(!atlas.should_repack());
+
+        // free cells in a checkerboard pattern, leaving no 32x32 hole
+        let mut kept = Vec::new();
+        for alloc in allocs {
+            if (alloc.rect.min / 16).sum() % 2 == 0 {
+                atlas.free(alloc.id);
+            } else {
+                kept.push(alloc);
+            }
+        }
+
+        assert_eq!(atlas.fragmentation(), 0.5);
+
+        let large = Vec2::splat(32);
+        assert!(atlas.alloc(size, large, &mut vec![0; 1024]).is_err());
+        assert!(atlas.should_repack());
+
+        let remap = atlas.repack();
+        assert!(!atlas.should_repack());
+        assert_eq!(remap.len(), kept.len());
+
+        for alloc in &kept {
+            assert!(remap.iter().any(|(old_id, _)| *old_id == alloc.id));
+        }
+
+        let alloc = atlas.alloc(size, large, &mut vec![0; 1024]).unwrap();
+        let rects = remap.iter().map(|(_, v)| v.rect).collect::<Vec<_>>();
+        for (i, rect) in rects.iter().enumerate() {
+            assert_eq!(rect.size(), cell);
+            assert!(!overlaps(rect, &alloc.rect));
+            assert!(rects[i + 1..].iter().all(|v| !overlaps(rect, v)));
+        }
+    }
+
+    #[test]
+    fn repack_needs_fragmentation() {
+        let size = Vec2::splat(32);
+        let mut atlas = Atlas::new(TextureFormat::R8Unorm, TreeAllocator::new(size));
+
+        atlas.alloc(size, size, &mut vec![0; 1024]).unwrap();
+        assert!(atlas.alloc(size, Vec2::splat(8), &mut vec![0; 64]).is_err());
+
+        // the atlas is full, repacking won't help
+        assert_eq!(atlas.fragmentation(), 0.0);
+        assert!(!atlas.should_repack());
+    }
+}
@@ -1,12 +1,15 @@
+use std::fmt;
+
 use gg_math::{Rect, Vec2};
+use gg_util::ahash::AHashMap;
 use wgpu::{Device, Queue, TextureFormat, TextureView};
 
 use super::{AllocationId, AllocatorKind, Atlas};
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct AtlasId(pub u32);
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct PoolAllocationId {
     pub atlas_id: AtlasId,
     pub alloc_id: AllocationId,
@@ -29,8 +32,27 @@ pub struct PoolImage {
 #[derive(Clone, Copy, Debug)]
 pub struct PoolConfig {
     pub max_size: Vec2<u32>,
+    pub max_standalone_size: Option<Vec2<u32>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ImageTooLargeError {
+    pub size: Vec2<u32>,
+    pub max_size: Vec2<u32>,
+}
+
+impl fmt::Display for ImageTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "image of size {}x{} exceeds maximum texture size {}x{}",
+            self.size.x, self.size.y, self.max_size.x, self.max_size.y
+        )
+    }
 }
 
+impl std::error::Error for ImageTooLargeError {}
+
 #[derive(Debug)]
 pub struct AtlasPool {
     config: PoolConfig,
@@ -45,8 +67,18 @@ impl AtlasPool {
         }
     }
 
-    pub fn alloc(&mut self, image: PoolImage) -> PoolAllocation {
-        self.alloc_inner(image, 0)
+    pub fn alloc(&mut self, image: PoolImage) -> Result<PoolAllocation, ImageTooLargeError> {
+        if image.size.cmp_le(self.config.max_size).all() {
+            return Ok(self.alloc_inner(image, 0));
+        }
+
+        match self.config.max_standalone_size {
+            Some(max_size) if image.size.cmp_le(max_size).all() => Ok(self.alloc_standalone(image)),
+            max_size => Err(ImageTooLargeError {
+                size: image.size,
+                max_size: max_size.unwrap_or(self.config.max_size),
+            }),
+        }
     }
 
     pub fn get(&self, atlas_id: AtlasId) -> &Atlas {
@@ -69,7 +101,7 @@ impl AtlasPool {
                 }
             }
 
-            if atlas.format() != image.format {
+            if atlas.format() != image.format || atlas.is_standalone() {
                 continue;
             }
 
@@ -92,9 +124,41 @@ impl AtlasPool {
         self.alloc_inner(image, start_idx)
     }
 
+    fn alloc_standalone(&mut self, mut image: PoolImage) -> PoolAllocation {
+        let mut atlas = Atlas::new_standalone(image.format, image.size);
+
+        let alloc = atlas
+            .alloc(image.size, image.size, &mut image.data)
+            .expect("standalone atlas must fit its image");
+
+        let free_slot = self
+            .atlases
+            .iter()
+            .position(|atlas| atlas.is_standalone() && atlas.is_empty());
+
+        let atlas_id = match free_slot {
+            Some(idx) => {
+                self.atlases[idx] = atlas;
+                AtlasId(idx as u32)
+            }
+            None => {
+                self.atlases.push(atlas);
+                AtlasId(self.atlases.len() as u32 - 1)
+            }
+        };
+
+        PoolAllocation {
+            id: PoolAllocationId {
+                atlas_id,
+                alloc_id: alloc.id,
+            },
+            rect: alloc.rect,
+        }
+    }
+
     fn create_new_atlas(&mut self, image: &PoolImage) {
         let side = image.size.map(|v| v.next_power_of_two()).max_component();
-        let size = Vec2::splat(side);
+        let size = Vec2::splat(side).min(self.config.max_size);
         let alloc = image
             .preferred_allocator
             .unwrap_or(AllocatorKind::Tree)
@@ -104,7 +168,40 @@ impl AtlasPool {
     }
 
     pub fn free(&mut self, id: PoolAllocationId) {
-        self.atlases[id.atlas_id.0 as usize].free(id.alloc_id);
+        let atlas = &mut self.atlases[id.atlas_id.0 as usize];
+        atlas.free(id.alloc_id);
+
+        // a standalone texture holds a single image, so it is released right
+        // away. the slot is kept to keep atlas ids stable, and is reused by
+        // the next standalone image
+        if atlas.is_standalone() {
+            *atlas = Atlas::new_standalone(atlas.format(), Vec2::splat(1));
+        }
+    }
+
+    // repacks fragmented atlases, returning new allocations by old ids
+    pub fn repack(&mut self) -> AHashMap<PoolAllocationId, PoolAllocation> {
+        let mut remap = AHashMap::new();
+
+        for (idx, atlas) in self.atlases.iter_mut().enumerate() {
+            if !atlas.should_repack() {
+                continue;
+            }
+
+            let atlas_id = AtlasId(idx as u32);
+            let id = |alloc_id| PoolAllocationId { atlas_id, alloc_id };
+
+            for (old_id, alloc) in atlas.repack() {
+                let alloc = PoolAllocation {
+                    id: id(alloc.id),
+                    rect: alloc.rect,
+                };
+
+                remap.insert(id(old_id), alloc);
+            }
+        }
+
+        remap
     }
 
     pub fn upload(&mut self, device: &Device, queue: &Queue) {
@@ -117,3 +214,77 @@ impl AtlasPool {
         self.atlases.iter().map(|atlas| atlas.texture_view())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(size: Vec2<u32>) -> PoolImage {
+        PoolImage {
+            size,
+            data: vec![0; (size.x * size.y * 4) as usize],
+            format: TextureFormat::Rgba8UnormSrgb,
+            preferred_allocator: None,
+        }
+    }
+
+    #[test]
+    fn oversized_image_gets_standalone_texture() {
+        let mut pool = AtlasPool::new(PoolConfig {
+            max_size: Vec2::splat(64),
+            max_standalone_size: Some(Vec2::splat(256)),
+        });
+
+        let small = pool.alloc(image(Vec2::splat(16))).unwrap();
+        assert!(!pool.get(small.id.atlas_id).is_standalone());
+
+        let large = pool.alloc(image(Vec2::new(100, 200))).unwrap();
+        let atlas = pool.get(large.id.atlas_id);
+        assert_ne!(large.id.atlas_id, small.id.atlas_id);
+        assert!(atlas.is_standalone());
+        assert_eq!(atlas.size(), Vec2::new(100, 200));
+        assert_eq!(large.rect.size(), Vec2::new(100, 200));
+
+        // small images never end up in standalone textures
+        let next = pool.alloc(image(Vec2::splat(16))).unwrap();
+        assert_eq!(next.id.atlas_id, small.id.atlas_id);
+    }
+
+    #[test]
+    fn oversized_image_without_standalone_textures() {
+        let mut pool = AtlasPool::new(PoolConfig {
+            max_size: Vec2::splat(64),
+            max_standalone_size: None,
+        });
+
+        let error = pool.alloc(image(Vec2::new(65, 10))).unwrap_err();
+        assert_eq!(error.size, Vec2::new(65, 10));
+        assert_eq!(error.max_size, Vec2::splat(64));
+        assert_eq!(
+            error.to_string(),
+            "image of size 65x10 exceeds maximum texture size 64x64"
+        );
+    }
+
+    #[test]
+    fn freed_standalone_texture_is_released_and_reused() {
+        let mut pool = AtlasPool::new(PoolConfig {
+            max_size: Vec2::splat(64),
+            max_standalone_size: Some(Vec2::splat(256)),
+        });
+
+        let first = pool.alloc(image(Vec2::new(100, 200))).unwrap();
+        let other = pool.alloc(image(Vec2::new(128, 128))).unwrap();
+        pool.free(first.id);
+
+        let atlas = pool.get(first.id.atlas_id);
+        assert!(atlas.is_empty());
+        assert_eq!(atlas.size(), Vec2::splat(1));
+
+        let second = pool.alloc(image(Vec2::new(200, 100))).unwrap();
+        assert_eq!(second.id.atlas_id, first.id.atlas_id);
+        assert_eq!(pool.get(second.id.atlas_id).size(), Vec2::new(200, 100));
+        assert_eq!(pool.get(other.id.atlas_id).size(), Vec2::splat(128));
+        assert_eq!(pool.texture_views().len(), 2);
+    }
+}
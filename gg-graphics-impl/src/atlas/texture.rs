@@ -92,6 +92,53 @@ impl AtlasTexture {
 
         self.size = new_size;
     }
+
+    // copies regions into a new texture, moving each to a new position.
+    // Everything outside of the regions is discarded
+    pub fn move_regions(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        moves: &[(Rect<u32>, Vec2<u32>)],
+        format: TextureFormat,
+    ) {
+        let new_texture = create_texture(device, self.size, format);
+        let old_texture = std::mem::replace(&mut self.texture, new_texture);
+
+        self.texture_view = self.texture.create_view(&Default::default());
+
+        let mut encoder = device.create_command_encoder(&Default::default());
+
+        for &(src_rect, dst_pos) in moves {
+            if src_rect.area() == 0 {
+                continue;
+            }
+
+            let mut src = old_texture.as_image_copy();
+            src.origin = Origin3d {
+                x: src_rect.min.x,
+                y: src_rect.min.y,
+                z: 0,
+            };
+
+            let mut dst = self.texture.as_image_copy();
+            dst.origin = Origin3d {
+                x: dst_pos.x,
+                y: dst_pos.y,
+                z: 0,
+            };
+
+            let size = Extent3d {
+                width: src_rect.width(),
+                height: src_rect.height(),
+                depth_or_array_layers: 1,
+            };
+
+            encoder.copy_texture_to_texture(src, dst, size);
+        }
+
+        queue.submit(std::iter::once(encoder.finish()));
+    }
 }
 
 fn create_texture(device: &Device, size: Vec2<u32>, format: TextureFormat) -> wgpu::Texture {
@@ -1,42 +1,80 @@
-use std::sync::atomic::Ordering;
+use std::error::Error as StdError;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use gg_assets::{Assets, Id};
 use gg_graphics::{
-    Backend, Color, Command, CommandList, DrawGlyph, DrawRect, FillImage, Image, NinePatchImage,
+    Backend, ClearFill, Color, Command, CommandList, CornerRadii, Cursor, DrawGlyph, DrawRect,
+    FillGradient, FillImage, FontFace, Image, NinePatchImage, PresentError, RasterizationQuality,
     SubpixelOffset,
 };
 use gg_math::{Affine2, Rect, Vec2};
 use gg_util::eyre::{eyre, Result};
 use wgpu::util::backend_bits_from_env;
 use wgpu::{
-    Backends, CommandEncoder, Device, DeviceDescriptor, Features, IndexFormat, Instance, LoadOp,
-    Operations, PowerPreference, PresentMode, Queue, RenderPassColorAttachment,
-    RenderPassDescriptor, RequestAdapterOptions, Surface, SurfaceConfiguration, TextureFormat,
-    TextureUsages, TextureView,
+    Backends, CommandBuffer, CommandEncoder, Device, DeviceDescriptor, Features, IndexFormat,
+    Instance, Limits, LoadOp, Operations, PowerPreference, PresentMode, Queue,
+    RenderPassColorAttachment, RenderPassDescriptor, RequestAdapterOptions, Surface,
+    SurfaceConfiguration, SurfaceError, SurfaceTexture, TextureFormat, TextureUsages, TextureView,
 };
+use wgpu_core::device::queue::{QueueSubmitError, QueueWriteError};
+use wgpu_core::device::DeviceError;
+use wgpu_core::resource::{BufferAccessError, CreateBufferError, CreateTextureError};
 use winit::window::Window;
 
 use crate::atlas::{AtlasPool, PoolConfig};
-use crate::batch::{Batcher, State, Vertex};
+use crate::batch::{Batcher, BlendMode, State, Vertex};
 use crate::bindings::Bindings;
 use crate::canvas::{Canvas, Canvases};
 use crate::glyphs::{GlyphKey, GlyphKeyKind, Glyphs};
 use crate::images::Images;
 use crate::pipeline::Pipelines;
+use crate::readback::Readback;
 
 #[derive(Clone, Copy, Debug)]
 pub struct BackendSettings {
-    pub vsync: bool,
+    pub present_mode: PresentModePref,
     pub prefer_low_power_gpu: bool,
     pub image_cell_size: Vec2<u16>,
+    pub max_texture_size: Option<u32>,
+    pub standalone_oversized_images: bool,
+    pub subpixel_text: bool,
+    pub glyph_image_quality: RasterizationQuality,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PresentModePref {
+    Auto,
+    Fifo,
+    Mailbox,
+    Immediate,
+}
+
+impl PresentModePref {
+    pub fn select(self, supported: &[PresentMode]) -> PresentMode {
+        let candidates: &[PresentMode] = match self {
+            PresentModePref::Auto => return PresentMode::AutoVsync,
+            PresentModePref::Fifo => &[],
+            PresentModePref::Mailbox => &[PresentMode::Mailbox],
+            PresentModePref::Immediate => &[PresentMode::Immediate, PresentMode::Mailbox],
+        };
+
+        candidates
+            .iter()
+            .copied()
+            .find(|mode| supported.contains(mode))
+            .unwrap_or(PresentMode::Fifo)
+    }
 }
 
 pub struct BackendImpl {
     settings: BackendSettings,
+    instance: Instance,
     device: Device,
+    device_lost: Arc<AtomicBool>,
     queue: Queue,
     surface: Surface,
+    present_modes: Vec<PresentMode>,
     batcher: Batcher,
     atlases: AtlasPool,
     images: Images,
@@ -47,6 +85,18 @@ pub struct BackendImpl {
     submitted_lists: Vec<CommandList>,
     recycled_lists: Vec<CommandList>,
     resolution: Vec2<u32>,
+    cursor: Cursor,
+    os_cursor_visible: bool,
+    screenshot_requested: bool,
+    screenshot: Option<Vec<u8>>,
+    frame: Option<Frame>,
+}
+
+// a frame recorded by `encode_frame`, waiting for `submit_frame`
+struct Frame {
+    surface_texture: SurfaceTexture,
+    commands: CommandBuffer,
+    readback: Option<Readback>,
 }
 
 impl BackendImpl {
@@ -57,32 +107,13 @@ impl BackendImpl {
         let size = window.inner_size();
         let resolution = Vec2::new(size.width, size.height);
 
-        let adapter = pollster::block_on(instance.request_adapter(&RequestAdapterOptions {
-            power_preference: if settings.prefer_low_power_gpu {
-                PowerPreference::LowPower
-            } else {
-                PowerPreference::HighPerformance
-            },
-            force_fallback_adapter: false,
-            compatible_surface: Some(&surface),
-        }))
-        .ok_or_else(|| eyre!("No adapter"))?;
-
-        let limits = adapter.limits();
-
-        let desc = &DeviceDescriptor {
-            label: None,
-            features: Features::TEXTURE_BINDING_ARRAY
-                | Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING,
-            limits: limits.clone(),
-        };
-
-        let (device, queue) = pollster::block_on(adapter.request_device(desc, None))?;
+        let (device, queue, limits, present_modes) =
+            request_device(&instance, &surface, &settings)?;
+        let device_lost = Arc::new(AtomicBool::new(false));
+        watch_device_lost(&device, &device_lost);
 
         let batcher = Batcher::new();
-        let atlases = AtlasPool::new(PoolConfig {
-            max_size: Vec2::splat(limits.max_texture_dimension_2d.min(8192)),
-        });
+        let atlases = AtlasPool::new(pool_config(&settings, &limits));
 
         let images = Images::new(assets, settings.image_cell_size);
         let glyphs = Glyphs::new();
@@ -92,9 +123,12 @@ impl BackendImpl {
 
         let mut backend = BackendImpl {
             settings,
+            instance,
             device,
+            device_lost,
             queue,
             surface,
+            present_modes,
             batcher,
             atlases,
             images,
@@ -105,12 +139,66 @@ impl BackendImpl {
             submitted_lists: Vec::new(),
             recycled_lists: Vec::new(),
             resolution,
+            cursor: Cursor::new(),
+            os_cursor_visible: true,
+            screenshot_requested: false,
+            screenshot: None,
+            frame: None,
         };
 
         backend.configure_surface();
 
         Ok(backend)
     }
+
+    pub fn cursor(&self) -> &Cursor {
+        &self.cursor
+    }
+
+    // winit has no custom cursor images, so on every platform the os cursor
+    // is hidden and the image is drawn at the mouse position instead
+    pub fn set_cursor_image(&mut self, image: Option<Id<Image>>, hotspot: Vec2<u32>) {
+        self.cursor.set_image(image, hotspot);
+    }
+
+    pub fn set_cursor_position(&mut self, pos: Option<Vec2<f32>>) {
+        self.cursor.set_position(pos);
+    }
+
+    pub fn update_window(&mut self, window: &Window) {
+        let visible = self.cursor.is_os_cursor_visible();
+        if self.os_cursor_visible != visible {
+            self.os_cursor_visible = visible;
+            window.set_cursor_visible(visible);
+        }
+    }
+
+    // the main window is captured during the next present
+    pub fn request_screenshot(&mut self) {
+        if !self.screenshot_requested {
+            self.screenshot_requested = true;
+            self.configure_surface();
+        }
+    }
+
+    // returns RGBA pixels, row by row. For the main window these are the ones
+    // captured after `request_screenshot`, or nothing if there are none yet
+    pub fn read_canvas(&self, canvas: &gg_graphics::Canvas) -> Vec<u8> {
+        match canvas.as_raw() {
+            Canvas::MainWindow => self.screenshot.clone().unwrap_or_default(),
+            raw @ Canvas::Texture { size, .. } => {
+                let texture = match self.canvases.get(raw) {
+                    Some(v) => &v.texture,
+                    None => return Vec::new(),
+                };
+
+                let mut encoder = self.device.create_command_encoder(&Default::default());
+                let readback = Readback::encode(&self.device, &mut encoder, texture, *size);
+                self.queue.submit(std::iter::once(encoder.finish()));
+                readback.read(&self.device)
+            }
+        }
+    }
 }
 
 impl Backend for BackendImpl {
@@ -136,36 +224,107 @@ impl Backend for BackendImpl {
         }
     }
 
-    fn present(&mut self, assets: &mut Assets) {
-        let submitted_lists = std::mem::take(&mut self.submitted_lists);
+    fn encode_frame(&mut self, assets: &mut Assets) -> Result<(), PresentError> {
+        let mut submitted_lists = std::mem::take(&mut self.submitted_lists);
         self.recycled_lists.clear();
 
+        let main_list = submitted_lists
+            .iter_mut()
+            .rev()
+            .find(|list| matches!(list.canvas.as_raw(), Canvas::MainWindow));
+
+        if let Some(list) = main_list {
+            self.cursor.encode(assets, list);
+        }
+
+        let res = self.encode_lists(assets, &submitted_lists);
+
+        self.submitted_lists = submitted_lists;
+        self.recycled_lists
+            .extend(self.submitted_lists.drain(..).rev());
+
+        res
+    }
+
+    fn submit_frame(&mut self) {
+        let frame = match self.frame.take() {
+            Some(v) => v,
+            None => return,
+        };
+
+        let _span = tracing::debug_span!("submit").entered();
+
+        self.queue.submit(std::iter::once(frame.commands));
+
+        if let Some(readback) = frame.readback {
+            self.screenshot = Some(readback.read(&self.device));
+        }
+
+        frame.surface_texture.present();
+
+        if self.screenshot_requested {
+            self.screenshot_requested = false;
+            self.configure_surface();
+        }
+    }
+
+    fn recycle_list(&mut self) -> Option<CommandList> {
+        self.recycled_lists.pop()
+    }
+}
+
+impl BackendImpl {
+    fn encode_lists(
+        &mut self,
+        assets: &mut Assets,
+        submitted_lists: &[CommandList],
+    ) -> Result<(), PresentError> {
+        let _span = tracing::debug_span!("encode", lists = submitted_lists.len()).entered();
+
+        if self.device_lost.load(Ordering::Relaxed) {
+            self.recover_device(assets);
+            return Err(PresentError::DeviceLost);
+        }
+
         self.images.cleanup(&mut self.atlases);
 
-        for list in &submitted_lists {
+        let remap = self.atlases.repack();
+        if !remap.is_empty() {
+            tracing::debug!(moved = remap.len(), "repacked atlases");
+            self.images.remap(&remap);
+            self.glyphs.remap(&remap);
+        }
+
+        for list in submitted_lists {
+            let _span = tracing::debug_span!("alloc_list", commands = list.list.len()).entered();
             self.alloc_list(assets, list);
         }
 
-        self.atlases.upload(&self.device, &self.queue);
+        {
+            let _span = tracing::debug_span!("upload").entered();
+            self.atlases.upload(&self.device, &self.queue);
+        }
+
         self.canvases.update();
 
-        let surface_texture = match self.surface.get_current_texture() {
+        // a frame that was never submitted is dropped before acquiring the next
+        self.frame = None;
+
+        let surface_texture = match self.acquire_surface_texture() {
             Ok(v) => v,
-            Err(_) => {
-                self.configure_surface();
-                self.surface.get_current_texture().unwrap()
+            Err(PresentError::DeviceLost) => {
+                self.recover_device(assets);
+                return Err(PresentError::DeviceLost);
             }
+            Err(e) => return Err(e),
         };
 
         let main_view = surface_texture.texture.create_view(&Default::default());
 
         let mut encoder = self.device.create_command_encoder(&Default::default());
 
-        for list in &submitted_lists {
-            let skip_view = match list.canvas.as_raw() {
-                Canvas::MainWindow => None,
-                Canvas::Texture { view, .. } => Some(view),
-            };
+        for list in submitted_lists {
+            let skip_view = self.canvases.get(list.canvas.as_raw()).map(|v| &v.view);
 
             self.bindings
                 .update(&self.device, &self.atlases, &self.canvases, skip_view);
@@ -174,51 +333,125 @@ impl Backend for BackendImpl {
                 self.pipelines.recreate(&self.device, &self.bindings);
             }
 
-            let clear_color = self.batch_list(assets, list);
-            self.encode_pass(&mut encoder, clear_color, list.canvas.as_raw(), &main_view);
+            let clear_color = {
+                let span = tracing::debug_span!(
+                    "batch_list",
+                    commands = list.list.len(),
+                    vertices = tracing::field::Empty,
+                );
+                let _guard = span.enter();
+                let clear_color = self.batch_list(assets, list);
+                span.record("vertices", self.batcher.num_vertices());
+                clear_color
+            };
+
+            {
+                let vertices = self.batcher.num_vertices();
+                let _span = tracing::debug_span!("encode_pass", vertices).entered();
+                self.encode_pass(&mut encoder, clear_color, list.canvas.as_raw(), &main_view);
+            }
         }
 
-        self.queue.submit(std::iter::once(encoder.finish()));
-        surface_texture.present();
+        let readback = self.screenshot_requested.then(|| {
+            let texture = &surface_texture.texture;
+            Readback::encode(&self.device, &mut encoder, texture, self.resolution)
+        });
 
-        self.submitted_lists = submitted_lists;
-        self.recycled_lists
-            .extend(self.submitted_lists.drain(..).rev());
+        self.frame = Some(Frame {
+            surface_texture,
+            commands: encoder.finish(),
+            readback,
+        });
+
+        Ok(())
     }
 
-    fn recycle_list(&mut self) -> Option<CommandList> {
-        self.recycled_lists.pop()
+    fn acquire_surface_texture(&mut self) -> Result<SurfaceTexture, PresentError> {
+        match self.surface.get_current_texture() {
+            Ok(v) => return Ok(v),
+            Err(SurfaceError::Timeout) => return Err(PresentError::Timeout),
+            Err(SurfaceError::OutOfMemory) => return Err(PresentError::DeviceLost),
+            Err(SurfaceError::Outdated | SurfaceError::Lost) => {}
+        }
+
+        self.configure_surface();
+
+        match self.surface.get_current_texture() {
+            Ok(v) => Ok(v),
+            Err(SurfaceError::Timeout) => Err(PresentError::Timeout),
+            Err(_) => Err(PresentError::DeviceLost),
+        }
+    }
+
+    fn recover_device(&mut self, assets: &Assets) {
+        let _span = tracing::warn_span!("recover_device").entered();
+
+        let (device, queue, limits, present_modes) =
+            match request_device(&self.instance, &self.surface, &self.settings) {
+                Ok(v) => v,
+                Err(error) => {
+                    tracing::error!("cannot recreate device: {}", error);
+                    self.device_lost.store(true, Ordering::Relaxed);
+                    return;
+                }
+            };
+
+        self.device_lost.store(false, Ordering::Relaxed);
+        watch_device_lost(&device, &self.device_lost);
+
+        self.atlases = AtlasPool::new(pool_config(&self.settings, &limits));
+        self.images = Images::new(assets, self.settings.image_cell_size);
+        self.glyphs = Glyphs::new();
+        self.canvases.recreate(&device);
+        self.bindings = Bindings::new(&device, &queue);
+        self.pipelines = Pipelines::new(&device, &self.bindings);
+        self.device = device;
+        self.queue = queue;
+        self.present_modes = present_modes;
+
+        self.configure_surface();
     }
-}
 
-impl BackendImpl {
     fn configure_surface(&mut self) {
+        // a frame can't outlive the configuration it was acquired with
+        self.frame = None;
+
+        // copying from the surface is only enabled while a screenshot is pending
+        let usage = if self.screenshot_requested {
+            TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC
+        } else {
+            TextureUsages::RENDER_ATTACHMENT
+        };
+
         self.surface.configure(
             &self.device,
             &SurfaceConfiguration {
-                usage: TextureUsages::RENDER_ATTACHMENT,
+                usage,
                 format: TextureFormat::Bgra8UnormSrgb,
                 width: self.resolution.x,
                 height: self.resolution.y,
-                present_mode: if self.settings.vsync {
-                    PresentMode::AutoVsync
-                } else {
-                    PresentMode::AutoNoVsync
-                },
+                present_mode: self.settings.present_mode.select(&self.present_modes),
             },
         )
     }
 
     fn alloc_list(&mut self, assets: &mut Assets, commands: &CommandList) {
+        let mut view = Affine2::identity();
+        let mut saved_views = Vec::new();
+
         for command in &commands.list {
             match command {
+                Command::Save => saved_views.push(view),
+                Command::Restore => view = saved_views.pop().unwrap_or(view),
+                &Command::PreTransform(v) => view = view * v,
+                &Command::PostTransform(v) => view = v * view,
                 Command::DrawRect(rect) => {
                     if let Some(image) = &rect.fill.image {
                         self.alloc_fill_image(assets, image);
                     }
                 }
                 Command::DrawGlyph(glyph) => {
-                    self.alloc_glyph(assets, glyph);
+                    self.alloc_glyph(assets, glyph, self.use_subpixel_text(&view));
                 }
                 _ => {}
             }
@@ -246,32 +479,44 @@ impl BackendImpl {
         }
     }
 
-    fn get_glyph_key(assets: &Assets, cmd: &DrawGlyph) -> Option<GlyphKey> {
+    fn use_subpixel_text(&self, view: &Affine2<f32>) -> bool {
+        self.settings.subpixel_text
+            && view.x == Vec2::new(1.0, 0.0)
+            && view.y == Vec2::new(0.0, 1.0)
+    }
+
+    fn get_glyph_key(&self, assets: &Assets, cmd: &DrawGlyph, subpixel: bool) -> Option<GlyphKey> {
         let font = match assets.get_by_id(cmd.font) {
             Some(v) => v,
             None => return None,
         };
 
+        Some(self.font_glyph_key(font, cmd, subpixel))
+    }
+
+    fn font_glyph_key(&self, font: &FontFace, cmd: &DrawGlyph, subpixel: bool) -> GlyphKey {
         let kind = if font.has_image(cmd.glyph) {
             GlyphKeyKind::Image {
                 size: cmd.size.ceil() as u32,
+                quality: self.settings.glyph_image_quality,
             }
         } else {
             GlyphKeyKind::Vector {
                 size: cmd.size.to_bits(),
                 subpixel_offset: SubpixelOffset::new(cmd.pos.fract()),
+                subpixel,
             }
         };
 
-        Some(GlyphKey {
+        GlyphKey {
             font: cmd.font,
             glyph: cmd.glyph,
             kind,
-        })
+        }
     }
 
-    fn alloc_glyph(&mut self, assets: &mut Assets, cmd: &DrawGlyph) {
-        if let Some(key) = Self::get_glyph_key(assets, cmd) {
+    fn alloc_glyph(&mut self, assets: &mut Assets, cmd: &DrawGlyph, subpixel: bool) {
+        if let Some(key) = self.get_glyph_key(assets, cmd, subpixel) {
             self.glyphs.alloc(&mut self.atlases, assets, key);
         }
     }
@@ -293,10 +538,12 @@ impl BackendImpl {
             view_proj: proj,
             view: Affine2::identity(),
             proj,
+            viewport: Affine2::identity(),
+            blend: BlendMode::Alpha,
         });
 
         let it = commands.list.iter().enumerate();
-        let (start_idx, clear_color) = it
+        let (start_idx, clear_fill) = it
             .flat_map(|(i, cmd)| match cmd {
                 Command::Clear(v) => Some((i + 1, Some(*v))),
                 _ => None,
@@ -304,7 +551,21 @@ impl BackendImpl {
             .next()
             .unwrap_or((0, None));
 
-        for command in &commands.list[start_idx..] {
+        if let Some(fill) = clear_fill.filter(ClearFill::is_gradient) {
+            let rect = Rect::new(Vec2::zero(), resolution.cast::<f32>());
+            let colors = [fill.top(), fill.top(), fill.bottom(), fill.bottom()];
+            self.batcher.save_state();
+            self.batcher
+                .modify_state(|state| state.blend = BlendMode::Replace);
+            self.emit_quad(rect, full_tex_rect(), 0, colors);
+            self.batcher.restore_state();
+        }
+
+        let mut rest = &commands.list[start_idx..];
+
+        while let Some((command, tail)) = rest.split_first() {
+            rest = tail;
+
             match command {
                 Command::Save => {
                     self.batcher.save_state();
@@ -316,9 +577,16 @@ impl BackendImpl {
                     self.set_scissor(rect, resolution);
                 }
                 Command::ClearScissor => {
+                    self.batcher
+                        .modify_state(|state| reset_scissor(state, resolution));
+                }
+                &Command::SetViewport(rect) => {
                     self.batcher.modify_state(|state| {
-                        state.scissor = full_scissor;
-                        state.normalized_scissor = normalized_full_scissor;
+                        let scale = rect.size() / resolution.cast::<f32>();
+                        state.viewport = Affine2::translation(rect.min) * Affine2::scaling(scale);
+                        state.proj = projection_matrix(resolution) * state.viewport;
+                        state.view_proj = state.proj * state.view;
+                        reset_scissor(state, resolution);
                     });
                 }
                 &Command::PreTransform(v) => {
@@ -334,29 +602,61 @@ impl BackendImpl {
                     });
                 }
                 Command::Clear(_) => {}
+                &Command::ClearRect(rect, color) => {
+                    self.batcher.save_state();
+                    self.batcher
+                        .modify_state(|state| state.blend = BlendMode::Replace);
+                    self.emit_rect(rect, full_tex_rect(), 0, color);
+                    self.batcher.restore_state();
+                }
                 Command::DrawRect(rect) => {
                     self.draw_rect(assets, rect);
                 }
+                Command::StrokeRect(stroke) => {
+                    for edge in stroke.edges() {
+                        self.emit_rect(edge, full_tex_rect(), 0, stroke.color);
+                    }
+                }
                 Command::DrawGlyph(glyph) => {
-                    self.draw_glyph(assets, glyph);
+                    let len = rest
+                        .iter()
+                        .take_while(
+                            |cmd| matches!(cmd, Command::DrawGlyph(v) if v.font == glyph.font),
+                        )
+                        .count();
+
+                    let (run, tail) = rest.split_at(len);
+                    rest = tail;
+
+                    let run = run.iter().flat_map(|cmd| match cmd {
+                        Command::DrawGlyph(v) => Some(v),
+                        _ => None,
+                    });
+
+                    self.draw_glyph_run(assets, std::iter::once(glyph).chain(run));
                 }
             }
         }
 
         self.batcher.flush();
-        clear_color
+        clear_fill.map(|fill| fill.top())
     }
 
     fn set_scissor(&mut self, rect: &Rect<f32>, resolution: Vec2<u32>) {
         self.batcher.modify_state(|state| {
+            let rect = Rect::from_min_max(
+                state.viewport.transform_point(rect.min),
+                state.viewport.transform_point(rect.max),
+            );
             let rect = rect.f_intersection(&state.scissor.cast::<f32>());
 
             let min = rect.min.fmax(Vec2::zero());
             let max = rect.max.fmin(resolution.cast()).fmax(min);
             let scissor = Rect::from_min_max(min, max);
 
-            let n_min = state.view_proj.transform_point(scissor.min);
-            let n_max = state.view_proj.transform_point(scissor.max);
+            let view_proj = projection_matrix(resolution) * state.view;
+            let n_min = view_proj.transform_point(scissor.min);
+            let n_max = view_proj.transform_point(scissor.max);
 
             state.normalized_scissor =
                 Rect::from_min_max(Vec2::new(n_min.x, n_max.y), Vec2::new(n_max.x, n_min.y));
@@ -366,24 +666,40 @@ impl BackendImpl {
     }
 
     fn draw_rect(&mut self, assets: &Assets, rect: &DrawRect) {
-        match &rect.fill.image {
+        let (tex_rect, tex_id) = match &rect.fill.image {
             Some(FillImage::Canvas(canvas)) => {
-                let tex_id = self.bindings.canvas_index(canvas.as_raw());
-                self.emit_rect(rect.rect, full_tex_rect(), tex_id, rect.fill.color);
+                (full_tex_rect(), self.bindings.canvas_index(canvas.as_raw()))
             }
             Some(FillImage::NinePatchImage(image)) => {
-                self.draw_nine_patch_rect(assets, rect.rect, rect.fill.color, *image);
-            }
-            Some(FillImage::SingleImage(image)) => {
-                self.draw_textured_rect(rect.rect, rect.fill.color, *image);
+                // gradients are not applied to nine-patch images
+                return self.draw_nine_patch_rect(assets, rect.rect, rect.fill.color, *image);
             }
+            Some(FillImage::SingleImage(image)) => self.image_tex(*image),
+            None => (full_tex_rect(), 0),
+        };
+
+        let shape = QuadShape {
+            rect: rect.rect,
+            radii: rect.corner_radii,
+        };
+
+        match &rect.fill.gradient {
+            Some(gradient) => self.emit_gradient_rect(
+                rect.rect,
+                tex_rect,
+                tex_id,
+                rect.fill.color,
+                gradient,
+                shape,
+            ),
             None => {
-                self.emit_rect(rect.rect, full_tex_rect(), 0, rect.fill.color);
+                let colors = [rect.fill.color; 4];
+                self.emit_shaped_quad(rect.rect, tex_rect, tex_id, colors, shape);
             }
         }
     }
 
-    fn draw_textured_rect(&mut self, rect: Rect<f32>, color: Color, image: Id<Image>) {
+    fn image_tex(&self, image: Id<Image>) -> (Rect<f32>, u32) {
         let (atlas_id, tex_rect) = self
             .images
             .get(&self.atlases, image)
@@ -391,7 +707,11 @@ impl BackendImpl {
             .unwrap_or((None, full_tex_rect()));
 
         let tex_id = atlas_id.map(|v| self.bindings.atlas_index(v)).unwrap_or(0);
+        (tex_rect, tex_id)
+    }
 
+    fn draw_textured_rect(&mut self, rect: Rect<f32>, color: Color, image: Id<Image>) {
+        let (tex_rect, tex_id) = self.image_tex(image);
         self.emit_rect(rect, tex_rect, tex_id, color);
     }
 
@@ -409,11 +729,8 @@ impl BackendImpl {
             }
         };
 
-        let top_left_size = get_image_size(assets, image.top_left.id());
-        let bottom_right_size = get_image_size(assets, image.top_left.id());
-
         let outer = rect;
-        let inner = Rect::from_min_max(rect.min + top_left_size, rect.max - bottom_right_size);
+        let inner = rect.shrink(&image.content_insets(assets));
 
         self.draw_textured_rect(inner, color, image.center.id());
 
@@ -460,33 +777,126 @@ impl BackendImpl {
         self.draw_textured_rect(rect, color, image.bottom_left.id());
     }
 
-    fn draw_glyph(&mut self, assets: &Assets, cmd: &DrawGlyph) {
-        let key = Self::get_glyph_key(assets, cmd);
-        let glyph = match key.and_then(|key| self.glyphs.get(key)) {
+    fn draw_glyph_run<'a>(
+        &mut self,
+        assets: &Assets,
+        mut run: impl Iterator<Item = &'a DrawGlyph>,
+    ) {
+        let first = match run.next() {
             Some(v) => v,
             None => return,
         };
 
-        let size = glyph.bounds.size() * cmd.size;
-        let offset = glyph.bounds.min * cmd.size + Vec2::new(0.0, -size.y);
-        let rect = Rect::new((cmd.pos + offset).floor(), size);
+        let font = match assets.get_by_id(first.font) {
+            Some(v) => v,
+            None => return,
+        };
 
-        let tex_id = self.bindings.atlas_index(glyph.alloc.id.atlas_id);
-        let tex_rect = self.atlases.get_normalized_rect(&glyph.alloc);
+        let mut cached_atlas = None;
+        let subpixel = self.use_subpixel_text(&self.batcher.state().view);
 
-        let color = if glyph.is_image {
-            [1.0, 1.0, 1.0, cmd.color.a].into()
-        } else {
-            Color {
-                r: cmd.color.r + 2.0,
-                ..cmd.color
-            }
-        };
+        for cmd in std::iter::once(first).chain(run) {
+            let key = self.font_glyph_key(font, cmd, subpixel);
+            let glyph = match self.glyphs.get(key) {
+                Some(v) => v,
+                None => continue,
+            };
 
-        self.emit_rect(rect, tex_rect, tex_id, color);
+            let atlas_id = glyph.alloc.id.atlas_id;
+            let (tex_id, atlas_size) = match cached_atlas {
+                Some((id, tex_id, atlas_size)) if id == atlas_id => (tex_id, atlas_size),
+                _ => {
+                    let tex_id = self.bindings.atlas_index(atlas_id);
+                    let atlas_size = self.atlases.get(atlas_id).size().cast::<f32>();
+                    cached_atlas = Some((atlas_id, tex_id, atlas_size));
+                    (tex_id, atlas_size)
+                }
+            };
+
+            let size = glyph.bounds.size() * cmd.size;
+            let offset = glyph.bounds.min * cmd.size + Vec2::new(0.0, -size.y);
+            let rect = Rect::new((cmd.pos + offset).floor(), size);
+
+            let tex_rect = glyph.alloc.rect.map(|v| v.cast::<f32>() / atlas_size);
+
+            let blend = if glyph.is_subpixel {
+                BlendMode::ComponentAlpha
+            } else {
+                BlendMode::Alpha
+            };
+
+            self.batcher.modify_state(|state| state.blend = blend);
+
+            let color = if glyph.is_image {
+                [1.0, 1.0, 1.0, cmd.color.a].into()
+            } else if glyph.is_subpixel {
+                Color {
+                    r: cmd.color.r + 4.0,
+                    ..cmd.color
+                }
+            } else {
+                Color {
+                    r: cmd.color.r + 2.0,
+                    ..cmd.color
+                }
+            };
+
+            self.emit_rect(rect, tex_rect, tex_id, color);
+        }
+
+        self.batcher
+            .modify_state(|state| state.blend = BlendMode::Alpha);
     }
 
     fn emit_rect(&mut self, rect: Rect<f32>, tex_rect: Rect<f32>, tex_id: u32, color: Color) {
+        self.emit_quad(rect, tex_rect, tex_id, [color; 4]);
+    }
+
+    // gradients are drawn as a grid of quads with interpolated vertex colors
+    fn emit_gradient_rect(
+        &mut self,
+        rect: Rect<f32>,
+        tex_rect: Rect<f32>,
+        tex_id: u32,
+        color: Color,
+        gradient: &FillGradient,
+        shape: QuadShape,
+    ) {
+        let color_at = |unit: Vec2<f32>| {
+            let c = gradient.color_at(unit);
+            Color::new(c.r * color.r, c.g * color.g, c.b * color.b, c.a * color.a)
+        };
+
+        let cell = Vec2::splat(1.0 / GRADIENT_CELLS as f32);
+
+        for y in 0..GRADIENT_CELLS {
+            for x in 0..GRADIENT_CELLS {
+                let unit = Rect::new(Vec2::new(x as f32, y as f32) * cell, cell);
+                let to_rect = |r: Rect<f32>, v: Vec2<f32>| r.min + r.size() * v;
+
+                let cell_rect =
+                    Rect::from_min_max(to_rect(rect, unit.min), to_rect(rect, unit.max));
+                let cell_tex =
+                    Rect::from_min_max(to_rect(tex_rect, unit.min), to_rect(tex_rect, unit.max));
+
+                let colors = unit.vertices().map(color_at);
+                self.emit_shaped_quad(cell_rect, cell_tex, tex_id, colors, shape);
+            }
+        }
+    }
+
+    fn emit_quad(&mut self, rect: Rect<f32>, tex_rect: Rect<f32>, tex_id: u32, colors: [Color; 4]) {
+        self.emit_shaped_quad(rect, tex_rect, tex_id, colors, QuadShape::default());
+    }
+
+    fn emit_shaped_quad(
+        &mut self,
+        rect: Rect<f32>,
+        tex_rect: Rect<f32>,
+        tex_id: u32,
+        colors: [Color; 4],
+        shape: QuadShape,
+    ) {
         let state = self.batcher.state();
 
         let mut vertices = rect.vertices();
@@ -506,12 +916,27 @@ impl BackendImpl {
         self.batcher
             .emit_indices(&[i, i + 1, i + 2, i, i + 2, i + 3]);
 
-        for (pos, tex) in vertices.into_iter().zip(tex_rect.vertices()) {
+        let center = shape.rect.center();
+        let half_size = shape.rect.size() * 0.5;
+        let radii = shape.radii;
+        let radii = [
+            radii.top_left,
+            radii.top_right,
+            radii.bottom_right,
+            radii.bottom_left,
+        ];
+
+        let it = vertices.into_iter().zip(rect.vertices());
+        let it = it.zip(tex_rect.vertices()).zip(colors);
+        for (((pos, local), tex), color) in it {
             self.batcher.emit_vertex(Vertex {
                 pos,
                 tex,
                 tex_id,
                 color,
+                local: local - center,
+                half_size,
+                radii,
             })
         }
     }
@@ -528,9 +953,12 @@ impl BackendImpl {
 
         let (view, clear_color) = match canvas {
             Canvas::MainWindow => (main_view, clear_color.or(Some(Color::BLACK))),
-            Canvas::Texture {
-                view, has_cleared, ..
-            } => {
+            Canvas::Texture { has_cleared, .. } => {
+                let view = match self.canvases.get(canvas) {
+                    Some(v) => &v.view,
+                    None => return,
+                };
+
                 if has_cleared.load(Ordering::SeqCst) {
                     (view, clear_color)
                 } else {
@@ -565,13 +993,18 @@ impl BackendImpl {
         pass.set_index_buffer(ibuf.slice(..), IndexFormat::Uint32);
 
         pass.set_bind_group(0, self.bindings.bind_group(), &[]);
-        pass.set_pipeline(self.pipelines.pipeline());
+        let mut blend = None;
 
         for batch in self.batcher.batches() {
             if batch.state.scissor.area() == 0 || batch.indices.is_empty() {
                 continue;
             }
 
+            if blend != Some(batch.state.blend) {
+                blend = Some(batch.state.blend);
+                pass.set_pipeline(self.pipelines.pipeline(batch.state.blend));
+            }
+
             pass.set_scissor_rect(
                 batch.state.scissor.min.x,
                 batch.state.scissor.min.y,
@@ -584,18 +1017,174 @@ impl BackendImpl {
     }
 }
 
+const GRADIENT_CELLS: u32 = 16;
+
+// the rounded rect a quad belongs to. Gradients split one rect into many
+// quads, which all share the shape of the whole rect
+#[derive(Clone, Copy, Debug, Default)]
+struct QuadShape {
+    rect: Rect<f32>,
+    radii: CornerRadii,
+}
+
 fn full_tex_rect() -> Rect<f32> {
     Rect::new(Vec2::zero(), Vec2::new(1.0, 1.0))
 }
 
-fn get_image_size(assets: &Assets, id: Id<Image>) -> Vec2<f32> {
-    assets
-        .get_by_id(id)
-        .map(|img| img.size.cast::<f32>())
-        .unwrap_or_else(Vec2::zero)
+fn reset_scissor(state: &mut State, resolution: Vec2<u32>) {
+    let res = resolution.cast::<f32>();
+    let min = state
+        .viewport
+        .transform_point(Vec2::zero())
+        .fmax(Vec2::zero());
+    let max = state.viewport.transform_point(res).fmin(res).fmax(min);
+
+    let proj = projection_matrix(resolution);
+    let n_min = proj.transform_point(min);
+    let n_max = proj.transform_point(max);
+
+    state.scissor = Rect::from_min_max(min, max).cast::<u32>();
+    state.normalized_scissor =
+        Rect::from_min_max(Vec2::new(n_min.x, n_max.y), Vec2::new(n_max.x, n_min.y));
 }
 
 fn projection_matrix(res: Vec2<u32>) -> Affine2<f32> {
     let res = res.cast::<f32>();
     Affine2::translation(Vec2::new(-1.0, 1.0)) * Affine2::scaling(Vec2::new(2.0, -2.0) / res)
 }
+
+fn request_device(
+    instance: &Instance,
+    surface: &Surface,
+    settings: &BackendSettings,
+) -> Result<(Device, Queue, Limits, Vec<PresentMode>)> {
+    let adapter = pollster::block_on(instance.request_adapter(&RequestAdapterOptions {
+        power_preference: if settings.prefer_low_power_gpu {
+            PowerPreference::LowPower
+        } else {
+            PowerPreference::HighPerformance
+        },
+        force_fallback_adapter: false,
+        compatible_surface: Some(surface),
+    }))
+    .ok_or_else(|| eyre!("No adapter"))?;
+
+    let limits = adapter.limits();
+    let present_modes = surface.get_supported_modes(&adapter);
+
+    let desc = &DeviceDescriptor {
+        label: None,
+        features: Features::TEXTURE_BINDING_ARRAY
+            | Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING,
+        limits: limits.clone(),
+    };
+
+    let (device, queue) = pollster::block_on(adapter.request_device(desc, None))?;
+
+    Ok((device, queue, limits, present_modes))
+}
+
+fn watch_device_lost(device: &Device, device_lost: &Arc<AtomicBool>) {
+    let device_lost = device_lost.clone();
+    device.on_uncaptured_error(move |error| {
+        if is_device_lost(&error) {
+            device_lost.store(true, Ordering::Relaxed);
+        }
+
+        tracing::error!("{}", error);
+    });
+}
+
+// a lost device shows up as a validation error caused by wgpu-core's
+// `DeviceError::Lost`, which isn't exposed through wgpu's own types
+fn is_device_lost(error: &wgpu::Error) -> bool {
+    let source = match error {
+        wgpu::Error::OutOfMemory { .. } => return true,
+        wgpu::Error::Validation { source, .. } => source,
+    };
+
+    let mut source: Option<&(dyn StdError + 'static)> = Some(&**source);
+    while let Some(error) = source {
+        if let Some(DeviceError::Lost) = device_error(error) {
+            return true;
+        }
+
+        source = error.source();
+    }
+
+    false
+}
+
+// the errors wrapping `DeviceError` are transparent, so it doesn't appear in
+// the source chain on its own
+fn device_error<'a>(error: &'a (dyn StdError + 'static)) -> Option<&'a DeviceError> {
+    if let Some(error) = error.downcast_ref::<DeviceError>() {
+        return Some(error);
+    }
+
+    if let Some(QueueSubmitError::Queue(error)) = error.downcast_ref() {
+        return Some(error);
+    }
+
+    if let Some(QueueWriteError::Queue(error)) = error.downcast_ref() {
+        return Some(error);
+    }
+
+    if let Some(CreateBufferError::Device(error)) = error.downcast_ref() {
+        return Some(error);
+    }
+
+    if let Some(CreateTextureError::Device(error)) = error.downcast_ref() {
+        return Some(error);
+    }
+
+    if let Some(BufferAccessError::Device(error)) = error.downcast_ref() {
+        return Some(error);
+    }
+
+    None
+}
+
+fn pool_config(settings: &BackendSettings, limits: &Limits) -> PoolConfig {
+    let max_texture_size = limits.max_texture_dimension_2d;
+    let max_atlas_size = settings.max_texture_size.unwrap_or(8192);
+    PoolConfig {
+        max_size: Vec2::splat(max_atlas_size.min(max_texture_size)),
+        max_standalone_size: settings
+            .standalone_oversized_images
+            .then(|| Vec2::splat(max_texture_size)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wgpu_core::error::ContextError;
+
+    use super::*;
+
+    fn validation_error(cause: impl StdError + Send + Sync + 'static) -> wgpu::Error {
+        let error = ContextError {
+            string: "Queue::submit",
+            cause: Box::new(cause),
+            label_key: "",
+            label: String::new(),
+        };
+
+        wgpu::Error::Validation {
+            source: Box::new(error),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn detects_lost_devices() {
+        let lost = validation_error(DeviceError::Lost);
+        assert!(is_device_lost(&lost));
+
+        let lost = validation_error(QueueSubmitError::Queue(DeviceError::Lost));
+        assert!(is_device_lost(&lost));
+
+        let invalid = validation_error(QueueSubmitError::Queue(DeviceError::Invalid));
+        assert!(!is_device_lost(&invalid));
+    }
+}
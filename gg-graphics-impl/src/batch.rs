@@ -14,11 +14,26 @@ pub struct State {
     pub view_proj: Affine2<f32>,
     pub view: Affine2<f32>,
     pub proj: Affine2<f32>,
+    pub viewport: Affine2<f32>,
+    pub blend: BlendMode,
 }
 
 impl State {
     fn requires_flush(&self, other: &State) -> bool {
-        self.scissor != other.scissor
+        self.scissor != other.scissor || self.blend != other.blend
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlendMode {
+    Alpha,
+    ComponentAlpha,
+    Replace,
+}
+
+impl Default for BlendMode {
+    fn default() -> BlendMode {
+        BlendMode::Alpha
     }
 }
 
@@ -29,11 +44,16 @@ pub struct Vertex {
     pub tex: Vec2<f32>,
     pub tex_id: u32,
     pub color: Color,
+    // position relative to the center of the rounded rect the vertex belongs
+    // to, its half size and corner radii. Zero radii disable rounding
+    pub local: Vec2<f32>,
+    pub half_size: Vec2<f32>,
+    pub radii: [f32; 4],
 }
 
 impl Vertex {
     pub const LAYOUT: VertexBufferLayout<'static> = VertexBufferLayout {
-        array_stride: 36,
+        array_stride: 68,
         step_mode: VertexStepMode::Vertex,
         attributes: &[
             VertexAttribute {
@@ -56,6 +76,21 @@ impl Vertex {
                 offset: 20,
                 shader_location: 3,
             },
+            VertexAttribute {
+                format: VertexFormat::Float32x2,
+                offset: 36,
+                shader_location: 4,
+            },
+            VertexAttribute {
+                format: VertexFormat::Float32x2,
+                offset: 44,
+                shader_location: 5,
+            },
+            VertexAttribute {
+                format: VertexFormat::Float32x4,
+                offset: 52,
+                shader_location: 6,
+            },
         ],
     };
 }
@@ -164,6 +199,10 @@ impl Batcher {
         self.vertices.len() as u32
     }
 
+    pub fn num_vertices(&self) -> usize {
+        self.vertices.len()
+    }
+
     pub fn emit_vertex(&mut self, vertex: Vertex) {
         self.vertices.push(vertex);
     }
@@ -3,8 +3,9 @@ use std::sync::{Arc, Weak};
 
 use gg_graphics::RawCanvas;
 use gg_math::Vec2;
+use gg_util::ahash::AHashMap;
 use wgpu::{
-    Device, Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    Device, Extent3d, Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
     TextureView,
 };
 
@@ -12,8 +13,8 @@ use wgpu::{
 pub enum Canvas {
     MainWindow,
     Texture {
+        id: u64,
         size: Vec2<u32>,
-        view: TextureView,
         view_index: AtomicU32,
         has_cleared: AtomicBool,
     },
@@ -25,20 +26,15 @@ impl RawCanvas for Canvas {
     }
 }
 
-pub struct Canvases {
-    list: Vec<Weak<Canvas>>,
-    strong_list: Vec<Arc<Canvas>>,
+// textures are kept apart from the canvases handed out to users, so that they
+// can be recreated on a new device
+pub struct CanvasTexture {
+    pub texture: Texture,
+    pub view: TextureView,
 }
 
-impl Canvases {
-    pub fn new() -> Canvases {
-        Canvases {
-            list: Vec::new(),
-            strong_list: Vec::new(),
-        }
-    }
-
-    pub fn create_canvas(&mut self, device: &Device, size: Vec2<u32>) -> Arc<Canvas> {
+impl CanvasTexture {
+    fn new(device: &Device, size: Vec2<u32>) -> CanvasTexture {
         let texture = device.create_texture(&TextureDescriptor {
             label: None,
             size: Extent3d {
@@ -50,43 +46,98 @@ impl Canvases {
             sample_count: 1,
             dimension: TextureDimension::D2,
             format: TextureFormat::Bgra8UnormSrgb,
-            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
         });
 
         let view = texture.create_view(&Default::default());
+        CanvasTexture { texture, view }
+    }
+}
+
+pub struct Canvases {
+    list: Vec<(u64, Weak<Canvas>)>,
+    strong_list: Vec<Arc<Canvas>>,
+    textures: AHashMap<u64, CanvasTexture>,
+    next_id: u64,
+}
+
+impl Canvases {
+    pub fn new() -> Canvases {
+        Canvases {
+            list: Vec::new(),
+            strong_list: Vec::new(),
+            textures: AHashMap::default(),
+            next_id: 0,
+        }
+    }
+
+    pub fn create_canvas(&mut self, device: &Device, size: Vec2<u32>) -> Arc<Canvas> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.textures.insert(id, CanvasTexture::new(device, size));
+
         let canvas = Arc::new(Canvas::Texture {
+            id,
             size,
-            view,
             view_index: AtomicU32::new(0),
             has_cleared: AtomicBool::new(false),
         });
 
-        self.list.push(Arc::downgrade(&canvas));
+        self.list.push((id, Arc::downgrade(&canvas)));
 
         canvas
     }
 
+    pub fn get(&self, canvas: &Canvas) -> Option<&CanvasTexture> {
+        match canvas {
+            Canvas::MainWindow => None,
+            Canvas::Texture { id, .. } => self.textures.get(id),
+        }
+    }
+
     pub fn update(&mut self) {
         self.strong_list.clear();
-        self.list.retain(|v| match v.upgrade() {
+        self.list.retain(|(id, v)| match v.upgrade() {
             Some(strong) => {
                 self.strong_list.push(strong);
                 true
             }
-            _ => false,
+            _ => {
+                self.textures.remove(id);
+                false
+            }
         });
     }
 
+    // old contents are lost, so every canvas is cleared on its next draw
+    pub fn recreate(&mut self, device: &Device) {
+        self.update();
+
+        for canvas in &self.strong_list {
+            if let Canvas::Texture {
+                id,
+                size,
+                has_cleared,
+                ..
+            } = &**canvas
+            {
+                self.textures.insert(*id, CanvasTexture::new(device, *size));
+                has_cleared.store(false, Ordering::SeqCst);
+            }
+        }
+    }
+
     pub fn texture_views(&self) -> impl ExactSizeIterator<Item = &TextureView> + '_ {
         let mut idx = 0;
         self.strong_list.iter().map(move |canvas| match &**canvas {
             Canvas::MainWindow => unreachable!(),
-            Canvas::Texture {
-                view, view_index, ..
-            } => {
+            Canvas::Texture { id, view_index, .. } => {
                 view_index.store(idx, Ordering::SeqCst);
                 idx += 1;
-                view
+                &self.textures[id].view
             }
         })
     }
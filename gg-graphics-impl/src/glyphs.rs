@@ -1,10 +1,10 @@
 use gg_assets::{Assets, Id};
-use gg_graphics::{FontFace, GlyphId, RasterizationCache, SubpixelOffset};
+use gg_graphics::{FontFace, GlyphId, RasterizationCache, RasterizationQuality, SubpixelOffset};
 use gg_math::{Rect, Vec2};
 use gg_util::ahash::AHashMap;
 use wgpu::TextureFormat;
 
-use crate::atlas::{AtlasPool, PoolAllocation, PoolImage};
+use crate::atlas::{AllocatorKind, AtlasPool, PoolAllocation, PoolAllocationId, PoolImage};
 
 #[derive(Debug, Default)]
 pub struct Glyphs {
@@ -18,6 +18,7 @@ pub struct Glyph {
     pub size: Vec2<u32>,
     pub alloc: PoolAllocation,
     pub is_image: bool,
+    pub is_subpixel: bool,
 }
 
 impl Glyphs {
@@ -40,12 +41,13 @@ impl Glyphs {
         };
 
         let res = match key.kind {
-            GlyphKeyKind::Image { size } => font
-                .get_image(key.glyph, size)
+            GlyphKeyKind::Image { size, quality } => font
+                .get_image(key.glyph, size, quality)
                 .map(|raster| (raster, TextureFormat::Rgba8UnormSrgb)),
             GlyphKeyKind::Vector {
                 size,
                 subpixel_offset,
+                subpixel: false,
             } => font
                 .rasterize(
                     &mut self.cache,
@@ -54,6 +56,18 @@ impl Glyphs {
                     subpixel_offset,
                 )
                 .map(|raster| (raster, TextureFormat::R8Unorm)),
+            GlyphKeyKind::Vector {
+                size,
+                subpixel_offset,
+                subpixel: true,
+            } => font
+                .rasterize_subpixel(
+                    &mut self.cache,
+                    key.glyph,
+                    f32::from_bits(size),
+                    subpixel_offset,
+                )
+                .map(|raster| (raster, TextureFormat::Rgba8Unorm)),
         };
 
         let (raster, format) = match res {
@@ -64,22 +78,40 @@ impl Glyphs {
             }
         };
 
-        let alloc = atlases.alloc(PoolImage {
+        let res = atlases.alloc(PoolImage {
             size: raster.size,
             data: raster.data,
             format,
-            preferred_allocator: None,
+            preferred_allocator: Some(AllocatorKind::Shelf),
         });
 
+        let alloc = match res {
+            Ok(v) => v,
+            Err(error) => {
+                tracing::error!(?key, %error, "failed to allocate glyph");
+                self.map.insert(key, None);
+                return;
+            }
+        };
+
         let glyph = Glyph {
             bounds: raster.bounds,
             size: raster.size,
             alloc,
             is_image: format == TextureFormat::Rgba8UnormSrgb,
+            is_subpixel: format == TextureFormat::Rgba8Unorm,
         };
 
         self.map.insert(key, Some(glyph));
     }
+
+    pub fn remap(&mut self, remap: &AHashMap<PoolAllocationId, PoolAllocation>) {
+        for glyph in self.map.values_mut().flatten() {
+            if let Some(&new_alloc) = remap.get(&glyph.alloc.id) {
+                glyph.alloc = new_alloc;
+            }
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -94,8 +126,72 @@ pub enum GlyphKeyKind {
     Vector {
         size: u32,
         subpixel_offset: SubpixelOffset,
+        subpixel: bool,
     },
     Image {
         size: u32,
+        quality: RasterizationQuality,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use gg_assets::{DirSource, Handle};
+
+    use super::*;
+    use crate::atlas::PoolConfig;
+
+    fn load_font(assets: &mut Assets, name: &str) -> Handle<FontFace> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../assets/fonts")
+            .join(name);
+        let data = std::fs::read(path).unwrap();
+        assets.insert(FontFace::new(data.into(), 0).unwrap())
+    }
+
+    #[test]
+    fn masks_and_images_use_separate_atlases() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+        let mut assets = Assets::new(DirSource::new(&path).unwrap());
+        let text = load_font(&mut assets, "OpenSans-Regular.ttf");
+        let emoji = load_font(&mut assets, "NotoColorEmoji.ttf");
+
+        let mask_key = GlyphKey {
+            font: text.id(),
+            glyph: assets.get(&text).unwrap().lookup_glyph('g'),
+            kind: GlyphKeyKind::Vector {
+                size: 16f32.to_bits(),
+                subpixel_offset: SubpixelOffset::new(Vec2::zero()),
+                subpixel: false,
+            },
+        };
+        let image_key = GlyphKey {
+            font: emoji.id(),
+            glyph: assets.get(&emoji).unwrap().lookup_glyph('😀'),
+            kind: GlyphKeyKind::Image {
+                size: 32,
+                quality: RasterizationQuality::Triangle,
+            },
+        };
+
+        let mut atlases = AtlasPool::new(PoolConfig {
+            max_size: Vec2::splat(1024),
+            max_standalone_size: None,
+        });
+        let mut glyphs = Glyphs::new();
+        glyphs.alloc(&mut atlases, &assets, mask_key);
+        glyphs.alloc(&mut atlases, &assets, image_key);
+
+        let mask = glyphs.get(mask_key).unwrap();
+        let image = glyphs.get(image_key).unwrap();
+        assert!(!mask.is_image);
+        assert!(image.is_image);
+        assert_ne!(mask.alloc.id.atlas_id, image.alloc.id.atlas_id);
+
+        let format = |glyph: Glyph| atlases.get(glyph.alloc.id.atlas_id).format();
+        assert_eq!(format(mask), TextureFormat::R8Unorm);
+        assert_eq!(format(image), TextureFormat::Rgba8UnormSrgb);
+    }
+}
@@ -1,15 +1,19 @@
-use gg_assets::{Assets, EventKind, EventReceiver, Id};
+use gg_assets::{Assets, EventReceiver, Id};
 use gg_graphics::Image;
 use gg_math::{Rect, Vec2};
-use gg_util::ahash::AHashMap;
+use gg_util::ahash::{AHashMap, AHashSet};
 use wgpu::TextureFormat;
 
-use crate::atlas::{AllocatorKind, AtlasId, AtlasPool, PoolAllocation, PoolImage};
+use crate::atlas::{
+    AllocatorKind, AtlasId, AtlasPool, PoolAllocation, PoolAllocationId, PoolImage,
+};
 
 #[derive(Debug)]
 pub struct Images {
     cell_size: Vec2<u16>,
     map: AHashMap<Id<Image>, PoolAllocation>,
+    // images that didn't fit, not retried until they change
+    failed: AHashSet<Id<Image>>,
     event_receiver: EventReceiver<Image>,
 }
 
@@ -18,6 +22,7 @@ impl Images {
         Images {
             cell_size,
             map: AHashMap::new(),
+            failed: AHashSet::new(),
             event_receiver: assets.subscribe(),
         }
     }
@@ -28,27 +33,23 @@ impl Images {
         Some((alloc.id.atlas_id, rect))
     }
 
-    pub fn alloc(&mut self, atlases: &mut AtlasPool, assets: &mut Assets, id: Id<Image>) {
-        let (size, data) = match assets.get_by_id_mut(id) {
+    // the pixels stay in the asset, so that they can be uploaded again after
+    // the device is lost, or read by others
+    pub fn alloc(&mut self, atlases: &mut AtlasPool, assets: &Assets, id: Id<Image>) {
+        if self.map.contains_key(&id) || self.failed.contains(&id) {
+            return;
+        }
+
+        let (size, data) = match assets.get_by_id(id) {
             Some(image) => {
-                let data = match image.data.take() {
-                    Some(v) => v,
-                    None => {
-                        if self.map.contains_key(&id) {
-                            return;
-                        }
-
-                        checkerboard(image.size)
-                    }
+                let data = match &image.data {
+                    Some(v) => v.clone(),
+                    None => checkerboard(image.size),
                 };
 
                 (image.size, data)
             }
             None => {
-                if self.map.contains_key(&id) {
-                    return;
-                }
-
                 let size = Vec2::new(16, 16);
                 (size, checkerboard(size))
             }
@@ -62,26 +63,40 @@ impl Images {
             None
         };
 
-        if let Some(old_alloc) = self.map.get(&id) {
-            atlases.free(old_alloc.id);
-        }
-
-        let new_alloc = atlases.alloc(PoolImage {
+        let res = atlases.alloc(PoolImage {
             size,
             data,
             format: TextureFormat::Rgba8UnormSrgb,
             preferred_allocator,
         });
 
-        self.map.insert(id, new_alloc);
+        match res {
+            Ok(new_alloc) => {
+                self.map.insert(id, new_alloc);
+            }
+            Err(error) => {
+                tracing::error!(?id, %error, "failed to allocate image");
+                self.failed.insert(id);
+            }
+        }
+    }
+
+    pub fn remap(&mut self, remap: &AHashMap<PoolAllocationId, PoolAllocation>) {
+        for alloc in self.map.values_mut() {
+            if let Some(&new_alloc) = remap.get(&alloc.id) {
+                *alloc = new_alloc;
+            }
+        }
     }
 
+    // created and updated images replace a placeholder or an older version,
+    // so they are uploaded again the next time they're drawn
     pub fn cleanup(&mut self, atlases: &mut AtlasPool) {
         for event in self.event_receiver.try_iter() {
-            if event.kind == EventKind::Removed {
-                if let Some(alloc) = self.map.remove(&event.asset) {
-                    atlases.free(alloc.id);
-                }
+            self.failed.remove(&event.asset);
+
+            if let Some(alloc) = self.map.remove(&event.asset) {
+                atlases.free(alloc.id);
             }
         }
     }
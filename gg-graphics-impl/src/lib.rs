@@ -6,5 +6,8 @@ mod canvas;
 mod glyphs;
 mod images;
 mod pipeline;
+mod readback;
+mod window;
 
-pub use self::backend::{BackendImpl, BackendSettings};
+pub use self::backend::{BackendImpl, BackendSettings, PresentModePref};
+pub use self::window::{set_window_icon, window_icon};
@@ -1,10 +1,11 @@
 use wgpu::{
-    BlendState, ColorTargetState, ColorWrites, Device, FragmentState, MultisampleState,
-    PipelineLayout, PipelineLayoutDescriptor, PrimitiveState, RenderPipeline,
-    RenderPipelineDescriptor, ShaderModule, ShaderModuleDescriptor, TextureFormat, VertexState,
+    BlendComponent, BlendFactor, BlendOperation, BlendState, ColorTargetState, ColorWrites, Device,
+    FragmentState, MultisampleState, PipelineLayout, PipelineLayoutDescriptor, PrimitiveState,
+    RenderPipeline, RenderPipelineDescriptor, ShaderModule, ShaderModuleDescriptor, TextureFormat,
+    VertexState,
 };
 
-use crate::batch::Vertex;
+use crate::batch::{BlendMode, Vertex};
 use crate::bindings::Bindings;
 
 #[derive(Debug)]
@@ -12,27 +13,56 @@ pub struct Pipelines {
     pipeline_layout: PipelineLayout,
     shader: ShaderModule,
     pipeline: RenderPipeline,
+    subpixel_pipeline: RenderPipeline,
+    replace_pipeline: RenderPipeline,
 }
 
 impl Pipelines {
     pub fn new(device: &Device, bindings: &Bindings) -> Pipelines {
         let pipeline_layout = create_pipeline_layout(device, bindings);
         let shader = create_shader(device);
-        let pipeline = create_pipeline(device, &pipeline_layout, &shader);
+        let pipeline = create_pipeline(device, &pipeline_layout, &shader, BlendMode::Alpha);
+        let subpixel_pipeline =
+            create_pipeline(device, &pipeline_layout, &shader, BlendMode::ComponentAlpha);
+        let replace_pipeline =
+            create_pipeline(device, &pipeline_layout, &shader, BlendMode::Replace);
         Pipelines {
             pipeline_layout,
             shader,
             pipeline,
+            subpixel_pipeline,
+            replace_pipeline,
         }
     }
 
     pub fn recreate(&mut self, device: &Device, bindings: &Bindings) {
         self.pipeline_layout = create_pipeline_layout(device, bindings);
-        self.pipeline = create_pipeline(device, &self.pipeline_layout, &self.shader);
+        self.pipeline = create_pipeline(
+            device,
+            &self.pipeline_layout,
+            &self.shader,
+            BlendMode::Alpha,
+        );
+        self.subpixel_pipeline = create_pipeline(
+            device,
+            &self.pipeline_layout,
+            &self.shader,
+            BlendMode::ComponentAlpha,
+        );
+        self.replace_pipeline = create_pipeline(
+            device,
+            &self.pipeline_layout,
+            &self.shader,
+            BlendMode::Replace,
+        );
     }
 
-    pub fn pipeline(&self) -> &RenderPipeline {
-        &self.pipeline
+    pub fn pipeline(&self, blend: BlendMode) -> &RenderPipeline {
+        match blend {
+            BlendMode::Alpha => &self.pipeline,
+            BlendMode::ComponentAlpha => &self.subpixel_pipeline,
+            BlendMode::Replace => &self.replace_pipeline,
+        }
     }
 }
 
@@ -55,7 +85,21 @@ fn create_pipeline(
     device: &Device,
     layout: &PipelineLayout,
     shader: &ShaderModule,
+    blend: BlendMode,
 ) -> RenderPipeline {
+    let blend = match blend {
+        BlendMode::Alpha => BlendState::ALPHA_BLENDING,
+        BlendMode::ComponentAlpha => BlendState {
+            color: BlendComponent {
+                src_factor: BlendFactor::One,
+                dst_factor: BlendFactor::OneMinusSrc,
+                operation: BlendOperation::Add,
+            },
+            alpha: BlendComponent::OVER,
+        },
+        BlendMode::Replace => BlendState::REPLACE,
+    };
+
     device.create_render_pipeline(&RenderPipelineDescriptor {
         label: None,
         layout: Some(layout),
@@ -72,7 +116,7 @@ fn create_pipeline(
             entry_point: "fs_main",
             targets: &[Some(ColorTargetState {
                 format: TextureFormat::Bgra8UnormSrgb,
-                blend: Some(BlendState::ALPHA_BLENDING),
+                blend: Some(blend),
                 write_mask: ColorWrites::default(),
             })],
         }),
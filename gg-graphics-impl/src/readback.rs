@@ -0,0 +1,96 @@
+use std::num::NonZeroU32;
+use std::sync::mpsc;
+
+use gg_math::Vec2;
+use wgpu::{
+    Buffer, BufferDescriptor, BufferUsages, CommandEncoder, Device, Extent3d, ImageCopyBuffer,
+    ImageDataLayout, Maintain, MapMode, Texture, COPY_BYTES_PER_ROW_ALIGNMENT,
+};
+
+// a texture copied into a buffer, waiting to be mapped. Textures are expected
+// to be BGRA with 4 bytes per pixel
+#[derive(Debug)]
+pub struct Readback {
+    buffer: Buffer,
+    size: Vec2<u32>,
+    padded_row: u32,
+}
+
+impl Readback {
+    pub fn encode(
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        texture: &Texture,
+        size: Vec2<u32>,
+    ) -> Readback {
+        let row = size.x * 4;
+        let padded_row = (row + COPY_BYTES_PER_ROW_ALIGNMENT - 1) / COPY_BYTES_PER_ROW_ALIGNMENT
+            * COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: u64::from(padded_row) * u64::from(size.y),
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            ImageCopyBuffer {
+                buffer: &buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(padded_row),
+                    rows_per_image: NonZeroU32::new(size.y),
+                },
+            },
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        Readback {
+            buffer,
+            size,
+            padded_row,
+        }
+    }
+
+    // blocks until the copy is submitted and finished, returns tightly packed
+    // RGBA rows
+    pub fn read(self, device: &Device) -> Vec<u8> {
+        let slice = self.buffer.slice(..);
+
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(MapMode::Read, move |res| {
+            let _ = sender.send(res);
+        });
+
+        device.poll(Maintain::Wait);
+
+        if let Err(error) = receiver.recv().expect("map callback not called") {
+            tracing::error!(%error, "failed to read back a texture");
+            return vec![0; (self.size.x * self.size.y * 4) as usize];
+        }
+
+        let row = (self.size.x * 4) as usize;
+        let mut pixels = Vec::with_capacity(row * self.size.y as usize);
+
+        {
+            let data = slice.get_mapped_range();
+            for padded in data.chunks_exact(self.padded_row as usize) {
+                pixels.extend_from_slice(&padded[..row]);
+            }
+        }
+
+        self.buffer.unmap();
+
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+
+        pixels
+    }
+}
@@ -3,6 +3,9 @@ struct VertexOutput {
     @location(0) tex: vec2<f32>,
     @location(1) tex_id: u32,
     @location(2) color: vec4<f32>,
+    @location(3) local: vec2<f32>,
+    @location(4) half_size: vec2<f32>,
+    @location(5) radii: vec4<f32>,
 };
 
 @group(0) @binding(0)
@@ -17,15 +20,29 @@ fn vs_main(
     @location(1) tex: vec2<f32>,
     @location(2) tex_id: u32,
     @location(3) color: vec4<f32>,
+    @location(4) local: vec2<f32>,
+    @location(5) half_size: vec2<f32>,
+    @location(6) radii: vec4<f32>,
 ) -> VertexOutput {
     var vertex: VertexOutput;
     vertex.pos = vec4<f32>(pos, 0.0, 1.0);
     vertex.tex = tex;
     vertex.tex_id = tex_id;
     vertex.color = color;
+    vertex.local = local;
+    vertex.half_size = half_size;
+    vertex.radii = radii;
     return vertex;
 }
 
+// radii are top left, top right, bottom right, bottom left, y points down
+fn rounded_rect_sdf(p: vec2<f32>, half_size: vec2<f32>, radii: vec4<f32>) -> f32 {
+    let side = select(radii.xw, radii.yz, p.x > 0.0);
+    let radius = select(side.x, side.y, p.y > 0.0);
+    let q = abs(p) - half_size + radius;
+    return min(max(q.x, q.y), 0.0) + length(max(q, vec2<f32>(0.0))) - radius;
+}
+
 @fragment
 fn fs_main(vertex: VertexOutput) -> @location(0) vec4<f32> {
     let col = vertex.color;
@@ -36,5 +53,22 @@ fn fs_main(vertex: VertexOutput) -> @location(0) vec4<f32> {
     let glyph_factor = f32(col.r > 1.5);
     let glyph_color = vec4<f32>(col.r - 2.0, col.g, col.b, tex_col.r);
 
-    return mix(col * tex_col, glyph_color, glyph_factor);
+    let subpixel_factor = f32(col.r > 3.5);
+    let coverage = tex_col.rgb * col.a;
+    let subpixel_color = vec4<f32>(
+        vec3<f32>(col.r - 4.0, col.g, col.b) * coverage,
+        max(coverage.r, max(coverage.g, coverage.b)),
+    );
+
+    let color = mix(col * tex_col, glyph_color, glyph_factor);
+    let color = mix(color, subpixel_color, subpixel_factor);
+
+    // derivatives must be taken in uniform control flow, so the distance is
+    // computed even for quads without rounding
+    let dist = rounded_rect_sdf(vertex.local, vertex.half_size, vertex.radii);
+    let coverage = clamp(0.5 - dist / max(fwidth(dist), 1e-6), 0.0, 1.0);
+    let radius = max(max(vertex.radii.x, vertex.radii.y), max(vertex.radii.z, vertex.radii.w));
+    let coverage = select(1.0, coverage, radius > 0.0);
+
+    return vec4<f32>(color.rgb, color.a * coverage);
 }
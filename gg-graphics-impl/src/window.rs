@@ -0,0 +1,23 @@
+use gg_assets::{Assets, Id};
+use gg_graphics::Image;
+use gg_util::eyre::{eyre, Result};
+use winit::window::{Icon, Window};
+
+pub fn window_icon(assets: &Assets, image: Id<Image>) -> Result<Icon> {
+    let image = assets
+        .get_by_id(image)
+        .ok_or_else(|| eyre!("window icon image is not loaded"))?;
+
+    let data = image
+        .data
+        .clone()
+        .ok_or_else(|| eyre!("window icon image has no pixel data"))?;
+
+    Ok(Icon::from_rgba(data, image.size.x, image.size.y)?)
+}
+
+pub fn set_window_icon(window: &Window, assets: &Assets, image: Option<Id<Image>>) -> Result<()> {
+    let icon = image.map(|image| window_icon(assets, image)).transpose()?;
+    window.set_window_icon(icon);
+    Ok(())
+}
@@ -0,0 +1,100 @@
+#![allow(dead_code)]
+
+use std::path::Path;
+
+use gg_assets::{Assets, DirSource};
+use gg_graphics::RasterizationQuality;
+use gg_graphics_impl::{BackendImpl, BackendSettings, PresentModePref};
+use gg_math::Vec2;
+use winit::event_loop::EventLoop;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+use winit::platform::unix::EventLoopExtUnix;
+#[cfg(windows)]
+use winit::platform::windows::EventLoopExtWindows;
+use winit::window::{Window, WindowBuilder};
+
+// tests using the harness need a display and a GPU. They are ignored by
+// default and run with `cargo test -- --ignored`
+pub struct Harness {
+    pub backend: BackendImpl,
+    pub assets: Assets,
+    pub window: Window,
+    _event_loop: EventLoop<()>,
+}
+
+impl Harness {
+    pub fn new() -> Harness {
+        Harness::with_settings(BackendSettings {
+            present_mode: PresentModePref::Fifo,
+            prefer_low_power_gpu: true,
+            image_cell_size: Vec2::splat(8),
+            max_texture_size: None,
+            standalone_oversized_images: true,
+            subpixel_text: false,
+            glyph_image_quality: RasterizationQuality::Triangle,
+        })
+    }
+
+    pub fn with_settings(settings: BackendSettings) -> Harness {
+        let unix = cfg!(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ));
+        let has_display =
+            std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some();
+        assert!(!unix || has_display, "no display to open a window on");
+
+        let event_loop = new_event_loop();
+        let window = WindowBuilder::new()
+            .with_visible(false)
+            .build(&event_loop)
+            .expect("cannot create a window");
+
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+        let assets = Assets::new(DirSource::new(&path).unwrap());
+
+        let backend =
+            BackendImpl::new(settings, &assets, &window).expect("cannot create a backend");
+
+        Harness {
+            backend,
+            assets,
+            window,
+            _event_loop: event_loop,
+        }
+    }
+}
+
+// tests run off the main thread
+#[cfg(any(
+    windows,
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn new_event_loop() -> EventLoop<()> {
+    EventLoop::new_any_thread()
+}
+
+#[cfg(not(any(
+    windows,
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+fn new_event_loop() -> EventLoop<()> {
+    EventLoop::new()
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use std::path::Path;
+
+use gg_graphics::{Backend, DrawGlyph, FontFace, GraphicsEncoder, Image};
+use gg_math::{Rect, Vec2};
+
+#[test]
+#[ignore = "needs a display and a GPU"]
+fn glyph_and_image_render_from_their_atlases() {
+    let mut h = common::Harness::new();
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets/fonts/OpenSans-Regular.ttf");
+    let face = FontFace::new(std::fs::read(path).unwrap().into(), 0).unwrap();
+    let glyph = face.lookup_glyph('O');
+    let font = h.assets.insert(face);
+
+    let image = h.assets.insert(Image {
+        size: Vec2::splat(4),
+        data: Some([0, 128, 255, 255].repeat(16)),
+    });
+
+    let canvas = h.backend.create_canvas(Vec2::new(128, 64));
+
+    let mut encoder = GraphicsEncoder::new(&canvas);
+    encoder.clear([0.0, 0.0, 0.0]);
+    encoder.glyph(DrawGlyph {
+        font: font.id(),
+        glyph,
+        size: 48.0,
+        pos: Vec2::new(8.0, 52.0),
+        color: [1.0, 1.0, 1.0].into(),
+    });
+    encoder
+        .rect(Rect::new(Vec2::new(64.0, 0.0), Vec2::new(64.0, 64.0)))
+        .fill_image(image.id());
+    h.backend.submit(encoder.finish());
+    h.backend.present(&mut h.assets).unwrap();
+
+    let pixels = h.backend.read_canvas(&canvas);
+    let pixel = |x: usize, y: usize| &pixels[(y * 128 + x) * 4..][..4];
+
+    for y in 0..64 {
+        for x in 64..128 {
+            let p = pixel(x, y);
+            let close = p
+                .iter()
+                .zip([0, 128, 255, 255])
+                .all(|(a, b)| a.abs_diff(b) <= 1);
+            assert!(close, "pixel {}x{}: {:?}", x, y, p);
+        }
+    }
+
+    // coverage is read from the red channel and tinted with the glyph color,
+    // so the glyph is gray and the inside of the O stays clear
+    let (mut min, mut max) = (Vec2::splat(usize::MAX), Vec2::zero());
+    for y in 0..64 {
+        for x in 0..64 {
+            let p = pixel(x, y);
+            assert!(p[0].abs_diff(p[1]) <= 1 && p[1].abs_diff(p[2]) <= 1);
+
+            if p[0] > 0 {
+                min = min.min(Vec2::new(x, y));
+                max = max.max(Vec2::new(x, y));
+            }
+        }
+    }
+
+    assert!(min.cmp_lt(max).all(), "glyph was not drawn");
+    let center = (min + max) / 2;
+    assert_eq!(pixel(center.x, center.y), [0, 0, 0, 255]);
+    assert!((0..64).any(|x| pixel(x, center.y)[0] > 250));
+}
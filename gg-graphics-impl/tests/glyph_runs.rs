@@ -0,0 +1,63 @@
+mod common;
+
+use std::path::Path;
+
+use gg_graphics::{Backend, Command, DrawGlyph, FontFace, GraphicsEncoder};
+use gg_math::Vec2;
+
+#[test]
+#[ignore = "needs a display and a GPU"]
+fn glyph_runs_match_single_glyphs() {
+    let mut h = common::Harness::new();
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets/fonts/OpenSans-Regular.ttf");
+    let face = FontFace::new(std::fs::read(path).unwrap().into(), 0).unwrap();
+    let text = "the quick brown fox jumps over the lazy dog ";
+    let glyphs = text
+        .chars()
+        .map(|c| face.lookup_glyph(c))
+        .collect::<Vec<_>>();
+    let font = h.assets.insert(face);
+
+    let paragraph = (0..5000)
+        .map(|i| DrawGlyph {
+            font: font.id(),
+            glyph: glyphs[i % glyphs.len()],
+            size: 10.0,
+            pos: Vec2::new((i % 100) as f32 * 5.0, 10.0 + (i / 100) as f32 * 10.0),
+            color: [1.0, 1.0, 1.0].into(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut render = |split_runs: bool| {
+        let canvas = h.backend.create_canvas(Vec2::new(512, 512));
+
+        let mut encoder = GraphicsEncoder::new(&canvas);
+        encoder.clear([0.0, 0.0, 0.0]);
+
+        for glyph in &paragraph {
+            encoder.glyph(*glyph);
+
+            // any other command ends a run
+            if split_runs {
+                encoder.command(Command::ClearScissor);
+            }
+        }
+
+        h.backend.submit(encoder.finish());
+        h.backend.present(&mut h.assets).unwrap();
+        h.backend.read_canvas(&canvas)
+    };
+
+    let batched = render(false);
+    let single = render(true);
+
+    assert!(
+        batched.chunks_exact(4).any(|p| p[0] > 0),
+        "nothing was drawn"
+    );
+    assert!(
+        batched == single,
+        "batched glyphs differ from single glyphs"
+    );
+}
@@ -0,0 +1,39 @@
+use gg_graphics_impl::PresentModePref;
+use wgpu::PresentMode;
+
+#[test]
+fn select_present_mode() {
+    let all = [
+        PresentMode::Fifo,
+        PresentMode::Mailbox,
+        PresentMode::Immediate,
+    ];
+    let fifo_only = [PresentMode::Fifo];
+    let no_mailbox = [PresentMode::Fifo, PresentMode::Immediate];
+
+    assert_eq!(PresentModePref::Mailbox.select(&all), PresentMode::Mailbox);
+    assert_eq!(
+        PresentModePref::Mailbox.select(&fifo_only),
+        PresentMode::Fifo
+    );
+    assert_eq!(
+        PresentModePref::Mailbox.select(&no_mailbox),
+        PresentMode::Fifo
+    );
+
+    assert_eq!(
+        PresentModePref::Immediate.select(&all),
+        PresentMode::Immediate
+    );
+    assert_eq!(
+        PresentModePref::Immediate.select(&[PresentMode::Fifo, PresentMode::Mailbox]),
+        PresentMode::Mailbox
+    );
+    assert_eq!(
+        PresentModePref::Immediate.select(&fifo_only),
+        PresentMode::Fifo
+    );
+
+    assert_eq!(PresentModePref::Fifo.select(&all), PresentMode::Fifo);
+    assert_eq!(PresentModePref::Auto.select(&[]), PresentMode::AutoVsync);
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use gg_graphics::{Backend, GraphicsEncoder};
+use gg_math::{Rect, Vec2};
+
+#[test]
+#[ignore = "needs a display and a GPU"]
+fn read_canvas() {
+    let mut h = common::Harness::new();
+
+    // rows of 100 pixels are not aligned to 256 bytes, so padding is stripped
+    let size = Vec2::new(100, 20);
+    let canvas = h.backend.create_canvas(size);
+
+    let mut encoder = GraphicsEncoder::new(&canvas);
+    encoder.clear([1.0, 0.0, 0.0]);
+    encoder.clear_rect(
+        Rect::new(Vec2::zero(), Vec2::new(10.0, 20.0)),
+        [0.0, 1.0, 0.0],
+    );
+    h.backend.submit(encoder.finish());
+    h.backend.present(&mut h.assets).unwrap();
+
+    let pixels = h.backend.read_canvas(&canvas);
+    assert_eq!(pixels.len(), 100 * 20 * 4);
+
+    for (i, pixel) in pixels.chunks_exact(4).enumerate() {
+        let expected = if i % 100 < 10 {
+            [0, 255, 0, 255]
+        } else {
+            [255, 0, 0, 255]
+        };
+
+        assert_eq!(pixel, expected, "pixel {}", i);
+    }
+}
+
+#[test]
+#[ignore = "needs a display and a GPU"]
+fn gradient_clear() {
+    let mut h = common::Harness::new();
+
+    let canvas = h.backend.create_canvas(Vec2::new(64, 64));
+
+    let mut encoder = GraphicsEncoder::new(&canvas);
+    encoder.clear_gradient([1.0, 0.0, 0.0], [0.0, 0.0, 1.0]);
+    h.backend.submit(encoder.finish());
+    h.backend.present(&mut h.assets).unwrap();
+
+    let pixels = h.backend.read_canvas(&canvas);
+    let column: Vec<&[u8]> = (0..64).map(|y| &pixels[y * 64 * 4..][..4]).collect();
+
+    assert!(column[0][0] > 250 && column[0][2] < 5, "{:?}", column[0]);
+    assert!(column[63][0] < 5 && column[63][2] > 250, "{:?}", column[63]);
+
+    for pair in column.windows(2) {
+        assert!(pair[0][0] >= pair[1][0] && pair[0][2] <= pair[1][2]);
+        assert_eq!(pair[1][3], 255);
+    }
+}
+
+#[test]
+#[ignore = "needs a display and a GPU"]
+fn clear_rect_keeps_blending() {
+    let mut h = common::Harness::new();
+
+    let canvas = h.backend.create_canvas(Vec2::new(64, 16));
+
+    let mut encoder = GraphicsEncoder::new(&canvas);
+    encoder.clear_gradient([1.0, 0.0, 0.0], [1.0, 0.0, 0.0]);
+    encoder.clear_rect(
+        Rect::new(Vec2::zero(), Vec2::new(32.0, 16.0)),
+        [0.0, 1.0, 0.0],
+    );
+    encoder
+        .rect(Rect::new(Vec2::zero(), Vec2::new(64.0, 16.0)))
+        .fill_color([0.0, 0.0, 0.0, 0.5]);
+    h.backend.submit(encoder.finish());
+    h.backend.present(&mut h.assets).unwrap();
+
+    let pixels = h.backend.read_canvas(&canvas);
+
+    // half transparent black over an opaque color darkens it, but leaves the
+    // canvas opaque
+    for (i, pixel) in pixels.chunks_exact(4).enumerate() {
+        let (lit, dark) = if i % 64 < 32 { (1, 0) } else { (0, 1) };
+        assert!((150..230).contains(&pixel[lit]), "pixel {}: {:?}", i, pixel);
+        assert!(pixel[dark] < 5 && pixel[2] < 5, "pixel {}: {:?}", i, pixel);
+        assert_eq!(pixel[3], 255, "pixel {}", i);
+    }
+}
+
+#[test]
+#[ignore = "needs a display and a GPU"]
+fn viewport_halves() {
+    let mut h = common::Harness::new();
+
+    let canvas = h.backend.create_canvas(Vec2::new(100, 20));
+
+    let mut encoder = GraphicsEncoder::new(&canvas);
+    encoder.clear([1.0, 0.0, 0.0]);
+
+    // an oversized rect is clipped to the left half
+    encoder.set_viewport(Rect::new(Vec2::zero(), Vec2::new(50.0, 20.0)));
+    encoder
+        .rect(Rect::new(Vec2::splat(-100.0), Vec2::splat(300.0)))
+        .fill_color([0.0, 1.0, 0.0]);
+
+    // the left half of the canvas maps to the left half of the right half
+    encoder.set_viewport(Rect::new(Vec2::new(50.0, 0.0), Vec2::new(50.0, 20.0)));
+    encoder
+        .rect(Rect::new(Vec2::zero(), Vec2::new(50.0, 20.0)))
+        .fill_color([0.0, 0.0, 1.0]);
+
+    h.backend.submit(encoder.finish());
+    h.backend.present(&mut h.assets).unwrap();
+
+    let pixels = h.backend.read_canvas(&canvas);
+
+    for (i, pixel) in pixels.chunks_exact(4).enumerate() {
+        let expected = match i % 100 {
+            0..=49 => [0, 255, 0, 255],
+            50..=74 => [0, 0, 255, 255],
+            _ => [255, 0, 0, 255],
+        };
+
+        assert_eq!(pixel, expected, "pixel {}", i);
+    }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use gg_graphics::{Backend, GraphicsEncoder};
+use gg_math::{Rect, Vec2};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Clone, Debug)]
+struct CapturedSpan {
+    name: &'static str,
+    fields: Vec<(&'static str, u64)>,
+}
+
+impl Visit for CapturedSpan {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.fields.push((field.name(), value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.fields.push((field.name(), value as u64));
+    }
+
+    fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+}
+
+#[derive(Clone, Default)]
+struct Capture {
+    spans: Arc<Mutex<Vec<CapturedSpan>>>,
+}
+
+impl Capture {
+    fn field(&self, span: &str, field: &str) -> Vec<u64> {
+        let spans = self.spans.lock().unwrap();
+        spans
+            .iter()
+            .filter(|s| s.name == span)
+            .flat_map(|s| s.fields.iter().filter(|(n, _)| *n == field))
+            .map(|&(_, v)| v)
+            .collect()
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let mut span = CapturedSpan {
+            name: attrs.metadata().name(),
+            fields: Vec::new(),
+        };
+        attrs.record(&mut span);
+
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(span);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, id: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        values.record(&mut spans[id.into_u64() as usize - 1]);
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+#[ignore = "needs a display and a GPU"]
+fn present_emits_phase_spans() {
+    let mut h = common::Harness::new();
+
+    let canvas = h.backend.create_canvas(Vec2::new(32, 32));
+
+    let mut encoder = GraphicsEncoder::new(&canvas);
+    encoder.clear([0.0, 0.0, 0.0]);
+    encoder
+        .rect(Rect::new(Vec2::zero(), Vec2::splat(8.0)))
+        .fill_color([1.0, 1.0, 1.0]);
+    encoder
+        .rect(Rect::new(Vec2::splat(8.0), Vec2::splat(8.0)))
+        .fill_color([1.0, 1.0, 1.0]);
+    h.backend.submit(encoder.finish());
+
+    let capture = Capture::default();
+    tracing::subscriber::with_default(capture.clone(), || {
+        h.backend.present(&mut h.assets).unwrap();
+    });
+
+    assert_eq!(capture.field("present", "lists"), [1]);
+    assert_eq!(capture.field("alloc_list", "commands"), [3]);
+    assert_eq!(capture.field("batch_list", "commands"), [3]);
+    assert_eq!(capture.field("batch_list", "vertices"), [8]);
+    assert_eq!(capture.field("encode_pass", "vertices"), [8]);
+
+    let spans = capture.spans.lock().unwrap();
+    assert!(spans.iter().any(|s| s.name == "upload"));
+}
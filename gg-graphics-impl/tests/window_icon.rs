@@ -0,0 +1,33 @@
+use gg_assets::{Assets, MemorySource};
+use gg_graphics::Image;
+use gg_graphics_impl::window_icon;
+use gg_math::Vec2;
+
+#[test]
+fn window_icon_from_asset() {
+    let mut assets = Assets::new(MemorySource::new());
+
+    let image = assets.insert(Image {
+        size: Vec2::new(16, 16),
+        data: Some(vec![255; 16 * 16 * 4]),
+    });
+
+    // the pixels stay in the asset after the icon is made
+    assert!(window_icon(&assets, image.id()).is_ok());
+    assert!(window_icon(&assets, image.id()).is_ok());
+    assert!(assets[&image].data.is_some());
+
+    let empty = assets.insert(Image {
+        size: Vec2::new(16, 16),
+        data: None,
+    });
+
+    let error = window_icon(&assets, empty.id()).unwrap_err();
+    assert_eq!(error.to_string(), "window icon image has no pixel data");
+
+    let wrong_size = assets.insert(Image {
+        size: Vec2::new(16, 16),
+        data: Some(vec![255; 4]),
+    });
+    assert!(window_icon(&assets, wrong_size.id()).is_err());
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use gg_assets::Assets;
 use gg_math::Vec2;
 
@@ -13,7 +15,35 @@ pub trait Backend: Send + Sync + 'static {
 
     fn resize(&mut self, new_resolution: Vec2<u32>);
 
-    fn present(&mut self, assets: &mut Assets);
+    fn present(&mut self, assets: &mut Assets) -> Result<(), PresentError> {
+        self.encode_frame(assets)?;
+        self.submit_frame();
+        Ok(())
+    }
+
+    // the part of presenting that reads assets, so that it can be done while
+    // they're at hand, and the rest on another thread
+    fn encode_frame(&mut self, assets: &mut Assets) -> Result<(), PresentError>;
+
+    // submits and presents the frame recorded by `encode_frame`, if any
+    fn submit_frame(&mut self);
 
     fn recycle_list(&mut self) -> Option<CommandList>;
 }
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PresentError {
+    Timeout,
+    DeviceLost,
+}
+
+impl fmt::Display for PresentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresentError::Timeout => f.write_str("timed out acquiring the next frame"),
+            PresentError::DeviceLost => f.write_str("graphics device was lost"),
+        }
+    }
+}
+
+impl std::error::Error for PresentError {}
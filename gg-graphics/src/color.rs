@@ -1,3 +1,5 @@
+use gg_math::lerp;
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Color {
     pub r: f32,
@@ -14,6 +16,81 @@ impl Color {
     pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Color {
         Color { r, g, b, a }
     }
+
+    // parses `#RRGGBB` or `#RRGGBBAA`, the `#` is optional
+    pub fn from_hex(hex: &str) -> Option<Color> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if !matches!(hex.len(), 6 | 8) || !hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let channel = |i: usize| {
+            let v = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+            Some(f32::from(v) / 255.0)
+        };
+
+        let a = if hex.len() == 8 { channel(3)? } else { 1.0 };
+        Some(Color::new(channel(0)?, channel(1)?, channel(2)?, a))
+    }
+
+    // hue is in degrees, saturation and lightness are from 0 to 1
+    pub fn hsl(h: f32, s: f32, l: f32) -> Color {
+        Color::hsla(h, s, l, 1.0)
+    }
+
+    pub fn hsla(h: f32, s: f32, l: f32, a: f32) -> Color {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        let m = l - chroma * 0.5;
+        Color::new(r + m, g + m, b + m, a)
+    }
+
+    // hue, saturation and lightness, alpha is dropped. Hue of grays is 0
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let chroma = max - min;
+        let l = (max + min) * 0.5;
+
+        if chroma <= 0.0 {
+            return (0.0, 0.0, l);
+        }
+
+        let h = if max == self.r {
+            ((self.g - self.b) / chroma).rem_euclid(6.0)
+        } else if max == self.g {
+            (self.b - self.r) / chroma + 2.0
+        } else {
+            (self.r - self.g) / chroma + 4.0
+        };
+
+        let s = chroma / (1.0 - (2.0 * l - 1.0).abs());
+        (h * 60.0, s, l)
+    }
+
+    pub fn with_alpha(self, a: f32) -> Color {
+        Color { a, ..self }
+    }
+
+    pub fn lerp(self, rhs: Color, t: f32) -> Color {
+        Color::new(
+            lerp(self.r, rhs.r, t),
+            lerp(self.g, rhs.g, t),
+            lerp(self.b, rhs.b, t),
+            lerp(self.a, rhs.a, t),
+        )
+    }
 }
 
 impl Default for Color {
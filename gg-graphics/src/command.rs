@@ -1,5 +1,5 @@
 use gg_assets::{Handle, Id};
-use gg_math::{Affine2, Rect, Vec2};
+use gg_math::{Affine2, Rect, SideOffsets, Vec2};
 
 use crate::{Canvas, Color, FontFace, GlyphId, Image, NinePatchImage};
 
@@ -15,10 +15,13 @@ pub enum Command {
     Restore,
     SetScissor(Rect<f32>),
     ClearScissor,
+    SetViewport(Rect<f32>),
     PreTransform(Affine2<f32>),
     PostTransform(Affine2<f32>),
-    Clear(Color),
+    Clear(ClearFill),
+    ClearRect(Rect<f32>, Color),
     DrawRect(DrawRect),
+    StrokeRect(StrokeRect),
     DrawGlyph(DrawGlyph),
 }
 
@@ -28,10 +31,171 @@ impl From<DrawRect> for Command {
     }
 }
 
+impl From<StrokeRect> for Command {
+    fn from(cmd: StrokeRect) -> Self {
+        Command::StrokeRect(cmd)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClearFill {
+    Color(Color),
+    VerticalGradient { top: Color, bottom: Color },
+}
+
+impl ClearFill {
+    pub fn top(&self) -> Color {
+        match *self {
+            ClearFill::Color(color) => color,
+            ClearFill::VerticalGradient { top, .. } => top,
+        }
+    }
+
+    pub fn bottom(&self) -> Color {
+        match *self {
+            ClearFill::Color(color) => color,
+            ClearFill::VerticalGradient { bottom, .. } => bottom,
+        }
+    }
+
+    pub fn is_gradient(&self) -> bool {
+        self.top() != self.bottom()
+    }
+}
+
+impl From<Color> for ClearFill {
+    fn from(color: Color) -> Self {
+        ClearFill::Color(color)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DrawRect {
     pub rect: Rect<f32>,
     pub fill: Fill,
+    pub corner_radii: CornerRadii,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CornerRadii {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    pub fn new(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> CornerRadii {
+        CornerRadii {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        }
+    }
+
+    pub fn all(radius: f32) -> CornerRadii {
+        CornerRadii::new(radius, radius, radius, radius)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.max() <= 0.0
+    }
+
+    pub fn max(&self) -> f32 {
+        self.top_left
+            .max(self.top_right)
+            .max(self.bottom_right)
+            .max(self.bottom_left)
+    }
+
+    // radii larger than half of the smaller side would make corners overlap
+    pub fn clamp(self, size: Vec2<f32>) -> CornerRadii {
+        let max = size.x.min(size.y).max(0.0) * 0.5;
+        let clamp = |v: f32| v.max(0.0).min(max);
+
+        CornerRadii::new(
+            clamp(self.top_left),
+            clamp(self.top_right),
+            clamp(self.bottom_right),
+            clamp(self.bottom_left),
+        )
+    }
+}
+
+impl From<f32> for CornerRadii {
+    fn from(radius: f32) -> Self {
+        CornerRadii::all(radius)
+    }
+}
+
+impl From<[f32; 4]> for CornerRadii {
+    fn from([top_left, top_right, bottom_right, bottom_left]: [f32; 4]) -> Self {
+        CornerRadii::new(top_left, top_right, bottom_right, bottom_left)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StrokeRect {
+    pub rect: Rect<f32>,
+    pub width: f32,
+    pub color: Color,
+    pub alignment: StrokeAlignment,
+}
+
+// where the stroke lies relative to the edges of the rect
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StrokeAlignment {
+    Inside,
+    Center,
+    Outside,
+}
+
+impl Default for StrokeAlignment {
+    fn default() -> StrokeAlignment {
+        StrokeAlignment::Inside
+    }
+}
+
+impl StrokeRect {
+    pub fn outer_rect(&self) -> Rect<f32> {
+        let offset = match self.alignment {
+            StrokeAlignment::Inside => 0.0,
+            StrokeAlignment::Center => self.width * 0.5,
+            StrokeAlignment::Outside => self.width,
+        };
+
+        self.rect.grow(&SideOffsets::new_equal(offset))
+    }
+
+    // top, bottom, left and right edges. The side edges don't overlap the
+    // top and bottom ones, and a stroke wider than the rect fills it whole
+    pub fn edges(&self) -> [Rect<f32>; 4] {
+        let outer = self.outer_rect();
+        let mut inner_min = outer.min + Vec2::splat(self.width);
+        let mut inner_max = outer.max - Vec2::splat(self.width);
+
+        for i in 0..2 {
+            if inner_min[i] > inner_max[i] {
+                let mid = (outer.min[i] + outer.max[i]) * 0.5;
+                inner_min[i] = mid;
+                inner_max[i] = mid;
+            }
+        }
+
+        [
+            Rect::from_min_max(outer.min, Vec2::new(outer.max.x, inner_min.y)),
+            Rect::from_min_max(Vec2::new(outer.min.x, inner_max.y), outer.max),
+            Rect::from_min_max(
+                Vec2::new(outer.min.x, inner_min.y),
+                Vec2::new(inner_min.x, inner_max.y),
+            ),
+            Rect::from_min_max(
+                Vec2::new(inner_max.x, inner_min.y),
+                Vec2::new(outer.max.x, inner_max.y),
+            ),
+        ]
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -47,6 +211,107 @@ pub struct DrawGlyph {
 pub struct Fill {
     pub color: Color,
     pub image: Option<FillImage>,
+    pub gradient: Option<FillGradient>,
+}
+
+// gradient coordinates are fractions of the rect size, (0, 0) being its min
+// corner. The gradient color is multiplied by the fill color
+#[derive(Clone, Debug, PartialEq)]
+pub enum FillGradient {
+    Linear {
+        start: Vec2<f32>,
+        end: Vec2<f32>,
+        stops: Vec<GradientStop>,
+    },
+    Radial {
+        center: Vec2<f32>,
+        radius: f32,
+        stops: Vec<GradientStop>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientStop {
+    pub offset: f32,
+    pub color: Color,
+}
+
+impl GradientStop {
+    pub fn new(offset: f32, color: impl Into<Color>) -> GradientStop {
+        GradientStop {
+            offset,
+            color: color.into(),
+        }
+    }
+}
+
+impl FillGradient {
+    pub fn linear(start: Vec2<f32>, end: Vec2<f32>, stops: Vec<GradientStop>) -> FillGradient {
+        FillGradient::Linear { start, end, stops }
+    }
+
+    pub fn radial(center: Vec2<f32>, radius: f32, stops: Vec<GradientStop>) -> FillGradient {
+        FillGradient::Radial {
+            center,
+            radius,
+            stops,
+        }
+    }
+
+    pub fn stops(&self) -> &[GradientStop] {
+        match self {
+            FillGradient::Linear { stops, .. } => stops,
+            FillGradient::Radial { stops, .. } => stops,
+        }
+    }
+
+    // color at `pos`, given in the same unit coordinates as the gradient.
+    // Stops are expected to be sorted by offset
+    pub fn color_at(&self, pos: Vec2<f32>) -> Color {
+        let t = match *self {
+            FillGradient::Linear { start, end, .. } => {
+                let dir = end - start;
+                let len_sq = dir.length_squared();
+                if len_sq > 0.0 {
+                    (pos - start).dot(dir) / len_sq
+                } else {
+                    0.0
+                }
+            }
+            FillGradient::Radial { center, radius, .. } => {
+                if radius > 0.0 {
+                    (pos - center).length() / radius
+                } else {
+                    0.0
+                }
+            }
+        };
+
+        let stops = self.stops();
+        let (first, last) = match (stops.first(), stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Color::WHITE,
+        };
+
+        if t <= first.offset {
+            return first.color;
+        }
+
+        for pair in stops.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if t <= b.offset {
+                let range = b.offset - a.offset;
+                let t = if range > 0.0 {
+                    (t - a.offset) / range
+                } else {
+                    1.0
+                };
+                return a.color.lerp(b.color, t);
+            }
+        }
+
+        last.color
+    }
 }
 
 #[derive(Clone, Debug)]
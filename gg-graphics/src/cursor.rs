@@ -0,0 +1,74 @@
+use gg_assets::{Assets, Id};
+use gg_math::{Rect, Vec2};
+
+use crate::{Color, Command, CommandList, CornerRadii, DrawRect, Fill, Image};
+
+// a cursor image drawn on top of the main canvas, for when the platform
+// cursor can't show images
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Cursor {
+    image: Option<Id<Image>>,
+    hotspot: Vec2<u32>,
+    pos: Option<Vec2<f32>>,
+}
+
+impl Cursor {
+    pub fn new() -> Cursor {
+        Cursor::default()
+    }
+
+    pub fn image(&self) -> Option<Id<Image>> {
+        self.image
+    }
+
+    pub fn hotspot(&self) -> Vec2<u32> {
+        self.hotspot
+    }
+
+    pub fn set_image(&mut self, image: Option<Id<Image>>, hotspot: Vec2<u32>) {
+        self.image = image;
+        self.hotspot = if image.is_some() {
+            hotspot
+        } else {
+            Vec2::zero()
+        };
+    }
+
+    pub fn position(&self) -> Option<Vec2<f32>> {
+        self.pos
+    }
+
+    pub fn set_position(&mut self, pos: Option<Vec2<f32>>) {
+        self.pos = pos;
+    }
+
+    pub fn is_os_cursor_visible(&self) -> bool {
+        self.image.is_none()
+    }
+
+    pub fn rect(&self, assets: &Assets) -> Option<Rect<f32>> {
+        let image = assets.get_by_id(self.image?)?;
+        let pos = self.pos? - self.hotspot.cast::<f32>();
+        Some(Rect::new(pos, image.size.cast::<f32>()))
+    }
+
+    pub fn encode(&self, assets: &Assets, list: &mut CommandList) {
+        let (rect, image) = match (self.rect(assets), self.image) {
+            (Some(rect), Some(image)) => (rect, image),
+            _ => return,
+        };
+
+        list.list.push(Command::Save);
+        list.list.push(Command::ClearScissor);
+        list.list.push(Command::DrawRect(DrawRect {
+            rect,
+            fill: Fill {
+                color: Color::WHITE,
+                image: Some(image.into()),
+                gradient: None,
+            },
+            corner_radii: CornerRadii::default(),
+        }));
+        list.list.push(Command::Restore);
+    }
+}
@@ -1,6 +1,9 @@
 use gg_math::{Affine2, Rect, Vec2};
 
-use crate::{Canvas, Color, Command, CommandList, DrawGlyph, DrawRect, Fill, FillImage};
+use crate::{
+    Canvas, ClearFill, Color, Command, CommandList, CornerRadii, DrawGlyph, DrawRect, Fill,
+    FillGradient, FillImage, StrokeAlignment, StrokeRect,
+};
 
 #[derive(Clone, Debug)]
 pub struct GraphicsEncoder {
@@ -35,7 +38,7 @@ impl GraphicsEncoder {
 
         match command {
             Command::SetScissor(rect) => self.scissor = rect.f_intersection(&self.scissor),
-            Command::ClearScissor => self.scissor = full_scissor(),
+            Command::ClearScissor | Command::SetViewport(_) => self.scissor = full_scissor(),
             Command::Save => self.saved_scissors.push(self.scissor),
             Command::Restore => {
                 self.scissor = self.saved_scissors.pop().unwrap_or_else(full_scissor);
@@ -66,6 +69,10 @@ impl GraphicsEncoder {
         self.command(Command::ClearScissor);
     }
 
+    pub fn set_viewport(&mut self, rect: impl Into<Rect<f32>>) {
+        self.command(Command::SetViewport(rect.into()));
+    }
+
     pub fn pre_transform(&mut self, affine: Affine2<f32>) {
         self.command(Command::PreTransform(affine));
     }
@@ -75,7 +82,18 @@ impl GraphicsEncoder {
     }
 
     pub fn clear(&mut self, color: impl Into<Color>) {
-        self.command(Command::Clear(color.into()));
+        self.command(Command::Clear(ClearFill::Color(color.into())));
+    }
+
+    pub fn clear_gradient(&mut self, top: impl Into<Color>, bottom: impl Into<Color>) {
+        self.command(Command::Clear(ClearFill::VerticalGradient {
+            top: top.into(),
+            bottom: bottom.into(),
+        }));
+    }
+
+    pub fn clear_rect(&mut self, rect: impl Into<Rect<f32>>, color: impl Into<Color>) {
+        self.command(Command::ClearRect(rect.into(), color.into()));
     }
 
     pub fn rect(&mut self, rect: impl Into<Rect<f32>>) -> RectEncoder<'_> {
@@ -86,7 +104,37 @@ impl GraphicsEncoder {
                 fill: Fill {
                     color: Color::WHITE,
                     image: None,
+                    gradient: None,
                 },
+                corner_radii: CornerRadii::default(),
+            },
+        }
+    }
+
+    pub fn rounded_rect(
+        &mut self,
+        rect: impl Into<Rect<f32>>,
+        radii: impl Into<CornerRadii>,
+    ) -> RectEncoder<'_> {
+        let rect = rect.into();
+        let mut encoder = self.rect(rect);
+        encoder.cmd.corner_radii = radii.into().clamp(rect.size());
+        encoder
+    }
+
+    pub fn stroke_rect(
+        &mut self,
+        rect: impl Into<Rect<f32>>,
+        width: f32,
+        color: impl Into<Color>,
+    ) -> StrokeEncoder<'_> {
+        StrokeEncoder {
+            encoder: self,
+            cmd: StrokeRect {
+                rect: rect.into(),
+                width,
+                color: color.into(),
+                alignment: StrokeAlignment::default(),
             },
         }
     }
@@ -119,6 +167,11 @@ impl RectEncoder<'_> {
         self.cmd.fill.image = Some(image.into());
         self
     }
+
+    pub fn fill_gradient(mut self, gradient: FillGradient) -> Self {
+        self.cmd.fill.gradient = Some(gradient);
+        self
+    }
 }
 
 impl Drop for RectEncoder<'_> {
@@ -127,6 +180,25 @@ impl Drop for RectEncoder<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct StrokeEncoder<'a> {
+    encoder: &'a mut GraphicsEncoder,
+    cmd: StrokeRect,
+}
+
+impl StrokeEncoder<'_> {
+    pub fn alignment(mut self, alignment: StrokeAlignment) -> Self {
+        self.cmd.alignment = alignment;
+        self
+    }
+}
+
+impl Drop for StrokeEncoder<'_> {
+    fn drop(&mut self) {
+        self.encoder.command(Command::StrokeRect(self.cmd));
+    }
+}
+
 fn full_scissor() -> Rect<f32> {
     Rect::new(Vec2::zero(), Vec2::splat(f32::INFINITY))
 }
@@ -72,10 +72,28 @@ impl FontFace {
     pub fn line_metrics(&self, size: f32) -> LineMetrics {
         let face = self.inner.borrow_face();
         let scale = size / face.units_per_em() as f32;
+
+        // fonts without post or OS/2 tables get something reasonable
+        let default_thickness = size / 14.0;
+        let underline = face.underline_metrics();
+        let strikeout = face.strikeout_metrics();
+
         LineMetrics {
             ascender: face.ascender() as f32 * scale,
             descender: face.descender() as f32 * scale,
             line_gap: face.line_gap() as f32 * scale,
+            underline_position: underline
+                .map(|v| v.position as f32 * scale)
+                .unwrap_or(-size * 0.1),
+            underline_thickness: underline
+                .map(|v| v.thickness as f32 * scale)
+                .unwrap_or(default_thickness),
+            strikeout_position: strikeout
+                .map(|v| v.position as f32 * scale)
+                .unwrap_or(size * 0.3),
+            strikeout_thickness: strikeout
+                .map(|v| v.thickness as f32 * scale)
+                .unwrap_or(default_thickness),
         }
     }
 
@@ -86,24 +104,77 @@ impl FontFace {
         size: f32,
         subpixel_offset: SubpixelOffset,
     ) -> Option<GlyphRaster> {
+        let (px_min, px_size, coverage) =
+            self.rasterize_coverage(cache, glyph, size, subpixel_offset, 1)?;
+        let data = coverage.iter().map(|&a| (a * 255.0) as u8).collect();
+        Some(GlyphRaster::new(px_min, px_size, size, data))
+    }
+
+    pub fn rasterize_subpixel(
+        &self,
+        cache: &mut RasterizationCache,
+        glyph: GlyphId,
+        size: f32,
+        subpixel_offset: SubpixelOffset,
+    ) -> Option<GlyphRaster> {
+        let (px_min, px_size, coverage) =
+            self.rasterize_coverage(cache, glyph, size, subpixel_offset, 3)?;
+
+        let row_len = px_size.x * 3;
+        let mut data = Vec::with_capacity(px_size.x * px_size.y * 4);
+
+        for row in coverage.chunks_exact(row_len) {
+            for x in 0..px_size.x {
+                let rgb = [0, 1, 2].map(|channel| {
+                    let center = x * 3 + channel;
+                    let sum = LCD_FILTER.iter().enumerate().fold(0.0, |sum, (i, w)| {
+                        let idx = (center + i).checked_sub(LCD_FILTER.len() / 2);
+                        sum + w * idx.and_then(|idx| row.get(idx)).unwrap_or(&0.0)
+                    });
+                    sum.min(1.0)
+                });
+
+                let alpha = rgb[0].max(rgb[1]).max(rgb[2]);
+                data.extend(rgb.map(|v| (v * 255.0) as u8));
+                data.push((alpha * 255.0) as u8);
+            }
+        }
+
+        Some(GlyphRaster::new(px_min, px_size, size, data))
+    }
+
+    fn rasterize_coverage(
+        &self,
+        cache: &mut RasterizationCache,
+        glyph: GlyphId,
+        size: f32,
+        subpixel_offset: SubpixelOffset,
+        oversample: usize,
+    ) -> Option<(Vec2<f32>, Vec2<usize>, Vec<f32>)> {
         let face = self.inner.borrow_face();
         let scale = size / face.units_per_em() as f32;
 
         let offset = subpixel_offset.get();
         let bbox = face.glyph_bounding_box(glyph)?;
-        let px_min =
+        let mut px_min =
             (Vec2::new((bbox.x_min as f32) * scale, (bbox.y_min as f32) * scale) + offset).floor();
-        let px_max =
+        let mut px_max =
             (Vec2::new((bbox.x_max as f32) * scale, (bbox.y_max as f32) * scale) + offset).ceil();
 
+        if oversample > 1 {
+            px_min.x -= 1.0;
+            px_max.x += 1.0;
+        }
+
         let px_width = (px_max.x - px_min.x).max(0.0) as usize;
         let px_height = (px_max.y - px_min.y).max(0.0) as usize;
         if px_width == 0 || px_height == 0 {
             return None;
         }
 
-        let mut data = vec![0; px_width * px_height];
-        cache.rasterizer.reset(px_width, px_height);
+        let width = px_width * oversample;
+        let mut coverage = vec![0.0; width * px_height];
+        cache.rasterizer.reset(width, px_height);
 
         face.outline_glyph(
             glyph,
@@ -113,24 +184,16 @@ impl FontFace {
                 last_move: None,
                 last_pos: point(0.0, 0.0),
                 scale,
+                x_scale: oversample as f32,
                 height: px_height as f32,
             },
         );
 
         cache
             .rasterizer
-            .for_each_pixel(|i, a| data[i] = (a * 255.0) as u8);
+            .for_each_pixel(|i, a| coverage[i] = a.min(1.0));
 
-        let raster_size = Vec2::new(px_width, px_height).cast::<u32>();
-
-        Some(GlyphRaster {
-            bounds: Rect::new(
-                Vec2::new(px_min.x, -px_min.y) / size,
-                raster_size.cast::<f32>() / size,
-            ),
-            size: raster_size,
-            data,
-        })
+        Some((px_min, Vec2::new(px_width, px_height), coverage))
     }
 
     pub fn has_image(&self, glyph: GlyphId) -> bool {
@@ -138,7 +201,12 @@ impl FontFace {
         face.glyph_raster_image(glyph, u16::MAX).is_some()
     }
 
-    pub fn get_image(&self, glyph: GlyphId, size: u32) -> Option<GlyphRaster> {
+    pub fn get_image(
+        &self,
+        glyph: GlyphId,
+        size: u32,
+        quality: RasterizationQuality,
+    ) -> Option<GlyphRaster> {
         let face = self.inner.borrow_face();
 
         let raster = match face.glyph_raster_image(glyph, size.min(u16::MAX.into()) as u16) {
@@ -154,7 +222,7 @@ impl FontFace {
         let size = (old_size.cast::<f32>() / scale * (size as f32)).cast::<u32>();
 
         if size.cmp_lt(old_size).any() {
-            image = image::imageops::resize(&image, size.x, size.y, FilterType::Triangle);
+            image = image::imageops::resize(&image, size.x, size.y, quality.filter());
         }
 
         Some(GlyphRaster {
@@ -172,6 +240,7 @@ impl FontFace {
         cache: &mut ShapingCache,
         size: f32,
         text: &str,
+        rtl: bool,
         buf: &mut Vec<ShapedGlyph>,
     ) {
         let face = self.inner.borrow_face();
@@ -179,7 +248,10 @@ impl FontFace {
 
         let mut buffer = std::mem::take(&mut cache.buffer);
         buffer.push_str(text);
-        buffer.set_direction(Direction::LeftToRight);
+        buffer.set_direction(match rtl {
+            true => Direction::RightToLeft,
+            false => Direction::LeftToRight,
+        });
 
         let glyphs = rustybuzz::shape(face, &[], buffer);
         let it = glyphs.glyph_infos().iter().zip(glyphs.glyph_positions());
@@ -196,11 +268,17 @@ impl FontFace {
 
 impl Asset for FontFace {}
 
-#[derive(Clone, Copy, Debug)]
+// decoration positions are the top edges of the strokes, measured upwards
+// from the baseline
+#[derive(Clone, Copy, Debug, Default)]
 pub struct LineMetrics {
     pub ascender: f32,
     pub descender: f32,
     pub line_gap: f32,
+    pub underline_position: f32,
+    pub underline_thickness: f32,
+    pub strikeout_position: f32,
+    pub strikeout_thickness: f32,
 }
 
 #[derive(Debug)]
@@ -210,6 +288,46 @@ pub struct GlyphRaster {
     pub data: Vec<u8>,
 }
 
+impl GlyphRaster {
+    fn new(px_min: Vec2<f32>, px_size: Vec2<usize>, size: f32, data: Vec<u8>) -> GlyphRaster {
+        let raster_size = px_size.cast::<u32>();
+
+        GlyphRaster {
+            bounds: Rect::new(
+                Vec2::new(px_min.x, -px_min.y) / size,
+                raster_size.cast::<f32>() / size,
+            ),
+            size: raster_size,
+            data,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RasterizationQuality {
+    Nearest,
+    Triangle,
+    Lanczos3,
+}
+
+impl RasterizationQuality {
+    fn filter(self) -> FilterType {
+        match self {
+            RasterizationQuality::Nearest => FilterType::Nearest,
+            RasterizationQuality::Triangle => FilterType::Triangle,
+            RasterizationQuality::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+impl Default for RasterizationQuality {
+    fn default() -> RasterizationQuality {
+        RasterizationQuality::Triangle
+    }
+}
+
+const LCD_FILTER: [f32; 5] = [1.0 / 9.0, 2.0 / 9.0, 3.0 / 9.0, 2.0 / 9.0, 1.0 / 9.0];
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct SubpixelOffset {
     sx: u8,
@@ -268,13 +386,14 @@ struct Outliner<'a> {
     last_move: Option<Point>,
     last_pos: Point,
     scale: f32,
+    x_scale: f32,
     height: f32,
 }
 
 impl Outliner<'_> {
     fn scale(&self, x: f32, y: f32) -> Point {
         point(
-            x * self.scale - self.origin.x,
+            (x * self.scale - self.origin.x) * self.x_scale,
             self.height - y * self.scale + self.origin.y,
         )
     }
@@ -7,6 +7,6 @@ pub use self::collection::{FontCollection, FontCollectionLoader};
 pub use self::db::FontDb;
 pub use self::face::{
     FontFace, FontFaceProps, FontStyle, FontWeight, GlyphId, GlyphRaster, LineMetrics,
-    RasterizationCache, ShapedGlyph, ShapingCache, SubpixelOffset,
+    RasterizationCache, RasterizationQuality, ShapedGlyph, ShapingCache, SubpixelOffset,
 };
 pub use self::family::FontFamily;
@@ -1,8 +1,10 @@
 use std::path::Path;
 use std::sync::Arc;
 
-use gg_assets::{Asset, AssetLoader, BytesAssetLoader, Handle, Id, LoaderCtx, LoaderRegistry};
-use gg_math::Vec2;
+use gg_assets::{
+    Asset, AssetLoader, Assets, BytesAssetLoader, Handle, Id, LoaderCtx, LoaderRegistry,
+};
+use gg_math::{SideOffsets, Vec2};
 use gg_util::async_trait;
 use gg_util::eyre::Result;
 
@@ -57,6 +59,19 @@ impl NinePatchImage {
             self.left.id(),
         ]
     }
+
+    pub fn content_insets(&self, assets: &Assets) -> SideOffsets<f32> {
+        let size = |handle: &Handle<Image>| {
+            assets
+                .get(handle)
+                .map(|img| img.size.cast::<f32>())
+                .unwrap_or_else(Vec2::zero)
+        };
+
+        let top_left = size(&self.top_left);
+        let bottom_right = size(&self.bottom_right);
+        SideOffsets::new(top_left.y, bottom_right.x, bottom_right.y, top_left.x)
+    }
 }
 
 impl Asset for NinePatchImage {
@@ -2,19 +2,26 @@ mod backend;
 mod canvas;
 mod color;
 mod command;
+mod cursor;
 mod encoder;
 mod font;
 mod image;
 mod text_layout;
+mod threaded;
 
-pub use self::backend::Backend;
+pub use self::backend::{Backend, PresentError};
 pub use self::canvas::{Canvas, RawCanvas};
 pub use self::color::Color;
-pub use self::command::{Command, CommandList, DrawGlyph, DrawRect, Fill, FillImage};
+pub use self::command::{
+    ClearFill, Command, CommandList, CornerRadii, DrawGlyph, DrawRect, Fill, FillGradient,
+    FillImage, GradientStop, StrokeAlignment, StrokeRect,
+};
+pub use self::cursor::Cursor;
 pub use self::encoder::GraphicsEncoder;
 pub use self::font::*;
 pub use self::image::{Image, NinePatchImage, PngLoader};
 pub use self::text_layout::{
-    ShapedText, Text, TextHAlign, TextLayouter, TextProperties, TextSegment, TextSegmentProperties,
-    TextVAlign,
+    ShapedText, Text, TextDecoration, TextHAlign, TextLayouter, TextProperties, TextSegment,
+    TextSegmentProperties, TextVAlign,
 };
+pub use self::threaded::ThreadedBackend;
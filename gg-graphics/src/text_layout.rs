@@ -2,13 +2,14 @@ use std::borrow::Cow;
 use std::ops::Range;
 
 use gg_assets::{Assets, Id};
-use gg_math::Vec2;
+use gg_math::{Rect, Vec2};
 use ttf_parser::GlyphId;
+use unicode_bidi::{BidiInfo, Level};
 use unicode_linebreak::BreakOpportunity;
 
 use crate::{
-    Color, DrawGlyph, FontDb, FontFace, FontFamily, FontStyle, FontWeight, ShapedGlyph,
-    ShapingCache,
+    Color, DrawGlyph, FontDb, FontFace, FontFamily, FontStyle, FontWeight, LineMetrics,
+    ShapedGlyph, ShapingCache,
 };
 
 #[derive(Clone, Debug, PartialEq)]
@@ -64,13 +65,45 @@ pub struct TextSegmentProperties {
     pub style: FontStyle,
     pub size: f32,
     pub color: Color,
+    pub underline: bool,
+    pub strikethrough: bool,
+    // added between glyphs, and for word spacing, after each space
+    pub letter_spacing: f32,
+    pub word_spacing: f32,
+}
+
+// underlines and strikethroughs, drawn as filled rects
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextDecoration {
+    pub rect: Rect<f32>,
+    pub color: Color,
 }
 
 #[derive(Clone, Debug)]
 pub struct ShapedText {
+    text: String,
     props: TextProperties,
     segments: Vec<RawSegment>,
     glyphs: Vec<ShapedGlyph>,
+    visible_glyphs: Option<usize>,
+}
+
+impl ShapedText {
+    pub fn num_glyphs(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn visible_glyphs(&mut self, count: usize) {
+        self.visible_glyphs = Some(count);
+    }
+
+    pub fn show_all_glyphs(&mut self) {
+        self.visible_glyphs = None;
+    }
 }
 
 #[derive(Debug, Default)]
@@ -81,9 +114,31 @@ pub struct TextLayouter {
     scratch_segments: Vec<RawSegment>,
     glyphs: Vec<ShapedGlyph>,
     output_glyphs: Vec<DrawGlyph>,
+    output_decorations: Vec<TextDecoration>,
+    carets: CaretMap,
     cache: ShapingCache,
 }
 
+// caret positions of the last layout, used for hit testing
+#[derive(Debug, Default)]
+struct CaretMap {
+    lines: Vec<CaretLine>,
+    carets: Vec<Caret>,
+}
+
+#[derive(Clone, Debug)]
+struct CaretLine {
+    top: f32,
+    height: f32,
+    carets: Range<usize>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Caret {
+    offset: usize,
+    x: f32,
+}
+
 #[derive(Clone, Debug)]
 struct RawSegment {
     face: Option<Id<FontFace>>,
@@ -92,11 +147,13 @@ struct RawSegment {
     tws_glyph_range: Range<usize>,
     props: TextSegmentProperties,
     linebreak: Option<BreakOpportunity>,
+    level: Level,
     flow_break: bool,
     width: f32,
     tws_width: f32,
     height: f32,
     ascender: f32,
+    metrics: LineMetrics,
 }
 
 impl RawSegment {
@@ -108,11 +165,13 @@ impl RawSegment {
             tws_glyph_range: 0..0,
             props,
             linebreak: None,
+            level: Level::ltr(),
             flow_break: false,
             width: 0.0,
             tws_width: 0.0,
             height: 0.0,
             ascender: 0.0,
+            metrics: LineMetrics::default(),
         }
     }
 }
@@ -136,6 +195,7 @@ impl TextLayouter {
         self.append_text(text);
 
         find_linebreaks(&self.text, &mut self.segments, &mut self.scratch_segments);
+        find_bidi_runs(&self.text, &mut self.segments, &mut self.scratch_segments);
 
         shape_segments(
             assets,
@@ -149,9 +209,11 @@ impl TextLayouter {
         measure_segments(assets, &text.props, &mut self.segments, &self.glyphs);
 
         ShapedText {
+            text: self.text.clone(),
             props: text.props,
             segments: self.segments.clone(),
             glyphs: self.glyphs.clone(),
+            visible_glyphs: None,
         }
     }
 
@@ -161,6 +223,10 @@ impl TextLayouter {
         measure_lines(&self.lines)
     }
 
+    pub fn height_for_width(&mut self, text: &mut ShapedText, width: f32) -> f32 {
+        self.measure(text, Vec2::new(width, f32::INFINITY)).y
+    }
+
     pub fn layout(
         &mut self,
         text: &mut ShapedText,
@@ -169,15 +235,91 @@ impl TextLayouter {
         let size = self.measure(text, max_size);
 
         place_glyphs(
+            &text.text,
             &text.props,
             &self.lines,
             &text.segments,
             &text.glyphs,
             &mut self.output_glyphs,
+            &mut self.output_decorations,
+            &mut self.carets,
             size,
             max_size,
+            text.visible_glyphs,
         );
 
+        if let Some(count) = text.visible_glyphs {
+            self.output_glyphs.truncate(count);
+        }
+
+        (size, &self.output_glyphs)
+    }
+
+    // decorations of the text passed to the last `layout` call
+    pub fn decorations(&self) -> &[TextDecoration] {
+        &self.output_decorations
+    }
+
+    // byte offset of the caret position closest to `pos`. Positions above or
+    // below the text snap to the first or the last line
+    pub fn hit_test(&self, text: &ShapedText, pos: Vec2<f32>) -> usize {
+        let lines = &self.carets.lines;
+        let line = match lines.iter().find(|line| pos.y < line.top + line.height) {
+            Some(v) => v,
+            None => match lines.last() {
+                Some(v) => v,
+                None => return 0,
+            },
+        };
+
+        let carets = &self.carets.carets[line.carets.clone()];
+        let closest = carets.iter().min_by(|a, b| {
+            let a = (a.x - pos.x).abs();
+            let b = (b.x - pos.x).abs();
+            a.total_cmp(&b)
+        });
+
+        closest.map_or(0, |v| v.offset.min(text.text.len()))
+    }
+
+    // zero-width rect spanning the line with the caret at `offset`, offsets
+    // inside a cluster are moved to its start
+    pub fn caret_rect(&self, text: &ShapedText, offset: usize) -> Rect<f32> {
+        let offset = offset.min(text.text.len());
+        let mut best: Option<(&CaretLine, Caret)> = None;
+
+        for line in &self.carets.lines {
+            for &caret in &self.carets.carets[line.carets.clone()] {
+                if caret.offset > offset {
+                    continue;
+                }
+
+                if best.map_or(true, |(_, v)| caret.offset > v.offset) {
+                    best = Some((line, caret));
+                }
+            }
+        }
+
+        match best {
+            Some((line, caret)) => {
+                Rect::new(Vec2::new(caret.x, line.top), Vec2::new(0.0, line.height))
+            }
+            None => Rect::new(Vec2::zero(), Vec2::zero()),
+        }
+    }
+
+    pub fn layout_with_colors(
+        &mut self,
+        text: &mut ShapedText,
+        max_size: Vec2<f32>,
+        mut color: impl FnMut(usize, &DrawGlyph) -> Color,
+    ) -> (Vec2<f32>, &[DrawGlyph]) {
+        let (size, _) = self.layout(text, max_size);
+
+        for (i, glyph) in self.output_glyphs.iter_mut().enumerate() {
+            glyph.color = color(i, glyph);
+        }
+
         (size, &self.output_glyphs)
     }
 
@@ -244,6 +386,54 @@ fn find_linebreaks(
     std::mem::swap(segments, scratch_segments);
 }
 
+// splits segments where the embedding level changes, so that every segment
+// is shaped in a single direction
+fn find_bidi_runs(
+    text: &str,
+    segments: &mut Vec<RawSegment>,
+    scratch_segments: &mut Vec<RawSegment>,
+) {
+    if segments.is_empty() {
+        return;
+    }
+
+    let bidi = BidiInfo::new(text, None);
+    if !bidi.has_rtl() {
+        return;
+    }
+
+    scratch_segments.clear();
+
+    for segment in segments.drain(..) {
+        let mut start = segment.range.start;
+        let mut level = bidi.levels[start];
+
+        for (i, _) in text[segment.range.clone()].char_indices() {
+            let i = segment.range.start + i;
+            if bidi.levels[i] == level {
+                continue;
+            }
+
+            scratch_segments.push(RawSegment {
+                range: start..i,
+                level,
+                ..RawSegment::new(segment.props.clone())
+            });
+
+            start = i;
+            level = bidi.levels[i];
+        }
+
+        scratch_segments.push(RawSegment {
+            range: start..segment.range.end,
+            level,
+            ..segment
+        });
+    }
+
+    std::mem::swap(segments, scratch_segments);
+}
+
 fn shape_segments(
     assets: &Assets,
     fonts: &FontDb,
@@ -274,30 +464,29 @@ fn shape_segments(
             let text_no_ws = text.trim_end();
             let text_ws = &text[text_no_ws.len()..];
 
+            let rtl = segment.level.is_rtl();
+
             let start_idx = glyphs.len();
-            face.shape(cache, size, text_no_ws, glyphs);
+            face.shape(cache, size, text_no_ws, rtl, glyphs);
             segment.glyph_range = start_idx..glyphs.len();
 
             let start_idx = glyphs.len();
-            face.shape(cache, size, text_ws, glyphs);
+            face.shape(cache, size, text_ws, rtl, glyphs);
             segment.tws_glyph_range = start_idx..glyphs.len();
 
-            let mut missing_idx = usize::MAX;
+            // RTL glyphs are in visual order, so look for the logically
+            // first missing glyph instead of the first one in the buffer
+            let missing_idx = glyphs[segment.glyph_range.clone()]
+                .iter()
+                .filter(|glyph| glyph.glyph == GlyphId(0))
+                .map(|glyph| glyph.cluster as usize)
+                .min();
 
-            for glyph in &glyphs[segment.glyph_range.clone()] {
-                if glyph.glyph == GlyphId(0) {
-                    if glyph.cluster == 0 && text_ws.is_empty() {
-                        continue 'outer;
-                    }
-
-                    missing_idx = glyph.cluster as usize;
-                    break;
-                }
-            }
-
-            if missing_idx == usize::MAX {
-                break;
-            }
+            let missing_idx = match missing_idx {
+                Some(0) if text_ws.is_empty() => continue 'outer,
+                Some(v) => v,
+                None => break,
+            };
 
             let split_idx = segment.range.start + missing_idx;
 
@@ -337,17 +526,28 @@ fn measure_segments(
         };
 
         let metrics = face.line_metrics(segment.props.size);
+        segment.metrics = metrics;
 
         segment.height = props.line_height * segment.props.size;
         segment.ascender =
             metrics.ascender + (segment.height - metrics.ascender + metrics.descender) * 0.5;
 
+        let letter_spacing = segment.props.letter_spacing;
+        let word_spacing = segment.props.word_spacing;
+
         for glyph in &glyphs[segment.glyph_range.clone()] {
-            segment.width += glyph.advance.x;
+            segment.width += glyph.advance.x + letter_spacing;
         }
 
         for glyph in &glyphs[segment.tws_glyph_range.clone()] {
-            segment.tws_width += glyph.advance.x;
+            segment.tws_width += glyph.advance.x + letter_spacing + word_spacing;
+        }
+
+        // spacing after the last glyph is trailing, so that it's dropped at
+        // the end of a line
+        if !segment.glyph_range.is_empty() {
+            segment.width -= letter_spacing;
+            segment.tws_width += letter_spacing;
         }
     }
 }
@@ -443,15 +643,24 @@ fn measure_lines(lines: &[Line]) -> Vec2<f32> {
 }
 
 fn place_glyphs(
+    text: &str,
     props: &TextProperties,
     lines: &[Line],
     segments: &[RawSegment],
     glyphs: &[ShapedGlyph],
     output: &mut Vec<DrawGlyph>,
+    decorations: &mut Vec<TextDecoration>,
+    carets: &mut CaretMap,
     size: Vec2<f32>,
     max_size: Vec2<f32>,
+    visible_glyphs: Option<usize>,
 ) {
     output.clear();
+    decorations.clear();
+    carets.lines.clear();
+    carets.carets.clear();
+
+    let mut order = Vec::new();
 
     let mut y = match props.v_align {
         TextVAlign::Start => 0.0,
@@ -500,31 +709,212 @@ fn place_glyphs(
         let mut cursor = Vec2::new(x, y);
         cursor.y += line.ascender;
 
-        for segment in &segments[line.range.clone()] {
+        let first_caret = carets.carets.len();
+        let mut line_end = None;
+
+        let line_segments = &segments[line.range.clone()];
+        visual_order(line_segments, &mut order);
+
+        for &i in &order {
+            let segment = &line_segments[i];
             let font = match segment.face {
                 Some(v) => v,
                 None => continue,
             };
 
-            for glyph in &glyphs[segment.glyph_range.clone()] {
-                output.push(DrawGlyph {
-                    font,
-                    glyph: glyph.glyph,
-                    size: segment.props.size,
-                    pos: cursor + glyph.offset,
+            // the trailing whitespace trimmed at the line end belongs to the
+            // logically last segment, wherever it ends up visually. In RTL
+            // paragraphs that is the leftmost segment
+            let is_line_end = i + 1 == line_segments.len();
+            let is_text_end = is_line_end && line.range.end == segments.len();
+
+            // decorations continue over spaces between words, but not past
+            // the end of the line
+            let mut width = segment.width;
+            if !is_line_end {
+                width += segment.tws_width;
+            }
+
+            let first_decoration = decorations.len();
+            let first_glyph = output.len();
+
+            let metrics = &segment.metrics;
+            let mut decorate = |position: f32, thickness: f32| {
+                let min = Vec2::new(cursor.x, cursor.y - position);
+                decorations.push(TextDecoration {
+                    rect: Rect::new(min, Vec2::new(width, thickness)),
                     color: segment.props.color,
                 });
+            };
 
-                cursor.x += glyph.advance.x;
+            if segment.props.underline {
+                decorate(metrics.underline_position, metrics.underline_thickness);
             }
 
-            cursor.x += segment.tws_width;
+            if segment.props.strikethrough {
+                decorate(metrics.strikeout_position, metrics.strikeout_thickness);
+            }
+
+            let letter_spacing = segment.props.letter_spacing;
+            let word_spacing = segment.props.word_spacing;
+            let ws_start = segment.range.start + text[segment.range.clone()].trim_end().len();
+
+            if segment.level.is_rtl() {
+                // glyphs come in visual order, and the whitespace after an RTL
+                // word is on its left. Carets are at the right edges of glyphs
+                if !is_line_end {
+                    let mut ws_x = cursor.x;
+                    for glyph in &glyphs[segment.tws_glyph_range.clone()] {
+                        ws_x += glyph.advance.x + letter_spacing + word_spacing;
+                        carets.carets.push(Caret {
+                            offset: ws_start + glyph.cluster as usize,
+                            x: ws_x,
+                        });
+                    }
+
+                    cursor.x += segment.tws_width;
+                }
+
+                let start_x = cursor.x;
+
+                for glyph in &glyphs[segment.glyph_range.clone()] {
+                    carets.carets.push(Caret {
+                        offset: segment.range.start + glyph.cluster as usize,
+                        x: cursor.x + glyph.advance.x,
+                    });
+
+                    output.push(DrawGlyph {
+                        font,
+                        glyph: glyph.glyph,
+                        size: segment.props.size,
+                        pos: cursor + glyph.offset,
+                        color: segment.props.color,
+                    });
+
+                    cursor.x += glyph.advance.x + letter_spacing;
+                }
+
+                cursor.x = start_x + segment.width;
+
+                if is_line_end {
+                    let offset = match is_text_end {
+                        true => segment.range.end,
+                        false => ws_start,
+                    };
+
+                    line_end = Some(Caret { offset, x: start_x });
+                }
+            } else {
+                let start_x = cursor.x;
+
+                for glyph in &glyphs[segment.glyph_range.clone()] {
+                    carets.carets.push(Caret {
+                        offset: segment.range.start + glyph.cluster as usize,
+                        x: cursor.x,
+                    });
+
+                    output.push(DrawGlyph {
+                        font,
+                        glyph: glyph.glyph,
+                        size: segment.props.size,
+                        pos: cursor + glyph.offset,
+                        color: segment.props.color,
+                    });
+
+                    cursor.x += glyph.advance.x + letter_spacing;
+                }
+
+                cursor.x = start_x + segment.width;
+
+                let mut ws_x = cursor.x;
+                if !segment.glyph_range.is_empty() {
+                    ws_x += letter_spacing;
+                }
+
+                if is_line_end {
+                    line_end = Some(Caret {
+                        offset: ws_start,
+                        x: ws_x,
+                    });
+                }
+
+                for glyph in &glyphs[segment.tws_glyph_range.clone()] {
+                    carets.carets.push(Caret {
+                        offset: ws_start + glyph.cluster as usize,
+                        x: ws_x,
+                    });
+
+                    ws_x += glyph.advance.x + letter_spacing + word_spacing;
+                }
+
+                cursor.x += segment.tws_width;
+
+                // the last line can put the caret after the whole text, other
+                // lines only before the space or the newline they were broken at
+                if is_text_end {
+                    line_end = Some(Caret {
+                        offset: segment.range.end,
+                        x: cursor.x,
+                    });
+                }
+            }
+
+            // decorations end at the first glyph that isn't revealed yet
+            if let Some(count) = visible_glyphs.filter(|&v| v < output.len()) {
+                if count <= first_glyph {
+                    decorations.truncate(first_decoration);
+                } else {
+                    for decoration in &mut decorations[first_decoration..] {
+                        decoration.rect.max.x = output[count].pos.x;
+                    }
+                }
+            }
 
             if segment.linebreak.is_some() {
                 cursor.x += spacing;
             }
         }
 
+        let line_carets = &carets.carets[first_caret..];
+        if let Some(end) = line_end.filter(|v| line_carets.iter().all(|c| c.offset != v.offset)) {
+            carets.carets.push(end);
+        }
+
+        carets.lines.push(CaretLine {
+            top: y,
+            height: line.height,
+            carets: first_caret..carets.carets.len(),
+        });
+
         y += line.height;
     }
 }
+
+// indices of `segments` in the order they appear on screen, found by
+// reversing runs of the same or higher embedding level from the highest
+// level down to the lowest odd one
+fn visual_order(segments: &[RawSegment], order: &mut Vec<usize>) {
+    order.clear();
+    order.extend(0..segments.len());
+
+    let levels = || segments.iter().map(|v| v.level.number());
+    let max_level = levels().max().unwrap_or(0);
+    let min_odd_level = match levels().filter(|v| v % 2 == 1).min() {
+        Some(v) => v,
+        None => return,
+    };
+
+    for level in (min_odd_level..=max_level).rev() {
+        let mut i = 0;
+
+        while i < order.len() {
+            let start = i;
+            while i < order.len() && segments[order[i]].level.number() >= level {
+                i += 1;
+            }
+
+            order[start..i].reverse();
+            i += 1;
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use gg_assets::Assets;
+use gg_math::Vec2;
+use gg_util::parking_lot::Mutex;
+
+use crate::{Backend, Canvas, CommandList, PresentError};
+
+// frames are encoded on the calling thread, which has the assets at hand, and
+// submitted on a render thread, so that waiting for vsync doesn't block the
+// caller. At most one frame is in flight
+pub struct ThreadedBackend<B> {
+    backend: Arc<Mutex<B>>,
+    submitted_lists: Vec<CommandList>,
+    recycled_lists: Vec<CommandList>,
+    frame_sender: Option<SyncSender<()>>,
+    recycled_receiver: Mutex<Receiver<Vec<CommandList>>>,
+    in_flight: bool,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl<B: Backend> ThreadedBackend<B> {
+    pub fn new(backend: B) -> ThreadedBackend<B> {
+        let backend = Arc::new(Mutex::new(backend));
+        let (frame_sender, frame_receiver) = mpsc::sync_channel(1);
+        let (recycled_sender, recycled_receiver) = mpsc::channel();
+
+        let worker = std::thread::Builder::new()
+            .name("render".into())
+            .spawn({
+                let backend = backend.clone();
+                move || render_thread(backend, frame_receiver, recycled_sender)
+            })
+            .expect("failed to spawn render thread");
+
+        ThreadedBackend {
+            backend,
+            submitted_lists: Vec::new(),
+            recycled_lists: Vec::new(),
+            frame_sender: Some(frame_sender),
+            recycled_receiver: Mutex::new(recycled_receiver),
+            in_flight: false,
+            worker: Some(worker),
+        }
+    }
+
+    fn wait_frame(&mut self) {
+        if !self.in_flight {
+            return;
+        }
+
+        self.in_flight = false;
+
+        if let Ok(lists) = self.recycled_receiver.get_mut().recv() {
+            self.recycled_lists.extend(lists);
+        }
+    }
+}
+
+impl<B: Backend> Backend for ThreadedBackend<B> {
+    fn get_main_canvas(&self) -> Canvas {
+        self.backend.lock().get_main_canvas()
+    }
+
+    fn create_canvas(&mut self, size: Vec2<u32>) -> Canvas {
+        self.backend.lock().create_canvas(size)
+    }
+
+    fn submit(&mut self, commands: CommandList) {
+        self.submitted_lists.push(commands);
+    }
+
+    fn resize(&mut self, new_resolution: Vec2<u32>) {
+        self.backend.lock().resize(new_resolution);
+    }
+
+    fn encode_frame(&mut self, assets: &mut Assets) -> Result<(), PresentError> {
+        self.wait_frame();
+
+        let mut backend = self.backend.lock();
+        for list in self.submitted_lists.drain(..) {
+            backend.submit(list);
+        }
+
+        let res = backend.encode_frame(assets);
+
+        // the rest are recycled by the render thread after submitting
+        self.recycled_lists
+            .extend(std::iter::from_fn(|| backend.recycle_list()));
+
+        res
+    }
+
+    fn submit_frame(&mut self) {
+        if let Some(sender) = &self.frame_sender {
+            self.in_flight = sender.send(()).is_ok();
+        }
+    }
+
+    fn recycle_list(&mut self) -> Option<CommandList> {
+        let receiver = self.recycled_receiver.get_mut();
+        if self.in_flight {
+            if let Ok(lists) = receiver.try_recv() {
+                self.in_flight = false;
+                self.recycled_lists.extend(lists);
+            }
+        }
+
+        self.recycled_lists.pop()
+    }
+}
+
+impl<B> Drop for ThreadedBackend<B> {
+    fn drop(&mut self) {
+        self.frame_sender = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn render_thread<B: Backend>(
+    backend: Arc<Mutex<B>>,
+    frame_receiver: Receiver<()>,
+    recycled_sender: mpsc::Sender<Vec<CommandList>>,
+) {
+    for () in frame_receiver {
+        let mut backend = backend.lock();
+        backend.submit_frame();
+
+        let recycled = std::iter::from_fn(|| backend.recycle_list()).collect();
+        if recycled_sender.send(recycled).is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use gg_graphics::{Canvas, ClearFill, Color, Command, GraphicsEncoder, RawCanvas};
+use gg_math::{Rect, Vec2};
+
+#[derive(Debug)]
+struct MockCanvas;
+
+impl RawCanvas for MockCanvas {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[test]
+fn clear_commands() {
+    let canvas = Canvas::from_raw(Arc::new(MockCanvas));
+    let mut encoder = GraphicsEncoder::new(&canvas);
+
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let rect = Rect::new(Vec2::splat(10.0), Vec2::splat(20.0));
+
+    encoder.clear(Color::BLACK);
+    encoder.clear_gradient(Color::WHITE, Color::BLACK);
+    encoder.clear_rect(rect, red);
+
+    let list = encoder.finish().list;
+    assert_eq!(list.len(), 3);
+    assert!(matches!(list[0], Command::Clear(fill) if fill == Color::BLACK.into()));
+    assert!(matches!(list[1], Command::Clear(fill) if fill.is_gradient()));
+    assert!(matches!(list[2], Command::ClearRect(r, c) if r == rect && c == red));
+}
+
+#[test]
+fn clear_fill_gradient() {
+    let solid = ClearFill::from(Color::BLACK);
+    assert!(!solid.is_gradient());
+    assert_eq!(solid.top(), solid.bottom());
+
+    let gradient = ClearFill::VerticalGradient {
+        top: Color::WHITE,
+        bottom: Color::BLACK,
+    };
+    assert!(gradient.is_gradient());
+    assert_eq!(gradient.top(), Color::WHITE);
+    assert_eq!(gradient.bottom(), Color::BLACK);
+}
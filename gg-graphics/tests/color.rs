@@ -0,0 +1,65 @@
+use gg_graphics::Color;
+
+fn assert_close(a: Color, b: Color) {
+    let close = |x: f32, y: f32| (x - y).abs() < 1e-4;
+    assert!(
+        close(a.r, b.r) && close(a.g, b.g) && close(a.b, b.b) && close(a.a, b.a),
+        "{:?} != {:?}",
+        a,
+        b
+    );
+}
+
+#[test]
+fn from_hex() {
+    assert_eq!(
+        Color::from_hex("#ff0000"),
+        Some(Color::new(1.0, 0.0, 0.0, 1.0))
+    );
+    assert_eq!(
+        Color::from_hex("00FF00"),
+        Some(Color::new(0.0, 1.0, 0.0, 1.0))
+    );
+    assert_eq!(
+        Color::from_hex("#0000ff00"),
+        Some(Color::new(0.0, 0.0, 1.0, 0.0))
+    );
+    assert_close(
+        Color::from_hex("#336699cc").unwrap(),
+        Color::new(0.2, 0.4, 0.6, 0.8),
+    );
+
+    for invalid in [
+        "", "#", "#fff", "#12345", "#1234567", "#gg0000", "#+1+2+3", "#ффф",
+    ] {
+        assert_eq!(Color::from_hex(invalid), None, "{}", invalid);
+    }
+}
+
+#[test]
+fn hsl_round_trip() {
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    assert_close(Color::hsl(0.0, 1.0, 0.5), red);
+    assert_close(Color::hsl(360.0, 1.0, 0.5), red);
+    assert_eq!(red.to_hsl(), (0.0, 1.0, 0.5));
+
+    assert_close(Color::hsl(120.0, 1.0, 0.5), Color::new(0.0, 1.0, 0.0, 1.0));
+    assert_close(Color::hsl(240.0, 1.0, 0.25), Color::new(0.0, 0.0, 0.5, 1.0));
+    assert_close(Color::hsl(90.0, 0.0, 0.3), Color::new(0.3, 0.3, 0.3, 1.0));
+
+    for color in [
+        Color::new(0.2, 0.4, 0.6, 1.0),
+        Color::new(0.9, 0.1, 0.5, 1.0),
+        Color::new(0.5, 0.7, 0.1, 1.0),
+    ] {
+        let (h, s, l) = color.to_hsl();
+        assert_close(Color::hsl(h, s, l), color);
+    }
+}
+
+#[test]
+fn with_alpha() {
+    let color = Color::hsla(0.0, 1.0, 0.5, 0.25);
+    assert_eq!(color.a, 0.25);
+    assert_eq!(color.with_alpha(0.75), Color::new(1.0, 0.0, 0.0, 0.75));
+}
@@ -0,0 +1,58 @@
+use std::any::Any;
+use std::path::Path;
+use std::sync::Arc;
+
+use gg_assets::{Assets, DirSource};
+use gg_graphics::{Canvas, Command, Cursor, FillImage, GraphicsEncoder, Image, RawCanvas};
+use gg_math::{Rect, Vec2};
+
+#[derive(Debug)]
+struct MockCanvas;
+
+impl RawCanvas for MockCanvas {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[test]
+fn cursor_image_from_asset() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+    let source = DirSource::new(&path.canonicalize().unwrap()).unwrap();
+    let mut assets = Assets::new(source);
+
+    let image = assets.insert(Image {
+        size: Vec2::new(16, 24),
+        data: Some(vec![255; 16 * 24 * 4]),
+    });
+
+    let mut cursor = Cursor::new();
+    assert!(cursor.is_os_cursor_visible());
+
+    cursor.set_image(Some(image.id()), Vec2::new(4, 2));
+    assert_eq!(cursor.image(), Some(image.id()));
+    assert_eq!(cursor.hotspot(), Vec2::new(4, 2));
+    assert!(!cursor.is_os_cursor_visible());
+    assert_eq!(cursor.rect(&assets), None);
+
+    cursor.set_position(Some(Vec2::new(100.0, 50.0)));
+    let rect = Rect::new(Vec2::new(96.0, 48.0), Vec2::new(16.0, 24.0));
+    assert_eq!(cursor.rect(&assets), Some(rect));
+
+    let canvas = Canvas::from_raw(Arc::new(MockCanvas));
+    let mut list = GraphicsEncoder::new(&canvas).finish();
+    cursor.encode(&assets, &mut list);
+
+    let drawn = list.list.iter().find_map(|cmd| match cmd {
+        Command::DrawRect(cmd) => Some(cmd),
+        _ => None,
+    });
+    let drawn = drawn.unwrap();
+    assert_eq!(drawn.rect, rect);
+    assert!(matches!(drawn.fill.image, Some(FillImage::SingleImage(id)) if id == image.id()));
+
+    cursor.set_image(None, Vec2::new(4, 2));
+    assert!(cursor.is_os_cursor_visible());
+    assert_eq!(cursor.hotspot(), Vec2::zero());
+    assert_eq!(cursor.rect(&assets), None);
+}
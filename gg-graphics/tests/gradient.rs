@@ -0,0 +1,69 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use gg_graphics::{Canvas, Color, Command, FillGradient, GradientStop, GraphicsEncoder, RawCanvas};
+use gg_math::{Rect, Vec2};
+
+#[derive(Debug)]
+struct MockCanvas;
+
+impl RawCanvas for MockCanvas {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn black_to_white() -> Vec<GradientStop> {
+    vec![
+        GradientStop::new(0.0, Color::BLACK),
+        GradientStop::new(1.0, Color::WHITE),
+    ]
+}
+
+#[test]
+fn encode_gradient_rect() {
+    let canvas = Canvas::from_raw(Arc::new(MockCanvas));
+    let mut encoder = GraphicsEncoder::new(&canvas);
+
+    let rect = Rect::new(Vec2::splat(10.0), Vec2::splat(20.0));
+    let gradient = FillGradient::linear(Vec2::zero(), Vec2::new(1.0, 0.0), black_to_white());
+
+    encoder.rect(rect).fill_gradient(gradient.clone());
+    encoder.rect(rect);
+
+    let list = encoder.finish().list;
+    assert_eq!(list.len(), 2);
+    assert!(matches!(
+        list[0],
+        Command::DrawRect(ref cmd) if cmd.rect == rect && cmd.fill.gradient == Some(gradient)
+    ));
+    assert!(matches!(list[1], Command::DrawRect(ref cmd) if cmd.fill.gradient.is_none()));
+}
+
+#[test]
+fn linear_gradient_colors() {
+    let gradient = FillGradient::linear(Vec2::zero(), Vec2::new(1.0, 0.0), black_to_white());
+
+    assert_eq!(gradient.color_at(Vec2::new(-1.0, 0.0)), Color::BLACK);
+    assert_eq!(
+        gradient.color_at(Vec2::new(0.5, 0.7)),
+        Color::new(0.5, 0.5, 0.5, 1.0)
+    );
+    assert_eq!(gradient.color_at(Vec2::new(2.0, 0.0)), Color::WHITE);
+}
+
+#[test]
+fn radial_gradient_colors() {
+    let red = Color::new(1.0, 0.0, 0.0, 1.0);
+    let stops = vec![
+        GradientStop::new(0.0, Color::BLACK),
+        GradientStop::new(0.5, red),
+        GradientStop::new(1.0, Color::WHITE),
+    ];
+    let gradient = FillGradient::radial(Vec2::splat(0.5), 0.5, stops);
+
+    assert_eq!(gradient.color_at(Vec2::splat(0.5)), Color::BLACK);
+    assert_eq!(gradient.color_at(Vec2::new(0.5, 0.75)), red);
+    assert_eq!(gradient.color_at(Vec2::new(1.0, 0.5)), Color::WHITE);
+    assert_eq!(gradient.color_at(Vec2::zero()), Color::WHITE);
+}
@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use gg_assets::{Assets, DirSource, Handle};
+use gg_graphics::{Image, NinePatchImage};
+use gg_math::{Rect, SideOffsets, Vec2};
+
+fn image(assets: &mut Assets, width: u32, height: u32) -> Handle<Image> {
+    assets.insert(Image {
+        size: Vec2::new(width, height),
+        data: Some(vec![255; (width * height * 4) as usize]),
+    })
+}
+
+#[test]
+fn content_insets() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+    let source = DirSource::new(&path.canonicalize().unwrap()).unwrap();
+    let mut assets = Assets::new(source);
+
+    let corner = image(&mut assets, 8, 8);
+    let horiz = image(&mut assets, 1, 8);
+    let vert = image(&mut assets, 8, 1);
+    let center = image(&mut assets, 1, 1);
+
+    let nine_patch = NinePatchImage {
+        center,
+        top_left: corner.clone(),
+        top: horiz.clone(),
+        top_right: corner.clone(),
+        right: vert.clone(),
+        bottom_right: corner.clone(),
+        bottom: horiz,
+        bottom_left: corner,
+        left: vert,
+    };
+
+    let insets = nine_patch.content_insets(&assets);
+    assert_eq!(insets, SideOffsets::new_equal(8.0));
+
+    let rect = Rect::new(Vec2::zero(), Vec2::new(100.0, 40.0));
+    let content = rect.shrink(&insets);
+    assert_eq!(content, Rect::new(Vec2::splat(8.0), Vec2::new(84.0, 24.0)));
+
+    let border = Rect::new(Vec2::zero(), Vec2::splat(8.0));
+    assert!(!content.contains(border.center()));
+}
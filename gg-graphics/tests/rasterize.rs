@@ -0,0 +1,80 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use gg_graphics::{FontFace, RasterizationCache, RasterizationQuality, SubpixelOffset};
+use gg_math::Vec2;
+
+fn load_font() -> FontFace {
+    load_font_file("OpenSans-Regular.ttf")
+}
+
+fn load_font_file(name: &str) -> FontFace {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../assets/fonts")
+        .join(name);
+    let data = std::fs::read(path).unwrap();
+    FontFace::new(Arc::from(data), 0).unwrap()
+}
+
+#[test]
+fn subpixel_coverage() {
+    let font = load_font();
+    let mut cache = RasterizationCache::default();
+    let glyph = font.lookup_glyph('l');
+    let offset = SubpixelOffset::new(Vec2::zero());
+
+    let gray = font.rasterize(&mut cache, glyph, 20.0, offset).unwrap();
+    let lcd = font
+        .rasterize_subpixel(&mut cache, glyph, 20.0, offset)
+        .unwrap();
+
+    assert_eq!(gray.data.len(), (gray.size.x * gray.size.y) as usize);
+    assert_eq!(lcd.data.len(), (lcd.size.x * lcd.size.y * 4) as usize);
+    assert_eq!(lcd.size, gray.size + Vec2::new(2, 0));
+
+    let pixels = lcd.data.chunks_exact(4);
+    assert!(pixels.clone().any(|px| px[0] != px[1] || px[1] != px[2]));
+    assert!(pixels
+        .clone()
+        .all(|px| px[3] == px[0].max(px[1]).max(px[2])));
+
+    for (gray_row, lcd_row) in gray
+        .data
+        .chunks_exact(gray.size.x as usize)
+        .zip(lcd.data.chunks_exact(lcd.size.x as usize * 4))
+    {
+        let gray_sum = gray_row.iter().map(|&v| v as u32).sum::<u32>();
+        let lcd_sum = lcd_row.chunks_exact(4).map(|px| px[1] as u32).sum::<u32>();
+        assert!(gray_sum.abs_diff(lcd_sum) <= 255);
+    }
+}
+
+#[test]
+fn image_quality() {
+    let font = load_font_file("NotoColorEmoji.ttf");
+    let glyph = font.lookup_glyph('😀');
+    assert!(font.has_image(glyph));
+
+    let nearest = font
+        .get_image(glyph, 24, RasterizationQuality::Nearest)
+        .unwrap();
+    let lanczos = font
+        .get_image(glyph, 24, RasterizationQuality::Lanczos3)
+        .unwrap();
+
+    assert_eq!(nearest.size, lanczos.size);
+    assert_eq!(nearest.bounds, lanczos.bounds);
+    assert_ne!(nearest.data, lanczos.data);
+
+    let keys = [
+        RasterizationQuality::Nearest,
+        RasterizationQuality::Lanczos3,
+        RasterizationQuality::Nearest,
+    ]
+    .into_iter()
+    .map(|quality| (glyph, 24, quality))
+    .collect::<HashSet<_>>();
+
+    assert_eq!(keys.len(), 2);
+}
@@ -0,0 +1,57 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use gg_graphics::{Canvas, Command, CornerRadii, GraphicsEncoder, RawCanvas};
+use gg_math::{Rect, Vec2};
+
+#[derive(Debug)]
+struct MockCanvas;
+
+impl RawCanvas for MockCanvas {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn encode(f: impl FnOnce(&mut GraphicsEncoder)) -> Vec<Command> {
+    let canvas = Canvas::from_raw(Arc::new(MockCanvas));
+    let mut encoder = GraphicsEncoder::new(&canvas);
+    f(&mut encoder);
+    encoder.finish().list
+}
+
+fn radii(cmd: &Command) -> CornerRadii {
+    match cmd {
+        Command::DrawRect(cmd) => cmd.corner_radii,
+        _ => panic!("expected a rect"),
+    }
+}
+
+#[test]
+fn rounded_rect_command() {
+    let rect = Rect::new(Vec2::zero(), Vec2::new(40.0, 20.0));
+
+    let list = encode(|encoder| {
+        encoder.rect(rect);
+        encoder.rounded_rect(rect, 4.0);
+        encoder.rounded_rect(rect, [1.0, 2.0, 3.0, 4.0]);
+    });
+
+    assert_eq!(list.len(), 3);
+    assert!(radii(&list[0]).is_zero());
+    assert_eq!(radii(&list[1]), CornerRadii::all(4.0));
+    assert_eq!(radii(&list[2]), CornerRadii::new(1.0, 2.0, 3.0, 4.0));
+}
+
+#[test]
+fn rounded_rect_clamps_radii() {
+    let rect = Rect::new(Vec2::zero(), Vec2::new(40.0, 20.0));
+
+    let list = encode(|encoder| {
+        encoder.rounded_rect(rect, 100.0);
+        encoder.rounded_rect(rect, [-5.0, 5.0, 15.0, 10.0]);
+    });
+
+    assert_eq!(radii(&list[0]), CornerRadii::all(10.0));
+    assert_eq!(radii(&list[1]), CornerRadii::new(0.0, 5.0, 10.0, 10.0));
+}
@@ -0,0 +1,95 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use gg_graphics::{
+    Canvas, Color, Command, GraphicsEncoder, RawCanvas, StrokeAlignment, StrokeRect,
+};
+use gg_math::{Rect, Vec2};
+
+#[derive(Debug)]
+struct MockCanvas;
+
+impl RawCanvas for MockCanvas {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn stroke(width: f32, alignment: StrokeAlignment) -> StrokeRect {
+    StrokeRect {
+        rect: Rect::new(Vec2::splat(10.0), Vec2::new(40.0, 20.0)),
+        width,
+        color: Color::WHITE,
+        alignment,
+    }
+}
+
+fn rect(x: f32, y: f32, w: f32, h: f32) -> Rect<f32> {
+    Rect::new(Vec2::new(x, y), Vec2::new(w, h))
+}
+
+#[test]
+fn encode_stroke_rect() {
+    let canvas = Canvas::from_raw(Arc::new(MockCanvas));
+    let mut encoder = GraphicsEncoder::new(&canvas);
+
+    let r = rect(10.0, 10.0, 40.0, 20.0);
+    encoder.stroke_rect(r, 2.0, Color::WHITE);
+    encoder
+        .stroke_rect(r, 2.0, Color::BLACK)
+        .alignment(StrokeAlignment::Outside);
+
+    let list = encoder.finish().list;
+    assert_eq!(list.len(), 2);
+    assert!(matches!(
+        list[0],
+        Command::StrokeRect(cmd) if cmd == StrokeRect {
+            rect: r,
+            width: 2.0,
+            color: Color::WHITE,
+            alignment: StrokeAlignment::Inside,
+        }
+    ));
+    assert!(matches!(
+        list[1],
+        Command::StrokeRect(cmd) if cmd.alignment == StrokeAlignment::Outside
+    ));
+}
+
+#[test]
+fn stroke_edges() {
+    assert_eq!(
+        stroke(2.0, StrokeAlignment::Inside).edges(),
+        [
+            rect(10.0, 10.0, 40.0, 2.0),
+            rect(10.0, 28.0, 40.0, 2.0),
+            rect(10.0, 12.0, 2.0, 16.0),
+            rect(48.0, 12.0, 2.0, 16.0),
+        ]
+    );
+
+    assert_eq!(
+        stroke(2.0, StrokeAlignment::Center).edges(),
+        [
+            rect(9.0, 9.0, 42.0, 2.0),
+            rect(9.0, 29.0, 42.0, 2.0),
+            rect(9.0, 11.0, 2.0, 18.0),
+            rect(49.0, 11.0, 2.0, 18.0),
+        ]
+    );
+
+    let outside = stroke(2.0, StrokeAlignment::Outside);
+    assert_eq!(outside.outer_rect(), rect(8.0, 8.0, 44.0, 24.0));
+    assert_eq!(outside.edges()[2], rect(8.0, 10.0, 2.0, 20.0));
+}
+
+#[test]
+fn wide_stroke_fills_rect() {
+    let edges = stroke(15.0, StrokeAlignment::Inside).edges();
+
+    // the rect is 20 high, so the top and bottom edges meet in the middle
+    assert_eq!(edges[0], rect(10.0, 10.0, 40.0, 10.0));
+    assert_eq!(edges[1], rect(10.0, 20.0, 40.0, 10.0));
+    assert_eq!(edges[2].height(), 0.0);
+    assert_eq!(edges[3].height(), 0.0);
+}
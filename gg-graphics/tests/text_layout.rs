@@ -0,0 +1,328 @@
+use std::borrow::Cow;
+use std::path::Path;
+
+use gg_assets::{Assets, MemorySource};
+use gg_graphics::{
+    Color, FontDb, FontFace, FontFamily, FontStyle, FontWeight, ShapedText, Text, TextDecoration,
+    TextLayouter, TextProperties, TextSegment, TextSegmentProperties,
+};
+use gg_math::Vec2;
+
+fn shape(layouter: &mut TextLayouter, text: &str) -> ShapedText {
+    shape_with(layouter, text, |_| {})
+}
+
+fn shape_with(
+    layouter: &mut TextLayouter,
+    text: &str,
+    f: impl FnOnce(&mut TextSegmentProperties),
+) -> ShapedText {
+    // the face is inserted directly instead of waiting for the asset loader
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets/fonts/OpenSans-Regular.ttf");
+    let face = FontFace::new(std::fs::read(path).unwrap().into(), 0).unwrap();
+
+    let mut assets = Assets::new(MemorySource::new());
+    let face = assets.insert(face);
+
+    let mut fonts = FontDb::new();
+    fonts.add_face(&face);
+    fonts.update(&assets);
+
+    let mut props = TextSegmentProperties {
+        font_family: FontFamily::new("Open Sans"),
+        weight: FontWeight::Normal,
+        style: FontStyle::Normal,
+        size: 20.0,
+        color: Color::WHITE,
+        underline: false,
+        strikethrough: false,
+        letter_spacing: 0.0,
+        word_spacing: 0.0,
+    };
+
+    f(&mut props);
+
+    let segments = [TextSegment {
+        text: Cow::Borrowed(text),
+        props,
+    }];
+
+    let text = Text {
+        segments: Cow::Borrowed(&segments),
+        props: TextProperties::default(),
+    };
+
+    layouter.shape(&assets, &fonts, &text)
+}
+
+#[test]
+fn height_for_width_monotonic() {
+    let mut layouter = TextLayouter::new();
+    let mut text = shape(
+        &mut layouter,
+        "The quick brown fox jumps over the lazy dog again and again",
+    );
+
+    let mut prev = layouter.height_for_width(&mut text, f32::INFINITY);
+    assert!(prev > 0.0);
+
+    for width in [800.0, 400.0, 200.0, 100.0, 50.0] {
+        let height = layouter.height_for_width(&mut text, width);
+        assert!(height >= prev, "{} < {} at width {}", height, prev, width);
+        prev = height;
+    }
+
+    let single = layouter.height_for_width(&mut text, f32::INFINITY);
+    assert!(prev > single);
+}
+
+#[test]
+fn visible_glyphs() {
+    let mut layouter = TextLayouter::new();
+    let mut text = shape(&mut layouter, "Hello");
+    let max_size = Vec2::splat(f32::INFINITY);
+
+    let (_, glyphs) = layouter.layout(&mut text, max_size);
+    assert_eq!(glyphs.len(), 5);
+
+    text.visible_glyphs(3);
+    let (_, glyphs) = layouter.layout(&mut text, max_size);
+    assert_eq!(glyphs.len(), 3);
+
+    text.show_all_glyphs();
+    let (_, glyphs) = layouter.layout(&mut text, max_size);
+    assert_eq!(glyphs.len(), 5);
+}
+
+#[test]
+fn per_glyph_colors() {
+    let mut layouter = TextLayouter::new();
+    let mut text = shape(&mut layouter, "Hello");
+    let max_size = Vec2::splat(f32::INFINITY);
+
+    let tint = |i: usize| Color::new(i as f32 / 4.0, 0.0, 0.0, 1.0);
+    let (_, glyphs) = layouter.layout_with_colors(&mut text, max_size, |i, _| tint(i));
+
+    for (i, glyph) in glyphs.iter().enumerate() {
+        assert_eq!(glyph.color, tint(i));
+    }
+
+    let (_, glyphs) = layouter.layout(&mut text, max_size);
+    assert!(glyphs.iter().all(|glyph| glyph.color == Color::WHITE));
+}
+
+#[test]
+fn decorations() {
+    let mut layouter = TextLayouter::new();
+    let max_size = Vec2::splat(f32::INFINITY);
+
+    let mut text = shape(&mut layouter, "Hello world");
+    layouter.layout(&mut text, max_size);
+    assert!(layouter.decorations().is_empty());
+
+    let mut text = shape_with(&mut layouter, "Hello world", |props| {
+        props.underline = true;
+        props.strikethrough = true;
+        props.color = Color::new(1.0, 0.0, 0.0, 1.0);
+    });
+
+    let (size, glyphs) = layouter.layout(&mut text, max_size);
+    let baseline = glyphs[0].pos.y;
+    let decorations = layouter.decorations();
+    assert!(!decorations.is_empty());
+
+    let (underlines, strikes): (Vec<&TextDecoration>, Vec<&TextDecoration>) =
+        decorations.iter().partition(|v| v.rect.min.y > baseline);
+
+    for run in [&underlines, &strikes] {
+        let start = run
+            .iter()
+            .map(|v| v.rect.min.x)
+            .fold(f32::INFINITY, f32::min);
+        let end = run.iter().map(|v| v.rect.max.x).fold(0.0, f32::max);
+        assert_eq!(start, 0.0);
+        assert!((end - size.x).abs() < 1e-3, "{} != {}", end, size.x);
+        assert!(run.iter().all(|v| v.rect.height() > 0.0));
+        assert!(run
+            .iter()
+            .all(|v| v.color == Color::new(1.0, 0.0, 0.0, 1.0)));
+    }
+
+    assert!(strikes.iter().all(|v| v.rect.max.y < baseline));
+}
+
+#[test]
+fn letter_and_word_spacing() {
+    let mut layouter = TextLayouter::new();
+    let max_size = Vec2::splat(f32::INFINITY);
+
+    let mut measure = |text: &str, letter_spacing: f32, word_spacing: f32| {
+        let mut text = shape_with(&mut layouter, text, |props| {
+            props.letter_spacing = letter_spacing;
+            props.word_spacing = word_spacing;
+        });
+        let width = layouter.measure(&mut text, max_size).x;
+        (width, text.num_glyphs())
+    };
+
+    let (plain, count) = measure("Hello", 0.0, 0.0);
+    let (spaced, _) = measure("Hello", 3.0, 0.0);
+    assert_eq!(count, 5);
+    assert!((spaced - plain - 3.0 * (count - 1) as f32).abs() < 1e-3);
+
+    let (plain, count) = measure("Hello world", 0.0, 0.0);
+    let (spaced, _) = measure("Hello world", 2.0, 5.0);
+    assert_eq!(count, 11);
+    assert!((spaced - plain - 2.0 * (count - 1) as f32 - 5.0).abs() < 1e-3);
+}
+
+#[test]
+fn spacing_affects_wrapping() {
+    let mut layouter = TextLayouter::new();
+
+    let mut text = shape(&mut layouter, "Hello world");
+    let width = layouter.measure(&mut text, Vec2::splat(f32::INFINITY)).x;
+    let one_line = layouter.height_for_width(&mut text, width + 1.0);
+
+    let mut text = shape_with(&mut layouter, "Hello world", |props| {
+        props.letter_spacing = 2.0
+    });
+    assert!(layouter.height_for_width(&mut text, width + 1.0) > one_line);
+}
+
+#[test]
+fn hit_test() {
+    let mut layouter = TextLayouter::new();
+    let max_size = Vec2::splat(f32::INFINITY);
+
+    let mut text = shape(&mut layouter, "Hello");
+    let (size, _) = layouter.layout(&mut text, max_size);
+    let mid_y = size.y * 0.5;
+
+    assert_eq!(layouter.hit_test(&text, Vec2::new(-10.0, mid_y)), 0);
+    assert_eq!(layouter.hit_test(&text, Vec2::new(size.x + 10.0, mid_y)), 5);
+    assert_eq!(layouter.hit_test(&text, Vec2::new(size.x + 10.0, -10.0)), 5);
+
+    // clicking right of the middle of `e` puts the caret after it
+    let e = layouter.caret_rect(&text, 1);
+    let l = layouter.caret_rect(&text, 2);
+    let x = e.min.x + (l.min.x - e.min.x) * 0.75;
+    assert_eq!(layouter.hit_test(&text, Vec2::new(x, mid_y)), 2);
+
+    let x = e.min.x + (l.min.x - e.min.x) * 0.25;
+    assert_eq!(layouter.hit_test(&text, Vec2::new(x, mid_y)), 1);
+}
+
+#[test]
+fn hit_test_lines() {
+    let mut layouter = TextLayouter::new();
+    let max_size = Vec2::splat(f32::INFINITY);
+
+    let mut text = shape(&mut layouter, "ab\ncd");
+    let (size, _) = layouter.layout(&mut text, max_size);
+
+    let first = layouter.caret_rect(&text, 0);
+    let second = layouter.caret_rect(&text, 3);
+    assert_eq!(first.min.x, second.min.x);
+    assert!(second.min.y >= first.max.y - 1e-3);
+    assert!((second.max.y - size.y).abs() < 1e-3);
+
+    let below = Vec2::new(-10.0, size.y + 10.0);
+    assert_eq!(layouter.hit_test(&text, below), 3);
+    let end = Vec2::new(size.x + 10.0, size.y - 1.0);
+    assert_eq!(layouter.hit_test(&text, end), 5);
+
+    for offset in 0..=text.text().len() {
+        let rect = layouter.caret_rect(&text, offset);
+        assert_eq!(layouter.hit_test(&text, rect.center()), offset);
+    }
+}
+
+#[test]
+fn rtl_text() {
+    let mut layouter = TextLayouter::new();
+    let max_size = Vec2::splat(f32::INFINITY);
+
+    // "shalom", four two-byte letters
+    let mut text = shape(&mut layouter, "שלום");
+    let (size, glyphs) = layouter.layout(&mut text, max_size);
+    assert_eq!(glyphs.len(), 4);
+    assert!(glyphs.windows(2).all(|w| w[0].pos.x < w[1].pos.x));
+
+    // the first letter is on the right, the caret moves to the left
+    let carets = (0..=8)
+        .step_by(2)
+        .map(|i| layouter.caret_rect(&text, i).min.x)
+        .collect::<Vec<_>>();
+    assert!((carets[0] - size.x).abs() < 1e-3);
+    assert!(carets[4].abs() < 1e-3);
+    assert!(carets.windows(2).all(|w| w[0] > w[1]));
+
+    let mid_y = size.y * 0.5;
+    assert_eq!(layouter.hit_test(&text, Vec2::new(size.x + 10.0, mid_y)), 0);
+    assert_eq!(layouter.hit_test(&text, Vec2::new(-10.0, mid_y)), 8);
+}
+
+#[test]
+fn mixed_direction_text() {
+    let mut layouter = TextLayouter::new();
+    let max_size = Vec2::splat(f32::INFINITY);
+
+    let mut text = shape(&mut layouter, "abc שלום def");
+    let (_, glyphs) = layouter.layout(&mut text, max_size);
+    assert_eq!(glyphs.len(), 10);
+    assert!(glyphs.windows(2).all(|w| w[0].pos.x < w[1].pos.x));
+
+    let x = |offset| layouter.caret_rect(&text, offset).min.x;
+
+    // latin words stay in place, the hebrew one is mirrored between them
+    let hebrew_start = "abc ".len();
+    let hebrew_end = hebrew_start + "שלום".len();
+    assert!(x(0) < x(3));
+    assert!(x(3) < x(hebrew_end - 2));
+    assert!(x(hebrew_end - 2) < x(hebrew_start));
+    assert!(x(hebrew_start) < x(hebrew_end + 1));
+    assert!(x(hebrew_end + 1) < x(text.text().len()));
+}
+
+#[test]
+fn rtl_paragraph_spacing() {
+    let mut layouter = TextLayouter::new();
+    let max_size = Vec2::splat(f32::INFINITY);
+
+    // the latin word ends up on the left, and the space between the words
+    // belongs to the hebrew one, even though it comes last visually
+    let mut spaced = shape(&mut layouter, "שלום abc");
+    let (size, glyphs) = layouter.layout(&mut spaced, max_size);
+    let gap = glyphs[3].pos.x - glyphs[2].pos.x;
+
+    let mut joined = shape(&mut layouter, "שלוםabc");
+    let (joined_size, glyphs) = layouter.layout(&mut joined, max_size);
+    let joined_gap = glyphs[3].pos.x - glyphs[2].pos.x;
+
+    assert!(gap > joined_gap + 1.0, "{} <= {}", gap, joined_gap);
+    assert!(size.x > joined_size.x);
+}
+
+#[test]
+fn visible_glyphs_clip_decorations() {
+    let mut layouter = TextLayouter::new();
+    let max_size = Vec2::splat(f32::INFINITY);
+
+    let mut text = shape_with(&mut layouter, "Hello world", |props| {
+        props.underline = true;
+    });
+
+    let (_, glyphs) = layouter.layout(&mut text, max_size);
+    let hidden_x = glyphs[3].pos.x;
+
+    text.visible_glyphs(3);
+    layouter.layout(&mut text, max_size);
+    let decorations = layouter.decorations();
+    assert_eq!(decorations.len(), 1);
+    assert_eq!(decorations[0].rect.max.x, hidden_x);
+
+    text.visible_glyphs(0);
+    layouter.layout(&mut text, max_size);
+    assert!(layouter.decorations().is_empty());
+}
@@ -0,0 +1,108 @@
+use std::any::Any;
+use std::path::Path;
+use std::sync::Arc;
+
+use gg_assets::{Assets, DirSource};
+use gg_graphics::{
+    Backend, Canvas, CommandList, GraphicsEncoder, PresentError, RawCanvas, ThreadedBackend,
+};
+use gg_math::Vec2;
+use gg_util::parking_lot::Mutex;
+
+#[derive(Debug)]
+struct MockCanvas;
+
+impl RawCanvas for MockCanvas {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[derive(Default)]
+struct MockBackend {
+    encoded: Arc<Mutex<Vec<(String, usize)>>>,
+    presented: Arc<Mutex<Vec<String>>>,
+    submitted_lists: Vec<CommandList>,
+    recycled_lists: Vec<CommandList>,
+}
+
+fn thread_name() -> String {
+    std::thread::current().name().unwrap_or_default().to_owned()
+}
+
+impl Backend for MockBackend {
+    fn get_main_canvas(&self) -> Canvas {
+        Canvas::from_raw(Arc::new(MockCanvas))
+    }
+
+    fn create_canvas(&mut self, _size: Vec2<u32>) -> Canvas {
+        Canvas::from_raw(Arc::new(MockCanvas))
+    }
+
+    fn submit(&mut self, commands: CommandList) {
+        self.submitted_lists.push(commands);
+    }
+
+    fn resize(&mut self, _new_resolution: Vec2<u32>) {}
+
+    fn encode_frame(&mut self, _assets: &mut Assets) -> Result<(), PresentError> {
+        let mut encoded = self.encoded.lock();
+
+        for list in self.submitted_lists.drain(..) {
+            encoded.push((thread_name(), list.list.len()));
+            self.recycled_lists.push(list);
+        }
+
+        Ok(())
+    }
+
+    fn submit_frame(&mut self) {
+        self.presented.lock().push(thread_name());
+    }
+
+    fn recycle_list(&mut self) -> Option<CommandList> {
+        self.recycled_lists.pop()
+    }
+}
+
+#[test]
+fn present_on_render_thread() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+    let source = DirSource::new(&path.canonicalize().unwrap()).unwrap();
+    let mut assets = Assets::new(source);
+
+    let mock = MockBackend::default();
+    let encoded = mock.encoded.clone();
+    let presented = mock.presented.clone();
+    let mut backend = ThreadedBackend::new(mock);
+    let canvas = backend.get_main_canvas();
+
+    let mut recycled = 0;
+    let mut recycled_list = None;
+
+    for frame in 0..4 {
+        let mut encoder = match recycled_list.take() {
+            Some(list) => {
+                recycled += 1;
+                GraphicsEncoder::new_recycled(&canvas, list)
+            }
+            None => GraphicsEncoder::new(&canvas),
+        };
+
+        for _ in 0..=frame {
+            encoder.save();
+        }
+
+        backend.submit(encoder.finish());
+        backend.present(&mut assets).unwrap();
+        recycled_list = backend.recycle_list();
+    }
+
+    drop(backend);
+
+    // assets are only read on the calling thread
+    let expected = (1..=4).map(|n| (thread_name(), n)).collect::<Vec<_>>();
+    assert_eq!(*encoded.lock(), expected);
+    assert_eq!(*presented.lock(), vec!["render"; 4]);
+    assert!(recycled > 0);
+}
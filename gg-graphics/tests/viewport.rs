@@ -0,0 +1,48 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use gg_graphics::{Canvas, Color, Command, GraphicsEncoder, RawCanvas};
+use gg_math::{Rect, Vec2};
+
+#[derive(Debug)]
+struct MockCanvas;
+
+impl RawCanvas for MockCanvas {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[test]
+fn split_viewports() {
+    let canvas = Canvas::from_raw(Arc::new(MockCanvas));
+    let mut encoder = GraphicsEncoder::new(&canvas);
+
+    let left = Rect::new(Vec2::zero(), Vec2::new(50.0, 100.0));
+    let right = Rect::new(Vec2::new(50.0, 0.0), Vec2::new(50.0, 100.0));
+    let scene = Rect::new(Vec2::zero(), Vec2::splat(100.0));
+
+    encoder.save();
+    encoder.set_viewport(left);
+    encoder.rect(scene).fill_color(Color::WHITE);
+    encoder.restore();
+
+    encoder.save();
+    encoder.set_scissor(Rect::new(Vec2::zero(), Vec2::splat(10.0)));
+    encoder.set_viewport(right);
+    assert_eq!(encoder.get_scissor().max, Vec2::splat(f32::INFINITY));
+    encoder.rect(scene).fill_color(Color::BLACK);
+    encoder.restore();
+
+    let list = encoder.finish().list;
+    let viewports = list
+        .iter()
+        .filter_map(|cmd| match cmd {
+            Command::SetViewport(rect) => Some(*rect),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(viewports, [left, right]);
+    assert!(matches!(list[2], Command::DrawRect(ref cmd) if cmd.rect == scene));
+}
@@ -101,4 +101,11 @@ impl ActionRegistry {
     pub fn get(&self, name: &str) -> Option<Action> {
         self.map.get(name).copied()
     }
+
+    pub fn name(&self, action: Action) -> Option<&'static str> {
+        self.map
+            .iter()
+            .find(|(_, &v)| v == action)
+            .map(|(&name, _)| name)
+    }
 }
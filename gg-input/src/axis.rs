@@ -0,0 +1,118 @@
+use std::fmt::{self, Display};
+
+use gg_util::ahash::{AHashMap, AHashSet};
+use serde::{Deserialize, Serialize};
+use winit::event::ModifiersState;
+
+use crate::binding::{Binding, BindingElement};
+use crate::gamepad::{display_gamepad, parse_gamepad, GamepadAxis, GamepadId};
+
+pub const DEFAULT_DEADZONE: f32 = 0.1;
+
+// in an input map, axes are bound with objects instead of strings:
+// `{"axis": "GamepadLeftStickX", "deadzone": 0.2}` or
+// `{"negative": "A", "positive": "D"}`
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "RawAxisBinding", into = "RawAxisBinding")]
+pub enum AxisBinding {
+    Gamepad {
+        id: GamepadId,
+        axis: GamepadAxis,
+        deadzone: f32,
+    },
+    Keys {
+        negative: Binding,
+        positive: Binding,
+    },
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum RawAxisBinding {
+    Gamepad {
+        axis: String,
+        #[serde(default = "default_deadzone")]
+        deadzone: f32,
+    },
+    Keys {
+        negative: Binding,
+        positive: Binding,
+    },
+}
+
+fn default_deadzone() -> f32 {
+    DEFAULT_DEADZONE
+}
+
+impl TryFrom<RawAxisBinding> for AxisBinding {
+    type Error = String;
+
+    fn try_from(raw: RawAxisBinding) -> Result<Self, Self::Error> {
+        Ok(match raw {
+            RawAxisBinding::Gamepad { axis, deadzone } => {
+                let (id, axis) = parse_gamepad(&axis)
+                    .ok_or_else(|| format!("invalid gamepad axis: {}", axis))?;
+
+                if !(0.0..1.0).contains(&deadzone) {
+                    return Err(format!("deadzone must be in [0, 1), got {}", deadzone));
+                }
+
+                AxisBinding::Gamepad { id, axis, deadzone }
+            }
+            RawAxisBinding::Keys { negative, positive } => AxisBinding::Keys { negative, positive },
+        })
+    }
+}
+
+impl From<AxisBinding> for RawAxisBinding {
+    fn from(binding: AxisBinding) -> Self {
+        match binding {
+            AxisBinding::Gamepad { deadzone, .. } => RawAxisBinding::Gamepad {
+                axis: binding.to_string(),
+                deadzone,
+            },
+            AxisBinding::Keys { negative, positive } => RawAxisBinding::Keys { negative, positive },
+        }
+    }
+}
+
+impl Display for AxisBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            AxisBinding::Gamepad { id, axis, .. } => display_gamepad(id, axis, f),
+            AxisBinding::Keys { negative, positive } => write!(f, "{}/{}", negative, positive),
+        }
+    }
+}
+
+impl AxisBinding {
+    pub(crate) fn value(
+        &self,
+        axes: &AHashMap<(GamepadId, GamepadAxis), f32>,
+        elements: &AHashSet<BindingElement>,
+        modifiers: ModifiersState,
+    ) -> f32 {
+        match *self {
+            AxisBinding::Gamepad { id, axis, deadzone } => {
+                let value = axes.get(&(id, axis)).copied().unwrap_or(0.0);
+                apply_deadzone(value, deadzone)
+            }
+            AxisBinding::Keys { negative, positive } => {
+                let negative = negative.is_pressed(elements, modifiers) as i32;
+                let positive = positive.is_pressed(elements, modifiers) as i32;
+                (positive - negative) as f32
+            }
+        }
+    }
+}
+
+// values inside the deadzone are zeroed, the rest is rescaled so that the
+// output still covers the whole [-1, 1] range without a jump at the edge
+fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    let magnitude = value.abs().min(1.0);
+    if magnitude <= deadzone {
+        return 0.0;
+    }
+
+    (magnitude - deadzone) / (1.0 - deadzone) * value.signum()
+}
@@ -1,11 +1,15 @@
 use std::fmt::{self, Debug, Display};
 use std::str::FromStr;
 
+use gg_util::ahash::AHashSet;
 use gg_util::eyre::{bail, eyre, Report};
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 use winit::event::{ModifiersState, MouseButton, VirtualKeyCode};
 
+use crate::gamepad::{display_gamepad, parse_gamepad, GamepadButton, GamepadId};
+
 const MAX_ELEMENTS: usize = 3;
+const MAX_STEPS: usize = 4;
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, DeserializeFromStr, SerializeDisplay)]
 pub struct Binding {
@@ -14,10 +18,18 @@ pub struct Binding {
     modifiers: ModifiersState,
 }
 
+// bindings pressed one after another, written as `Ctrl-K Ctrl-S`
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, DeserializeFromStr, SerializeDisplay)]
+pub struct Sequence {
+    steps: [Binding; MAX_STEPS],
+    steps_len: usize,
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum BindingElement {
     Keyboard(VirtualKeyCode),
     Mouse(MouseButton),
+    GamepadButton(GamepadId, GamepadButton),
 }
 
 impl Binding {
@@ -28,6 +40,129 @@ impl Binding {
     pub fn modifiers(&self) -> ModifiersState {
         self.modifiers
     }
+
+    pub(crate) fn is_pressed(
+        &self,
+        elements: &AHashSet<BindingElement>,
+        modifiers: ModifiersState,
+    ) -> bool {
+        modifiers.contains(self.modifiers) && self.elements().all(|c| elements.contains(&c))
+    }
+
+    // whether pressing `element` completes the binding
+    pub(crate) fn is_pressed_by(
+        &self,
+        element: BindingElement,
+        elements: &AHashSet<BindingElement>,
+        modifiers: ModifiersState,
+    ) -> bool {
+        self.elements().any(|v| v == element) && self.is_pressed(elements, modifiers)
+    }
+
+    // like the display form, but with modifiers joined by `+`
+    pub fn hint(&self) -> String {
+        struct Hint<'a>(&'a Binding);
+
+        impl Display for Hint<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt_joined(f, "+")
+            }
+        }
+
+        Hint(self).to_string()
+    }
+
+    fn fmt_joined(&self, f: &mut fmt::Formatter<'_>, sep: &str) -> fmt::Result {
+        display_modifiers(self.modifiers, sep, f)?;
+
+        for (i, element) in self.elements().enumerate() {
+            if !self.modifiers.is_empty() || i > 0 {
+                f.write_str(sep)?;
+            }
+
+            match element {
+                BindingElement::Keyboard(key) => key.fmt(f)?,
+                BindingElement::Mouse(btn) => display_mouse_button(btn, f)?,
+                BindingElement::GamepadButton(id, btn) => display_gamepad(id, btn, f)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Sequence {
+    pub fn steps(&self) -> impl Iterator<Item = Binding> {
+        self.steps.into_iter().take(self.steps_len)
+    }
+
+    pub(crate) fn step(&self, i: usize) -> Option<Binding> {
+        self.steps().nth(i)
+    }
+
+    pub fn hint(&self) -> String {
+        self.steps()
+            .map(|v| v.hint())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl FromStr for Sequence {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let first = s
+            .split_whitespace()
+            .next()
+            .unwrap_or(s)
+            .parse::<Binding>()?;
+
+        let mut steps = [first; MAX_STEPS];
+        let mut i = 0;
+
+        for part in s.split_whitespace() {
+            if i < steps.len() {
+                steps[i] = part.parse()?;
+                i += 1;
+            } else {
+                bail!("too many steps in a sequence");
+            }
+        }
+
+        if i < 2 {
+            bail!("sequence must have at least two steps");
+        }
+
+        Ok(Sequence {
+            steps,
+            steps_len: i,
+        })
+    }
+}
+
+impl Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+
+            write!(f, "{}", step)?;
+        }
+
+        Ok(())
+    }
+}
+
+// pressing a modifier key neither advances nor breaks a sequence
+pub(crate) fn is_modifier_key(element: BindingElement) -> bool {
+    use VirtualKeyCode::*;
+
+    matches!(
+        element,
+        BindingElement::Keyboard(LControl | RControl | LShift | RShift | LAlt | RAlt | LWin | RWin)
+    )
 }
 
 impl FromStr for Binding {
@@ -46,6 +181,10 @@ impl FromStr for Binding {
                 let element = parse_key(part)
                     .map(BindingElement::Keyboard)
                     .or_else(|| parse_mouse_button(part).map(BindingElement::Mouse))
+                    .or_else(|| {
+                        parse_gamepad(part)
+                            .map(|(id, button)| BindingElement::GamepadButton(id, button))
+                    })
                     .ok_or_else(|| eyre!("invalid binding element: {}", part))?;
 
                 if i < elements.len() {
@@ -71,20 +210,7 @@ impl FromStr for Binding {
 
 impl Display for Binding {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        display_modifiers(self.modifiers, f)?;
-
-        for (i, element) in self.elements().enumerate() {
-            if !self.modifiers.is_empty() || i > 0 {
-                f.write_str("-")?;
-            }
-
-            match element {
-                BindingElement::Keyboard(key) => key.fmt(f)?,
-                BindingElement::Mouse(btn) => display_mouse_button(btn, f)?,
-            }
-        }
-
-        Ok(())
+        self.fmt_joined(f, "-")
     }
 }
 
@@ -133,13 +259,13 @@ fn parse_modifier(s: &str) -> Option<ModifiersState> {
     None
 }
 
-fn display_modifiers(mods: ModifiersState, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+fn display_modifiers(mods: ModifiersState, sep: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let mut tail = false;
 
     for &(pat, modifier) in &MODIFIERS {
         if mods.contains(modifier) {
             if tail {
-                f.write_str("-")?;
+                f.write_str(sep)?;
             }
 
             f.write_str(pat)?;
@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+pub struct InputConfig {
+    // max time between two steps of a sequence binding
+    pub sequence_timeout: Duration,
+    // max time and cursor travel between presses counted as a double click
+    pub click_interval: Duration,
+    pub click_distance: f32,
+    // cursor travel after a press before dragging starts
+    pub drag_threshold: f32,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        InputConfig {
+            sequence_timeout: Duration::from_secs(1),
+            click_interval: Duration::from_millis(500),
+            click_distance: 4.0,
+            drag_threshold: 4.0,
+        }
+    }
+}
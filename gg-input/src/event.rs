@@ -1,15 +1,18 @@
 use gg_math::Vec2;
 pub use winit::event::{ElementState, MouseButton, VirtualKeyCode};
 
-use crate::Action;
+use crate::{Action, GamepadButton, GamepadId};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Event {
     Keyboard(KeyboardEvent),
     Mouse(MouseEvent),
+    Drag(DragEvent),
+    Gamepad(GamepadEvent),
     Scroll(ScrollEvent),
-    Char(char),
+    Text(TextEvent),
     Action(ActionEvent),
+    Axis(AxisEvent),
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -22,6 +25,29 @@ pub struct KeyboardEvent {
 pub struct MouseEvent {
     pub state: ElementState,
     pub button: MouseButton,
+    // 1 for a single click, 2 for a double click and so on
+    pub clicks: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DragEvent {
+    pub button: MouseButton,
+    pub start: Vec2<f32>,
+    pub current: Vec2<f32>,
+    // movement since the previous drag event of this button
+    pub delta: Vec2<f32>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GamepadEvent {
+    pub state: ElementState,
+    pub id: GamepadId,
+    pub button: GamepadButton,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TextEvent {
+    pub ch: char,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -35,6 +61,12 @@ pub struct ActionEvent {
     pub action: Action,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AxisEvent {
+    pub action: Action,
+    pub value: f32,
+}
+
 impl Event {
     pub fn pressed_action(&self, action: impl Into<Action>) -> bool {
         match self {
@@ -0,0 +1,142 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use gg_util::eyre::{eyre, Report};
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Ord, PartialOrd)]
+pub struct GamepadId(pub u32);
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    Mode,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+const BUTTONS: [(&str, GamepadButton); 17] = [
+    ("South", GamepadButton::South),
+    ("East", GamepadButton::East),
+    ("North", GamepadButton::North),
+    ("West", GamepadButton::West),
+    ("LeftBumper", GamepadButton::LeftBumper),
+    ("RightBumper", GamepadButton::RightBumper),
+    ("LeftTrigger", GamepadButton::LeftTrigger),
+    ("RightTrigger", GamepadButton::RightTrigger),
+    ("Select", GamepadButton::Select),
+    ("Start", GamepadButton::Start),
+    ("Mode", GamepadButton::Mode),
+    ("LeftStick", GamepadButton::LeftStick),
+    ("RightStick", GamepadButton::RightStick),
+    ("DPadUp", GamepadButton::DPadUp),
+    ("DPadDown", GamepadButton::DPadDown),
+    ("DPadLeft", GamepadButton::DPadLeft),
+    ("DPadRight", GamepadButton::DPadRight),
+];
+
+impl GamepadButton {
+    pub fn name(self) -> &'static str {
+        BUTTONS.iter().find(|(_, v)| *v == self).unwrap().0
+    }
+}
+
+impl FromStr for GamepadButton {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BUTTONS
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|(_, v)| *v)
+            .ok_or_else(|| eyre!("invalid gamepad button: {}", s))
+    }
+}
+
+impl Display for GamepadButton {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// sticks go from -1 (left, down) to 1 (right, up), analog triggers from 0 to 1
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+const AXES: [(&str, GamepadAxis); 6] = [
+    ("LeftStickX", GamepadAxis::LeftStickX),
+    ("LeftStickY", GamepadAxis::LeftStickY),
+    ("RightStickX", GamepadAxis::RightStickX),
+    ("RightStickY", GamepadAxis::RightStickY),
+    ("LeftTrigger", GamepadAxis::LeftTrigger),
+    ("RightTrigger", GamepadAxis::RightTrigger),
+];
+
+impl GamepadAxis {
+    pub fn name(self) -> &'static str {
+        AXES.iter().find(|(_, v)| *v == self).unwrap().0
+    }
+}
+
+impl FromStr for GamepadAxis {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AXES.iter()
+            .find(|(name, _)| *name == s)
+            .map(|(_, v)| *v)
+            .ok_or_else(|| eyre!("invalid gamepad axis: {}", s))
+    }
+}
+
+impl Display for GamepadAxis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// `GamepadSouth` is the south button of the first gamepad, `Gamepad2South`
+// is the same button of the third one. Axes are named the same way
+pub(crate) fn parse_gamepad<T: FromStr>(s: &str) -> Option<(GamepadId, T)> {
+    let s = s.strip_prefix("Gamepad")?;
+    let digits = s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let (id, element) = s.split_at(digits);
+
+    let id = match id {
+        "" => 0,
+        _ => id.parse().ok()?,
+    };
+
+    Some((GamepadId(id), element.parse().ok()?))
+}
+
+pub(crate) fn display_gamepad(
+    id: GamepadId,
+    element: impl Display,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    if id.0 == 0 {
+        write!(f, "Gamepad{}", element)
+    } else {
+        write!(f, "Gamepad{}{}", id.0, element)
+    }
+}
@@ -1,25 +1,36 @@
 mod action;
+mod axis;
 mod binding;
+mod config;
 mod event;
+mod gamepad;
 mod map;
 
 use std::path::Path;
+use std::time::Instant;
 
 use gg_math::Vec2;
-use gg_util::ahash::AHashSet;
+use gg_util::ahash::{AHashMap, AHashSet};
 use gg_util::eyre::{Context, Result};
 use winit::event::{KeyboardInput, ModifiersState, MouseScrollDelta, WindowEvent};
 
 use self::action::ActionRegistry;
 pub use self::action::{Action, ActionKind};
-use self::binding::BindingElement;
+pub use self::axis::{AxisBinding, DEFAULT_DEADZONE};
+use self::binding::{is_modifier_key, BindingElement};
+pub use self::binding::{Binding, Sequence};
+pub use self::config::InputConfig;
 pub use self::event::*;
+pub use self::gamepad::{GamepadAxis, GamepadButton, GamepadId};
 use self::map::InputMap;
 
 #[derive(Debug, Default)]
 pub struct Input {
     actions: ActionRegistry,
     map: InputMap,
+    // bumped whenever the bindings change
+    bindings_revision: u64,
+    config: InputConfig,
     state: State,
     events: Vec<Event>,
 }
@@ -31,8 +42,36 @@ struct State {
     elements: AHashSet<BindingElement>,
     modifiers: ModifiersState,
     mouse_pos: Vec2<f32>,
+    gamepad_axes: AHashMap<(GamepadId, GamepadAxis), f32>,
+    // last axis values reported through `Event::Axis`
+    axis_values: AHashMap<Action, f32>,
+    // start of the current frame, `None` before the first one
+    now: Option<Instant>,
+    // sequences with the number of matched steps
+    sequences: Vec<(Sequence, Action, usize)>,
+    last_sequence_step: Option<Instant>,
+    clicks: AHashMap<MouseButton, Click>,
+    drags: AHashMap<MouseButton, Drag>,
 }
 
+#[derive(Clone, Copy, Debug)]
+struct Drag {
+    start: Vec2<f32>,
+    last: Vec2<f32>,
+    // set once the cursor leaves the threshold
+    active: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Click {
+    count: u32,
+    time: Instant,
+    pos: Vec2<f32>,
+}
+
+// smaller changes of an axis value don't produce events
+const AXIS_EVENT_THRESHOLD: f32 = 0.01;
+
 impl Input {
     pub fn new() -> Input {
         Input::default()
@@ -46,22 +85,90 @@ impl Input {
         let path = path.as_ref();
         let data = std::fs::read_to_string(path)
             .wrap_err_with(|| format!("cannot read {}", path.display()))?;
-        self.map.parse(&self.actions, &data)
+        self.load_str(&data)
+            .wrap_err_with(|| format!("cannot load {}", path.display()))
+    }
+
+    pub fn load_str(&mut self, data: &str) -> Result<()> {
+        self.bindings_revision += 1;
+        self.map.parse(&self.actions, data)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let data = self
+            .save_str()
+            .wrap_err_with(|| format!("cannot save {}", path.display()))?;
+        std::fs::write(path, data).wrap_err_with(|| format!("cannot write {}", path.display()))
+    }
+
+    pub fn save_str(&self) -> Result<String> {
+        self.map.serialize(&self.actions)
+    }
+
+    pub fn bind(&mut self, action: impl Into<Action>, binding: Binding) {
+        self.bindings_revision += 1;
+        self.map.add_binding(binding, action.into());
+    }
+
+    // removes all bindings of the action, including axis bindings
+    pub fn unbind(&mut self, action: impl Into<Action>) {
+        self.bindings_revision += 1;
+        self.map.remove_action(action.into());
+        self.update_actions();
+        self.update_axes();
+    }
+
+    pub fn bind_axis(&mut self, action: impl Into<Action>, binding: AxisBinding) {
+        self.bindings_revision += 1;
+        self.map.add_axis_binding(binding, action.into());
+    }
+
+    pub fn bind_sequence(&mut self, action: impl Into<Action>, sequence: Sequence) {
+        self.bindings_revision += 1;
+        self.map.add_sequence(sequence, action.into());
+    }
+
+    pub fn bindings_for(&self, action: impl Into<Action>) -> impl Iterator<Item = Binding> + '_ {
+        self.map.bindings_for(action.into())
+    }
+
+    pub fn bindings_revision(&self) -> u64 {
+        self.bindings_revision
+    }
+
+    pub fn config(&self) -> &InputConfig {
+        &self.config
+    }
+
+    pub fn config_mut(&mut self) -> &mut InputConfig {
+        &mut self.config
     }
 
     pub fn begin_frame(&mut self) {
+        self.begin_frame_at(Instant::now());
+    }
+
+    pub fn begin_frame_at(&mut self, now: Instant) {
+        self.state.now = Some(now);
         self.events.clear();
     }
 
+    fn now(&self) -> Instant {
+        self.state.now.unwrap_or_else(Instant::now)
+    }
+
     pub fn process_event(&mut self, event: WindowEvent) {
         match event {
             WindowEvent::CursorMoved { position, .. } => {
                 self.state.mouse_pos = Vec2::new(position.x as f32, position.y as f32);
+                self.update_drags();
             }
 
             WindowEvent::ModifiersChanged(v) => {
                 self.state.modifiers = v;
                 self.update_actions();
+                self.update_axes();
             }
 
             WindowEvent::MouseWheel { delta, .. } => {
@@ -76,10 +183,32 @@ impl Input {
                 self.process_keyboard_input(input);
             }
 
+            WindowEvent::ReceivedCharacter(ch) => {
+                self.process_char(ch);
+            }
+
             _ => {}
         }
     }
 
+    // gamepads aren't reported by winit, so the application forwards them
+    // from its gamepad library
+    pub fn process_gamepad_button(
+        &mut self,
+        id: GamepadId,
+        button: GamepadButton,
+        state: ElementState,
+    ) {
+        self.events
+            .push(Event::Gamepad(GamepadEvent { state, id, button }));
+        self.process_element(state, BindingElement::GamepadButton(id, button));
+    }
+
+    pub fn process_gamepad_axis(&mut self, id: GamepadId, axis: GamepadAxis, value: f32) {
+        self.state.gamepad_axes.insert((id, axis), value);
+        self.update_axes();
+    }
+
     fn process_scroll(&mut self, delta: MouseScrollDelta) {
         let delta = match delta {
             MouseScrollDelta::LineDelta(x, y) => Vec2::new(x, y),
@@ -90,10 +219,81 @@ impl Input {
     }
 
     fn process_mouse_input(&mut self, state: ElementState, button: MouseButton) {
-        self.events.push(Event::Mouse(MouseEvent { state, button }));
+        match state {
+            ElementState::Pressed => {
+                self.update_clicks(button);
+
+                let pos = self.state.mouse_pos;
+                self.state.drags.insert(
+                    button,
+                    Drag {
+                        start: pos,
+                        last: pos,
+                        active: false,
+                    },
+                );
+            }
+            ElementState::Released => {
+                self.state.drags.remove(&button);
+            }
+        }
+
+        let clicks = self.state.clicks.get(&button).map_or(1, |v| v.count);
+        self.events.push(Event::Mouse(MouseEvent {
+            state,
+            button,
+            clicks,
+        }));
         self.process_element(state, BindingElement::Mouse(button));
     }
 
+    fn update_drags(&mut self) {
+        let current = self.state.mouse_pos;
+
+        for (&button, drag) in &mut self.state.drags {
+            if !drag.active && (current - drag.start).length() <= self.config.drag_threshold {
+                continue;
+            }
+
+            drag.active = true;
+            self.events.push(Event::Drag(DragEvent {
+                button,
+                start: drag.start,
+                current,
+                delta: current - drag.last,
+            }));
+            drag.last = current;
+        }
+    }
+
+    fn update_clicks(&mut self, button: MouseButton) {
+        let time = self.now();
+        let pos = self.state.mouse_pos;
+        let config = &self.config;
+
+        let count = match self.state.clicks.get(&button) {
+            Some(last)
+                if time.saturating_duration_since(last.time) <= config.click_interval
+                    && (last.pos - pos).length() <= config.click_distance =>
+            {
+                last.count + 1
+            }
+            _ => 1,
+        };
+
+        self.state.clicks.insert(button, Click { count, time, pos });
+    }
+
+    fn process_char(&mut self, ch: char) {
+        // enter, backspace and the like are handled through keyboard events.
+        // macOS also reports arrows and function keys as private use chars
+        if ch.is_control() || ('\u{f700}'..='\u{f8ff}').contains(&ch) {
+            return;
+        }
+
+        self.events.push(Event::Text(TextEvent { ch }));
+    }
+
     fn process_keyboard_input(&mut self, input: KeyboardInput) {
         let code = match input.virtual_keycode {
             Some(v) => v,
@@ -112,6 +312,7 @@ impl Input {
         match state {
             ElementState::Pressed => {
                 self.state.elements.insert(element);
+                self.update_sequences(element);
             }
             ElementState::Released => {
                 self.state.elements.remove(&element);
@@ -119,6 +320,7 @@ impl Input {
         }
 
         self.update_actions();
+        self.update_axes();
     }
 
     fn update_actions(&mut self) {
@@ -148,6 +350,79 @@ impl Input {
         std::mem::swap(old_set, new_set);
     }
 
+    fn update_sequences(&mut self, element: BindingElement) {
+        if is_modifier_key(element) {
+            return;
+        }
+
+        let now = self.now();
+        let state = &mut self.state;
+
+        if let Some(last) = state.last_sequence_step {
+            if now.saturating_duration_since(last) > self.config.sequence_timeout {
+                state.sequences.clear();
+            }
+        }
+
+        let matches = |sequence: Sequence, step| {
+            sequence.step(step).map_or(false, |b| {
+                b.is_pressed_by(element, &state.elements, state.modifiers)
+            })
+        };
+
+        // sequences that don't match the pressed element are reset
+        let mut next = state
+            .sequences
+            .iter()
+            .filter(|&&(sequence, _, step)| matches(sequence, step))
+            .map(|&(sequence, action, step)| (sequence, action, step + 1))
+            .collect::<Vec<_>>();
+
+        for (sequence, action) in self.map.sequences() {
+            if matches(sequence, 0) && !next.contains(&(sequence, action, 1)) {
+                next.push((sequence, action, 1));
+            }
+        }
+
+        let completed = next
+            .iter()
+            .filter(|&&(sequence, _, step)| sequence.step(step).is_none())
+            .map(|&(_, action, _)| action)
+            .collect::<Vec<_>>();
+
+        if completed.is_empty() {
+            state.sequences = next;
+        } else {
+            state.sequences.clear();
+        }
+
+        state.last_sequence_step = Some(now);
+
+        // sequences fire momentarily, so they are never reported as held
+        for action in completed {
+            for state in [ElementState::Pressed, ElementState::Released] {
+                self.events
+                    .push(Event::Action(ActionEvent { action, state }));
+            }
+        }
+    }
+
+    fn update_axes(&mut self) {
+        for (_, action) in self.map.axis_bindings() {
+            let value = self.axis_value(action);
+            let old = self.state.axis_values.get(&action).copied().unwrap_or(0.0);
+
+            // always report reaching the rest position or the ends of the range
+            let crossed =
+                (value - old).abs() >= AXIS_EVENT_THRESHOLD || value == 0.0 || value.abs() == 1.0;
+
+            if value != old && crossed {
+                self.state.axis_values.insert(action, value);
+                self.events.push(Event::Axis(AxisEvent { action, value }));
+            }
+        }
+    }
+
     pub fn events(&self) -> impl Iterator<Item = Event> + '_ {
         self.events.iter().copied()
     }
@@ -161,6 +436,21 @@ impl Input {
         self.events().any(|ev| ev.pressed_action(action))
     }
 
+    // sum of all bindings of the action, clamped to [-1, 1]
+    pub fn axis_value(&self, action: impl Into<Action>) -> f32 {
+        let action = action.into();
+        let state = &self.state;
+
+        self.map
+            .axis_bindings()
+            .filter(|&(_, a)| a == action)
+            .map(|(binding, _)| {
+                binding.value(&state.gamepad_axes, &state.elements, state.modifiers)
+            })
+            .sum::<f32>()
+            .clamp(-1.0, 1.0)
+    }
+
     pub fn is_key_pressed(&self, key: VirtualKeyCode) -> bool {
         self.state.elements.contains(&BindingElement::Keyboard(key))
     }
@@ -169,6 +459,21 @@ impl Input {
         self.state.elements.contains(&BindingElement::Mouse(button))
     }
 
+    pub fn is_gamepad_button_pressed(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.state
+            .elements
+            .contains(&BindingElement::GamepadButton(id, button))
+    }
+
+    // number of clicks in the current press of the button, 0 if it isn't held
+    pub fn click_count(&self, button: MouseButton) -> u32 {
+        if !self.is_mouse_button_pressed(button) {
+            return 0;
+        }
+
+        self.state.clicks.get(&button).map_or(0, |v| v.count)
+    }
+
     pub fn mouse_pos(&self) -> Vec2<f32> {
         self.state.mouse_pos
     }
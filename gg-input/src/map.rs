@@ -1,31 +1,106 @@
 use gg_util::ahash::{AHashMap, AHashSet};
-use gg_util::eyre::Result;
+use gg_util::eyre::{bail, Result};
 use winit::event::ModifiersState;
 
 use crate::action::ActionRegistry;
-use crate::binding::{Binding, BindingElement};
+use crate::axis::AxisBinding;
+use crate::binding::{Binding, BindingElement, Sequence};
 use crate::Action;
 
 #[derive(Clone, Debug, Default)]
 pub struct InputMap {
     map: AHashMap<BindingElement, Vec<(Binding, Action)>>,
+    axes: Vec<(AxisBinding, Action)>,
+    sequences: Vec<(Sequence, Action)>,
+}
+
+enum Entry {
+    Binding(Binding),
+    Axis(AxisBinding),
+    Sequence(Sequence),
+}
+
+impl Entry {
+    fn parse(value: serde_json::Value) -> Result<Entry> {
+        Ok(match value {
+            serde_json::Value::String(ref s) if s.contains(char::is_whitespace) => {
+                Entry::Sequence(serde_json::from_value(value)?)
+            }
+            serde_json::Value::String(_) => Entry::Binding(serde_json::from_value(value)?),
+            _ => Entry::Axis(serde_json::from_value(value)?),
+        })
+    }
 }
 
 impl InputMap {
     pub fn parse(&mut self, actions: &ActionRegistry, data: &str) -> Result<()> {
-        let list: Vec<(String, Binding)> = serde_json::from_str(data)?;
+        let list: Vec<(String, serde_json::Value)> = serde_json::from_str(data)?;
+
+        let mut bindings = Vec::with_capacity(list.len());
+        let mut unknown = Vec::new();
+
+        for (action_name, value) in list {
+            let entry = Entry::parse(value)?;
 
-        for (action_name, binding) in list {
             if let Some(action) = actions.get(&action_name) {
-                self.add_binding(binding, action);
+                bindings.push((entry, action));
             } else {
-                tracing::warn!("no such action: {}", action_name);
+                let binding = match entry {
+                    Entry::Binding(v) => v.to_string(),
+                    Entry::Axis(v) => v.to_string(),
+                    Entry::Sequence(v) => v.to_string(),
+                };
+
+                unknown.push(format!("`{}` (bound to {})", action_name, binding));
+            }
+        }
+
+        if !unknown.is_empty() {
+            bail!("no such actions: {}", unknown.join(", "));
+        }
+
+        for (entry, action) in bindings {
+            match entry {
+                Entry::Binding(binding) => self.add_binding(binding, action),
+                Entry::Axis(binding) => self.add_axis_binding(binding, action),
+                Entry::Sequence(sequence) => self.add_sequence(sequence, action),
             }
         }
 
         Ok(())
     }
 
+    // bindings are sorted by action name, so that saving the same map always
+    // produces the same output
+    pub fn serialize(&self, actions: &ActionRegistry) -> Result<String> {
+        let name = |action| match actions.name(action) {
+            Some(v) => Ok(v),
+            None => bail!("cannot save a binding of an unregistered action"),
+        };
+
+        let mut list = Vec::new();
+
+        for (element, bindings) in &self.map {
+            for &(binding, action) in bindings {
+                if binding.elements().next() == Some(*element) {
+                    list.push((name(action)?, serde_json::to_value(binding)?));
+                }
+            }
+        }
+
+        for &(binding, action) in &self.axes {
+            list.push((name(action)?, serde_json::to_value(binding)?));
+        }
+
+        for &(sequence, action) in &self.sequences {
+            list.push((name(action)?, serde_json::to_value(sequence)?));
+        }
+
+        list.sort_by(|a, b| (a.0, a.1.to_string()).cmp(&(b.0, b.1.to_string())));
+
+        Ok(serde_json::to_string_pretty(&list)?)
+    }
+
     pub fn add_binding(&mut self, binding: Binding, action: Action) {
         self.remove_binding(binding, action);
 
@@ -42,6 +117,51 @@ impl InputMap {
         }
     }
 
+    pub fn remove_action(&mut self, action: Action) {
+        for bindings in self.map.values_mut() {
+            bindings.retain(|&(_, a)| a != action);
+        }
+
+        self.axes.retain(|&(_, a)| a != action);
+        self.sequences.retain(|&(_, a)| a != action);
+    }
+
+    pub fn add_sequence(&mut self, sequence: Sequence, action: Action) {
+        self.remove_sequence(sequence, action);
+        self.sequences.push((sequence, action));
+    }
+
+    pub fn remove_sequence(&mut self, sequence: Sequence, action: Action) {
+        self.sequences.retain(|&v| v != (sequence, action));
+    }
+
+    pub fn sequences(&self) -> impl Iterator<Item = (Sequence, Action)> + '_ {
+        self.sequences.iter().copied()
+    }
+
+    pub fn add_axis_binding(&mut self, binding: AxisBinding, action: Action) {
+        self.remove_axis_binding(binding, action);
+        self.axes.push((binding, action));
+    }
+
+    pub fn remove_axis_binding(&mut self, binding: AxisBinding, action: Action) {
+        self.axes.retain(|&v| v != (binding, action));
+    }
+
+    pub fn axis_bindings(&self) -> impl Iterator<Item = (AxisBinding, Action)> + '_ {
+        self.axes.iter().copied()
+    }
+
+    pub fn bindings_for(&self, action: Action) -> impl Iterator<Item = Binding> + '_ {
+        self.map
+            .iter()
+            .flat_map(|(element, bindings)| bindings.iter().map(move |v| (element, v)))
+            .filter(move |(element, (binding, a))| {
+                *a == action && binding.elements().next() == Some(**element)
+            })
+            .map(|(_, (binding, _))| *binding)
+    }
+
     pub fn filter<'s: 'c, 'c>(
         &'s self,
         elements: &'c AHashSet<BindingElement>,
@@ -51,10 +171,7 @@ impl InputMap {
             .iter()
             .flat_map(|el| self.map.get(el))
             .flatten()
-            .filter(move |(binding, _)| {
-                modifiers.contains(binding.modifiers())
-                    && binding.elements().all(|c| elements.contains(&c))
-            })
+            .filter(move |(binding, _)| binding.is_pressed(elements, modifiers))
             .map(|(_, action)| *action)
     }
 }
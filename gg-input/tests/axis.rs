@@ -0,0 +1,116 @@
+use gg_input::{
+    AxisBinding, AxisEvent, ElementState, Event, GamepadAxis, GamepadId, Input, VirtualKeyCode,
+};
+use winit::event::{DeviceId, KeyboardInput, WindowEvent};
+
+gg_input::action! {
+    pub enum TestAction {
+        MoveX = "test.move_x",
+        Throttle = "test.throttle",
+    }
+}
+
+fn input() -> Input {
+    let mut input = Input::new();
+    input.register_action::<TestAction>();
+    input
+}
+
+#[allow(deprecated)]
+fn key(input: &mut Input, code: VirtualKeyCode, state: ElementState) {
+    input.process_event(WindowEvent::KeyboardInput {
+        device_id: unsafe { DeviceId::dummy() },
+        input: KeyboardInput {
+            scancode: 0,
+            state,
+            virtual_keycode: Some(code),
+            modifiers: Default::default(),
+        },
+        is_synthetic: false,
+    });
+}
+
+fn axis_events(input: &Input) -> Vec<f32> {
+    input
+        .events()
+        .filter_map(|ev| match ev {
+            Event::Axis(AxisEvent { value, .. }) => Some(value),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn key_axis() {
+    let mut input = input();
+    input
+        .load_str(r#"[["test.move_x", {"negative": "A", "positive": "D"}]]"#)
+        .unwrap();
+
+    key(&mut input, VirtualKeyCode::D, ElementState::Pressed);
+    assert_eq!(input.axis_value(TestAction::MoveX), 1.0);
+    assert_eq!(axis_events(&input), [1.0]);
+
+    // opposing keys cancel out
+    input.begin_frame();
+    key(&mut input, VirtualKeyCode::A, ElementState::Pressed);
+    assert_eq!(input.axis_value(TestAction::MoveX), 0.0);
+
+    key(&mut input, VirtualKeyCode::D, ElementState::Released);
+    assert_eq!(input.axis_value(TestAction::MoveX), -1.0);
+    assert_eq!(axis_events(&input), [0.0, -1.0]);
+}
+
+#[test]
+fn deadzone() {
+    let mut input = input();
+    input
+        .load_str(
+            r#"[
+                ["test.move_x", {"axis": "GamepadLeftStickX", "deadzone": 0.5}],
+                ["test.throttle", {"axis": "Gamepad1RightTrigger"}]
+            ]"#,
+        )
+        .unwrap();
+
+    let pad = GamepadId(0);
+
+    input.process_gamepad_axis(pad, GamepadAxis::LeftStickX, 0.25);
+    assert_eq!(input.axis_value(TestAction::MoveX), 0.0);
+    assert!(axis_events(&input).is_empty());
+
+    input.process_gamepad_axis(pad, GamepadAxis::LeftStickX, -0.75);
+    assert_eq!(input.axis_value(TestAction::MoveX), -0.5);
+
+    input.process_gamepad_axis(pad, GamepadAxis::LeftStickX, 1.3);
+    assert_eq!(input.axis_value(TestAction::MoveX), 1.0);
+
+    // the throttle is bound to another gamepad
+    input.process_gamepad_axis(pad, GamepadAxis::RightTrigger, 1.0);
+    assert_eq!(input.axis_value(TestAction::Throttle), 0.0);
+
+    input.process_gamepad_axis(GamepadId(1), GamepadAxis::RightTrigger, 0.05);
+    assert_eq!(input.axis_value(TestAction::Throttle), 0.0);
+}
+
+#[test]
+fn invalid_axis() {
+    let mut input = input();
+    let res = input.load_str(r#"[["test.move_x", {"axis": "GamepadLeftStick"}]]"#);
+    assert!(res.is_err());
+
+    let res =
+        input.load_str(r#"[["test.move_x", {"axis": "GamepadLeftStickX", "deadzone": 1.0}]]"#);
+    assert!(res.is_err());
+
+    input.bind_axis(
+        TestAction::MoveX,
+        AxisBinding::Gamepad {
+            id: GamepadId(0),
+            axis: GamepadAxis::LeftStickY,
+            deadzone: 0.0,
+        },
+    );
+    input.process_gamepad_axis(GamepadId(0), GamepadAxis::LeftStickY, 0.25);
+    assert_eq!(input.axis_value(TestAction::MoveX), 0.25);
+}
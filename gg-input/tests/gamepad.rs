@@ -0,0 +1,52 @@
+use gg_input::{Binding, ElementState, GamepadButton, GamepadId, Input};
+
+gg_input::action! {
+    pub enum TestAction {
+        Jump = "test.jump",
+        Pause = "test.pause",
+    }
+}
+
+fn input() -> Input {
+    let mut input = Input::new();
+    input.register_action::<TestAction>();
+    input
+}
+
+#[test]
+fn parse_display() {
+    for s in ["GamepadSouth", "Gamepad1Start", "Gamepad2DPadLeft"] {
+        let binding = s.parse::<Binding>().unwrap();
+        assert_eq!(binding.to_string(), s);
+    }
+
+    assert_eq!(
+        "Gamepad0South".parse::<Binding>().unwrap().to_string(),
+        "GamepadSouth"
+    );
+    assert!("GamepadJump".parse::<Binding>().is_err());
+}
+
+#[test]
+fn button_fires_action() {
+    let mut input = input();
+    input
+        .load_str(r#"[["test.jump", "GamepadSouth"], ["test.pause", "Gamepad1Start"]]"#)
+        .unwrap();
+
+    let pad = GamepadId(0);
+    input.begin_frame();
+    input.process_gamepad_button(pad, GamepadButton::South, ElementState::Pressed);
+    assert!(input.has_action_pressed(TestAction::Jump));
+    assert!(input.is_action_pressed(TestAction::Jump));
+    assert!(input.is_gamepad_button_pressed(pad, GamepadButton::South));
+
+    // the pause binding belongs to the second gamepad
+    input.process_gamepad_button(pad, GamepadButton::Start, ElementState::Pressed);
+    assert!(!input.is_action_pressed(TestAction::Pause));
+
+    input.begin_frame();
+    input.process_gamepad_button(pad, GamepadButton::South, ElementState::Released);
+    assert!(!input.has_action_pressed(TestAction::Jump));
+    assert!(!input.is_action_pressed(TestAction::Jump));
+}
@@ -0,0 +1,65 @@
+use gg_input::{Binding, ElementState, GamepadButton, GamepadId, Input};
+
+gg_input::action! {
+    pub enum TestAction {
+        Save = "test.save",
+        Open = "test.open",
+    }
+}
+
+fn input() -> Input {
+    let mut input = Input::new();
+    input.register_action::<TestAction>();
+    input
+}
+
+#[test]
+fn valid_map() {
+    let mut input = input();
+    input
+        .load_str(r#"[["test.save", "Ctrl-S"], ["test.open", "Ctrl-O"]]"#)
+        .unwrap();
+
+    let save = input.bindings_for(TestAction::Save).collect::<Vec<_>>();
+    assert_eq!(save, ["Ctrl-S".parse::<Binding>().unwrap()]);
+}
+
+#[test]
+fn unknown_action() {
+    let mut input = input();
+    let err = input
+        .load_str(r#"[["test.save", "Ctrl-S"], ["test.sav", "Ctrl-W"]]"#)
+        .unwrap_err()
+        .to_string();
+
+    assert!(err.contains("`test.sav`"), "{}", err);
+    assert!(err.contains("Ctrl-W"), "{}", err);
+    assert_eq!(input.bindings_for(TestAction::Save).count(), 0);
+}
+
+#[test]
+fn rebind_and_save() {
+    let mut input = input();
+    input
+        .load_str(r#"[["test.save", "Ctrl-S"], ["test.open", "Ctrl-O"]]"#)
+        .unwrap();
+
+    input.unbind(TestAction::Save);
+    input.bind(TestAction::Save, "GamepadStart".parse().unwrap());
+    input.bind(TestAction::Save, "F2".parse().unwrap());
+    assert_eq!(input.bindings_for(TestAction::Save).count(), 2);
+
+    let saved = input.save_str().unwrap();
+    assert!(!saved.contains("Ctrl-S"), "{}", saved);
+
+    let mut reloaded = self::input();
+    reloaded.load_str(&saved).unwrap();
+    assert_eq!(reloaded.save_str().unwrap(), saved);
+
+    let open = reloaded.bindings_for(TestAction::Open).collect::<Vec<_>>();
+    assert_eq!(open, ["Ctrl-O".parse::<Binding>().unwrap()]);
+
+    reloaded.process_gamepad_button(GamepadId(0), GamepadButton::Start, ElementState::Pressed);
+    assert!(reloaded.has_action_pressed(TestAction::Save));
+    assert!(!reloaded.is_action_pressed(TestAction::Open));
+}
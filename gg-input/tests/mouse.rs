@@ -0,0 +1,123 @@
+use std::time::{Duration, Instant};
+
+use gg_input::{DragEvent, ElementState, Event, Input, MouseButton, MouseEvent};
+use gg_math::Vec2;
+use winit::dpi::PhysicalPosition;
+use winit::event::{DeviceId, WindowEvent};
+
+#[allow(deprecated)]
+fn button(input: &mut Input, button: MouseButton, state: ElementState) {
+    input.process_event(WindowEvent::MouseInput {
+        device_id: unsafe { DeviceId::dummy() },
+        state,
+        button,
+        modifiers: Default::default(),
+    });
+}
+
+#[allow(deprecated)]
+fn move_to(input: &mut Input, x: f64, y: f64) {
+    input.process_event(WindowEvent::CursorMoved {
+        device_id: unsafe { DeviceId::dummy() },
+        position: PhysicalPosition::new(x, y),
+        modifiers: Default::default(),
+    });
+}
+
+fn click(input: &mut Input, at: Instant) -> u32 {
+    input.begin_frame_at(at);
+    button(input, MouseButton::Left, ElementState::Pressed);
+    let held = input.click_count(MouseButton::Left);
+    button(input, MouseButton::Left, ElementState::Released);
+    assert_eq!(input.click_count(MouseButton::Left), 0);
+
+    let clicks = input
+        .events()
+        .filter_map(|ev| match ev {
+            Event::Mouse(MouseEvent { clicks, .. }) => Some(clicks),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(clicks, [held, held]);
+    held
+}
+
+#[test]
+fn double_click() {
+    let mut input = Input::new();
+    let start = Instant::now();
+
+    assert_eq!(click(&mut input, start), 1);
+    assert_eq!(click(&mut input, start + Duration::from_millis(200)), 2);
+    assert_eq!(click(&mut input, start + Duration::from_millis(400)), 3);
+}
+
+#[test]
+fn slow_clicks() {
+    let mut input = Input::new();
+    let start = Instant::now();
+
+    assert_eq!(click(&mut input, start), 1);
+    assert_eq!(click(&mut input, start + Duration::from_secs(1)), 1);
+}
+
+#[test]
+fn clicks_far_apart() {
+    let mut input = Input::new();
+    let start = Instant::now();
+
+    assert_eq!(click(&mut input, start), 1);
+    move_to(&mut input, 10.0, 0.0);
+    assert_eq!(click(&mut input, start + Duration::from_millis(100)), 1);
+}
+
+fn drags(input: &Input) -> Vec<DragEvent> {
+    input
+        .events()
+        .filter_map(|ev| match ev {
+            Event::Drag(ev) => Some(ev),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn drag() {
+    let mut input = Input::new();
+
+    move_to(&mut input, 10.0, 10.0);
+    button(&mut input, MouseButton::Left, ElementState::Pressed);
+
+    // small movements don't start a drag
+    move_to(&mut input, 12.0, 10.0);
+    assert!(drags(&input).is_empty());
+
+    input.begin_frame();
+    move_to(&mut input, 20.0, 10.0);
+    move_to(&mut input, 25.0, 15.0);
+
+    let start = Vec2::new(10.0, 10.0);
+    assert_eq!(
+        drags(&input),
+        [
+            DragEvent {
+                button: MouseButton::Left,
+                start,
+                current: Vec2::new(20.0, 10.0),
+                delta: Vec2::new(10.0, 0.0),
+            },
+            DragEvent {
+                button: MouseButton::Left,
+                start,
+                current: Vec2::new(25.0, 15.0),
+                delta: Vec2::new(5.0, 5.0),
+            },
+        ]
+    );
+
+    input.begin_frame();
+    button(&mut input, MouseButton::Left, ElementState::Released);
+    move_to(&mut input, 40.0, 40.0);
+    assert!(drags(&input).is_empty());
+}
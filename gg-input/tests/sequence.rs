@@ -0,0 +1,122 @@
+use std::time::{Duration, Instant};
+
+use gg_input::{Binding, ElementState, Input, Sequence, VirtualKeyCode};
+use winit::event::{DeviceId, KeyboardInput, ModifiersState, WindowEvent};
+
+gg_input::action! {
+    pub enum TestAction {
+        Save = "test.save",
+        Open = "test.open",
+    }
+}
+
+fn input() -> Input {
+    let mut input = Input::new();
+    input.register_action::<TestAction>();
+    input
+        .load_str(r#"[["test.save", "Ctrl-K Ctrl-S"], ["test.open", "Ctrl-K O"]]"#)
+        .unwrap();
+    input
+}
+
+#[allow(deprecated)]
+fn key(input: &mut Input, code: VirtualKeyCode, state: ElementState) {
+    input.process_event(WindowEvent::KeyboardInput {
+        device_id: unsafe { DeviceId::dummy() },
+        input: KeyboardInput {
+            scancode: 0,
+            state,
+            virtual_keycode: Some(code),
+            modifiers: Default::default(),
+        },
+        is_synthetic: false,
+    });
+}
+
+fn tap(input: &mut Input, code: VirtualKeyCode) {
+    key(input, code, ElementState::Pressed);
+    key(input, code, ElementState::Released);
+}
+
+fn ctrl(input: &mut Input, pressed: bool) {
+    let (state, modifiers) = match pressed {
+        true => (ElementState::Pressed, ModifiersState::CTRL),
+        false => (ElementState::Released, ModifiersState::empty()),
+    };
+
+    key(input, VirtualKeyCode::LControl, state);
+    input.process_event(WindowEvent::ModifiersChanged(modifiers));
+}
+
+#[test]
+fn two_chords() {
+    let mut input = input();
+    let start = Instant::now();
+
+    input.begin_frame_at(start);
+    ctrl(&mut input, true);
+    tap(&mut input, VirtualKeyCode::K);
+    assert!(!input.has_action_pressed(TestAction::Save));
+
+    input.begin_frame_at(start + Duration::from_millis(300));
+    tap(&mut input, VirtualKeyCode::S);
+    assert!(input.has_action_pressed(TestAction::Save));
+    assert!(!input.has_action_pressed(TestAction::Open));
+    assert!(!input.is_action_pressed(TestAction::Save));
+
+    // the sequence has to be started over
+    input.begin_frame_at(start + Duration::from_millis(400));
+    tap(&mut input, VirtualKeyCode::S);
+    assert!(!input.has_action_pressed(TestAction::Save));
+}
+
+#[test]
+fn timeout() {
+    let mut input = input();
+    let start = Instant::now();
+
+    input.begin_frame_at(start);
+    ctrl(&mut input, true);
+    tap(&mut input, VirtualKeyCode::K);
+
+    input.begin_frame_at(start + Duration::from_secs(2));
+    tap(&mut input, VirtualKeyCode::S);
+    assert!(!input.has_action_pressed(TestAction::Save));
+
+    input.config_mut().sequence_timeout = Duration::from_secs(5);
+    input.begin_frame_at(start + Duration::from_secs(3));
+    tap(&mut input, VirtualKeyCode::K);
+    input.begin_frame_at(start + Duration::from_secs(7));
+    tap(&mut input, VirtualKeyCode::S);
+    assert!(input.has_action_pressed(TestAction::Save));
+}
+
+#[test]
+fn mismatch_resets() {
+    let mut input = input();
+    let start = Instant::now();
+
+    input.begin_frame_at(start);
+    ctrl(&mut input, true);
+    tap(&mut input, VirtualKeyCode::K);
+    tap(&mut input, VirtualKeyCode::X);
+    tap(&mut input, VirtualKeyCode::S);
+    assert!(!input.has_action_pressed(TestAction::Save));
+
+    // modifier keys don't count as steps
+    tap(&mut input, VirtualKeyCode::K);
+    ctrl(&mut input, false);
+    tap(&mut input, VirtualKeyCode::O);
+    assert!(input.has_action_pressed(TestAction::Open));
+}
+
+#[test]
+fn hints() {
+    let hint = |s: &str| s.parse::<Binding>().unwrap().hint();
+    assert_eq!(hint("Ctrl-Shift-S"), "Ctrl+Shift+S");
+    assert_eq!(hint("Alt-MouseLeft"), "Alt+MouseLeft");
+    assert_eq!(hint("Minus"), "Minus");
+
+    let sequence = "Ctrl-K Ctrl-S".parse::<Sequence>().unwrap();
+    assert_eq!(sequence.hint(), "Ctrl+K, Ctrl+S");
+}
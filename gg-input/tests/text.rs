@@ -0,0 +1,28 @@
+use gg_input::{Event, Input, TextEvent};
+use winit::event::WindowEvent;
+
+fn text(input: &Input) -> String {
+    input
+        .events()
+        .filter_map(|ev| match ev {
+            Event::Text(TextEvent { ch }) => Some(ch),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn received_chars() {
+    let mut input = Input::new();
+
+    for ch in [
+        'h', 'é', '\r', 'ї', '\u{8}', '\t', '字', '\u{7f}', '\u{f700}',
+    ] {
+        input.process_event(WindowEvent::ReceivedCharacter(ch));
+    }
+
+    assert_eq!(text(&input), "héї字");
+
+    input.begin_frame();
+    assert_eq!(text(&input), "");
+}
@@ -3,8 +3,8 @@ mod fps_counter;
 use std::time::Instant;
 
 use gg_assets::{Assets, DirSource};
-use gg_graphics::{Backend, FontDb, GraphicsEncoder, TextLayouter};
-use gg_graphics_impl::{BackendImpl, BackendSettings};
+use gg_graphics::{Backend, FontDb, GraphicsEncoder, RasterizationQuality, TextLayouter};
+use gg_graphics_impl::{BackendImpl, BackendSettings, PresentModePref};
 use gg_input::Input;
 use gg_math::{Rect, Vec2};
 use gg_ui::{views, AppendChild, UiAction, UiContext, View, ViewExt};
@@ -58,9 +58,13 @@ fn main() -> Result<()> {
         .build(&event_loop)?;
 
     let settings = BackendSettings {
-        vsync: false,
+        present_mode: PresentModePref::Immediate,
         prefer_low_power_gpu: true,
         image_cell_size: Vec2::splat(8),
+        max_texture_size: None,
+        standalone_oversized_images: true,
+        subpixel_text: false,
+        glyph_image_quality: RasterizationQuality::Triangle,
     };
 
     let mut backend = BackendImpl::new(settings, &assets, &window)?;
@@ -73,8 +77,6 @@ fn main() -> Result<()> {
     let mut ui = gg_ui::Driver::new();
     let mut text_layouter = TextLayouter::new();
 
-    let mut dt = 0.0;
-
     event_loop.run(move |event, _, control_flow| match event {
         Event::NewEvents(_) => {
             input.begin_frame();
@@ -93,6 +95,8 @@ fn main() -> Result<()> {
             let size = window.inner_size();
             let size = Vec2::new(size.width, size.height);
             backend.resize(size);
+            backend.set_cursor_position(Some(input.mouse_pos()));
+            backend.update_window(&window);
 
             let mut encoder = if let Some(list) = recycled_list.take() {
                 GraphicsEncoder::new_recycled(&main_canvas, list)
@@ -111,17 +115,17 @@ fn main() -> Result<()> {
                 text_layouter: &mut text_layouter,
                 encoder: &mut encoder,
                 input: &input,
-                dt,
             };
 
             ui.run(build_ui(fps_counter.fps()), ui_ctx, &mut ());
 
             backend.submit(encoder.finish());
-            backend.present(&mut assets);
+            if let Err(error) = backend.present(&mut assets) {
+                tracing::warn!("cannot present frame: {}", error);
+            }
             recycled_list = backend.recycle_list();
 
             let elapsed = frame_start.elapsed();
-            dt = elapsed.as_secs_f32();
             fps_counter.add_sample(elapsed);
             frame_start = Instant::now();
 
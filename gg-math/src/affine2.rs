@@ -5,6 +5,7 @@ use num_traits::Float;
 use crate::{Rotation2, Vec2};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Affine2<T> {
     pub x: Vec2<T>,
@@ -52,15 +53,31 @@ impl<T: Float> Affine2<T> {
     }
 
     #[inline]
-    pub fn inverse(&self) -> Affine2<T> {
-        let det = self.x.x * self.y.y - self.x.y * self.y.x;
+    pub fn determinant(&self) -> T {
+        self.x.x * self.y.y - self.x.y * self.y.x
+    }
+
+    // inverts the linear part, then moves the translation through it
+    #[inline]
+    pub fn try_inverse(&self) -> Option<Affine2<T>> {
+        let det = self.determinant();
+        if det.abs() < T::epsilon() {
+            return None;
+        }
+
         let x = Vec2::new(self.y.y, -self.x.y) / det;
         let y = Vec2::new(-self.y.x, self.x.x) / det;
         let z = Vec2::new(
             -x.x * self.z.x - y.x * self.z.y,
             -x.y * self.z.x - y.y * self.z.y,
         );
-        Affine2::new(x, y, z)
+
+        Some(Affine2::new(x, y, z))
+    }
+
+    #[inline]
+    pub fn inverse(&self) -> Affine2<T> {
+        self.try_inverse().expect("transform is not invertible")
     }
 
     fn x_row(&self) -> Vec2<T> {
@@ -1,18 +1,47 @@
 mod affine2;
+mod mat3;
+mod mat4;
 mod rect;
 mod rotation2;
 mod side_offsets;
 mod vec2;
+mod vec3;
+mod vec4;
 
 use num_traits::Float;
 
 pub use self::affine2::Affine2;
+pub use self::mat3::Mat3;
+pub use self::mat4::Mat4;
 pub use self::rect::Rect;
 pub use self::rotation2::Rotation2;
 pub use self::side_offsets::SideOffsets;
 pub use self::vec2::Vec2;
+pub use self::vec3::Vec3;
+pub use self::vec4::Vec4;
 
 #[inline]
 pub fn lerp<T: Float>(start: T, end: T, time: T) -> T {
     start + (end - start) * time
 }
+
+// unlike `Ord::clamp`, works for floats. NaN is passed through
+#[inline]
+pub fn clamp<T: PartialOrd>(v: T, lo: T, hi: T) -> T {
+    if v < lo {
+        lo
+    } else if v > hi {
+        hi
+    } else {
+        v
+    }
+}
+
+// hermite interpolation from 0 at `edge0` to 1 at `edge1`, flat outside
+#[inline]
+pub fn smoothstep<T: Float>(edge0: T, edge1: T, x: T) -> T {
+    let (zero, one) = (T::zero(), T::one());
+    let three = one + one + one;
+    let t = clamp((x - edge0) / (edge1 - edge0), zero, one);
+    t * t * (three - (one + one) * t)
+}
@@ -0,0 +1,148 @@
+use std::ops::Mul;
+
+use num_traits::Float;
+
+use crate::{Affine2, Vec2, Vec3};
+
+// column-major, usable as a homogeneous 2D transform
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Mat3<T> {
+    pub x: Vec3<T>,
+    pub y: Vec3<T>,
+    pub z: Vec3<T>,
+}
+
+impl<T> Mat3<T> {
+    #[inline]
+    pub fn new(x: Vec3<T>, y: Vec3<T>, z: Vec3<T>) -> Mat3<T> {
+        Mat3 { x, y, z }
+    }
+}
+
+impl<T: Float> Mat3<T> {
+    #[inline]
+    pub fn identity() -> Mat3<T> {
+        let (zero, one) = (T::zero(), T::one());
+        Mat3::new(
+            Vec3::new(one, zero, zero),
+            Vec3::new(zero, one, zero),
+            Vec3::new(zero, zero, one),
+        )
+    }
+
+    // maps the rectangle to [-1, 1] clip space, `bottom` may be greater than
+    // `top` to flip the y axis
+    #[inline]
+    pub fn orthographic(left: T, right: T, bottom: T, top: T) -> Mat3<T> {
+        let (zero, one, two) = (T::zero(), T::one(), T::one() + T::one());
+        Mat3::new(
+            Vec3::new(two / (right - left), zero, zero),
+            Vec3::new(zero, two / (top - bottom), zero),
+            Vec3::new(
+                -(right + left) / (right - left),
+                -(top + bottom) / (top - bottom),
+                one,
+            ),
+        )
+    }
+
+    #[inline]
+    pub fn transpose(&self) -> Mat3<T> {
+        Mat3::new(self.x_row(), self.y_row(), self.z_row())
+    }
+
+    #[inline]
+    pub fn determinant(&self) -> T {
+        self.x.dot(self.y.cross(self.z))
+    }
+
+    #[inline]
+    pub fn try_inverse(&self) -> Option<Mat3<T>> {
+        let det = self.determinant();
+        if det.abs() < T::epsilon() {
+            return None;
+        }
+
+        let rows = Mat3::new(
+            self.y.cross(self.z),
+            self.z.cross(self.x),
+            self.x.cross(self.y),
+        );
+
+        Some(rows.transpose() * (T::one() / det))
+    }
+
+    #[inline]
+    pub fn inverse(&self) -> Mat3<T> {
+        self.try_inverse().expect("matrix is not invertible")
+    }
+
+    fn x_row(&self) -> Vec3<T> {
+        Vec3::new(self.x.x, self.y.x, self.z.x)
+    }
+
+    fn y_row(&self) -> Vec3<T> {
+        Vec3::new(self.x.y, self.y.y, self.z.y)
+    }
+
+    fn z_row(&self) -> Vec3<T> {
+        Vec3::new(self.x.z, self.y.z, self.z.z)
+    }
+
+    #[inline]
+    pub fn transform_vector(&self, vec: Vec2<T>) -> Vec2<T> {
+        (*self * vec.extend(T::zero())).truncate()
+    }
+
+    #[inline]
+    pub fn transform_point(&self, point: Vec2<T>) -> Vec2<T> {
+        let v = *self * point.extend(T::one());
+        v.truncate() / v.z
+    }
+}
+
+impl<T: Float> Mul for Mat3<T> {
+    type Output = Mat3<T>;
+
+    #[inline]
+    fn mul(self, rhs: Mat3<T>) -> Mat3<T> {
+        Mat3::new(self * rhs.x, self * rhs.y, self * rhs.z)
+    }
+}
+
+impl<T: Float> Mul<Vec3<T>> for Mat3<T> {
+    type Output = Vec3<T>;
+
+    #[inline]
+    fn mul(self, rhs: Vec3<T>) -> Vec3<T> {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+}
+
+impl<T: Float> Mul<T> for Mat3<T> {
+    type Output = Mat3<T>;
+
+    #[inline]
+    fn mul(self, rhs: T) -> Mat3<T> {
+        Mat3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Float> From<Affine2<T>> for Mat3<T> {
+    #[inline]
+    fn from(affine: Affine2<T>) -> Mat3<T> {
+        Mat3::new(
+            affine.x.extend(T::zero()),
+            affine.y.extend(T::zero()),
+            affine.z.extend(T::one()),
+        )
+    }
+}
+
+impl<T: Float> Default for Mat3<T> {
+    fn default() -> Mat3<T> {
+        Mat3::identity()
+    }
+}
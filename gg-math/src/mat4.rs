@@ -0,0 +1,219 @@
+use std::ops::Mul;
+
+use num_traits::Float;
+
+use crate::{Vec3, Vec4};
+
+// column-major, projections follow the wgpu convention of a right-handed view
+// space and [0, 1] depth
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Mat4<T> {
+    pub x: Vec4<T>,
+    pub y: Vec4<T>,
+    pub z: Vec4<T>,
+    pub w: Vec4<T>,
+}
+
+impl<T> Mat4<T> {
+    #[inline]
+    pub fn new(x: Vec4<T>, y: Vec4<T>, z: Vec4<T>, w: Vec4<T>) -> Mat4<T> {
+        Mat4 { x, y, z, w }
+    }
+}
+
+impl<T: Float> Mat4<T> {
+    #[inline]
+    pub fn identity() -> Mat4<T> {
+        let (zero, one) = (T::zero(), T::one());
+        Mat4::new(
+            Vec4::new(one, zero, zero, zero),
+            Vec4::new(zero, one, zero, zero),
+            Vec4::new(zero, zero, one, zero),
+            Vec4::new(zero, zero, zero, one),
+        )
+    }
+
+    #[inline]
+    pub fn translation(vec: Vec3<T>) -> Mat4<T> {
+        let mut mat = Mat4::identity();
+        mat.w = vec.extend(T::one());
+        mat
+    }
+
+    #[inline]
+    pub fn scaling(vec: Vec3<T>) -> Mat4<T> {
+        let zero = T::zero();
+        Mat4::new(
+            Vec4::new(vec.x, zero, zero, zero),
+            Vec4::new(zero, vec.y, zero, zero),
+            Vec4::new(zero, zero, vec.z, zero),
+            Vec4::new(zero, zero, zero, T::one()),
+        )
+    }
+
+    #[inline]
+    pub fn orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Mat4<T> {
+        let (zero, one, two) = (T::zero(), T::one(), T::one() + T::one());
+        Mat4::new(
+            Vec4::new(two / (right - left), zero, zero, zero),
+            Vec4::new(zero, two / (top - bottom), zero, zero),
+            Vec4::new(zero, zero, one / (near - far), zero),
+            Vec4::new(
+                -(right + left) / (right - left),
+                -(top + bottom) / (top - bottom),
+                near / (near - far),
+                one,
+            ),
+        )
+    }
+
+    // `fov_y` is in radians, `aspect` is width / height
+    #[inline]
+    pub fn perspective(fov_y: T, aspect: T, near: T, far: T) -> Mat4<T> {
+        let (zero, one, two) = (T::zero(), T::one(), T::one() + T::one());
+        let f = one / (fov_y / two).tan();
+        Mat4::new(
+            Vec4::new(f / aspect, zero, zero, zero),
+            Vec4::new(zero, f, zero, zero),
+            Vec4::new(zero, zero, far / (near - far), -one),
+            Vec4::new(zero, zero, near * far / (near - far), zero),
+        )
+    }
+
+    #[inline]
+    pub fn transpose(&self) -> Mat4<T> {
+        let [x, y, z, w] = self.columns();
+        Mat4::new(
+            Vec4::new(x[0], y[0], z[0], w[0]),
+            Vec4::new(x[1], y[1], z[1], w[1]),
+            Vec4::new(x[2], y[2], z[2], w[2]),
+            Vec4::new(x[3], y[3], z[3], w[3]),
+        )
+    }
+
+    fn columns(&self) -> [[T; 4]; 4] {
+        [self.x.into(), self.y.into(), self.z.into(), self.w.into()]
+    }
+
+    // expansion by 2x2 minors of the first two and the last two columns
+    fn minors(&self) -> ([T; 6], [T; 6]) {
+        let [a0, a1, a2, a3] = self.columns();
+
+        let s = [
+            a0[0] * a1[1] - a1[0] * a0[1],
+            a0[0] * a1[2] - a1[0] * a0[2],
+            a0[0] * a1[3] - a1[0] * a0[3],
+            a0[1] * a1[2] - a1[1] * a0[2],
+            a0[1] * a1[3] - a1[1] * a0[3],
+            a0[2] * a1[3] - a1[2] * a0[3],
+        ];
+
+        let c = [
+            a2[0] * a3[1] - a3[0] * a2[1],
+            a2[0] * a3[2] - a3[0] * a2[2],
+            a2[0] * a3[3] - a3[0] * a2[3],
+            a2[1] * a3[2] - a3[1] * a2[2],
+            a2[1] * a3[3] - a3[1] * a2[3],
+            a2[2] * a3[3] - a3[2] * a2[3],
+        ];
+
+        (s, c)
+    }
+
+    #[inline]
+    pub fn determinant(&self) -> T {
+        let (s, c) = self.minors();
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
+    pub fn try_inverse(&self) -> Option<Mat4<T>> {
+        let det = self.determinant();
+        if det.abs() < T::epsilon() {
+            return None;
+        }
+
+        let [a0, a1, a2, a3] = self.columns();
+        let (s, c) = self.minors();
+
+        let x = Vec4::new(
+            a1[1] * c[5] - a1[2] * c[4] + a1[3] * c[3],
+            -a0[1] * c[5] + a0[2] * c[4] - a0[3] * c[3],
+            a3[1] * s[5] - a3[2] * s[4] + a3[3] * s[3],
+            -a2[1] * s[5] + a2[2] * s[4] - a2[3] * s[3],
+        );
+
+        let y = Vec4::new(
+            -a1[0] * c[5] + a1[2] * c[2] - a1[3] * c[1],
+            a0[0] * c[5] - a0[2] * c[2] + a0[3] * c[1],
+            -a3[0] * s[5] + a3[2] * s[2] - a3[3] * s[1],
+            a2[0] * s[5] - a2[2] * s[2] + a2[3] * s[1],
+        );
+
+        let z = Vec4::new(
+            a1[0] * c[4] - a1[1] * c[2] + a1[3] * c[0],
+            -a0[0] * c[4] + a0[1] * c[2] - a0[3] * c[0],
+            a3[0] * s[4] - a3[1] * s[2] + a3[3] * s[0],
+            -a2[0] * s[4] + a2[1] * s[2] - a2[3] * s[0],
+        );
+
+        let w = Vec4::new(
+            -a1[0] * c[3] + a1[1] * c[1] - a1[2] * c[0],
+            a0[0] * c[3] - a0[1] * c[1] + a0[2] * c[0],
+            -a3[0] * s[3] + a3[1] * s[1] - a3[2] * s[0],
+            a2[0] * s[3] - a2[1] * s[1] + a2[2] * s[0],
+        );
+
+        Some(Mat4::new(x, y, z, w) * (T::one() / det))
+    }
+
+    #[inline]
+    pub fn inverse(&self) -> Mat4<T> {
+        self.try_inverse().expect("matrix is not invertible")
+    }
+
+    #[inline]
+    pub fn transform_vector(&self, vec: Vec3<T>) -> Vec3<T> {
+        (*self * vec.extend(T::zero())).truncate()
+    }
+
+    #[inline]
+    pub fn transform_point(&self, point: Vec3<T>) -> Vec3<T> {
+        let v = *self * point.extend(T::one());
+        v.truncate() / v.w
+    }
+}
+
+impl<T: Float> Mul for Mat4<T> {
+    type Output = Mat4<T>;
+
+    #[inline]
+    fn mul(self, rhs: Mat4<T>) -> Mat4<T> {
+        Mat4::new(self * rhs.x, self * rhs.y, self * rhs.z, self * rhs.w)
+    }
+}
+
+impl<T: Float> Mul<Vec4<T>> for Mat4<T> {
+    type Output = Vec4<T>;
+
+    #[inline]
+    fn mul(self, rhs: Vec4<T>) -> Vec4<T> {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
+    }
+}
+
+impl<T: Float> Mul<T> for Mat4<T> {
+    type Output = Mat4<T>;
+
+    #[inline]
+    fn mul(self, rhs: T) -> Mat4<T> {
+        Mat4::new(self.x * rhs, self.y * rhs, self.z * rhs, self.w * rhs)
+    }
+}
+
+impl<T: Float> Default for Mat4<T> {
+    fn default() -> Mat4<T> {
+        Mat4::identity()
+    }
+}
@@ -3,6 +3,7 @@ use num_traits::{Float, Num, NumCast};
 use crate::{SideOffsets, Vec2};
 
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Rect<T> {
     pub min: Vec2<T>,
@@ -98,6 +99,11 @@ impl<T: Num + Copy> Rect<T> {
         )
     }
 
+    #[inline]
+    pub fn translate(&self, offset: Vec2<T>) -> Rect<T> {
+        Rect::from_min_max(self.min + offset, self.max + offset)
+    }
+
     #[inline]
     pub fn vertices(&self) -> [Vec2<T>; 4] {
         [
@@ -124,6 +130,16 @@ impl<T: Ord + Copy> Rect<T> {
         let max = self.max.min(rhs.max).max(min);
         Rect::from_min_max(min, max)
     }
+
+    #[inline]
+    pub fn union(&self, rhs: &Rect<T>) -> Rect<T> {
+        Rect::from_min_max(self.min.min(rhs.min), self.max.max(rhs.max))
+    }
+
+    #[inline]
+    pub fn clamp_point(&self, point: Vec2<T>) -> Vec2<T> {
+        point.clamp(self.min, self.max)
+    }
 }
 
 impl<T: Float> Rect<T> {
@@ -133,6 +149,16 @@ impl<T: Float> Rect<T> {
         let max = self.max.fmin(rhs.max).fmax(min);
         Rect::from_min_max(min, max)
     }
+
+    #[inline]
+    pub fn f_union(&self, rhs: &Rect<T>) -> Rect<T> {
+        Rect::from_min_max(self.min.fmin(rhs.min), self.max.fmax(rhs.max))
+    }
+
+    #[inline]
+    pub fn f_clamp_point(&self, point: Vec2<T>) -> Vec2<T> {
+        point.fclamp(self.min, self.max)
+    }
 }
 
 impl<T: PartialOrd + Copy> Rect<T> {
@@ -5,6 +5,7 @@ use num_traits::Float;
 use crate::Vec2;
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Rotation2<T> {
     pub cos: T,
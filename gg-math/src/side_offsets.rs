@@ -3,6 +3,7 @@ use num_traits::Num;
 use crate::Vec2;
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SideOffsets<T> {
     pub top: T,
     pub right: T,
@@ -5,9 +5,10 @@ use std::ops::{
 
 use num_traits::{Float, Num, NumCast, Signed, Zero};
 
-use crate::lerp;
+use crate::{lerp, Vec3};
 
 #[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Vec2<T> {
     pub x: T,
@@ -62,6 +63,11 @@ impl<T> Vec2<T> {
         self
     }
 
+    #[inline]
+    pub fn extend(self, z: T) -> Vec3<T> {
+        Vec3::new(self.x, self.y, z)
+    }
+
     #[inline]
     pub fn map<U, F>(self, mut f: F) -> Vec2<U>
     where
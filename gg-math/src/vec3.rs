@@ -0,0 +1,484 @@
+use std::fmt::{self, Debug};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Not, Sub, SubAssign,
+};
+
+use num_traits::{Float, Num, NumCast, Signed, Zero};
+
+use crate::{lerp, Vec2, Vec4};
+
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Debug> Debug for Vec3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:?}, {:?}, {:?}]", self.x, self.y, self.z)
+    }
+}
+
+impl<T> Vec3<T> {
+    #[inline]
+    pub const fn new(x: T, y: T, z: T) -> Vec3<T> {
+        Vec3 { x, y, z }
+    }
+
+    #[inline]
+    pub const fn splat(v: T) -> Vec3<T>
+    where
+        T: Copy,
+    {
+        Vec3::new(v, v, v)
+    }
+
+    #[inline]
+    pub fn zero() -> Vec3<T>
+    where
+        T: Zero,
+    {
+        Vec3::new(T::zero(), T::zero(), T::zero())
+    }
+
+    #[inline]
+    pub fn set_x(mut self, x: T) -> Vec3<T> {
+        self.x = x;
+        self
+    }
+
+    #[inline]
+    pub fn set_y(mut self, y: T) -> Vec3<T> {
+        self.y = y;
+        self
+    }
+
+    #[inline]
+    pub fn set_z(mut self, z: T) -> Vec3<T> {
+        self.z = z;
+        self
+    }
+
+    #[inline]
+    pub fn truncate(self) -> Vec2<T> {
+        Vec2::new(self.x, self.y)
+    }
+
+    #[inline]
+    pub fn extend(self, w: T) -> Vec4<T> {
+        Vec4::new(self.x, self.y, self.z, w)
+    }
+
+    #[inline]
+    pub fn map<U, F>(self, mut f: F) -> Vec3<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Vec3::new(f(self.x), f(self.y), f(self.z))
+    }
+
+    #[inline]
+    pub fn try_map<U, E, F>(self, mut f: F) -> Result<Vec3<U>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        Ok(Vec3::new(f(self.x)?, f(self.y)?, f(self.z)?))
+    }
+
+    #[inline]
+    pub fn zip_map<U, F>(self, rhs: Vec3<T>, mut f: F) -> Vec3<U>
+    where
+        F: FnMut(T, T) -> U,
+    {
+        Vec3::new(f(self.x, rhs.x), f(self.y, rhs.y), f(self.z, rhs.z))
+    }
+
+    #[inline]
+    pub fn fold<U, F>(self, mut acc: U, mut f: F) -> U
+    where
+        F: FnMut(U, T) -> U,
+    {
+        acc = f(acc, self.x);
+        acc = f(acc, self.y);
+        acc = f(acc, self.z);
+        acc
+    }
+
+    #[inline]
+    pub fn reduce<F>(self, mut f: F) -> T
+    where
+        F: FnMut(T, T) -> T,
+    {
+        let xy = f(self.x, self.y);
+        f(xy, self.z)
+    }
+
+    #[inline]
+    pub fn try_cast<U>(self) -> Option<Vec3<U>>
+    where
+        T: NumCast,
+        U: NumCast,
+    {
+        self.try_map(|v| U::from(v).ok_or(())).ok()
+    }
+
+    #[inline]
+    pub fn cast<U>(self) -> Vec3<U>
+    where
+        T: NumCast,
+        U: NumCast,
+    {
+        self.try_cast().expect("cast failed")
+    }
+}
+
+impl<T: Num + Copy> Vec3<T> {
+    #[inline]
+    pub fn sum(self) -> T {
+        self.reduce(T::add)
+    }
+
+    #[inline]
+    pub fn product(self) -> T {
+        self.reduce(T::mul)
+    }
+
+    #[inline]
+    pub fn dot(self, rhs: Vec3<T>) -> T {
+        (self * rhs).sum()
+    }
+
+    #[inline]
+    pub fn cross(self, rhs: Vec3<T>) -> Vec3<T> {
+        Vec3::new(
+            self.y * rhs.z - self.z * rhs.y,
+            self.z * rhs.x - self.x * rhs.z,
+            self.x * rhs.y - self.y * rhs.x,
+        )
+    }
+
+    #[inline]
+    pub fn length_squared(self) -> T {
+        (self * self).sum()
+    }
+
+    #[inline]
+    pub fn abs(self) -> Vec3<T>
+    where
+        T: Signed,
+    {
+        self.map(|v| v.abs())
+    }
+}
+
+impl<T: PartialOrd> Vec3<T> {
+    #[inline]
+    pub fn cmp_lt(self, rhs: Vec3<T>) -> Vec3<bool> {
+        self.zip_map(rhs, |a, b| a < b)
+    }
+
+    #[inline]
+    pub fn cmp_le(self, rhs: Vec3<T>) -> Vec3<bool> {
+        self.zip_map(rhs, |a, b| a <= b)
+    }
+
+    #[inline]
+    pub fn cmp_eq(self, rhs: Vec3<T>) -> Vec3<bool> {
+        self.zip_map(rhs, |a, b| a == b)
+    }
+
+    #[inline]
+    pub fn cmp_ge(self, rhs: Vec3<T>) -> Vec3<bool> {
+        self.zip_map(rhs, |a, b| a >= b)
+    }
+
+    #[inline]
+    pub fn cmp_gt(self, rhs: Vec3<T>) -> Vec3<bool> {
+        self.zip_map(rhs, |a, b| a > b)
+    }
+
+    #[inline]
+    pub fn cmp_ne(self, rhs: Vec3<T>) -> Vec3<bool> {
+        self.zip_map(rhs, |a, b| a != b)
+    }
+}
+
+impl<T: Ord> Vec3<T> {
+    #[inline]
+    pub fn min(self, rhs: Vec3<T>) -> Vec3<T> {
+        self.zip_map(rhs, std::cmp::min)
+    }
+
+    #[inline]
+    pub fn max(self, rhs: Vec3<T>) -> Vec3<T> {
+        self.zip_map(rhs, std::cmp::max)
+    }
+
+    #[inline]
+    pub fn clamp(self, lo: Vec3<T>, hi: Vec3<T>) -> Vec3<T> {
+        self.max(lo).min(hi)
+    }
+
+    #[inline]
+    pub fn min_component(self) -> T {
+        self.reduce(std::cmp::min)
+    }
+
+    #[inline]
+    pub fn max_component(self) -> T {
+        self.reduce(std::cmp::max)
+    }
+}
+
+impl<T: Float> Vec3<T> {
+    #[inline]
+    pub fn fmin(self, rhs: Vec3<T>) -> Vec3<T> {
+        self.zip_map(rhs, T::min)
+    }
+
+    #[inline]
+    pub fn fmax(self, rhs: Vec3<T>) -> Vec3<T> {
+        self.zip_map(rhs, T::max)
+    }
+
+    #[inline]
+    pub fn trunc(self) -> Vec3<T> {
+        self.map(T::trunc)
+    }
+
+    #[inline]
+    pub fn fract(self) -> Vec3<T> {
+        self.map(T::fract)
+    }
+
+    #[inline]
+    pub fn fclamp(self, lo: Vec3<T>, hi: Vec3<T>) -> Vec3<T> {
+        self.fmax(lo).fmin(hi)
+    }
+
+    #[inline]
+    pub fn length(self) -> T {
+        self.length_squared().sqrt()
+    }
+
+    #[inline]
+    pub fn try_normalize(self) -> Option<Vec3<T>> {
+        let len_sq = self.length_squared();
+        if len_sq < T::epsilon() {
+            None
+        } else {
+            Some(self / len_sq.sqrt())
+        }
+    }
+
+    #[inline]
+    pub fn normalize(self) -> Vec3<T> {
+        self / self.length()
+    }
+
+    #[inline]
+    pub fn round(self) -> Vec3<T> {
+        self.map(T::round)
+    }
+
+    #[inline]
+    pub fn floor(self) -> Vec3<T> {
+        self.map(T::floor)
+    }
+
+    #[inline]
+    pub fn ceil(self) -> Vec3<T> {
+        self.map(T::ceil)
+    }
+
+    #[inline]
+    pub fn lerp(self, rhs: Vec3<T>, time: T) -> Vec3<T> {
+        self.zip_map(rhs, |a, b| lerp(a, b, time))
+    }
+}
+
+impl Vec3<bool> {
+    #[inline]
+    pub fn all(self) -> bool {
+        self.x && self.y && self.z
+    }
+
+    #[inline]
+    pub fn any(self) -> bool {
+        self.x || self.y || self.z
+    }
+}
+
+impl<T> From<(Vec2<T>, T)> for Vec3<T> {
+    #[inline]
+    fn from((vec2, z): (Vec2<T>, T)) -> Self {
+        Vec3::new(vec2.x, vec2.y, z)
+    }
+}
+
+impl<T> From<[T; 3]> for Vec3<T> {
+    #[inline]
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl<T> From<Vec3<T>> for [T; 3] {
+    #[inline]
+    fn from(v: Vec3<T>) -> Self {
+        [v.x, v.y, v.z]
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        self.map(T::neg)
+    }
+}
+
+impl Not for Vec3<bool> {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        self.map(bool::not)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        self.zip_map(rhs, T::add)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self.zip_map(rhs, T::sub)
+    }
+}
+
+impl<T: Mul<Output = T>> Mul for Vec3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        self.zip_map(rhs, T::mul)
+    }
+}
+
+impl<T: Div<Output = T>> Div for Vec3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        self.zip_map(rhs, T::div)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: T) -> Self {
+        self.map(|v| v * rhs)
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Vec3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: T) -> Self {
+        self.map(|v| v / rhs)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec3<T> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec3<T> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<T: MulAssign> MulAssign for Vec3<T> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        self.x *= rhs.x;
+        self.y *= rhs.y;
+        self.z *= rhs.z;
+    }
+}
+
+impl<T: DivAssign> DivAssign for Vec3<T> {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        self.x /= rhs.x;
+        self.y /= rhs.y;
+        self.z /= rhs.z;
+    }
+}
+
+impl<T: MulAssign + Copy> MulAssign<T> for Vec3<T> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+    }
+}
+
+impl<T: DivAssign + Copy> DivAssign<T> for Vec3<T> {
+    #[inline]
+    fn div_assign(&mut self, rhs: T) {
+        self.x /= rhs;
+        self.y /= rhs;
+        self.z /= rhs;
+    }
+}
+
+impl<T> Index<usize> for Vec3<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Vec3<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
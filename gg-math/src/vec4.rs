@@ -0,0 +1,496 @@
+use std::fmt::{self, Debug};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Not, Sub, SubAssign,
+};
+
+use num_traits::{Float, Num, NumCast, Signed, Zero};
+
+use crate::{lerp, Vec3};
+
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Vec4<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+impl<T: Debug> Debug for Vec4<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{:?}, {:?}, {:?}, {:?}]",
+            self.x, self.y, self.z, self.w
+        )
+    }
+}
+
+impl<T> Vec4<T> {
+    #[inline]
+    pub const fn new(x: T, y: T, z: T, w: T) -> Vec4<T> {
+        Vec4 { x, y, z, w }
+    }
+
+    #[inline]
+    pub const fn splat(v: T) -> Vec4<T>
+    where
+        T: Copy,
+    {
+        Vec4::new(v, v, v, v)
+    }
+
+    #[inline]
+    pub fn zero() -> Vec4<T>
+    where
+        T: Zero,
+    {
+        Vec4::new(T::zero(), T::zero(), T::zero(), T::zero())
+    }
+
+    #[inline]
+    pub fn set_x(mut self, x: T) -> Vec4<T> {
+        self.x = x;
+        self
+    }
+
+    #[inline]
+    pub fn set_y(mut self, y: T) -> Vec4<T> {
+        self.y = y;
+        self
+    }
+
+    #[inline]
+    pub fn set_z(mut self, z: T) -> Vec4<T> {
+        self.z = z;
+        self
+    }
+
+    #[inline]
+    pub fn set_w(mut self, w: T) -> Vec4<T> {
+        self.w = w;
+        self
+    }
+
+    #[inline]
+    pub fn truncate(self) -> Vec3<T> {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    #[inline]
+    pub fn map<U, F>(self, mut f: F) -> Vec4<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Vec4::new(f(self.x), f(self.y), f(self.z), f(self.w))
+    }
+
+    #[inline]
+    pub fn try_map<U, E, F>(self, mut f: F) -> Result<Vec4<U>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        Ok(Vec4::new(f(self.x)?, f(self.y)?, f(self.z)?, f(self.w)?))
+    }
+
+    #[inline]
+    pub fn zip_map<U, F>(self, rhs: Vec4<T>, mut f: F) -> Vec4<U>
+    where
+        F: FnMut(T, T) -> U,
+    {
+        Vec4::new(
+            f(self.x, rhs.x),
+            f(self.y, rhs.y),
+            f(self.z, rhs.z),
+            f(self.w, rhs.w),
+        )
+    }
+
+    #[inline]
+    pub fn fold<U, F>(self, mut acc: U, mut f: F) -> U
+    where
+        F: FnMut(U, T) -> U,
+    {
+        acc = f(acc, self.x);
+        acc = f(acc, self.y);
+        acc = f(acc, self.z);
+        acc = f(acc, self.w);
+        acc
+    }
+
+    #[inline]
+    pub fn reduce<F>(self, mut f: F) -> T
+    where
+        F: FnMut(T, T) -> T,
+    {
+        let xy = f(self.x, self.y);
+        let xyz = f(xy, self.z);
+        f(xyz, self.w)
+    }
+
+    #[inline]
+    pub fn try_cast<U>(self) -> Option<Vec4<U>>
+    where
+        T: NumCast,
+        U: NumCast,
+    {
+        self.try_map(|v| U::from(v).ok_or(())).ok()
+    }
+
+    #[inline]
+    pub fn cast<U>(self) -> Vec4<U>
+    where
+        T: NumCast,
+        U: NumCast,
+    {
+        self.try_cast().expect("cast failed")
+    }
+}
+
+impl<T: Num + Copy> Vec4<T> {
+    #[inline]
+    pub fn sum(self) -> T {
+        self.reduce(T::add)
+    }
+
+    #[inline]
+    pub fn product(self) -> T {
+        self.reduce(T::mul)
+    }
+
+    #[inline]
+    pub fn dot(self, rhs: Vec4<T>) -> T {
+        (self * rhs).sum()
+    }
+
+    #[inline]
+    pub fn length_squared(self) -> T {
+        (self * self).sum()
+    }
+
+    #[inline]
+    pub fn abs(self) -> Vec4<T>
+    where
+        T: Signed,
+    {
+        self.map(|v| v.abs())
+    }
+}
+
+impl<T: PartialOrd> Vec4<T> {
+    #[inline]
+    pub fn cmp_lt(self, rhs: Vec4<T>) -> Vec4<bool> {
+        self.zip_map(rhs, |a, b| a < b)
+    }
+
+    #[inline]
+    pub fn cmp_le(self, rhs: Vec4<T>) -> Vec4<bool> {
+        self.zip_map(rhs, |a, b| a <= b)
+    }
+
+    #[inline]
+    pub fn cmp_eq(self, rhs: Vec4<T>) -> Vec4<bool> {
+        self.zip_map(rhs, |a, b| a == b)
+    }
+
+    #[inline]
+    pub fn cmp_ge(self, rhs: Vec4<T>) -> Vec4<bool> {
+        self.zip_map(rhs, |a, b| a >= b)
+    }
+
+    #[inline]
+    pub fn cmp_gt(self, rhs: Vec4<T>) -> Vec4<bool> {
+        self.zip_map(rhs, |a, b| a > b)
+    }
+
+    #[inline]
+    pub fn cmp_ne(self, rhs: Vec4<T>) -> Vec4<bool> {
+        self.zip_map(rhs, |a, b| a != b)
+    }
+}
+
+impl<T: Ord> Vec4<T> {
+    #[inline]
+    pub fn min(self, rhs: Vec4<T>) -> Vec4<T> {
+        self.zip_map(rhs, std::cmp::min)
+    }
+
+    #[inline]
+    pub fn max(self, rhs: Vec4<T>) -> Vec4<T> {
+        self.zip_map(rhs, std::cmp::max)
+    }
+
+    #[inline]
+    pub fn clamp(self, lo: Vec4<T>, hi: Vec4<T>) -> Vec4<T> {
+        self.max(lo).min(hi)
+    }
+
+    #[inline]
+    pub fn min_component(self) -> T {
+        self.reduce(std::cmp::min)
+    }
+
+    #[inline]
+    pub fn max_component(self) -> T {
+        self.reduce(std::cmp::max)
+    }
+}
+
+impl<T: Float> Vec4<T> {
+    #[inline]
+    pub fn fmin(self, rhs: Vec4<T>) -> Vec4<T> {
+        self.zip_map(rhs, T::min)
+    }
+
+    #[inline]
+    pub fn fmax(self, rhs: Vec4<T>) -> Vec4<T> {
+        self.zip_map(rhs, T::max)
+    }
+
+    #[inline]
+    pub fn trunc(self) -> Vec4<T> {
+        self.map(T::trunc)
+    }
+
+    #[inline]
+    pub fn fract(self) -> Vec4<T> {
+        self.map(T::fract)
+    }
+
+    #[inline]
+    pub fn fclamp(self, lo: Vec4<T>, hi: Vec4<T>) -> Vec4<T> {
+        self.fmax(lo).fmin(hi)
+    }
+
+    #[inline]
+    pub fn length(self) -> T {
+        self.length_squared().sqrt()
+    }
+
+    #[inline]
+    pub fn try_normalize(self) -> Option<Vec4<T>> {
+        let len_sq = self.length_squared();
+        if len_sq < T::epsilon() {
+            None
+        } else {
+            Some(self / len_sq.sqrt())
+        }
+    }
+
+    #[inline]
+    pub fn normalize(self) -> Vec4<T> {
+        self / self.length()
+    }
+
+    #[inline]
+    pub fn round(self) -> Vec4<T> {
+        self.map(T::round)
+    }
+
+    #[inline]
+    pub fn floor(self) -> Vec4<T> {
+        self.map(T::floor)
+    }
+
+    #[inline]
+    pub fn ceil(self) -> Vec4<T> {
+        self.map(T::ceil)
+    }
+
+    #[inline]
+    pub fn lerp(self, rhs: Vec4<T>, time: T) -> Vec4<T> {
+        self.zip_map(rhs, |a, b| lerp(a, b, time))
+    }
+}
+
+impl Vec4<bool> {
+    #[inline]
+    pub fn all(self) -> bool {
+        self.x && self.y && self.z && self.w
+    }
+
+    #[inline]
+    pub fn any(self) -> bool {
+        self.x || self.y || self.z || self.w
+    }
+}
+
+impl<T> From<(Vec3<T>, T)> for Vec4<T> {
+    #[inline]
+    fn from((vec3, w): (Vec3<T>, T)) -> Self {
+        Vec4::new(vec3.x, vec3.y, vec3.z, w)
+    }
+}
+
+impl<T> From<[T; 4]> for Vec4<T> {
+    #[inline]
+    fn from([x, y, z, w]: [T; 4]) -> Self {
+        Vec4::new(x, y, z, w)
+    }
+}
+
+impl<T> From<Vec4<T>> for [T; 4] {
+    #[inline]
+    fn from(v: Vec4<T>) -> Self {
+        [v.x, v.y, v.z, v.w]
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec4<T> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        self.map(T::neg)
+    }
+}
+
+impl Not for Vec4<bool> {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        self.map(bool::not)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec4<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        self.zip_map(rhs, T::add)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec4<T> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self.zip_map(rhs, T::sub)
+    }
+}
+
+impl<T: Mul<Output = T>> Mul for Vec4<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        self.zip_map(rhs, T::mul)
+    }
+}
+
+impl<T: Div<Output = T>> Div for Vec4<T> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        self.zip_map(rhs, T::div)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec4<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: T) -> Self {
+        self.map(|v| v * rhs)
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Vec4<T> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: T) -> Self {
+        self.map(|v| v / rhs)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec4<T> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+        self.w += rhs.w;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec4<T> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+        self.w -= rhs.w;
+    }
+}
+
+impl<T: MulAssign> MulAssign for Vec4<T> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        self.x *= rhs.x;
+        self.y *= rhs.y;
+        self.z *= rhs.z;
+        self.w *= rhs.w;
+    }
+}
+
+impl<T: DivAssign> DivAssign for Vec4<T> {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        self.x /= rhs.x;
+        self.y /= rhs.y;
+        self.z /= rhs.z;
+        self.w /= rhs.w;
+    }
+}
+
+impl<T: MulAssign + Copy> MulAssign<T> for Vec4<T> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+        self.w *= rhs;
+    }
+}
+
+impl<T: DivAssign + Copy> DivAssign<T> for Vec4<T> {
+    #[inline]
+    fn div_assign(&mut self, rhs: T) {
+        self.x /= rhs;
+        self.y /= rhs;
+        self.z /= rhs;
+        self.w /= rhs;
+    }
+}
+
+impl<T> Index<usize> for Vec4<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Vec4<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            3 => &mut self.w,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
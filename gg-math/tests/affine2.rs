@@ -0,0 +1,35 @@
+use gg_math::{Affine2, Rotation2, Vec2};
+
+fn approx(a: Vec2<f32>, b: Vec2<f32>) -> bool {
+    (a - b).length() < 1e-5
+}
+
+#[test]
+fn inverse_round_trip() {
+    let t = Affine2::translation(Vec2::new(10.0, -4.0))
+        * Affine2::rotation(Rotation2::from_angle(1.2))
+        * Affine2::scaling(Vec2::new(3.0, 0.5));
+    let inv = t.inverse();
+
+    for p in [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.5, -2.0),
+        Vec2::new(-30.0, 7.25),
+    ] {
+        assert!(approx(inv.transform_point(t.transform_point(p)), p));
+        assert!(approx(t.transform_point(inv.transform_point(p)), p));
+        assert!(approx(inv.transform_vector(t.transform_vector(p)), p));
+    }
+
+    let id = t * inv;
+    assert!(approx(id.x, Vec2::new(1.0, 0.0)));
+    assert!(approx(id.y, Vec2::new(0.0, 1.0)));
+    assert!(approx(id.z, Vec2::zero()));
+}
+
+#[test]
+fn singular() {
+    let t = Affine2::translation(Vec2::new(1.0, 1.0)) * Affine2::scaling(Vec2::new(2.0, 0.0));
+    assert_eq!(t.try_inverse(), None);
+    assert!(Affine2::<f32>::identity().try_inverse().is_some());
+}
@@ -0,0 +1,32 @@
+use gg_math::{clamp, lerp, smoothstep, Vec2};
+
+#[test]
+fn smoothstep_values() {
+    assert_eq!(smoothstep(1.0, 3.0, 1.0), 0.0);
+    assert_eq!(smoothstep(1.0, 3.0, 3.0), 1.0);
+    assert_eq!(smoothstep(1.0, 3.0, 2.0), 0.5);
+    assert_eq!(smoothstep(1.0, 3.0, 1.5), 0.15625);
+
+    // clamped outside the edges
+    assert_eq!(smoothstep(1.0, 3.0, -10.0), 0.0);
+    assert_eq!(smoothstep(1.0, 3.0, 10.0), 1.0);
+}
+
+#[test]
+fn clamp_values() {
+    assert_eq!(clamp(5, 0, 3), 3);
+    assert_eq!(clamp(-0.5, 0.0, 1.0), 0.0);
+    assert_eq!(clamp(0.25, 0.0, 1.0), 0.25);
+    assert!(clamp(f32::NAN, 0.0, 1.0).is_nan());
+}
+
+#[test]
+fn vec2_lerp() {
+    let a = Vec2::new(0.0, 10.0);
+    let b = Vec2::new(4.0, -2.0);
+
+    assert_eq!(a.lerp(b, 0.0), a);
+    assert_eq!(a.lerp(b, 1.0), b);
+    assert_eq!(a.lerp(b, 0.25), Vec2::new(1.0, 7.0));
+    assert_eq!(lerp(2.0, 4.0, 0.5), 3.0);
+}
@@ -0,0 +1,110 @@
+use gg_math::{Affine2, Mat3, Mat4, Rotation2, Vec2, Vec3, Vec4};
+
+fn approx_mat3(a: Mat3<f32>, b: Mat3<f32>) -> bool {
+    [(a.x, b.x), (a.y, b.y), (a.z, b.z)]
+        .iter()
+        .all(|&(a, b)| (a - b).abs().fold(0.0, f32::max) < 1e-5)
+}
+
+fn approx_mat4(a: Mat4<f32>, b: Mat4<f32>) -> bool {
+    [(a.x, b.x), (a.y, b.y), (a.z, b.z), (a.w, b.w)]
+        .iter()
+        .all(|&(a, b)| (a - b).abs().fold(0.0, f32::max) < 1e-5)
+}
+
+fn approx_vec3(a: Vec3<f32>, b: Vec3<f32>) -> bool {
+    (a - b).abs().fold(0.0, f32::max) < 1e-5
+}
+
+#[test]
+fn mat4_inverse() {
+    let m = Mat4::new(
+        Vec4::new(2.0, 0.5, -1.0, 0.0),
+        Vec4::new(0.0, 3.0, 1.0, 0.25),
+        Vec4::new(1.0, -2.0, 4.0, 0.0),
+        Vec4::new(5.0, 6.0, -7.0, 1.0),
+    );
+
+    assert!(approx_mat4(m * m.inverse(), Mat4::identity()));
+    assert!(approx_mat4(m.inverse() * m, Mat4::identity()));
+    assert!(approx_mat4(m.transpose().transpose(), m));
+
+    let t = Mat4::translation(Vec3::new(1.0, 2.0, 3.0)) * Mat4::scaling(Vec3::splat(2.0));
+    let p = Vec3::new(-4.0, 0.5, 8.0);
+    assert!(approx_vec3(
+        t.transform_point(p),
+        Vec3::new(-7.0, 3.0, 19.0)
+    ));
+    assert!(approx_vec3(t.transform_vector(p), p * 2.0));
+    assert!(approx_vec3(
+        t.inverse().transform_point(t.transform_point(p)),
+        p
+    ));
+
+    assert_eq!(Mat4::scaling(Vec3::new(1.0, 0.0, 1.0)).try_inverse(), None);
+}
+
+#[test]
+fn mat3_inverse() {
+    let m = Mat3::new(
+        Vec3::new(2.0, 0.5, -1.0),
+        Vec3::new(0.0, 3.0, 1.0),
+        Vec3::new(1.0, -2.0, 4.0),
+    );
+
+    assert!(approx_mat3(m * m.inverse(), Mat3::identity()));
+    assert_eq!(
+        Mat3::<f32>::new(Vec3::zero(), Vec3::zero(), Vec3::zero()).try_inverse(),
+        None
+    );
+
+    let affine = Affine2::translation(Vec2::new(3.0, -1.0))
+        * Affine2::rotation(Rotation2::from_angle(0.5))
+        * Affine2::scaling(Vec2::new(2.0, 0.5));
+    let p = Vec2::new(1.5, -2.0);
+    let diff = Mat3::from(affine).transform_point(p) - affine.transform_point(p);
+    assert!(diff.length() < 1e-5);
+}
+
+#[test]
+fn orthographic() {
+    let proj = Mat4::orthographic(0.0, 800.0, 600.0, 0.0, 0.0, 10.0);
+    let corner = |x, y, z| proj.transform_point(Vec3::new(x, y, z));
+
+    assert!(approx_vec3(
+        corner(0.0, 0.0, 0.0),
+        Vec3::new(-1.0, 1.0, 0.0)
+    ));
+    assert!(approx_vec3(
+        corner(800.0, 0.0, 0.0),
+        Vec3::new(1.0, 1.0, 0.0)
+    ));
+    assert!(approx_vec3(
+        corner(0.0, 600.0, -10.0),
+        Vec3::new(-1.0, -1.0, 1.0)
+    ));
+    assert!(approx_vec3(
+        corner(800.0, 600.0, -5.0),
+        Vec3::new(1.0, -1.0, 0.5)
+    ));
+
+    let proj = Mat3::orthographic(0.0, 800.0, 600.0, 0.0);
+    let corner = |x, y| proj.transform_point(Vec2::new(x, y));
+
+    assert!((corner(0.0, 0.0) - Vec2::new(-1.0, 1.0)).length() < 1e-5);
+    assert!((corner(800.0, 600.0) - Vec2::new(1.0, -1.0)).length() < 1e-5);
+}
+
+#[test]
+fn perspective() {
+    let proj = Mat4::perspective(std::f32::consts::FRAC_PI_2, 2.0, 1.0, 100.0);
+
+    assert!(approx_vec3(
+        proj.transform_point(Vec3::new(2.0, 1.0, -1.0)),
+        Vec3::new(1.0, 1.0, 0.0)
+    ));
+    assert!(approx_vec3(
+        proj.transform_point(Vec3::new(0.0, 0.0, -100.0)),
+        Vec3::new(0.0, 0.0, 1.0)
+    ));
+}
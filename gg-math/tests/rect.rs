@@ -0,0 +1,60 @@
+use gg_math::{Rect, SideOffsets, Vec2};
+
+#[test]
+fn contains_edges() {
+    let rect = Rect::new(Vec2::new(1, 2), Vec2::new(3, 4));
+
+    assert!(rect.contains(Vec2::new(1, 2)));
+    assert!(rect.contains(Vec2::new(4, 6)));
+    assert!(rect.contains(Vec2::new(4, 3)));
+    assert!(!rect.contains(Vec2::new(5, 3)));
+    assert!(!rect.contains(Vec2::new(0, 2)));
+}
+
+#[test]
+fn union() {
+    let a = Rect::new(Vec2::new(0, 0), Vec2::new(2, 2));
+    let b = Rect::new(Vec2::new(5, -3), Vec2::new(1, 1));
+    assert_eq!(
+        a.union(&b),
+        Rect::from_min_max(Vec2::new(0, -3), Vec2::new(6, 2))
+    );
+    assert_eq!(b.union(&a), a.union(&b));
+
+    let (a, b) = (a.cast::<f32>(), b.cast::<f32>());
+    assert_eq!(
+        a.f_union(&b),
+        Rect::from_min_max(Vec2::new(0.0, -3.0), Vec2::new(6.0, 2.0))
+    );
+}
+
+#[test]
+fn grow_asymmetric() {
+    let rect = Rect::new(Vec2::new(10.0, 10.0), Vec2::new(20.0, 10.0));
+    let offsets = SideOffsets::new(1.0, 2.0, 3.0, 4.0);
+
+    let grown = rect.grow(&offsets);
+    assert_eq!(
+        grown,
+        Rect::from_min_max(Vec2::new(6.0, 9.0), Vec2::new(32.0, 23.0))
+    );
+    assert_eq!(grown.size(), rect.size() + offsets.size());
+    assert_eq!(grown.shrink(&offsets), rect);
+}
+
+#[test]
+fn center_clamp_translate() {
+    let rect = Rect::new(Vec2::new(0, 0), Vec2::new(10, 4));
+
+    assert_eq!(rect.center(), Vec2::new(5, 2));
+    assert_eq!(rect.clamp_point(Vec2::new(-3, 2)), Vec2::new(0, 2));
+    assert_eq!(rect.clamp_point(Vec2::new(12, 7)), Vec2::new(10, 4));
+    assert_eq!(
+        rect.cast::<f32>().f_clamp_point(Vec2::new(5.5, -1.0)),
+        Vec2::new(5.5, 0.0)
+    );
+    assert_eq!(
+        rect.translate(Vec2::new(-1, 3)),
+        Rect::new(Vec2::new(-1, 3), Vec2::new(10, 4))
+    );
+}
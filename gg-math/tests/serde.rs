@@ -0,0 +1,36 @@
+#![cfg(feature = "serde")]
+
+use gg_math::{Affine2, Rect, SideOffsets, Vec2, Vec3};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(v: T, json: &str) {
+    assert_eq!(serde_json::to_string(&v).unwrap(), json);
+    assert_eq!(serde_json::from_str::<T>(json).unwrap(), v);
+}
+
+#[test]
+fn vectors() {
+    round_trip(Vec2::new(1.5, -2.0), r#"{"x":1.5,"y":-2.0}"#);
+    round_trip(Vec3::new(1, 2, 3), r#"{"x":1,"y":2,"z":3}"#);
+}
+
+#[test]
+fn rect() {
+    round_trip(
+        Rect::new(Vec2::new(1, 2), Vec2::new(3, 4)),
+        r#"{"min":{"x":1,"y":2},"max":{"x":4,"y":6}}"#,
+    );
+    round_trip(
+        SideOffsets::new(1, 2, 3, 4),
+        r#"{"top":1,"right":2,"bottom":3,"left":4}"#,
+    );
+}
+
+#[test]
+fn affine() {
+    round_trip(
+        Affine2::translation(Vec2::new(5.0, 6.0)),
+        r#"{"x":{"x":1.0,"y":0.0},"y":{"x":0.0,"y":1.0},"z":{"x":5.0,"y":6.0}}"#,
+    );
+}
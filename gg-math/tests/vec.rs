@@ -0,0 +1,63 @@
+use gg_math::{Vec2, Vec3, Vec4};
+
+#[test]
+fn dot() {
+    assert_eq!(Vec3::new(1, 2, 3).dot(Vec3::new(4, -5, 6)), 12);
+    assert_eq!(Vec4::new(1.0, 2.0, 3.0, 4.0).dot(Vec4::splat(0.5)), 5.0);
+    assert_eq!(
+        Vec3::new(1, 0, 0).cross(Vec3::new(0, 1, 0)),
+        Vec3::new(0, 0, 1)
+    );
+}
+
+#[test]
+fn length() {
+    assert_eq!(Vec3::new(2.0, 3.0, 6.0).length(), 7.0);
+    assert_eq!(Vec4::new(1.0, 1.0, 1.0, 1.0).length(), 2.0);
+    assert_eq!(Vec3::new(0.0, 0.0, 0.0).try_normalize(), None);
+    assert_eq!(
+        Vec4::new(0.0, 3.0, 0.0, 4.0).normalize(),
+        Vec4::new(0.0, 0.6, 0.0, 0.8)
+    );
+}
+
+#[test]
+fn cast() {
+    assert_eq!(
+        Vec3::new(1.7f32, -2.2, 3.0).cast::<i32>(),
+        Vec3::new(1, -2, 3)
+    );
+    assert_eq!(
+        Vec4::new(1u8, 2, 3, 4).cast::<f32>(),
+        Vec4::new(1.0, 2.0, 3.0, 4.0)
+    );
+    assert_eq!(Vec3::new(-1i32, 0, 1).try_cast::<u32>(), None);
+}
+
+#[test]
+fn conversions() {
+    let v = Vec2::new(1, 2).extend(3);
+    assert_eq!(v, Vec3::new(1, 2, 3));
+    assert_eq!(Vec3::from((Vec2::new(1, 2), 3)), v);
+    assert_eq!(v.truncate(), Vec2::new(1, 2));
+
+    let v = v.extend(4);
+    assert_eq!(v, Vec4::from([1, 2, 3, 4]));
+    assert_eq!(Vec4::from((Vec3::new(1, 2, 3), 4)), v);
+    assert_eq!(<[i32; 4]>::from(v), [1, 2, 3, 4]);
+    assert_eq!(v.truncate(), Vec3::new(1, 2, 3));
+}
+
+#[test]
+fn ops() {
+    let a = Vec4::new(1, 2, 3, 4);
+    assert_eq!(a + a, a * 2);
+    assert_eq!(-a, Vec4::new(-1, -2, -3, -4));
+    assert_eq!(
+        a.zip_map(Vec4::splat(2), |a, b| a % b),
+        Vec4::new(1, 0, 1, 0)
+    );
+    assert_eq!(a.max_component(), 4);
+    assert!(a.cmp_lt(Vec4::splat(5)).all());
+    assert_eq!(a[3], 4);
+}
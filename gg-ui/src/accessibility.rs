@@ -0,0 +1,59 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Role {
+    Window,
+    Group,
+    Button,
+    CheckBox,
+    Slider,
+    Text,
+    TextInput,
+}
+
+impl Role {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Window => "window",
+            Role::Group => "group",
+            Role::Button => "button",
+            Role::CheckBox => "checkbox",
+            Role::Slider => "slider",
+            Role::Text => "text",
+            Role::TextInput => "text-input",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNode {
+    pub role: Role,
+    pub name: Option<String>,
+    pub children: Vec<AccessibilityNode>,
+}
+
+impl AccessibilityNode {
+    pub fn new(role: Role) -> AccessibilityNode {
+        AccessibilityNode {
+            role,
+            name: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> AccessibilityNode {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn with_children(mut self, children: Vec<AccessibilityNode>) -> AccessibilityNode {
+        self.children = children;
+        self
+    }
+
+    pub fn find(&self, role: Role) -> Option<&AccessibilityNode> {
+        if self.role == role {
+            return Some(self);
+        }
+
+        self.children.iter().find_map(|child| child.find(role))
+    }
+}
@@ -3,5 +3,8 @@ gg_input::action! {
         Touch = "ui.touch",
         TransposeScroll = "ui.transpose-scroll",
         DebugDraw = "ui.debug-draw",
+        FocusNext = "ui.focus-next",
+        FocusPrev = "ui.focus-prev",
+        Activate = "ui.activate",
     }
 }
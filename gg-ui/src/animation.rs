@@ -0,0 +1,103 @@
+use gg_math::{lerp, smoothstep};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    SmoothStep,
+}
+
+impl Easing {
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t.clamp(0.0, 1.0),
+            Easing::SmoothStep => smoothstep(0.0, 1.0, t),
+        }
+    }
+}
+
+// a value moving towards its target over a fixed duration
+#[derive(Clone, Copy, Debug)]
+pub struct Animation {
+    start: f32,
+    target: f32,
+    value: f32,
+    elapsed: f32,
+    duration: f32,
+    easing: Easing,
+}
+
+impl Animation {
+    pub fn new(value: f32, duration: f32, easing: Easing) -> Animation {
+        Animation {
+            start: value,
+            target: value,
+            value,
+            elapsed: duration,
+            duration,
+            easing,
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    pub fn easing(&self) -> Easing {
+        self.easing
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.value == self.target
+    }
+
+    // restarts the animation from the current value, unless the target is
+    // the same
+    pub fn set_target(&mut self, target: f32) {
+        if target == self.target {
+            return;
+        }
+
+        self.start = self.value;
+        self.target = target;
+        self.elapsed = 0.0;
+    }
+
+    pub fn set_duration(&mut self, duration: f32) {
+        self.duration = duration;
+    }
+
+    pub fn set_easing(&mut self, easing: Easing) {
+        self.easing = easing;
+    }
+
+    pub fn tick(&mut self, dt: f32) -> f32 {
+        if self.is_settled() {
+            return self.value;
+        }
+
+        self.elapsed += dt;
+
+        let t = if self.duration > 0.0 {
+            self.elapsed / self.duration
+        } else {
+            1.0
+        };
+
+        // exact, so that the animation settles
+        self.value = if t >= 1.0 {
+            self.target
+        } else {
+            lerp(self.start, self.target, self.easing.apply(t))
+        };
+
+        self.value
+    }
+}
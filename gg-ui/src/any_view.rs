@@ -2,7 +2,9 @@ use std::any::Any;
 
 use gg_math::Vec2;
 
-use crate::{Bounds, DrawCtx, Event, Hover, LayoutCtx, LayoutHints, UpdateCtx, View};
+use crate::{
+    AccessibilityNode, Bounds, DrawCtx, Event, Hover, LayoutCtx, LayoutHints, UpdateCtx, View,
+};
 
 pub trait AnyView<D: 'static>: Any + View<D> {
     fn as_any(&mut self) -> &mut dyn Any;
@@ -37,6 +39,10 @@ impl<D: 'static> View<D> for Box<dyn AnyView<D>> {
         (**self).layout(ctx, size)
     }
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        (**self).accessibility(nodes)
+    }
+
     fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
         (**self).hover(ctx, bounds)
     }
@@ -0,0 +1,40 @@
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug)]
+pub struct AnimationClock {
+    last_tick: Option<Instant>,
+    max_step: Duration,
+}
+
+impl AnimationClock {
+    pub fn new(max_step: Duration) -> AnimationClock {
+        AnimationClock {
+            last_tick: None,
+            max_step,
+        }
+    }
+
+    pub fn max_step(&self) -> Duration {
+        self.max_step
+    }
+
+    pub fn set_max_step(&mut self, max_step: Duration) {
+        self.max_step = max_step;
+    }
+
+    pub fn tick(&mut self, now: Instant) -> f32 {
+        let step = match self.last_tick {
+            Some(last) => now.saturating_duration_since(last).min(self.max_step),
+            None => Duration::ZERO,
+        };
+
+        self.last_tick = Some(now);
+        step.as_secs_f32()
+    }
+}
+
+impl Default for AnimationClock {
+    fn default() -> AnimationClock {
+        AnimationClock::new(Duration::from_millis(100))
+    }
+}
@@ -1,16 +1,28 @@
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 
 use gg_assets::Assets;
 use gg_graphics::{FontDb, GraphicsEncoder, TextLayouter};
 use gg_input::Input;
 use gg_math::{Rect, Vec2};
 
-use crate::{AnyView, Bounds, DrawCtx, LayoutCtx, UiAction, UpdateCtx, View};
+use crate::views::text;
+use crate::{
+    AccessibilityNode, AnimationClock, AnyView, Bounds, DrawCtx, Focus, HitMap, LayoutCtx,
+    LayoutOverlay, Role, UiAction, UpdateCtx, View, ViewExt, ViewId,
+};
 
 pub struct Driver<D> {
     old_view: Option<Box<dyn AnyView<D>>>,
     size: Vec2<f32>,
     num_layers: u32,
+    clock: AnimationClock,
+    accessibility: AccessibilityNode,
+    overlay: Option<LayoutOverlay>,
+    hit_map: HitMap,
+    focus: Focus,
+    redraw_requested: bool,
+    bindings_revision: u64,
 }
 
 impl<D: 'static> Driver<D> {
@@ -19,10 +31,62 @@ impl<D: 'static> Driver<D> {
             old_view: None,
             size: Vec2::zero(),
             num_layers: 1,
+            clock: AnimationClock::default(),
+            accessibility: AccessibilityNode::new(Role::Window),
+            overlay: None,
+            hit_map: HitMap::new(),
+            focus: Focus::new(),
+            redraw_requested: false,
+            bindings_revision: 0,
         }
     }
 
+    pub fn clock(&mut self) -> &mut AnimationClock {
+        &mut self.clock
+    }
+
+    pub fn focus(&mut self) -> &mut Focus {
+        &mut self.focus
+    }
+
+    // whether a view wants another frame after the last run, even without
+    // any input
+    pub fn redraw_requested(&self) -> bool {
+        self.redraw_requested
+    }
+
+    pub fn accessibility_tree(&self) -> &AccessibilityNode {
+        &self.accessibility
+    }
+
+    pub fn set_layout_overlay(&mut self, enabled: bool) {
+        if enabled != self.overlay.is_some() {
+            self.overlay = enabled.then(LayoutOverlay::new);
+        }
+    }
+
+    pub fn layout_overlay(&self) -> Option<&LayoutOverlay> {
+        self.overlay.as_ref()
+    }
+
+    pub fn hit_map(&self) -> &HitMap {
+        &self.hit_map
+    }
+
+    pub fn view_at(&self, pos: Vec2<f32>) -> Option<ViewId> {
+        self.hit_map.view_at(pos)
+    }
+
+    pub fn path_at(&self, pos: Vec2<f32>) -> Vec<ViewId> {
+        self.hit_map.path_at(pos)
+    }
+
     pub fn run<V: AnyView<D>>(&mut self, view: V, ctx: UiContext, data: &mut D) {
+        self.run_at(view, ctx, data, Instant::now())
+    }
+
+    pub fn run_at<V: AnyView<D>>(&mut self, view: V, ctx: UiContext, data: &mut D, now: Instant) {
+        let dt = self.clock.tick(now);
         let mut view: Box<dyn AnyView<D>> = Box::new(view);
 
         let changed = match self.old_view.take() {
@@ -30,29 +94,50 @@ impl<D: 'static> Driver<D> {
             _ => true,
         };
 
-        if changed || ctx.bounds.size() != self.size {
+        // views like shortcut hints show the bindings, relayout when they change
+        let rebound = ctx.input.bindings_revision() != self.bindings_revision;
+        self.bindings_revision = ctx.input.bindings_revision();
+
+        if changed || rebound || ctx.bounds.size() != self.size {
             let mut l_ctx = LayoutCtx {
                 assets: ctx.assets,
                 fonts: ctx.fonts,
+                input: ctx.input,
                 text_layouter: ctx.text_layouter,
             };
 
             let hints = view.pre_layout(&mut l_ctx);
             self.size = view.layout(&mut l_ctx, ctx.bounds.size());
             self.num_layers = hints.num_layers;
+
+            let mut nodes = Vec::new();
+            view.accessibility(&mut nodes);
+            self.accessibility = AccessibilityNode::new(Role::Window).with_children(nodes);
         }
 
         let mut bounds = Bounds::new(Rect::new(ctx.bounds.min, self.size));
+        self.redraw_requested = false;
 
         let mut u_ctx = UpdateCtx {
             assets: ctx.assets,
             input: ctx.input,
             data,
-            dt: ctx.dt,
+            focus: &mut self.focus,
+            dt,
             layer: 0,
+            redraw_requested: &mut self.redraw_requested,
         };
 
+        u_ctx.focus.begin_frame();
         view.update(&mut u_ctx, bounds);
+        u_ctx.focus.end_registration();
+
+        // Shift-Tab also matches the plain Tab binding
+        if ctx.input.has_action_pressed(UiAction::FocusPrev) {
+            u_ctx.focus.focus_prev();
+        } else if ctx.input.has_action_pressed(UiAction::FocusNext) {
+            u_ctx.focus.focus_next();
+        }
 
         for layer in (0..self.num_layers).rev() {
             u_ctx.layer = layer;
@@ -71,13 +156,22 @@ impl<D: 'static> Driver<D> {
         let pressed = ctx.input.has_action_pressed(UiAction::DebugDraw);
         let debug_draw = DEBUG_DRAW.fetch_xor(pressed, Ordering::Relaxed) ^ pressed;
 
+        if let Some(overlay) = &mut self.overlay {
+            overlay.clear();
+            overlay.add_bounds(bounds.rect);
+        }
+
+        self.hit_map.clear();
+
         let mut d_ctx = DrawCtx {
             assets: ctx.assets,
             text_layouter: ctx.text_layouter,
             encoder: ctx.encoder,
             layer: 0,
-            dt: ctx.dt,
+            dt,
             debug_draw,
+            overlay: self.overlay.as_mut(),
+            hit_map: Some(&mut self.hit_map),
         };
 
         for layer in 0..self.num_layers {
@@ -85,6 +179,40 @@ impl<D: 'static> Driver<D> {
             view.draw(&mut d_ctx, bounds);
         }
 
+        if let Some(overlay) = &self.overlay {
+            let mouse_pos = ctx.input.mouse_pos();
+            overlay.draw(ctx.encoder, mouse_pos);
+
+            if let Some(entry) = overlay.hovered(mouse_pos) {
+                let mut label = text::<D>(entry.label()).wrap(false).padding(2.0);
+
+                let mut l_ctx = LayoutCtx {
+                    assets: ctx.assets,
+                    fonts: ctx.fonts,
+                    input: ctx.input,
+                    text_layouter: ctx.text_layouter,
+                };
+
+                label.pre_layout(&mut l_ctx);
+                let label_size = label.layout(&mut l_ctx, Vec2::zero());
+                let label_rect = Rect::new(entry.rect.min, label_size);
+
+                let mut d_ctx = DrawCtx {
+                    assets: ctx.assets,
+                    text_layouter: ctx.text_layouter,
+                    encoder: ctx.encoder,
+                    layer: 0,
+                    dt,
+                    debug_draw,
+                    overlay: None,
+                    hit_map: None,
+                };
+
+                overlay.draw_label_background(d_ctx.encoder, label_rect);
+                label.draw(&mut d_ctx, Bounds::new(label_rect));
+            }
+        }
+
         self.old_view = Some(view);
     }
 }
@@ -102,5 +230,4 @@ pub struct UiContext<'a> {
     pub text_layouter: &'a mut TextLayouter,
     pub encoder: &'a mut GraphicsEncoder,
     pub input: &'a Input,
-    pub dt: f32,
 }
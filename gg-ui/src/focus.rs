@@ -0,0 +1,69 @@
+// position of a focusable view in traversal order. Views are rebuilt every
+// frame, so they register again during each update
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct FocusId(pub u32);
+
+#[derive(Clone, Debug, Default)]
+pub struct Focus {
+    focused: Option<FocusId>,
+    num_focusable: u32,
+}
+
+impl Focus {
+    pub fn new() -> Focus {
+        Focus::default()
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.num_focusable = 0;
+    }
+
+    // called once all views are updated, drops the focus if its view is gone
+    pub fn end_registration(&mut self) {
+        if self.focused.map_or(false, |v| v.0 >= self.num_focusable) {
+            self.focused = None;
+        }
+    }
+
+    pub fn register(&mut self) -> FocusId {
+        let id = FocusId(self.num_focusable);
+        self.num_focusable += 1;
+        id
+    }
+
+    pub fn num_focusable(&self) -> u32 {
+        self.num_focusable
+    }
+
+    pub fn focused(&self) -> Option<FocusId> {
+        self.focused
+    }
+
+    pub fn is_focused(&self, id: FocusId) -> bool {
+        self.focused == Some(id)
+    }
+
+    pub fn focus(&mut self, id: FocusId) {
+        self.focused = Some(id);
+    }
+
+    pub fn blur(&mut self) {
+        self.focused = None;
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focused = match (self.focused, self.num_focusable) {
+            (_, 0) => None,
+            (Some(id), n) => Some(FocusId((id.0 + 1) % n)),
+            (None, _) => Some(FocusId(0)),
+        };
+    }
+
+    pub fn focus_prev(&mut self) {
+        self.focused = match (self.focused, self.num_focusable) {
+            (_, 0) => None,
+            (Some(id), n) => Some(FocusId((id.0 + n - 1) % n)),
+            (None, n) => Some(FocusId(n - 1)),
+        };
+    }
+}
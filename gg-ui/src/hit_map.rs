@@ -0,0 +1,73 @@
+use gg_math::{Rect, Vec2};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ViewId(pub u64);
+
+impl ViewId {
+    pub fn new(id: u64) -> ViewId {
+        ViewId(id)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HitEntry {
+    pub id: ViewId,
+    pub rect: Rect<f32>,
+    pub parent: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct HitMap {
+    entries: Vec<HitEntry>,
+    stack: Vec<usize>,
+}
+
+impl HitMap {
+    pub fn new() -> HitMap {
+        HitMap::default()
+    }
+
+    pub fn entries(&self) -> &[HitEntry] {
+        &self.entries
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.stack.clear();
+    }
+
+    pub fn push(&mut self, id: ViewId, rect: Rect<f32>) {
+        self.stack.push(self.entries.len());
+        self.entries.push(HitEntry {
+            id,
+            rect,
+            parent: self.stack.iter().rev().nth(1).copied(),
+        });
+    }
+
+    pub fn pop(&mut self) {
+        self.stack.pop();
+    }
+
+    pub fn view_at(&self, pos: Vec2<f32>) -> Option<ViewId> {
+        self.entry_at(pos).map(|idx| self.entries[idx].id)
+    }
+
+    pub fn path_at(&self, pos: Vec2<f32>) -> Vec<ViewId> {
+        let mut path = Vec::new();
+        let mut cur = self.entry_at(pos);
+
+        while let Some(idx) = cur {
+            path.push(self.entries[idx].id);
+            cur = self.entries[idx].parent;
+        }
+
+        path
+    }
+
+    fn entry_at(&self, pos: Vec2<f32>) -> Option<usize> {
+        self.entries
+            .iter()
+            .rposition(|entry| entry.rect.contains(pos))
+    }
+}
@@ -0,0 +1,126 @@
+use gg_graphics::{Color, GraphicsEncoder};
+use gg_math::{Rect, Vec2};
+
+const BOUNDS_COLOR: Color = Color::new(1.0, 0.0, 1.0, 0.6);
+const HOVERED_COLOR: Color = Color::new(0.0, 1.0, 1.0, 1.0);
+const PADDING_COLOR: Color = Color::new(1.0, 0.8, 0.0, 0.25);
+const LABEL_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.8);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OverlayEntry {
+    pub rect: Rect<f32>,
+    pub content: Option<Rect<f32>>,
+}
+
+impl OverlayEntry {
+    // measured size shown next to the hovered view
+    pub fn label(&self) -> String {
+        let size = self.rect.size();
+        format!("{:.0}x{:.0}", size.x, size.y)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LayoutOverlay {
+    entries: Vec<OverlayEntry>,
+}
+
+impl LayoutOverlay {
+    pub fn new() -> LayoutOverlay {
+        LayoutOverlay::default()
+    }
+
+    pub fn entries(&self) -> &[OverlayEntry] {
+        &self.entries
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn add_bounds(&mut self, rect: Rect<f32>) {
+        self.entries.push(OverlayEntry {
+            rect,
+            content: None,
+        });
+    }
+
+    pub fn add_padding(&mut self, rect: Rect<f32>, content: Rect<f32>) {
+        self.entries.push(OverlayEntry {
+            rect,
+            content: Some(content),
+        });
+    }
+
+    pub fn hovered(&self, pos: Vec2<f32>) -> Option<&OverlayEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.content.is_none() && entry.rect.contains(pos))
+            .min_by(|a, b| area(a.rect).total_cmp(&area(b.rect)))
+    }
+
+    pub fn outline(rect: Rect<f32>) -> [Rect<f32>; 4] {
+        let size = rect.size();
+        [
+            Rect::new(rect.min, Vec2::new(size.x, 1.0)),
+            Rect::new(
+                Vec2::new(rect.min.x, rect.max.y - 1.0),
+                Vec2::new(size.x, 1.0),
+            ),
+            Rect::new(rect.min, Vec2::new(1.0, size.y)),
+            Rect::new(
+                Vec2::new(rect.max.x - 1.0, rect.min.y),
+                Vec2::new(1.0, size.y),
+            ),
+        ]
+    }
+
+    pub(crate) fn draw(&self, encoder: &mut GraphicsEncoder, mouse_pos: Vec2<f32>) {
+        encoder.save();
+        encoder.clear_scissor();
+
+        for entry in &self.entries {
+            if let Some(content) = entry.content {
+                for strip in padding_strips(entry.rect, content) {
+                    encoder.rect(strip).fill_color(PADDING_COLOR);
+                }
+            } else {
+                for edge in LayoutOverlay::outline(entry.rect) {
+                    encoder.rect(edge).fill_color(BOUNDS_COLOR);
+                }
+            }
+        }
+
+        if let Some(entry) = self.hovered(mouse_pos) {
+            for edge in LayoutOverlay::outline(entry.rect) {
+                encoder.rect(edge).fill_color(HOVERED_COLOR);
+            }
+        }
+
+        encoder.restore();
+    }
+
+    pub(crate) fn draw_label_background(&self, encoder: &mut GraphicsEncoder, rect: Rect<f32>) {
+        encoder.rect(rect).fill_color(LABEL_COLOR);
+    }
+}
+
+fn area(rect: Rect<f32>) -> f32 {
+    let size = rect.size();
+    size.x * size.y
+}
+
+fn padding_strips(rect: Rect<f32>, content: Rect<f32>) -> [Rect<f32>; 4] {
+    [
+        Rect::from_min_max(rect.min, Vec2::new(rect.max.x, content.min.y)),
+        Rect::from_min_max(Vec2::new(rect.min.x, content.max.y), rect.max),
+        Rect::from_min_max(
+            Vec2::new(rect.min.x, content.min.y),
+            Vec2::new(content.min.x, content.max.y),
+        ),
+        Rect::from_min_max(
+            Vec2::new(content.max.x, content.min.y),
+            Vec2::new(rect.max.x, content.max.y),
+        ),
+    ]
+}
@@ -1,6 +1,12 @@
+mod accessibility;
 mod action;
+mod animation;
 mod any_view;
+mod clock;
 mod driver;
+mod focus;
+mod hit_map;
+mod layout_overlay;
 mod view;
 mod view_ext;
 mod view_seq;
@@ -8,9 +14,15 @@ pub mod views;
 
 pub use gg_input::Event;
 
+pub use self::accessibility::{AccessibilityNode, Role};
 pub use self::action::UiAction;
+pub use self::animation::{Animation, Easing};
 pub use self::any_view::AnyView;
+pub use self::clock::AnimationClock;
 pub use self::driver::{Driver, UiContext};
+pub use self::focus::{Focus, FocusId};
+pub use self::hit_map::{HitEntry, HitMap, ViewId};
+pub use self::layout_overlay::{LayoutOverlay, OverlayEntry};
 pub use self::view::{Bounds, DrawCtx, Hover, LayoutCtx, LayoutHints, UpdateCtx, View};
 pub use self::view_ext::{AppendChild, SetChildren, ViewExt};
 pub use self::view_seq::{IntoViewSeq, ViewSeq};
@@ -3,7 +3,7 @@ use gg_graphics::{FontDb, GraphicsEncoder, TextLayouter};
 use gg_input::Input;
 use gg_math::{Rect, Vec2};
 
-use crate::Event;
+use crate::{AccessibilityNode, Event, Focus, HitMap, LayoutOverlay};
 
 pub trait View<D> {
     fn init(&mut self, old: &mut Self) -> bool
@@ -24,6 +24,10 @@ pub trait View<D> {
         size
     }
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        let _ = nodes;
+    }
+
     fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
         if bounds.clip_rect.contains(ctx.input.mouse_pos()) {
             Hover::Direct
@@ -68,6 +72,7 @@ impl Default for LayoutHints {
 pub struct LayoutCtx<'a> {
     pub assets: &'a Assets,
     pub fonts: &'a FontDb,
+    pub input: &'a Input,
     pub text_layouter: &'a mut TextLayouter,
 }
 
@@ -106,8 +111,10 @@ pub struct UpdateCtx<'a, D> {
     pub assets: &'a Assets,
     pub input: &'a Input,
     pub data: &'a mut D,
+    pub focus: &'a mut Focus,
     pub layer: u32,
     pub dt: f32,
+    pub redraw_requested: &'a mut bool,
 }
 
 impl<D> UpdateCtx<'_, D> {
@@ -116,10 +123,17 @@ impl<D> UpdateCtx<'_, D> {
             assets: self.assets,
             input: self.input,
             data: self.data,
+            focus: self.focus,
             layer: self.layer,
             dt: self.dt,
+            redraw_requested: self.redraw_requested,
         }
     }
+
+    // asks for another frame, e.g. while something is animating
+    pub fn request_redraw(&mut self) {
+        *self.redraw_requested = true;
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -162,6 +176,8 @@ pub struct DrawCtx<'a> {
     pub layer: u32,
     pub dt: f32,
     pub debug_draw: bool,
+    pub overlay: Option<&'a mut LayoutOverlay>,
+    pub hit_map: Option<&'a mut HitMap>,
 }
 
 impl DrawCtx<'_> {
@@ -173,6 +189,8 @@ impl DrawCtx<'_> {
             layer: self.layer,
             dt: self.dt,
             debug_draw: self.debug_draw,
+            overlay: self.overlay.as_deref_mut(),
+            hit_map: self.hit_map.as_deref_mut(),
         }
     }
 }
@@ -2,7 +2,7 @@ use gg_math::SideOffsets;
 
 use crate::views::constrain::{MaxHeight, MaxWidth, MinHeight, MinWidth, Stretch};
 use crate::views::*;
-use crate::{AnyView, IntoViewSeq, View};
+use crate::{AnyView, IntoViewSeq, Role, View, ViewId};
 
 pub trait AppendChild<D, V: View<D>> {
     type Output: View<D>;
@@ -56,6 +56,14 @@ pub trait ViewExt<D>: View<D> + Sized {
     fn padding<O: Into<SideOffsets<f32>>>(self, offsets: O) -> Padding<Self> {
         padding(offsets, self)
     }
+
+    fn accessible(self, role: Role, name: impl Into<String>) -> Accessible<Self> {
+        accessible(role, name, self)
+    }
+
+    fn id(self, id: ViewId) -> Identified<Self> {
+        identified(id, self)
+    }
 }
 
 impl<D, V> ViewExt<D> for V where V: View<D> + Sized {}
@@ -2,7 +2,9 @@ use std::convert::{AsMut, AsRef};
 
 use gg_math::Vec2;
 
-use crate::{Bounds, DrawCtx, Event, Hover, LayoutCtx, LayoutHints, UpdateCtx, View};
+use crate::{
+    AccessibilityNode, Bounds, DrawCtx, Event, Hover, LayoutCtx, LayoutHints, UpdateCtx, View,
+};
 
 pub trait ViewSeq<D> {
     fn len(&self) -> usize;
@@ -17,6 +19,8 @@ pub trait ViewSeq<D> {
 
     fn layout(&mut self, ctx: &mut LayoutCtx, size: Vec2<f32>, idx: usize) -> Vec2<f32>;
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>, idx: usize);
+
     fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, idx: usize) -> Hover;
 
     fn update(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, idx: usize);
@@ -43,6 +47,8 @@ impl<D> ViewSeq<D> for () {
         size
     }
 
+    fn accessibility(&mut self, _: &mut Vec<AccessibilityNode>, _: usize) {}
+
     fn hover(&mut self, _: &mut UpdateCtx<D>, _: Bounds, _: usize) -> Hover {
         Hover::None
     }
@@ -89,6 +95,14 @@ where
         }
     }
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>, idx: usize) {
+        if idx == 0 {
+            self.0.accessibility(nodes)
+        } else {
+            self.1.accessibility(nodes, idx - 1)
+        }
+    }
+
     fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, idx: usize) -> Hover {
         if idx == 0 {
             self.0.hover(ctx, bounds)
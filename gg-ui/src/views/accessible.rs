@@ -0,0 +1,64 @@
+use gg_input::Event;
+use gg_math::Vec2;
+
+use crate::{
+    AccessibilityNode, Bounds, DrawCtx, Hover, LayoutCtx, LayoutHints, Role, UpdateCtx, View,
+};
+
+pub fn accessible<V>(role: Role, name: impl Into<String>, view: V) -> Accessible<V> {
+    Accessible {
+        view,
+        role,
+        name: name.into(),
+    }
+}
+
+pub struct Accessible<V> {
+    view: V,
+    role: Role,
+    name: String,
+}
+
+impl<D, V: View<D>> View<D> for Accessible<V> {
+    fn init(&mut self, old: &mut Self) -> bool
+    where
+        Self: Sized,
+    {
+        (self.role != old.role || self.name != old.name) | self.view.init(&mut old.view)
+    }
+
+    fn pre_layout(&mut self, ctx: &mut LayoutCtx) -> LayoutHints {
+        self.view.pre_layout(ctx)
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, size: Vec2<f32>) -> Vec2<f32> {
+        self.view.layout(ctx, size)
+    }
+
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        let mut children = Vec::new();
+        self.view.accessibility(&mut children);
+
+        nodes.push(
+            AccessibilityNode::new(self.role)
+                .with_name(&self.name)
+                .with_children(children),
+        );
+    }
+
+    fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
+        self.view.hover(ctx, bounds)
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) {
+        self.view.update(ctx, bounds);
+    }
+
+    fn handle(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, event: Event) -> bool {
+        self.view.handle(ctx, bounds, event)
+    }
+
+    fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
+        self.view.draw(ctx, bounds);
+    }
+}
@@ -0,0 +1,142 @@
+use gg_input::Event;
+use gg_math::Vec2;
+
+use crate::{
+    AccessibilityNode, Animation, Bounds, DrawCtx, Easing, Hover, LayoutCtx, LayoutHints,
+    UpdateCtx, View,
+};
+
+pub fn animated<VF, V>(
+    target: f32,
+    duration: f32,
+    easing: Easing,
+    view_factory: VF,
+) -> Animated<VF, V>
+where
+    VF: FnOnce(f32) -> V,
+{
+    Animated {
+        animation: Animation::new(target, duration, easing),
+        value: target,
+        view: None,
+        view_factory: Some(view_factory),
+    }
+}
+
+pub struct Animated<VF, V> {
+    animation: Animation,
+    value: f32,
+    view: Option<V>,
+    view_factory: Option<VF>,
+}
+
+impl<VF, V> Animated<VF, V>
+where
+    VF: FnOnce(f32) -> V,
+{
+    fn ensure_init(&mut self) {
+        if let Some(factory) = self.view_factory.take() {
+            self.view = Some(factory(self.value));
+        }
+    }
+}
+
+impl<D, VF, V> View<D> for Animated<VF, V>
+where
+    VF: FnOnce(f32) -> V,
+    V: View<D>,
+{
+    fn init(&mut self, old: &mut Self) -> bool
+    where
+        Self: Sized,
+    {
+        // the old animation was already advanced during its update
+        let mut animation = old.animation;
+        animation.set_duration(self.animation.duration());
+        animation.set_easing(self.animation.easing());
+        animation.set_target(self.animation.target());
+
+        self.animation = animation;
+        self.value = animation.value();
+        self.ensure_init();
+
+        let changed = self.value != old.value;
+
+        if let (Some(view), Some(old_view)) = (&mut self.view, &mut old.view) {
+            view.init(old_view) || changed
+        } else {
+            true
+        }
+    }
+
+    fn pre_layout(&mut self, ctx: &mut LayoutCtx) -> LayoutHints {
+        self.ensure_init();
+
+        if let Some(view) = &mut self.view {
+            view.pre_layout(ctx)
+        } else {
+            LayoutHints::default()
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, size: Vec2<f32>) -> Vec2<f32> {
+        self.ensure_init();
+
+        if let Some(view) = &mut self.view {
+            view.layout(ctx, size)
+        } else {
+            size
+        }
+    }
+
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        self.ensure_init();
+
+        if let Some(view) = &mut self.view {
+            view.accessibility(nodes)
+        }
+    }
+
+    fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
+        self.ensure_init();
+
+        if let Some(view) = &mut self.view {
+            view.hover(ctx, bounds)
+        } else {
+            Hover::None
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) {
+        self.animation.tick(ctx.dt);
+
+        // the next frame rebuilds the view with the new value
+        if !self.animation.is_settled() || self.animation.value() != self.value {
+            ctx.request_redraw();
+        }
+
+        self.ensure_init();
+
+        if let Some(view) = &mut self.view {
+            view.update(ctx, bounds)
+        }
+    }
+
+    fn handle(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, event: Event) -> bool {
+        self.ensure_init();
+
+        if let Some(view) = &mut self.view {
+            view.handle(ctx, bounds, event)
+        } else {
+            false
+        }
+    }
+
+    fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
+        self.ensure_init();
+
+        if let Some(view) = &mut self.view {
+            view.draw(ctx, bounds)
+        }
+    }
+}
@@ -1,20 +1,51 @@
-use crate::{views, SetChildren, View, ViewExt};
+use gg_input::Action;
+
+use crate::{views, Role, SetChildren, View, ViewExt};
 
 pub fn button<D>(
     label: impl Into<String>,
     callback: impl FnOnce(&mut D) + 'static,
 ) -> impl View<D> {
+    let label = label.into();
+
     views::stateful(0, |state| {
-        let label = format!("{} ({})", label.into(), state);
+        let text = format!("{} ({})", label, state);
+        let content = views::text(text).wrap(false);
+
+        frame(label, content, |(data, state): &mut (D, i32)| {
+            *state += 1;
+            callback(data)
+        })
+    })
+}
 
-        views::overlay().padding(4.0).children((
+pub fn button_with_action<D>(
+    label: impl Into<String>,
+    action: impl Into<Action>,
+    callback: impl FnOnce(&mut D) + 'static,
+) -> impl View<D> {
+    let label = label.into();
+
+    let content = views::hstack().children((
+        views::text(label.clone()).wrap(false),
+        views::shortcut_hint(action).padding([8.0, 0.0, 0.0, 0.0]),
+    ));
+
+    frame(label, content, callback)
+}
+
+fn frame<D>(
+    label: String,
+    content: impl View<D>,
+    callback: impl FnOnce(&mut D) + 'static,
+) -> impl View<D> {
+    views::overlay()
+        .padding(4.0)
+        .children((
             views::rect([0.1; 3]),
-            views::text(label).wrap(false).padding([10.0, 20.0]),
-            views::touch_area(|(data, state)| {
-                *state += 1;
-                callback(data)
-            }),
+            content.padding([10.0, 20.0]),
+            views::touch_area(callback),
             views::nothing().stretch(2.0),
         ))
-    })
+        .accessible(Role::Button, label)
 }
@@ -0,0 +1,64 @@
+use gg_input::Event;
+use gg_math::Vec2;
+
+use crate::{
+    views, AccessibilityNode, Bounds, DrawCtx, LayoutCtx, LayoutHints, Role, UpdateCtx, View,
+};
+
+const SIZE: f32 = 24.0;
+const MARK_INSET: f32 = 6.0;
+
+pub fn checkbox<D>(checked: bool, on_toggle: impl FnOnce(&mut D, bool)) -> impl View<D> {
+    Checkbox {
+        checked,
+        touch_area: views::touch_area(move |data| on_toggle(data, !checked)),
+    }
+}
+
+struct Checkbox<T> {
+    checked: bool,
+    touch_area: T,
+}
+
+impl<D, T: View<D>> View<D> for Checkbox<T> {
+    fn init(&mut self, old: &mut Self) -> bool {
+        self.checked != old.checked
+    }
+
+    fn pre_layout(&mut self, _ctx: &mut LayoutCtx) -> LayoutHints {
+        LayoutHints {
+            min_size: Vec2::splat(SIZE),
+            max_size: Vec2::splat(SIZE),
+            ..LayoutHints::default()
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, _size: Vec2<f32>) -> Vec2<f32> {
+        Vec2::splat(SIZE)
+    }
+
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        let name = if self.checked { "checked" } else { "unchecked" };
+        nodes.push(AccessibilityNode::new(Role::CheckBox).with_name(name));
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) {
+        self.touch_area.update(ctx, bounds);
+    }
+
+    fn handle(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, event: Event) -> bool {
+        self.touch_area.handle(ctx, bounds, event)
+    }
+
+    fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
+        ctx.encoder.rect(bounds.rect).fill_color([0.1; 3]);
+        ctx.encoder.stroke_rect(bounds.rect, 2.0, [0.5; 3]);
+
+        if self.checked {
+            let mark = bounds.rect.shrink(&MARK_INSET.into());
+            ctx.encoder.rounded_rect(mark, 2.0).fill_color([0.9; 3]);
+        }
+
+        self.touch_area.draw(ctx, bounds);
+    }
+}
@@ -1,6 +1,6 @@
 use gg_math::Vec2;
 
-use crate::{Bounds, DrawCtx, Event, LayoutCtx, LayoutHints, UpdateCtx, View};
+use crate::{AccessibilityNode, Bounds, DrawCtx, Event, LayoutCtx, LayoutHints, UpdateCtx, View};
 
 pub fn choose<VT, VF>(condition: bool, view_t: VT, view_f: VF) -> Choice<VT, VF> {
     Choice {
@@ -47,6 +47,22 @@ where
         }
     }
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        if self.condition {
+            self.view_t.accessibility(nodes)
+        } else {
+            self.view_f.accessibility(nodes)
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) {
+        if self.condition {
+            self.view_t.update(ctx, bounds)
+        } else {
+            self.view_f.update(ctx, bounds)
+        }
+    }
+
     fn handle(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, event: Event) -> bool {
         if self.condition {
             self.view_t.handle(ctx, bounds, event)
@@ -1,8 +1,8 @@
 use gg_math::Vec2;
 
 use crate::{
-    AppendChild, Bounds, DrawCtx, Event, Hover, IntoViewSeq, LayoutCtx, LayoutHints, SetChildren,
-    UpdateCtx, View,
+    AccessibilityNode, AppendChild, Bounds, DrawCtx, Event, Hover, IntoViewSeq, LayoutCtx,
+    LayoutHints, SetChildren, UpdateCtx, View,
 };
 
 pub fn constrain<V, C>(view: V, constraint: C) -> Constrain<V, C> {
@@ -68,6 +68,10 @@ where
         self.view.layout(ctx, size)
     }
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        self.view.accessibility(nodes)
+    }
+
     fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
         self.view.hover(ctx, bounds)
     }
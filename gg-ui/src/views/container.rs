@@ -5,8 +5,8 @@ use gg_math::{Rect, Vec2};
 
 use crate::view_seq::{Append, HasMetaSeq};
 use crate::{
-    AppendChild, Bounds, Hover, IntoViewSeq, LayoutCtx, LayoutHints, SetChildren, UpdateCtx, View,
-    ViewSeq,
+    AccessibilityNode, AppendChild, Bounds, Hover, IntoViewSeq, LayoutCtx, LayoutHints, Role,
+    SetChildren, UpdateCtx, View, ViewSeq,
 };
 
 #[derive(Clone, Copy)]
@@ -144,6 +144,18 @@ where
             .layout(ctx, &mut self.children, self.meta.as_mut(), size)
     }
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        let mut children = Vec::new();
+
+        for i in 0..self.children.len() {
+            self.children.accessibility(&mut children, i);
+        }
+
+        if !children.is_empty() {
+            nodes.push(AccessibilityNode::new(Role::Group).with_children(children));
+        }
+    }
+
     fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
         let meta = self.meta.as_mut();
         let mut hover = Hover::None;
@@ -173,7 +185,8 @@ where
     fn update(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) {
         let meta = self.meta.as_mut();
 
-        for (i, child) in meta.iter().enumerate().rev() {
+        // children register focus during update, keep them in traversal order
+        for (i, child) in meta.iter().enumerate() {
             let rect = Rect::new(bounds.rect.min + child.pos, child.size);
             let bounds = bounds.child(rect, child.hover);
             self.children.update(ctx, bounds, i);
@@ -209,6 +222,11 @@ where
             let rect = Rect::new(bounds.rect.min + child.pos, child.size);
 
             let bounds = bounds.child(rect, child.hover);
+
+            if let (0, Some(overlay)) = (ctx.layer, ctx.overlay.as_deref_mut()) {
+                overlay.add_bounds(bounds.rect);
+            }
+
             self.children.draw(ctx, bounds, i);
 
             if child.hover.is_some() && ctx.debug_draw {
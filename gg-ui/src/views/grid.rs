@@ -0,0 +1,196 @@
+use gg_math::Vec2;
+
+use super::container::{container, ChildMeta, Container, Layout};
+use super::stack::MinorAlign;
+use super::table::ColumnWidth;
+use crate::{LayoutCtx, LayoutHints, ViewSeq};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridConfig {
+    pub columns: Vec<ColumnWidth>,
+    pub spacing: Vec2<f32>,
+    pub h_align: MinorAlign,
+    pub v_align: MinorAlign,
+}
+
+impl GridConfig {
+    // equally wide columns
+    pub fn new(num_columns: usize) -> GridConfig {
+        GridConfig {
+            columns: vec![ColumnWidth::Weight(1.0); num_columns.max(1)],
+            spacing: Vec2::zero(),
+            h_align: MinorAlign::Center,
+            v_align: MinorAlign::Center,
+        }
+    }
+}
+
+pub struct Grid {
+    config: GridConfig,
+    column_widths: Vec<f32>,
+    row_heights: Vec<f32>,
+}
+
+pub fn grid<D>(config: GridConfig) -> Container<D, Grid, ()> {
+    container(Grid {
+        config,
+        column_widths: Vec::new(),
+        row_heights: Vec::new(),
+    })
+}
+
+impl Grid {
+    fn num_columns(&self) -> usize {
+        self.config.columns.len().max(1)
+    }
+
+    fn num_rows(&self, num_children: usize) -> usize {
+        (num_children + self.num_columns() - 1) / self.num_columns()
+    }
+
+    fn min_sizes(&self, meta: &[ChildMeta]) -> (Vec<f32>, Vec<f32>) {
+        let num_columns = self.num_columns();
+        let mut widths = vec![0.0f32; num_columns];
+        let mut heights = vec![0.0f32; self.num_rows(meta.len())];
+
+        for (i, child) in meta.iter().enumerate() {
+            let (row, col) = (i / num_columns, i % num_columns);
+            widths[col] = widths[col].max(child.hints.min_size.x);
+            heights[row] = heights[row].max(child.hints.min_size.y);
+        }
+
+        for (width, column) in widths.iter_mut().zip(&self.config.columns) {
+            if let ColumnWidth::Fixed(v) = *column {
+                *width = v;
+            }
+        }
+
+        (widths, heights)
+    }
+
+    // weighted columns share the space left after the other columns
+    fn column_widths(&self, meta: &[ChildMeta], available: f32) -> Vec<f32> {
+        let (mut widths, _) = self.min_sizes(meta);
+
+        let total_weight: f32 = self
+            .config
+            .columns
+            .iter()
+            .map(|v| match v {
+                ColumnWidth::Weight(weight) => *weight,
+                _ => 0.0,
+            })
+            .sum();
+
+        let used: f32 = widths.iter().sum();
+        let remaining = (available - used).max(0.0);
+
+        if total_weight > 0.0 {
+            for (width, column) in widths.iter_mut().zip(&self.config.columns) {
+                if let ColumnWidth::Weight(weight) = *column {
+                    *width += (remaining * weight / total_weight).floor();
+                }
+            }
+        }
+
+        widths
+    }
+}
+
+impl<D, C> Layout<D, C> for Grid
+where
+    C: ViewSeq<D>,
+{
+    fn pre_layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        children: &mut C,
+        meta: &mut [ChildMeta],
+    ) -> LayoutHints {
+        let mut hints = LayoutHints::default();
+
+        for (i, child) in meta.iter_mut().enumerate() {
+            if child.changed {
+                child.hints = children.pre_layout(ctx, i);
+            }
+
+            hints.num_layers = hints.num_layers.max(child.hints.num_layers);
+        }
+
+        let (widths, heights) = self.min_sizes(meta);
+        let spacing = self.config.spacing;
+
+        hints.min_size = Vec2::new(
+            widths.iter().sum::<f32>() + spacing.x * gaps(widths.len()),
+            heights.iter().sum::<f32>() + spacing.y * gaps(heights.len()),
+        );
+
+        hints
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        children: &mut C,
+        meta: &mut [ChildMeta],
+        adviced: Vec2<f32>,
+    ) -> Vec2<f32> {
+        let num_columns = self.num_columns();
+        let spacing = self.config.spacing;
+
+        let available = adviced.x - spacing.x * gaps(num_columns);
+        self.column_widths = self.column_widths(meta, available);
+        self.row_heights = self.min_sizes(meta).1;
+
+        for (i, child) in meta.iter_mut().enumerate() {
+            let (row, col) = (i / num_columns, i % num_columns);
+            let cell = Vec2::new(self.column_widths[col], self.row_heights[row]);
+            let size = cell.fclamp(child.hints.min_size, child.hints.max_size);
+
+            if size != child.size || child.changed {
+                child.size = children.layout(ctx, size, i);
+            }
+
+            // children may end up taller than their minimum, e.g. wrapped text
+            self.row_heights[row] = self.row_heights[row].max(child.size.y);
+        }
+
+        let mut y = 0.0;
+        let mut row_offsets = Vec::with_capacity(self.row_heights.len());
+        for &height in &self.row_heights {
+            row_offsets.push(y);
+            y += height + spacing.y;
+        }
+
+        for (i, child) in meta.iter_mut().enumerate() {
+            let (row, col) = (i / num_columns, i % num_columns);
+            let x = self.column_widths[..col].iter().sum::<f32>() + spacing.x * col as f32;
+
+            let cell = Vec2::new(self.column_widths[col], self.row_heights[row]);
+            child.pos = Vec2::new(
+                x + align_offset(self.config.h_align, cell.x, child.size.x),
+                row_offsets[row] + align_offset(self.config.v_align, cell.y, child.size.y),
+            );
+        }
+
+        let used = Vec2::new(
+            self.column_widths.iter().sum::<f32>() + spacing.x * gaps(num_columns),
+            self.row_heights.iter().sum::<f32>() + spacing.y * gaps(self.row_heights.len()),
+        );
+
+        used.fmax(adviced)
+    }
+}
+
+// number of spacings between `count` cells
+fn gaps(count: usize) -> f32 {
+    count.saturating_sub(1) as f32
+}
+
+fn align_offset(align: MinorAlign, cell: f32, size: f32) -> f32 {
+    match align {
+        MinorAlign::Start => 0.0,
+        MinorAlign::Center => (cell - size) * 0.5,
+        MinorAlign::End => cell - size,
+    }
+}
@@ -0,0 +1,60 @@
+use gg_input::Event;
+use gg_math::Vec2;
+
+use crate::{
+    AccessibilityNode, Bounds, DrawCtx, Hover, LayoutCtx, LayoutHints, UpdateCtx, View, ViewId,
+};
+
+pub fn identified<V>(id: ViewId, view: V) -> Identified<V> {
+    Identified { view, id }
+}
+
+pub struct Identified<V> {
+    view: V,
+    id: ViewId,
+}
+
+impl<D, V: View<D>> View<D> for Identified<V> {
+    fn init(&mut self, old: &mut Self) -> bool
+    where
+        Self: Sized,
+    {
+        self.view.init(&mut old.view)
+    }
+
+    fn pre_layout(&mut self, ctx: &mut LayoutCtx) -> LayoutHints {
+        self.view.pre_layout(ctx)
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, size: Vec2<f32>) -> Vec2<f32> {
+        self.view.layout(ctx, size)
+    }
+
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        self.view.accessibility(nodes);
+    }
+
+    fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
+        self.view.hover(ctx, bounds)
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) {
+        self.view.update(ctx, bounds);
+    }
+
+    fn handle(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, event: Event) -> bool {
+        self.view.handle(ctx, bounds, event)
+    }
+
+    fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
+        if let Some(hit_map) = ctx.hit_map.as_deref_mut() {
+            hit_map.push(self.id, bounds.clip_rect);
+        }
+
+        self.view.draw(ctx, bounds);
+
+        if let Some(hit_map) = ctx.hit_map.as_deref_mut() {
+            hit_map.pop();
+        }
+    }
+}
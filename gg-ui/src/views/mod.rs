@@ -1,29 +1,49 @@
+mod accessible;
+mod animated;
 mod button;
+mod checkbox;
 mod choice;
 pub mod constrain;
 pub mod container;
+pub mod grid;
+mod identified;
 mod nothing;
 mod overlay;
 mod padding;
 mod rect;
 mod scrollable;
+mod shortcut;
+mod slider;
+mod split;
 pub mod stack;
 mod stateful;
+mod table;
 mod text;
+mod text_input;
 mod tooltip;
 mod touch_area;
 
-pub use self::button::button;
+pub use self::accessible::{accessible, Accessible};
+pub use self::animated::{animated, Animated};
+pub use self::button::{button, button_with_action};
+pub use self::checkbox::checkbox;
 pub use self::choice::{choose, Choice};
 pub use self::constrain::{constrain, Constrain};
 pub use self::container::{container, Container};
+pub use self::grid::{grid, Grid, GridConfig};
+pub use self::identified::{identified, Identified};
 pub use self::nothing::{nothing, Nothing};
 pub use self::overlay::{overlay, Overlay};
 pub use self::padding::{padding, Padding};
 pub use self::rect::{rect, RectView};
-pub use self::scrollable::{scrollable, Scrollable};
+pub use self::scrollable::{scrollable, Scrollable, ScrollbarPolicy};
+pub use self::shortcut::{shortcut_hint, ShortcutHint};
+pub use self::slider::{slider, Slider};
+pub use self::split::{split, Split};
 pub use self::stack::{hstack, vstack, Stack};
 pub use self::stateful::{stateful, Stateful};
+pub use self::table::{column, table, Column, ColumnWidth, Table};
 pub use self::text::{text, TextView};
+pub use self::text_input::{text_input, TextField};
 pub use self::tooltip::{tooltip, Tooltip};
 pub use self::touch_area::{touch_area, TouchArea};
@@ -1,8 +1,8 @@
 use gg_math::{SideOffsets, Vec2};
 
 use crate::{
-    AppendChild, Bounds, DrawCtx, Event, Hover, IntoViewSeq, LayoutCtx, LayoutHints, SetChildren,
-    UpdateCtx, View,
+    AccessibilityNode, AppendChild, Bounds, DrawCtx, Event, Hover, IntoViewSeq, LayoutCtx,
+    LayoutHints, SetChildren, UpdateCtx, View,
 };
 
 pub fn padding<O, V>(offsets: O, view: V) -> Padding<V>
@@ -69,6 +69,10 @@ impl<D, V: View<D>> View<D> for Padding<V> {
         self.view.layout(ctx, size - self.offsets.size()) + self.offsets.size()
     }
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        self.view.accessibility(nodes)
+    }
+
     fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
         let bounds = bounds.child(bounds.rect.shrink(&self.offsets), Hover::None);
         self.view.hover(ctx, bounds)
@@ -85,7 +89,13 @@ impl<D, V: View<D>> View<D> for Padding<V> {
     }
 
     fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
-        let bounds = bounds.child(bounds.rect.shrink(&self.offsets), bounds.hover);
+        let outer = bounds.rect;
+        let bounds = bounds.child(outer.shrink(&self.offsets), bounds.hover);
+
+        if let (0, Some(overlay)) = (ctx.layer, ctx.overlay.as_deref_mut()) {
+            overlay.add_padding(outer, bounds.rect);
+        }
+
         self.view.draw(ctx, bounds);
     }
 }
@@ -1,7 +1,10 @@
-use gg_input::Event;
+use gg_input::{DragEvent, Event, MouseButton};
 use gg_math::{Rect, Vec2};
 
-use crate::{Bounds, DrawCtx, Hover, LayoutCtx, LayoutHints, UiAction, UpdateCtx, View};
+use super::stack::Orientation;
+use crate::{
+    AccessibilityNode, Bounds, DrawCtx, Hover, LayoutCtx, LayoutHints, UiAction, UpdateCtx, View,
+};
 
 pub fn scrollable<V>(view: V) -> Scrollable<V> {
     Scrollable {
@@ -10,18 +13,208 @@ pub fn scrollable<V>(view: V) -> Scrollable<V> {
         offset: Vec2::zero(),
         target_offset: Vec2::zero(),
         inner_size: Vec2::zero(),
+        scroll_to: None,
+        minimap: None,
+        policy: ScrollbarPolicy::Auto,
+        opacity: 1.0,
+        idle_time: 0.0,
     }
 }
 
+const MINIMAP_WIDTH: f32 = 12.0;
+const SCROLLBAR_WIDTH: f32 = 6.0;
+const MIN_THUMB_LENGTH: f32 = 16.0;
+
+// auto scrollbars start fading after this many seconds without scrolling
+const FADE_DELAY: f32 = 1.0;
+const FADE_SPEED: f32 = 4.0;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScrollbarPolicy {
+    Always,
+    Auto,
+    Never,
+}
+
 pub struct Scrollable<V> {
     view: V,
     hints: LayoutHints,
     offset: Vec2<f32>,
     target_offset: Vec2<f32>,
     inner_size: Vec2<f32>,
+    scroll_to: Option<Vec2<f32>>,
+    minimap: Option<Vec<f32>>,
+    policy: ScrollbarPolicy,
+    opacity: f32,
+    idle_time: f32,
 }
 
 impl<V> Scrollable<V> {
+    pub fn scroll_to(mut self, pos: Vec2<f32>) -> Self {
+        self.scroll_to = Some(pos);
+        self.offset = -pos;
+        self.target_offset = -pos;
+        self
+    }
+
+    pub fn with_minimap(mut self, markers: impl IntoIterator<Item = f32>) -> Self {
+        self.minimap = Some(markers.into_iter().collect());
+        self
+    }
+
+    pub fn scrollbars(mut self, policy: ScrollbarPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn scroll_position(&self) -> Vec2<f32> {
+        -self.offset
+    }
+
+    // scrollbars are placed to the left of the minimap
+    pub fn scrollbar_track(&self, outer: Rect<f32>, orientation: Orientation) -> Option<Rect<f32>> {
+        if self.policy == ScrollbarPolicy::Never || !self.overflows(outer, orientation) {
+            return None;
+        }
+
+        match orientation {
+            Orientation::Vertical => {
+                let max_x = self.minimap_rect(outer).map_or(outer.max.x, |v| v.min.x);
+
+                let mut max_y = outer.max.y;
+                if self.overflows(outer, Orientation::Horizontal) {
+                    max_y -= SCROLLBAR_WIDTH;
+                }
+
+                Some(Rect::from_min_max(
+                    Vec2::new(max_x - SCROLLBAR_WIDTH, outer.min.y),
+                    Vec2::new(max_x, max_y),
+                ))
+            }
+            Orientation::Horizontal => {
+                let mut max_x = self.minimap_rect(outer).map_or(outer.max.x, |v| v.min.x);
+                if self.overflows(outer, Orientation::Vertical) {
+                    max_x -= SCROLLBAR_WIDTH;
+                }
+
+                Some(Rect::from_min_max(
+                    Vec2::new(outer.min.x, outer.max.y - SCROLLBAR_WIDTH),
+                    Vec2::new(max_x, outer.max.y),
+                ))
+            }
+        }
+    }
+
+    // the thumb length is proportional to the visible part of the content
+    pub fn scrollbar_thumb(&self, outer: Rect<f32>, orientation: Orientation) -> Option<Rect<f32>> {
+        let track = self.scrollbar_track(outer, orientation)?;
+        let (axis, _) = orientation.indices();
+
+        let track_len = track.size()[axis];
+        let ratio = outer.size()[axis] / self.inner_size[axis];
+        let thumb_len = (track_len * ratio).max(MIN_THUMB_LENGTH).min(track_len);
+
+        let max_offset = self.inner_size[axis] - outer.size()[axis];
+        let progress = (-self.offset[axis] / max_offset).clamp(0.0, 1.0);
+
+        let mut min = track.min;
+        min[axis] += progress * (track_len - thumb_len);
+        let mut size = track.size();
+        size[axis] = thumb_len;
+
+        Some(Rect::new(min, size))
+    }
+
+    fn overflows(&self, outer: Rect<f32>, orientation: Orientation) -> bool {
+        let (axis, _) = orientation.indices();
+        self.inner_size[axis] > outer.size()[axis]
+    }
+
+    // moves the content so that the thumb follows the pointer
+    fn drag_thumb(&mut self, outer: Rect<f32>, orientation: Orientation, delta: f32) -> bool {
+        let (track, thumb) = match (
+            self.scrollbar_track(outer, orientation),
+            self.scrollbar_thumb(outer, orientation),
+        ) {
+            (Some(track), Some(thumb)) => (track, thumb),
+            _ => return false,
+        };
+
+        let (axis, _) = orientation.indices();
+        let free_len = track.size()[axis] - thumb.size()[axis];
+        if free_len <= 0.0 {
+            return true;
+        }
+
+        let max_offset = self.inner_size[axis] - outer.size()[axis];
+        let offset = self.offset[axis] - delta * max_offset / free_len;
+        self.offset[axis] = offset.clamp(-max_offset, 0.0);
+        self.target_offset[axis] = self.offset[axis];
+        self.idle_time = 0.0;
+
+        true
+    }
+
+    fn draw_scrollbars(&self, ctx: &mut DrawCtx, outer: Rect<f32>) {
+        if self.opacity <= 0.0 {
+            return;
+        }
+
+        for orientation in [Orientation::Vertical, Orientation::Horizontal] {
+            let (track, thumb) = match (
+                self.scrollbar_track(outer, orientation),
+                self.scrollbar_thumb(outer, orientation),
+            ) {
+                (Some(track), Some(thumb)) => (track, thumb),
+                _ => continue,
+            };
+
+            ctx.encoder
+                .rect(track)
+                .fill_color([1.0, 1.0, 1.0, 0.1 * self.opacity]);
+            ctx.encoder
+                .rounded_rect(thumb, SCROLLBAR_WIDTH * 0.5)
+                .fill_color([1.0, 1.0, 1.0, 0.5 * self.opacity]);
+        }
+    }
+
+    pub fn minimap_rect(&self, outer: Rect<f32>) -> Option<Rect<f32>> {
+        self.minimap.as_ref()?;
+
+        Some(Rect::new(
+            Vec2::new(outer.max.x - MINIMAP_WIDTH, outer.min.y),
+            Vec2::new(MINIMAP_WIDTH, outer.size().y),
+        ))
+    }
+
+    pub fn minimap_indicator(&self, outer: Rect<f32>) -> Option<Rect<f32>> {
+        let strip = self.minimap_rect(outer)?;
+        let factor = (strip.size().y / self.inner_size.y).min(1.0);
+
+        Some(Rect::new(
+            Vec2::new(strip.min.x, strip.min.y - self.offset.y * factor),
+            Vec2::new(strip.size().x, outer.size().y * factor),
+        ))
+    }
+
+    fn draw_minimap(&self, ctx: &mut DrawCtx, outer: Rect<f32>, markers: &[f32]) {
+        let (strip, indicator) = match (self.minimap_rect(outer), self.minimap_indicator(outer)) {
+            (Some(strip), Some(indicator)) => (strip, indicator),
+            _ => return,
+        };
+
+        ctx.encoder.rect(strip).fill_color([0.0, 0.0, 0.0, 0.3]);
+
+        for &marker in markers {
+            let y = strip.min.y + marker.clamp(0.0, 1.0) * strip.size().y;
+            ctx.encoder
+                .rect([strip.min.x, y, strip.size().x, 1.0])
+                .fill_color([1.0, 1.0, 1.0, 0.5]);
+        }
+
+        ctx.encoder.rect(indicator).fill_color([1.0, 0.0, 0.0, 0.3]);
+    }
+
     fn inner_bounds(&self, outer: Bounds) -> Bounds {
         outer.with_scissor(outer.rect).child(
             Rect::new(outer.rect.min + self.offset.floor(), self.inner_size),
@@ -36,9 +229,15 @@ impl<D, V: View<D>> View<D> for Scrollable<V> {
         Self: Sized,
     {
         self.hints = old.hints;
-        self.offset = old.offset;
-        self.target_offset = old.target_offset;
         self.inner_size = old.inner_size;
+        self.opacity = old.opacity;
+        self.idle_time = old.idle_time;
+
+        // a scroll target is applied once, when it changes
+        if self.scroll_to.is_none() || self.scroll_to == old.scroll_to {
+            self.offset = old.offset;
+            self.target_offset = old.target_offset;
+        }
 
         self.view.init(&mut old.view)
     }
@@ -67,6 +266,10 @@ impl<D, V: View<D>> View<D> for Scrollable<V> {
         size
     }
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        self.view.accessibility(nodes)
+    }
+
     fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
         let inner = self.view.hover(ctx, self.inner_bounds(bounds));
 
@@ -80,10 +283,47 @@ impl<D, V: View<D>> View<D> for Scrollable<V> {
     fn update(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) {
         let diff = self.target_offset - self.offset;
         self.offset += diff.map(|v| (v.abs() * ctx.dt * 8.0).ceil().min(v.abs()).copysign(v));
+
+        let mouse_pos = ctx.input.mouse_pos();
+        let over_track = [Orientation::Vertical, Orientation::Horizontal]
+            .into_iter()
+            .flat_map(|v| self.scrollbar_track(bounds.rect, v))
+            .any(|track| track.contains(mouse_pos));
+
+        if diff != Vec2::zero() || over_track {
+            self.idle_time = 0.0;
+        } else {
+            self.idle_time += ctx.dt;
+        }
+
+        self.opacity = match self.policy {
+            ScrollbarPolicy::Always => 1.0,
+            ScrollbarPolicy::Never => 0.0,
+            ScrollbarPolicy::Auto if self.idle_time < FADE_DELAY => 1.0,
+            ScrollbarPolicy::Auto => (self.opacity - ctx.dt * FADE_SPEED).max(0.0),
+        };
+
         self.view.update(ctx, self.inner_bounds(bounds))
     }
 
     fn handle(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, event: Event) -> bool {
+        if let Event::Drag(DragEvent {
+            button: MouseButton::Left,
+            start,
+            delta,
+            ..
+        }) = event
+        {
+            // drags are recognized by where they started, since the thumb moves
+            for orientation in [Orientation::Vertical, Orientation::Horizontal] {
+                let (axis, _) = orientation.indices();
+                let track = self.scrollbar_track(bounds.rect, orientation);
+                if ctx.layer == 0 && track.map_or(false, |v| v.contains(start)) {
+                    return self.drag_thumb(bounds.rect, orientation, delta[axis]);
+                }
+            }
+        }
+
         if self.view.handle(ctx, self.inner_bounds(bounds), event) {
             return true;
         }
@@ -112,7 +352,6 @@ impl<D, V: View<D>> View<D> for Scrollable<V> {
     fn draw(&mut self, ctx: &mut DrawCtx, outer_bounds: Bounds) {
         let inner_bounds = self.inner_bounds(outer_bounds);
         let outer = outer_bounds.rect;
-        let inner = inner_bounds.rect;
 
         ctx.encoder.save();
         ctx.encoder.set_scissor(outer);
@@ -124,35 +363,11 @@ impl<D, V: View<D>> View<D> for Scrollable<V> {
             return;
         }
 
-        let mut thumb_factor = outer.size() / inner.size();
-        if thumb_factor.x < 1.0 && thumb_factor.y < 1.0 {
-            thumb_factor = (outer.size() - Vec2::new(7.0, 0.0)) / inner.size();
+        if let Some(markers) = &self.minimap {
+            self.draw_minimap(ctx, outer, markers);
         }
 
-        let thumb_size = outer.size() * thumb_factor;
-        let thumb_offset = -self.offset * thumb_factor;
-
-        if thumb_factor.x < 1.0 {
-            ctx.encoder
-                .rect([
-                    outer.min.x + thumb_offset.x,
-                    outer.max.y - 4.0,
-                    thumb_size.x,
-                    3.0,
-                ])
-                .fill_color([1.0, 0.0, 0.0, 0.3]);
-        }
-
-        if thumb_factor.y < 1.0 {
-            ctx.encoder
-                .rect([
-                    outer.max.x - 4.0,
-                    outer.min.y + thumb_offset.y,
-                    3.0,
-                    thumb_size.y,
-                ])
-                .fill_color([1.0, 0.0, 0.0, 0.3]);
-        }
+        self.draw_scrollbars(ctx, outer);
 
         ctx.encoder.restore();
     }
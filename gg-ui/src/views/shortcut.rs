@@ -0,0 +1,71 @@
+use gg_input::{Action, Binding};
+use gg_math::Vec2;
+
+use crate::views::{self, TextView};
+use crate::{Bounds, DrawCtx, LayoutCtx, LayoutHints, View};
+
+pub fn shortcut_hint<D>(action: impl Into<Action>) -> ShortcutHint<D> {
+    ShortcutHint {
+        action: action.into(),
+        binding: None,
+        text: None,
+    }
+}
+
+pub struct ShortcutHint<D> {
+    action: Action,
+    // binding the cached text was made for
+    binding: Option<Binding>,
+    text: Option<TextView<D>>,
+}
+
+impl<D> ShortcutHint<D> {
+    pub fn hint(&self) -> Option<&str> {
+        self.text.as_ref().map(|v| v.text())
+    }
+}
+
+impl<D> View<D> for ShortcutHint<D> {
+    fn init(&mut self, old: &mut Self) -> bool
+    where
+        Self: Sized,
+    {
+        if self.action == old.action {
+            self.binding = old.binding;
+            self.text = old.text.take();
+            false
+        } else {
+            true
+        }
+    }
+
+    fn pre_layout(&mut self, ctx: &mut LayoutCtx) -> LayoutHints {
+        let binding = ctx.input.bindings_for(self.action).next();
+        if binding != self.binding {
+            self.binding = binding;
+            self.text = binding.map(|binding| {
+                views::text(format!("({})", binding.hint()))
+                    .wrap(false)
+                    .color([0.6; 3])
+            });
+        }
+
+        match &mut self.text {
+            Some(text) => text.pre_layout(ctx),
+            None => LayoutHints::default(),
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, size: Vec2<f32>) -> Vec2<f32> {
+        match &mut self.text {
+            Some(text) => text.layout(ctx, size),
+            None => Vec2::zero(),
+        }
+    }
+
+    fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
+        if let Some(text) = &mut self.text {
+            text.draw(ctx, bounds);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::ops::RangeInclusive;
+
+use gg_input::{DragEvent, ElementState, Event, KeyboardEvent, MouseButton, VirtualKeyCode};
+use gg_math::{Rect, Vec2};
+
+use crate::{
+    AccessibilityNode, Bounds, DrawCtx, FocusId, LayoutCtx, LayoutHints, Role, UiAction, UpdateCtx,
+    View,
+};
+
+const HEIGHT: f32 = 24.0;
+const THUMB_WIDTH: f32 = 12.0;
+const TRACK_HEIGHT: f32 = 4.0;
+
+pub fn slider<D, F>(value: f32, range: RangeInclusive<f32>, on_change: F) -> Slider<F>
+where
+    F: FnMut(&mut D, f32),
+{
+    let (min, max) = range.into_inner();
+    Slider {
+        value: value.clamp(min, max),
+        min,
+        max,
+        step: (max - min) / 100.0,
+        on_change,
+        focus_id: None,
+        focused: false,
+    }
+}
+
+pub struct Slider<F> {
+    value: f32,
+    min: f32,
+    max: f32,
+    step: f32,
+    on_change: F,
+    focus_id: Option<FocusId>,
+    focused: bool,
+}
+
+impl<F> Slider<F> {
+    pub fn step(mut self, v: f32) -> Self {
+        self.step = v;
+        self
+    }
+
+    fn track(&self, rect: Rect<f32>) -> (f32, f32) {
+        let start = rect.min.x + THUMB_WIDTH * 0.5;
+        let end = rect.max.x - THUMB_WIDTH * 0.5;
+        (start, end.max(start))
+    }
+
+    fn value_at(&self, rect: Rect<f32>, x: f32) -> f32 {
+        let (start, end) = self.track(rect);
+        let t = if end > start {
+            ((x - start) / (end - start)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        self.min + t * (self.max - self.min)
+    }
+
+    fn thumb_x(&self, rect: Rect<f32>) -> f32 {
+        let (start, end) = self.track(rect);
+        let t = if self.max > self.min {
+            (self.value - self.min) / (self.max - self.min)
+        } else {
+            0.0
+        };
+
+        start + t * (end - start)
+    }
+
+    fn focus<D>(&mut self, ctx: &mut UpdateCtx<D>) {
+        if let Some(id) = self.focus_id {
+            ctx.focus.focus(id);
+            self.focused = true;
+        }
+    }
+
+    fn set_value<D>(&mut self, data: &mut D, value: f32)
+    where
+        F: FnMut(&mut D, f32),
+    {
+        let value = value.clamp(self.min, self.max);
+        if value != self.value {
+            self.value = value;
+            (self.on_change)(data, value);
+        }
+    }
+}
+
+impl<D, F> View<D> for Slider<F>
+where
+    F: FnMut(&mut D, f32),
+{
+    fn pre_layout(&mut self, _ctx: &mut LayoutCtx) -> LayoutHints {
+        LayoutHints {
+            stretch: 1.0,
+            min_size: Vec2::new(THUMB_WIDTH * 2.0, HEIGHT),
+            max_size: Vec2::new(f32::INFINITY, HEIGHT),
+            ..LayoutHints::default()
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, size: Vec2<f32>) -> Vec2<f32> {
+        Vec2::new(size.x, HEIGHT)
+    }
+
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        nodes.push(AccessibilityNode::new(Role::Slider).with_name(self.value.to_string()));
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<D>, _bounds: Bounds) {
+        let id = ctx.focus.register();
+        self.focus_id = Some(id);
+        self.focused = ctx.focus.is_focused(id);
+    }
+
+    fn handle(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, event: Event) -> bool {
+        self.focused = self.focus_id.map_or(false, |id| ctx.focus.is_focused(id));
+
+        if event.pressed_action(UiAction::Touch) && bounds.hover.is_direct() {
+            self.focus(ctx);
+            let value = self.value_at(bounds.rect, ctx.input.mouse_pos().x);
+            self.set_value(ctx.data, value);
+            return true;
+        }
+
+        match event {
+            Event::Drag(DragEvent {
+                button: MouseButton::Left,
+                start,
+                current,
+                ..
+            }) if bounds.clip_rect.contains(start) => {
+                self.focus(ctx);
+                let value = self.value_at(bounds.rect, current.x);
+                self.set_value(ctx.data, value);
+                true
+            }
+            Event::Keyboard(KeyboardEvent {
+                state: ElementState::Pressed,
+                code,
+            }) if self.focused => {
+                let delta = match code {
+                    VirtualKeyCode::Left => -self.step,
+                    VirtualKeyCode::Right => self.step,
+                    _ => return false,
+                };
+
+                self.set_value(ctx.data, self.value + delta);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
+        let rect = bounds.rect;
+        let (start, end) = self.track(rect);
+        let center_y = rect.center().y;
+        let thumb_x = self.thumb_x(rect);
+
+        let track = Rect::from_min_max(
+            Vec2::new(start, center_y - TRACK_HEIGHT * 0.5),
+            Vec2::new(end, center_y + TRACK_HEIGHT * 0.5),
+        );
+        ctx.encoder
+            .rounded_rect(track, TRACK_HEIGHT * 0.5)
+            .fill_color([0.2; 3]);
+
+        let filled = Rect::from_min_max(track.min, Vec2::new(thumb_x, track.max.y));
+        ctx.encoder
+            .rounded_rect(filled, TRACK_HEIGHT * 0.5)
+            .fill_color([0.3, 0.5, 1.0]);
+
+        let thumb = Rect::new(
+            Vec2::new(thumb_x - THUMB_WIDTH * 0.5, rect.min.y),
+            Vec2::new(THUMB_WIDTH, rect.size().y),
+        );
+        let color = if self.focused { [0.95; 3] } else { [0.8; 3] };
+        ctx.encoder
+            .rounded_rect(thumb, THUMB_WIDTH * 0.5)
+            .fill_color(color);
+    }
+}
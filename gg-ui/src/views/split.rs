@@ -0,0 +1,272 @@
+use gg_input::Event;
+use gg_math::{Rect, Vec2};
+
+use super::stack::Orientation;
+use crate::{
+    AccessibilityNode, Bounds, DrawCtx, Hover, LayoutCtx, LayoutHints, UiAction, UpdateCtx, View,
+};
+
+const HANDLE_SIZE: f32 = 6.0;
+
+pub fn split<D, L, R, F>(
+    orientation: Orientation,
+    left: L,
+    right: R,
+    ratio: f32,
+    on_resize: F,
+) -> Split<L, R, F>
+where
+    F: FnOnce(&mut D, f32),
+{
+    Split {
+        orientation,
+        left,
+        right,
+        ratio: ratio.clamp(0.0, 1.0),
+        on_resize: Some(on_resize),
+        left_hints: LayoutHints::default(),
+        right_hints: LayoutHints::default(),
+        size: Vec2::zero(),
+        left_size: Vec2::zero(),
+        right_size: Vec2::zero(),
+        drag: None,
+        resized: false,
+    }
+}
+
+pub struct Split<L, R, F> {
+    orientation: Orientation,
+    left: L,
+    right: R,
+    ratio: f32,
+    on_resize: Option<F>,
+    left_hints: LayoutHints,
+    right_hints: LayoutHints,
+    size: Vec2<f32>,
+    left_size: Vec2<f32>,
+    right_size: Vec2<f32>,
+    drag: Option<f32>,
+    resized: bool,
+}
+
+impl<L, R, F> Split<L, R, F> {
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    pub fn left_size(&self) -> Vec2<f32> {
+        self.left_size
+    }
+
+    pub fn right_size(&self) -> Vec2<f32> {
+        self.right_size
+    }
+
+    pub fn drag_by(&mut self, delta: f32) {
+        let available = self.available();
+
+        if available <= 0.0 {
+            return;
+        }
+
+        let (min, max) = self.left_range(available);
+        let left = ((available * self.ratio).clamp(min, max) + delta).clamp(min, max);
+        let ratio = left / available;
+
+        if ratio != self.ratio {
+            self.ratio = ratio;
+            self.resized = true;
+        }
+    }
+
+    fn available(&self) -> f32 {
+        let (maj, _) = self.orientation.indices();
+        (self.size[maj] - HANDLE_SIZE).max(0.0)
+    }
+
+    fn left_range(&self, available: f32) -> (f32, f32) {
+        let (maj, _) = self.orientation.indices();
+        let min = self.left_hints.min_size[maj].max(available - self.right_hints.max_size[maj]);
+        let max = self.left_hints.max_size[maj].min(available - self.right_hints.min_size[maj]);
+        (min, max.max(min))
+    }
+
+    fn handle_rect(&self, rect: Rect<f32>) -> Rect<f32> {
+        let (maj, _) = self.orientation.indices();
+        let mut pos = rect.min;
+        pos[maj] += self.left_size[maj];
+        let mut size = rect.size();
+        size[maj] = HANDLE_SIZE;
+        Rect::new(pos, size)
+    }
+
+    fn child_bounds(&self, bounds: Bounds) -> (Bounds, Bounds) {
+        let (maj, _) = self.orientation.indices();
+        let left = Rect::new(bounds.rect.min, self.left_size);
+        let mut pos = bounds.rect.min;
+        pos[maj] += self.left_size[maj] + HANDLE_SIZE;
+        let right = Rect::new(pos, self.right_size);
+        (
+            bounds.child(left, bounds.hover),
+            bounds.child(right, bounds.hover),
+        )
+    }
+}
+
+impl<D, L, R, F> View<D> for Split<L, R, F>
+where
+    L: View<D>,
+    R: View<D>,
+    F: FnOnce(&mut D, f32),
+{
+    fn init(&mut self, old: &mut Self) -> bool
+    where
+        Self: Sized,
+    {
+        let mut changed = old.resized || self.orientation != old.orientation;
+
+        self.left_hints = old.left_hints;
+        self.right_hints = old.right_hints;
+        self.size = old.size;
+        self.left_size = old.left_size;
+        self.right_size = old.right_size;
+        self.drag = old.drag;
+
+        if self.drag.is_some() || old.resized {
+            self.ratio = old.ratio;
+        } else {
+            changed |= self.ratio != old.ratio;
+        }
+
+        changed |= self.left.init(&mut old.left);
+        changed |= self.right.init(&mut old.right);
+        changed
+    }
+
+    fn pre_layout(&mut self, ctx: &mut LayoutCtx) -> LayoutHints {
+        let (maj, min) = self.orientation.indices();
+
+        self.left_hints = self.left.pre_layout(ctx);
+        self.right_hints = self.right.pre_layout(ctx);
+
+        let mut min_size = Vec2::zero();
+        min_size[maj] =
+            self.left_hints.min_size[maj] + self.right_hints.min_size[maj] + HANDLE_SIZE;
+        min_size[min] = self.left_hints.min_size[min].max(self.right_hints.min_size[min]);
+
+        LayoutHints {
+            stretch: self.left_hints.stretch.max(self.right_hints.stretch),
+            min_size,
+            num_layers: self.left_hints.num_layers.max(self.right_hints.num_layers),
+            ..LayoutHints::default()
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, size: Vec2<f32>) -> Vec2<f32> {
+        let (maj, _) = self.orientation.indices();
+
+        self.size = size;
+        self.resized = false;
+
+        let available = self.available();
+        let (min, max) = self.left_range(available);
+
+        let mut left = size;
+        left[maj] = (available * self.ratio).clamp(min, max);
+        let mut right = size;
+        right[maj] = available - left[maj];
+
+        self.left_size = self.left.layout(ctx, left);
+        self.right_size = self.right.layout(ctx, right);
+        self.left_size[maj] = left[maj];
+        self.right_size[maj] = right[maj];
+
+        size
+    }
+
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        self.left.accessibility(nodes);
+        self.right.accessibility(nodes);
+    }
+
+    fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
+        if ctx.layer == 0
+            && self
+                .handle_rect(bounds.rect)
+                .contains(ctx.input.mouse_pos())
+        {
+            return Hover::Direct;
+        }
+
+        let (left, right) = self.child_bounds(bounds);
+        let hover = self.left.hover(ctx, left);
+        if hover.is_some() {
+            return Hover::Indirect;
+        }
+
+        match self.right.hover(ctx, right) {
+            Hover::None => Hover::None,
+            _ => Hover::Indirect,
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) {
+        let (maj, _) = self.orientation.indices();
+
+        if let Some(last) = self.drag {
+            if ctx.input.is_action_pressed(UiAction::Touch) {
+                let pos = ctx.input.mouse_pos()[maj];
+                self.drag_by(pos - last);
+                self.drag = Some(pos);
+            } else {
+                self.drag = None;
+            }
+        }
+
+        if self.resized {
+            if let Some(on_resize) = self.on_resize.take() {
+                on_resize(ctx.data, self.ratio);
+            }
+        }
+
+        let (left, right) = self.child_bounds(bounds);
+        self.left.update(ctx, left);
+        self.right.update(ctx, right);
+    }
+
+    fn handle(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, event: Event) -> bool {
+        let (left, right) = self.child_bounds(bounds);
+        if self.left.handle(ctx, left, event) || self.right.handle(ctx, right, event) {
+            return true;
+        }
+
+        if ctx.layer == 0 && event.pressed_action(UiAction::Touch) {
+            let mouse_pos = ctx.input.mouse_pos();
+            if self.handle_rect(bounds.rect).contains(mouse_pos) {
+                let (maj, _) = self.orientation.indices();
+                self.drag = Some(mouse_pos[maj]);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
+        let (left, right) = self.child_bounds(bounds);
+        self.left.draw(ctx, left);
+        self.right.draw(ctx, right);
+
+        if ctx.layer == 0 {
+            let color = if self.drag.is_some() {
+                [0.5, 0.5, 0.5, 1.0]
+            } else {
+                [0.2, 0.2, 0.2, 1.0]
+            };
+
+            ctx.encoder
+                .rect(self.handle_rect(bounds.rect))
+                .fill_color(color);
+        }
+    }
+}
@@ -10,7 +10,7 @@ pub enum Orientation {
 }
 
 impl Orientation {
-    fn indices(self) -> (usize, usize) {
+    pub(crate) fn indices(self) -> (usize, usize) {
         match self {
             Orientation::Horizontal => (0, 1),
             Orientation::Vertical => (1, 0),
@@ -1,7 +1,7 @@
 use gg_input::Event;
 use gg_math::Vec2;
 
-use crate::{Bounds, DrawCtx, Hover, LayoutCtx, LayoutHints, UpdateCtx, View};
+use crate::{AccessibilityNode, Bounds, DrawCtx, Hover, LayoutCtx, LayoutHints, UpdateCtx, View};
 
 pub fn stateful<D, S, VF, V>(state: S, view_factory: VF) -> Stateful<S, VF, V>
 where
@@ -47,8 +47,10 @@ where
                 assets: ctx.assets,
                 input: ctx.input,
                 data: &mut combined_data,
+                focus: ctx.focus,
                 layer: ctx.layer,
                 dt: ctx.dt,
+                redraw_requested: ctx.redraw_requested,
             };
 
             let res = f(&mut self.view, &mut ctx);
@@ -102,6 +104,14 @@ where
         }
     }
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        self.ensure_init();
+
+        if let Some(view) = &mut self.view {
+            view.accessibility(nodes)
+        }
+    }
+
     fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
         self.with_ctx(ctx, |view, ctx| {
             if let Some(view) = view {
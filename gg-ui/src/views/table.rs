@@ -0,0 +1,356 @@
+use gg_input::Event;
+use gg_math::{Rect, Vec2};
+
+use super::text::{text, TextView};
+use crate::{
+    AccessibilityNode, Bounds, DrawCtx, Hover, LayoutCtx, LayoutHints, Role, UiAction, UpdateCtx,
+    View,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnWidth {
+    Fixed(f32),
+    Weight(f32),
+    Auto,
+}
+
+pub struct Column<D> {
+    header: TextView<D>,
+    width: ColumnWidth,
+    on_sort: Option<Box<dyn FnOnce(&mut D)>>,
+}
+
+pub fn column<D>(label: impl Into<String>, width: ColumnWidth) -> Column<D> {
+    Column {
+        header: text(label),
+        width,
+        on_sort: None,
+    }
+}
+
+impl<D> Column<D> {
+    pub fn on_sort(mut self, on_sort: impl FnOnce(&mut D) + 'static) -> Self {
+        self.on_sort = Some(Box::new(on_sort));
+        self
+    }
+}
+
+pub fn table<D, R, V, F>(
+    columns: Vec<Column<D>>,
+    rows: impl IntoIterator<Item = R>,
+    mut build_cell: F,
+) -> Table<D, V>
+where
+    F: FnMut(&R, usize) -> V,
+{
+    let num_columns = columns.len();
+    let mut cells = Vec::new();
+    let mut num_rows = 0;
+
+    for row in rows {
+        cells.extend((0..num_columns).map(|col| build_cell(&row, col)));
+        num_rows += 1;
+    }
+
+    let num_children = num_columns * (num_rows + 1);
+
+    Table {
+        columns,
+        cells,
+        num_rows,
+        hints: vec![LayoutHints::default(); num_children],
+        hovers: vec![Hover::None; num_children],
+        column_offsets: vec![0.0; num_columns + 1],
+        row_offsets: vec![0.0; num_rows + 2],
+        sorted: None,
+    }
+}
+
+pub struct Table<D, V> {
+    columns: Vec<Column<D>>,
+    cells: Vec<V>,
+    num_rows: usize,
+    hints: Vec<LayoutHints>,
+    hovers: Vec<Hover>,
+    column_offsets: Vec<f32>,
+    row_offsets: Vec<f32>,
+    sorted: Option<usize>,
+}
+
+impl<D, V> Table<D, V> {
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn header_rect(&self, col: usize) -> Rect<f32> {
+        self.child_rect(0, col)
+    }
+
+    pub fn cell_rect(&self, row: usize, col: usize) -> Rect<f32> {
+        self.child_rect(row + 1, col)
+    }
+
+    fn child_rect(&self, row: usize, col: usize) -> Rect<f32> {
+        Rect::from_min_max(
+            Vec2::new(self.column_offsets[col], self.row_offsets[row]),
+            Vec2::new(self.column_offsets[col + 1], self.row_offsets[row + 1]),
+        )
+    }
+
+    fn child_bounds(&self, bounds: Bounds, idx: usize) -> Bounds {
+        let (row, col) = (idx / self.columns.len(), idx % self.columns.len());
+        let rect = self.child_rect(row, col);
+        let rect = Rect::new(bounds.rect.min + rect.min, rect.size());
+        bounds.child(rect, self.hovers[idx])
+    }
+
+    fn column_widths(&self, available: f32) -> Vec<f32> {
+        let num_columns = self.columns.len();
+        let mut widths = vec![0.0; num_columns];
+        let mut total_weight = 0.0;
+
+        for (col, column) in self.columns.iter().enumerate() {
+            let min_width = self
+                .hints
+                .iter()
+                .skip(col)
+                .step_by(num_columns)
+                .fold(0.0f32, |acc, hints| acc.max(hints.min_size.x));
+
+            widths[col] = match column.width {
+                ColumnWidth::Fixed(width) => width,
+                ColumnWidth::Weight(weight) => {
+                    total_weight += weight;
+                    min_width
+                }
+                ColumnWidth::Auto => min_width,
+            };
+        }
+
+        let used: f32 = widths.iter().sum();
+        let remaining = (available - used).max(0.0);
+
+        if total_weight > 0.0 {
+            for (col, column) in self.columns.iter().enumerate() {
+                if let ColumnWidth::Weight(weight) = column.width {
+                    widths[col] += (remaining * weight / total_weight).floor();
+                }
+            }
+        }
+
+        widths
+    }
+}
+
+impl<D, V: View<D>> Table<D, V> {
+    fn child(&mut self, idx: usize) -> &mut dyn View<D> {
+        let num_columns = self.columns.len();
+        if idx < num_columns {
+            &mut self.columns[idx].header
+        } else {
+            &mut self.cells[idx - num_columns]
+        }
+    }
+
+    fn num_children(&self) -> usize {
+        self.columns.len() + self.cells.len()
+    }
+}
+
+impl<D, V: View<D>> View<D> for Table<D, V> {
+    fn init(&mut self, old: &mut Self) -> bool
+    where
+        Self: Sized,
+    {
+        let same_shape = self.num_rows == old.num_rows
+            && self.columns.len() == old.columns.len()
+            && self
+                .columns
+                .iter()
+                .zip(&old.columns)
+                .all(|(a, b)| a.width == b.width);
+
+        if !same_shape || old.sorted.is_some() {
+            return true;
+        }
+
+        self.hints = old.hints.clone();
+        self.column_offsets = old.column_offsets.clone();
+        self.row_offsets = old.row_offsets.clone();
+
+        let mut changed = false;
+
+        for (column, old_column) in self.columns.iter_mut().zip(&mut old.columns) {
+            changed |= column.header.init(&mut old_column.header);
+        }
+
+        for (cell, old_cell) in self.cells.iter_mut().zip(&mut old.cells) {
+            changed |= cell.init(old_cell);
+        }
+
+        changed
+    }
+
+    fn pre_layout(&mut self, ctx: &mut LayoutCtx) -> LayoutHints {
+        let num_columns = self.columns.len();
+        let mut num_layers = 1;
+
+        for idx in 0..self.num_children() {
+            let hints = self.child(idx).pre_layout(ctx);
+            num_layers = num_layers.max(hints.num_layers);
+            self.hints[idx] = hints;
+        }
+
+        let min_width = self.column_widths(0.0).iter().sum();
+        let min_height = self
+            .hints
+            .chunks(num_columns.max(1))
+            .map(|row| row.iter().fold(0.0f32, |acc, h| acc.max(h.min_size.y)))
+            .sum();
+
+        LayoutHints {
+            stretch: 1.0,
+            min_size: Vec2::new(min_width, min_height),
+            num_layers,
+            ..LayoutHints::default()
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, size: Vec2<f32>) -> Vec2<f32> {
+        let num_columns = self.columns.len();
+        let widths = self.column_widths(size.x);
+
+        let mut offset = 0.0;
+        for (col, width) in widths.iter().enumerate() {
+            self.column_offsets[col] = offset;
+            offset += width;
+        }
+        self.column_offsets[num_columns] = offset;
+
+        let mut offset = 0.0;
+        for row in 0..=self.num_rows {
+            self.row_offsets[row] = offset;
+
+            let start = row * num_columns;
+            let min_height = self.hints[start..start + num_columns]
+                .iter()
+                .fold(0.0f32, |acc, h| acc.max(h.min_size.y));
+
+            let mut height = min_height;
+            for (col, &width) in widths.iter().enumerate() {
+                let size = self
+                    .child(start + col)
+                    .layout(ctx, Vec2::new(width, min_height));
+                height = height.max(size.y);
+            }
+
+            offset += height;
+        }
+        self.row_offsets[self.num_rows + 1] = offset;
+
+        Vec2::new(size.x.max(self.column_offsets[num_columns]), offset)
+    }
+
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        let mut children = Vec::new();
+
+        for idx in 0..self.num_children() {
+            self.child(idx).accessibility(&mut children);
+        }
+
+        nodes.push(AccessibilityNode::new(Role::Group).with_children(children));
+    }
+
+    fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
+        let mut hover = Hover::None;
+
+        for idx in (0..self.num_children()).rev() {
+            self.hovers[idx] = Hover::None;
+
+            if ctx.layer >= self.hints[idx].num_layers || hover.is_some() {
+                continue;
+            }
+
+            let bounds = self.child_bounds(bounds, idx);
+            self.hovers[idx] = self.child(idx).hover(ctx, bounds);
+
+            if self.hovers[idx].is_some() {
+                hover = Hover::Indirect;
+            }
+        }
+
+        hover
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) {
+        if let Some(col) = self.sorted {
+            if let Some(on_sort) = self.columns[col].on_sort.take() {
+                on_sort(ctx.data);
+            }
+        }
+
+        for idx in 0..self.num_children() {
+            let bounds = self.child_bounds(bounds, idx);
+            self.child(idx).update(ctx, bounds);
+        }
+    }
+
+    fn handle(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, event: Event) -> bool {
+        for idx in (0..self.num_children()).rev() {
+            if ctx.layer >= self.hints[idx].num_layers {
+                continue;
+            }
+
+            let bounds = self.child_bounds(bounds, idx);
+            if self.child(idx).handle(ctx, bounds, event) {
+                return true;
+            }
+        }
+
+        if ctx.layer != 0 || !event.pressed_action(UiAction::Touch) {
+            return false;
+        }
+
+        let mouse_pos = ctx.input.mouse_pos();
+        for col in 0..self.columns.len() {
+            if self.columns[col].on_sort.is_none() {
+                continue;
+            }
+
+            if self.child_bounds(bounds, col).clip_rect.contains(mouse_pos) {
+                self.sorted = Some(col);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
+        if ctx.layer == 0 {
+            let header = Rect::new(
+                bounds.rect.min,
+                Vec2::new(bounds.rect.width(), self.row_offsets[1]),
+            );
+            ctx.encoder.rect(header).fill_color([0.2, 0.2, 0.2, 1.0]);
+        }
+
+        for idx in 0..self.num_children() {
+            if ctx.layer >= self.hints[idx].num_layers {
+                continue;
+            }
+
+            let bounds = self.child_bounds(bounds, idx);
+
+            if let (0, Some(overlay)) = (ctx.layer, ctx.overlay.as_deref_mut()) {
+                overlay.add_bounds(bounds.rect);
+            }
+
+            self.child(idx).draw(ctx, bounds);
+        }
+    }
+}
@@ -7,13 +7,14 @@ use gg_graphics::{
 };
 use gg_math::Vec2;
 
-use crate::{Bounds, DrawCtx, LayoutCtx, View};
+use crate::{AccessibilityNode, Bounds, DrawCtx, LayoutCtx, Role, View};
 
 pub fn text<D>(text: impl Into<String>) -> TextView<D> {
     TextView {
         phantom: PhantomData,
         text: text.into(),
         props: TextProperties::default(),
+        color: Color::WHITE,
         shaped_text: None,
     }
 }
@@ -22,6 +23,7 @@ pub struct TextView<D> {
     phantom: PhantomData<fn(D)>,
     text: String,
     props: TextProperties,
+    color: Color,
     shaped_text: Option<ShapedText>,
 }
 
@@ -30,6 +32,15 @@ impl<D> TextView<D> {
         self.props.wrap = v;
         self
     }
+
+    pub fn color(mut self, v: impl Into<Color>) -> Self {
+        self.color = v.into();
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
 }
 
 impl<D> View<D> for TextView<D> {
@@ -37,7 +48,7 @@ impl<D> View<D> for TextView<D> {
     where
         Self: Sized,
     {
-        if self.text == old.text {
+        if self.text == old.text && self.color == old.color {
             self.shaped_text = old.shaped_text.take();
             false
         } else {
@@ -57,7 +68,11 @@ impl<D> View<D> for TextView<D> {
                     weight: FontWeight::Normal,
                     style: FontStyle::Normal,
                     size: 20.0,
-                    color: Color::WHITE,
+                    color: self.color,
+                    underline: false,
+                    strikethrough: false,
+                    letter_spacing: 0.0,
+                    word_spacing: 0.0,
                 },
             }];
 
@@ -72,6 +87,10 @@ impl<D> View<D> for TextView<D> {
         ctx.text_layouter.measure(shaped_text, size).fmax(size)
     }
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        nodes.push(AccessibilityNode::new(Role::Text).with_name(&self.text));
+    }
+
     fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
         if !ctx.encoder.get_scissor().intersects(&bounds.rect) {
             return;
@@ -85,6 +104,11 @@ impl<D> View<D> for TextView<D> {
                 glyph.pos += bounds.rect.min;
                 ctx.encoder.glyph(glyph);
             }
+
+            for decoration in ctx.text_layouter.decorations() {
+                let rect = decoration.rect.translate(bounds.rect.min);
+                ctx.encoder.rect(rect).fill_color(decoration.color);
+            }
         }
     }
 }
@@ -0,0 +1,327 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use gg_graphics::{
+    Color, FontFamily, FontStyle, FontWeight, ShapedText, Text, TextProperties, TextSegment,
+    TextSegmentProperties,
+};
+use gg_input::{ElementState, Event, KeyboardEvent, VirtualKeyCode};
+use gg_math::{Rect, Vec2};
+
+use crate::{
+    views, AccessibilityNode, Bounds, DrawCtx, FocusId, LayoutCtx, LayoutHints, Role, UiAction,
+    UpdateCtx, View,
+};
+
+const FONT_SIZE: f32 = 20.0;
+const PADDING: f32 = 6.0;
+
+pub fn text_input<D>(
+    value: impl Into<String>,
+    on_change: impl FnMut(&mut D, &str) + 'static,
+) -> impl View<D> {
+    // the value only seeds the state, later edits are kept by the stateful
+    views::stateful(value.into(), move |text: &String| TextField {
+        text: text.clone(),
+        on_change,
+        caret: text.len(),
+        anchor: text.len(),
+        focus_id: None,
+        focused: false,
+        pending_click: None,
+        shaped_text: None,
+    })
+}
+
+pub struct TextField<F> {
+    text: String,
+    on_change: F,
+    caret: usize,
+    anchor: usize,
+    focus_id: Option<FocusId>,
+    focused: bool,
+    pending_click: Option<Vec2<f32>>,
+    shaped_text: Option<ShapedText>,
+}
+
+impl<F> TextField<F> {
+    fn selection(&self) -> Range<usize> {
+        self.caret.min(self.anchor)..self.caret.max(self.anchor)
+    }
+
+    fn clamp_to(&mut self, text: &str) {
+        self.caret = floor_char_boundary(text, self.caret);
+        self.anchor = floor_char_boundary(text, self.anchor);
+    }
+
+    fn replace_selection(&mut self, text: &mut String, with: &str) {
+        let selection = self.selection();
+        text.replace_range(selection.clone(), with);
+        self.caret = selection.start + with.len();
+        self.anchor = self.caret;
+    }
+
+    fn move_caret(&mut self, offset: usize, extend: bool) {
+        self.caret = offset;
+        if !extend {
+            self.anchor = offset;
+        }
+    }
+
+    // returns whether the text was changed
+    fn handle_key(&mut self, text: &mut String, code: VirtualKeyCode, shift: bool) -> bool {
+        self.clamp_to(text);
+        let selection = self.selection();
+
+        match code {
+            VirtualKeyCode::Back | VirtualKeyCode::Delete => {
+                if selection.is_empty() {
+                    self.anchor = match code {
+                        VirtualKeyCode::Back => prev_char_boundary(text, self.caret),
+                        _ => next_char_boundary(text, self.caret),
+                    };
+                }
+
+                if self.selection().is_empty() {
+                    return false;
+                }
+
+                self.replace_selection(text, "");
+                return true;
+            }
+            VirtualKeyCode::Left if !shift && !selection.is_empty() => {
+                self.move_caret(selection.start, false)
+            }
+            VirtualKeyCode::Right if !shift && !selection.is_empty() => {
+                self.move_caret(selection.end, false)
+            }
+            VirtualKeyCode::Left => self.move_caret(prev_char_boundary(text, self.caret), shift),
+            VirtualKeyCode::Right => self.move_caret(next_char_boundary(text, self.caret), shift),
+            VirtualKeyCode::Home => self.move_caret(0, shift),
+            VirtualKeyCode::End => self.move_caret(text.len(), shift),
+            _ => {}
+        }
+
+        false
+    }
+
+    fn text_origin(&self, bounds: Bounds) -> Vec2<f32> {
+        bounds.rect.min + Vec2::splat(PADDING)
+    }
+
+    fn shape(&mut self, ctx: &mut LayoutCtx) -> &mut ShapedText {
+        let text = &self.text;
+        self.shaped_text.get_or_insert_with(|| {
+            let segments = [TextSegment {
+                text: Cow::Borrowed(text),
+                props: TextSegmentProperties {
+                    font_family: FontFamily::new("Open Sans")
+                        .push("Noto Color Emoji")
+                        .push("Noto Sans")
+                        .push("Noto Sans JP"),
+                    weight: FontWeight::Normal,
+                    style: FontStyle::Normal,
+                    size: FONT_SIZE,
+                    color: Color::WHITE,
+                    underline: false,
+                    strikethrough: false,
+                    letter_spacing: 0.0,
+                    word_spacing: 0.0,
+                },
+            }];
+
+            let text = Text {
+                segments: Cow::Borrowed(&segments),
+                props: TextProperties {
+                    wrap: false,
+                    ..TextProperties::default()
+                },
+            };
+
+            ctx.text_layouter.shape(ctx.assets, ctx.fonts, &text)
+        })
+    }
+}
+
+impl<D, F> View<(D, String)> for TextField<F>
+where
+    F: FnMut(&mut D, &str),
+{
+    fn init(&mut self, old: &mut Self) -> bool {
+        self.caret = old.caret;
+        self.anchor = old.anchor;
+        self.pending_click = old.pending_click.take();
+
+        if self.text == old.text {
+            self.shaped_text = old.shaped_text.take();
+            false
+        } else {
+            true
+        }
+    }
+
+    fn pre_layout(&mut self, _ctx: &mut LayoutCtx) -> LayoutHints {
+        LayoutHints {
+            stretch: 1.0,
+            ..LayoutHints::default()
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, size: Vec2<f32>) -> Vec2<f32> {
+        let shaped_text = self.shape(ctx);
+        let text_size = ctx
+            .text_layouter
+            .measure(shaped_text, Vec2::splat(f32::INFINITY));
+
+        let height = text_size.y.max(FONT_SIZE) + PADDING * 2.0;
+        Vec2::new(size.x, height.max(size.y))
+    }
+
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        nodes.push(AccessibilityNode::new(Role::TextInput).with_name(&self.text));
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<(D, String)>, _bounds: Bounds) {
+        let id = ctx.focus.register();
+        self.focus_id = Some(id);
+        self.focused = ctx.focus.is_focused(id);
+    }
+
+    fn handle(&mut self, ctx: &mut UpdateCtx<(D, String)>, bounds: Bounds, event: Event) -> bool {
+        self.focused = self.focus_id.map_or(false, |id| ctx.focus.is_focused(id));
+
+        if event.pressed_action(UiAction::Touch) {
+            if !bounds.hover.is_direct() {
+                if self.focused {
+                    ctx.focus.blur();
+                    self.focused = false;
+                }
+
+                return false;
+            }
+
+            if let Some(id) = self.focus_id {
+                ctx.focus.focus(id);
+                self.focused = true;
+            }
+
+            // clicks are hit-tested during drawing, once the text is laid out
+            self.pending_click = Some(ctx.input.mouse_pos() - self.text_origin(bounds));
+            return true;
+        }
+
+        if !self.focused {
+            return false;
+        }
+
+        let (data, text) = &mut *ctx.data;
+
+        let changed = match event {
+            Event::Text(ev) if !ev.ch.is_control() => {
+                self.clamp_to(text);
+                self.replace_selection(text, ev.ch.encode_utf8(&mut [0; 4]));
+                true
+            }
+            Event::Keyboard(KeyboardEvent {
+                state: ElementState::Pressed,
+                code,
+            }) => {
+                let shift = ctx.input.is_key_pressed(VirtualKeyCode::LShift)
+                    || ctx.input.is_key_pressed(VirtualKeyCode::RShift);
+                self.handle_key(text, code, shift)
+            }
+            _ => return false,
+        };
+
+        if changed {
+            (self.on_change)(data, text);
+        }
+
+        true
+    }
+
+    fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
+        ctx.encoder.rect(bounds.rect).fill_color([0.1; 3]);
+
+        if self.focused {
+            ctx.encoder.stroke_rect(bounds.rect, 1.0, [0.4; 3]);
+        }
+
+        let mut shaped_text = match self.shaped_text.take() {
+            Some(v) => v,
+            None => return,
+        };
+
+        let origin = self.text_origin(bounds);
+        let inner = Rect::from_min_max(origin, bounds.rect.max - Vec2::splat(PADDING));
+
+        ctx.encoder.save();
+        ctx.encoder.set_scissor(bounds.rect);
+
+        let (_size, glyphs) = ctx.text_layouter.layout(&mut shaped_text, inner.size());
+        for glyph in glyphs {
+            let mut glyph = *glyph;
+            glyph.pos += origin;
+            ctx.encoder.glyph(glyph);
+        }
+
+        if let Some(pos) = self.pending_click.take() {
+            let offset = ctx.text_layouter.hit_test(&shaped_text, pos);
+            self.move_caret(offset, false);
+        }
+
+        let caret_rect = |offset| {
+            // edits made this frame are shaped on the next one
+            let offset = floor_char_boundary(&self.text, offset);
+            let rect = ctx.text_layouter.caret_rect(&shaped_text, offset);
+            let height = if rect.size().y > 0.0 {
+                rect.size().y
+            } else {
+                inner.size().y
+            };
+
+            Rect::new(origin + rect.min, Vec2::new(0.0, height))
+        };
+
+        let selection = self.selection();
+        let caret = caret_rect(self.caret);
+
+        if !selection.is_empty() {
+            let start = caret_rect(selection.start);
+            let end = caret_rect(selection.end);
+            let rect = Rect::from_min_max(start.min, Vec2::new(end.max.x, start.max.y));
+            ctx.encoder.rect(rect).fill_color([0.3, 0.5, 1.0, 0.4]);
+        }
+
+        if self.focused {
+            let rect = Rect::new(caret.min, Vec2::new(1.0, caret.size().y));
+            ctx.encoder.rect(rect).fill_color(Color::WHITE);
+        }
+
+        ctx.encoder.restore();
+        self.shaped_text = Some(shaped_text);
+    }
+}
+
+fn floor_char_boundary(text: &str, offset: usize) -> usize {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    offset
+}
+
+fn prev_char_boundary(text: &str, offset: usize) -> usize {
+    text[..offset]
+        .char_indices()
+        .next_back()
+        .map_or(0, |(i, _)| i)
+}
+
+fn next_char_boundary(text: &str, offset: usize) -> usize {
+    text[offset..]
+        .chars()
+        .next()
+        .map_or(offset, |c| offset + c.len_utf8())
+}
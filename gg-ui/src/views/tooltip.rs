@@ -1,6 +1,8 @@
 use gg_math::{Rect, Vec2};
 
-use crate::{Bounds, DrawCtx, Event, Hover, LayoutCtx, LayoutHints, UpdateCtx, View};
+use crate::{
+    AccessibilityNode, Bounds, DrawCtx, Event, Hover, LayoutCtx, LayoutHints, UpdateCtx, View,
+};
 
 pub fn tooltip<V, VT>(view: V, contents: VT) -> Tooltip<V, VT> {
     Tooltip {
@@ -51,6 +53,10 @@ where
         self.view.layout(ctx, size)
     }
 
+    fn accessibility(&mut self, nodes: &mut Vec<AccessibilityNode>) {
+        self.view.accessibility(nodes)
+    }
+
     fn hover(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds) -> Hover {
         if ctx.layer < self.view_layers {
             self.view.hover(ctx, bounds)
@@ -1,4 +1,4 @@
-use crate::{Bounds, Event, LayoutCtx, LayoutHints, UiAction, UpdateCtx, View};
+use crate::{Bounds, DrawCtx, Event, FocusId, LayoutCtx, LayoutHints, UiAction, UpdateCtx, View};
 
 pub fn touch_area<D, F>(callback: F) -> TouchArea<F>
 where
@@ -6,11 +6,15 @@ where
 {
     TouchArea {
         callback: Some(callback),
+        focus_id: None,
+        focused: false,
     }
 }
 
 pub struct TouchArea<F> {
     callback: Option<F>,
+    focus_id: Option<FocusId>,
+    focused: bool,
 }
 
 impl<D, F> View<D> for TouchArea<F>
@@ -24,8 +28,26 @@ where
         }
     }
 
+    fn update(&mut self, ctx: &mut UpdateCtx<D>, _bounds: Bounds) {
+        let id = ctx.focus.register();
+        self.focus_id = Some(id);
+        self.focused = ctx.focus.is_focused(id);
+    }
+
     fn handle(&mut self, ctx: &mut UpdateCtx<D>, bounds: Bounds, event: Event) -> bool {
-        if event.pressed_action(UiAction::Touch) && bounds.hover.is_direct() {
+        self.focused = self.focus_id.map_or(false, |id| ctx.focus.is_focused(id));
+
+        let touched = event.pressed_action(UiAction::Touch) && bounds.hover.is_direct();
+        let activated = event.pressed_action(UiAction::Activate) && self.focused;
+
+        if touched {
+            if let Some(id) = self.focus_id {
+                ctx.focus.focus(id);
+                self.focused = true;
+            }
+        }
+
+        if touched || activated {
             if let Some(callback) = self.callback.take() {
                 callback(ctx.data);
                 return true;
@@ -34,4 +56,10 @@ where
 
         false
     }
+
+    fn draw(&mut self, ctx: &mut DrawCtx, bounds: Bounds) {
+        if self.focused {
+            ctx.encoder.stroke_rect(bounds.rect, 2.0, [0.3, 0.5, 1.0]);
+        }
+    }
 }
@@ -0,0 +1,81 @@
+mod common;
+
+use gg_math::Vec2;
+use gg_ui::{views, AccessibilityNode, Role, SetChildren, View};
+
+use self::common::Fixture;
+
+fn collect<D>(view: &mut impl View<D>) -> Vec<AccessibilityNode> {
+    let mut fixture = Fixture::new();
+    let mut ctx = fixture.layout_ctx();
+
+    view.pre_layout(&mut ctx);
+    view.layout(&mut ctx, Vec2::new(400.0, 100.0));
+
+    let mut nodes = Vec::new();
+    view.accessibility(&mut nodes);
+    nodes
+}
+
+#[test]
+fn button_node() {
+    let mut view = views::button::<()>("Save", |_| {});
+    let nodes = collect(&mut view);
+
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].role, Role::Button);
+    assert_eq!(nodes[0].role.as_str(), "button");
+    assert_eq!(nodes[0].name.as_deref(), Some("Save"));
+}
+
+#[test]
+fn container_aggregates_children() {
+    let mut view = views::vstack::<()>().children((
+        views::text("Title"),
+        views::nothing(),
+        views::button("Open", |_| {}),
+    ));
+    let nodes = collect(&mut view);
+
+    let expected = AccessibilityNode::new(Role::Group).with_children(vec![
+        AccessibilityNode::new(Role::Text).with_name("Title"),
+        AccessibilityNode::new(Role::Button)
+            .with_name("Open")
+            .with_children(vec![AccessibilityNode::new(Role::Group).with_children(
+                vec![AccessibilityNode::new(Role::Text).with_name("Open (0)")],
+            )]),
+    ]);
+    assert_eq!(nodes, [expected]);
+}
+
+#[test]
+fn slider_node() {
+    let mut view = views::slider::<(), _>(0.5, 0.0..=1.0, |_, _| {});
+    let nodes = collect(&mut view);
+
+    assert_eq!(
+        nodes,
+        [AccessibilityNode::new(Role::Slider).with_name("0.5")]
+    );
+    assert_eq!(nodes[0].role.as_str(), "slider");
+}
+
+#[test]
+fn accessible_keeps_child_nodes() {
+    let mut view = views::accessible(
+        Role::Group,
+        "Toolbar",
+        views::hstack::<()>().children((views::text("Cut"), views::text("Paste"))),
+    );
+    let nodes = collect(&mut view);
+
+    let expected = AccessibilityNode::new(Role::Group)
+        .with_name("Toolbar")
+        .with_children(vec![AccessibilityNode::new(Role::Group).with_children(
+            vec![
+                AccessibilityNode::new(Role::Text).with_name("Cut"),
+                AccessibilityNode::new(Role::Text).with_name("Paste"),
+            ],
+        )]);
+    assert_eq!(nodes, [expected]);
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use gg_math::{Rect, Vec2};
+use gg_ui::{views, Animation, Bounds, Driver, Easing, UpdateCtx, View};
+
+use self::common::Fixture;
+
+struct RecordValue(f32);
+
+impl View<Vec<f32>> for RecordValue {
+    fn update(&mut self, ctx: &mut UpdateCtx<Vec<f32>>, _bounds: Bounds) {
+        ctx.data.push(self.0);
+    }
+}
+
+fn assert_converges(values: &[f32], target: f32) {
+    for pair in values.windows(2) {
+        assert!(pair[0] <= pair[1], "not monotonic: {:?}", values);
+    }
+
+    assert_eq!(values.last(), Some(&target));
+}
+
+#[test]
+fn animation_converges() {
+    for easing in [Easing::Linear, Easing::SmoothStep] {
+        let mut animation = Animation::new(0.0, 0.5, easing);
+        assert!(animation.is_settled());
+
+        animation.set_target(10.0);
+        assert!(!animation.is_settled());
+
+        let values = (0..8).map(|_| animation.tick(0.1)).collect::<Vec<_>>();
+        assert_converges(&values, 10.0);
+        assert!(animation.is_settled());
+        assert_eq!(values[4], 10.0);
+    }
+}
+
+#[test]
+fn animated_view() {
+    let mut fixture = Fixture::new();
+
+    let mut driver = Driver::new();
+    let mut values = Vec::new();
+    let mut redraws = Vec::new();
+    let start = Instant::now();
+
+    for frame in 0..10 {
+        let mut encoder = fixture.encoder();
+        let ctx = fixture.ui_ctx(Rect::new(Vec2::zero(), Vec2::splat(100.0)), &mut encoder);
+
+        // the first frame starts settled, the target changes afterwards
+        let target = if frame == 0 { 0.0 } else { 100.0 };
+        let view = views::animated(target, 0.5, Easing::SmoothStep, RecordValue);
+
+        let now = start + Duration::from_millis(frame * 100);
+        driver.run_at(view, ctx, &mut values, now);
+        redraws.push(driver.redraw_requested());
+    }
+
+    assert_converges(&values, 100.0);
+    assert!(values[2] > 0.0 && values[2] < 100.0);
+
+    // redraws are requested until the settled value is shown
+    assert_eq!(redraws.iter().filter(|&&v| v).count(), 5);
+    assert!(!redraws[0]);
+    assert!(!redraws[9]);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use gg_input::{ActionEvent, ElementState, Event};
+use gg_math::{Rect, Vec2};
+use gg_ui::{views, Bounds, Focus, Hover, UiAction, UpdateCtx, View};
+
+use self::common::Fixture;
+
+fn click(checked: bool) -> Option<bool> {
+    let fixture = Fixture::new();
+
+    let mut reported = None;
+    let mut view = views::checkbox(checked, |reported: &mut Option<bool>, v| {
+        *reported = Some(v);
+    });
+
+    let mut focus = Focus::new();
+    let mut ctx = UpdateCtx {
+        assets: &fixture.assets,
+        input: &fixture.input,
+        data: &mut reported,
+        focus: &mut focus,
+        layer: 0,
+        dt: 0.0,
+        redraw_requested: &mut false,
+    };
+
+    let bounds = Bounds {
+        hover: Hover::Direct,
+        ..Bounds::new(Rect::new(Vec2::zero(), Vec2::splat(24.0)))
+    };
+
+    let event = Event::Action(ActionEvent {
+        state: ElementState::Pressed,
+        action: UiAction::Touch.into(),
+    });
+
+    assert!(view.handle(&mut ctx, bounds, event));
+    reported
+}
+
+#[test]
+fn checkbox_toggles() {
+    assert_eq!(click(false), Some(true));
+    assert_eq!(click(true), Some(false));
+}
+
+#[test]
+fn checkbox_layout() {
+    let mut fixture = Fixture::new();
+    let mut ctx = fixture.layout_ctx();
+
+    let mut view = views::checkbox(false, |_: &mut (), _| {});
+    let hints = view.pre_layout(&mut ctx);
+    assert_eq!(hints.min_size, hints.max_size);
+
+    let size = view.layout(&mut ctx, Vec2::new(400.0, 100.0));
+    assert_eq!(size, hints.min_size);
+    assert_eq!(size.x, size.y);
+}
@@ -0,0 +1,86 @@
+// each test binary uses only some of the helpers
+#![allow(dead_code)]
+
+use std::any::Any;
+use std::path::Path;
+use std::sync::Arc;
+
+use gg_assets::{Assets, MemorySource};
+use gg_graphics::{Canvas, FontDb, FontFace, GraphicsEncoder, RawCanvas, TextLayouter};
+use gg_input::Input;
+use gg_math::Rect;
+use gg_ui::{LayoutCtx, UiContext};
+
+#[derive(Debug)]
+pub struct MockCanvas;
+
+impl RawCanvas for MockCanvas {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// assets live in memory and fonts are inserted directly, so that tests never
+// wait for the asset loader
+pub struct Fixture {
+    pub assets: Assets,
+    pub fonts: FontDb,
+    pub input: Input,
+    pub text_layouter: TextLayouter,
+    pub canvas: Canvas,
+}
+
+impl Fixture {
+    pub fn new() -> Fixture {
+        Fixture {
+            assets: Assets::new(MemorySource::new()),
+            fonts: FontDb::new(),
+            input: Input::new(),
+            text_layouter: TextLayouter::new(),
+            canvas: Canvas::from_raw(Arc::new(MockCanvas)),
+        }
+    }
+
+    pub fn with_fonts() -> Fixture {
+        let mut fixture = Fixture::new();
+
+        let path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets/fonts/OpenSans-Regular.ttf");
+        let data = std::fs::read(path).unwrap();
+        let face = FontFace::new(data.into(), 0).unwrap();
+
+        let face = fixture.assets.insert(face);
+        fixture.fonts.add_face(&face);
+        fixture.fonts.update(&fixture.assets);
+
+        fixture
+    }
+
+    pub fn encoder(&self) -> GraphicsEncoder {
+        GraphicsEncoder::new(&self.canvas)
+    }
+
+    pub fn layout_ctx(&mut self) -> LayoutCtx<'_> {
+        LayoutCtx {
+            assets: &self.assets,
+            fonts: &self.fonts,
+            input: &self.input,
+            text_layouter: &mut self.text_layouter,
+        }
+    }
+
+    pub fn ui_ctx<'a>(
+        &'a mut self,
+        bounds: Rect<f32>,
+        encoder: &'a mut GraphicsEncoder,
+    ) -> UiContext<'a> {
+        UiContext {
+            bounds,
+            assets: &self.assets,
+            fonts: &self.fonts,
+            text_layouter: &mut self.text_layouter,
+            encoder,
+            input: &self.input,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use gg_math::{Rect, Vec2};
+use gg_ui::{views, Bounds, Driver, SetChildren, UpdateCtx, View, ViewExt, ViewId};
+
+use self::common::Fixture;
+
+struct RecordDt;
+
+impl View<Vec<f32>> for RecordDt {
+    fn update(&mut self, ctx: &mut UpdateCtx<Vec<f32>>, _bounds: Bounds) {
+        ctx.data.push(ctx.dt);
+    }
+}
+
+#[test]
+fn animation_clock() {
+    let mut fixture = Fixture::new();
+
+    let mut driver = Driver::new();
+    driver.clock().set_max_step(Duration::from_millis(100));
+
+    let mut dts = Vec::new();
+    let start = Instant::now();
+
+    for millis in [0, 20, 5000] {
+        let mut encoder = fixture.encoder();
+        let ctx = fixture.ui_ctx(Rect::new(Vec2::zero(), Vec2::splat(100.0)), &mut encoder);
+
+        let now = start + Duration::from_millis(millis);
+        driver.run_at(RecordDt, ctx, &mut dts, now);
+    }
+
+    assert_eq!(dts, [0.0, 0.02, 0.1]);
+}
+
+#[test]
+fn view_at() {
+    let mut fixture = Fixture::with_fonts();
+    let mut encoder = fixture.encoder();
+
+    let button_id = ViewId::new(1);
+    let view = views::overlay().children((
+        views::rect([0.1; 3]),
+        views::button("OK", |_: &mut ()| {})
+            .max_width(150.0)
+            .max_height(80.0)
+            .id(button_id),
+    ));
+
+    let mut driver = Driver::new();
+    let ctx = fixture.ui_ctx(Rect::new(Vec2::zero(), Vec2::splat(400.0)), &mut encoder);
+
+    driver.run(view, ctx, &mut ());
+
+    assert_eq!(driver.view_at(Vec2::splat(200.0)), Some(button_id));
+    assert_eq!(driver.path_at(Vec2::splat(200.0)), [button_id]);
+    assert_eq!(driver.view_at(Vec2::splat(5.0)), None);
+    assert!(driver.path_at(Vec2::splat(5.0)).is_empty());
+}
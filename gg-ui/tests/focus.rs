@@ -0,0 +1,84 @@
+mod common;
+
+use gg_input::{ElementState, Input, VirtualKeyCode};
+use gg_math::{Rect, Vec2};
+use gg_ui::{views, Driver, FocusId, SetChildren, UiAction, View};
+use winit::event::{DeviceId, KeyboardInput, WindowEvent};
+
+use self::common::Fixture;
+
+#[allow(deprecated)]
+fn key(input: &mut Input, code: VirtualKeyCode, state: ElementState) {
+    input.process_event(WindowEvent::KeyboardInput {
+        device_id: unsafe { DeviceId::dummy() },
+        input: KeyboardInput {
+            scancode: 0,
+            state,
+            virtual_keycode: Some(code),
+            modifiers: Default::default(),
+        },
+        is_synthetic: false,
+    });
+}
+
+fn buttons() -> impl View<Vec<&'static str>> {
+    views::vstack().children((
+        views::button("First", |pressed: &mut Vec<&'static str>| {
+            pressed.push("first")
+        }),
+        views::button("Second", |pressed: &mut Vec<&'static str>| {
+            pressed.push("second")
+        }),
+    ))
+}
+
+#[test]
+fn tab_moves_focus() {
+    let mut fixture = Fixture::with_fonts();
+    fixture.input.register_action::<UiAction>();
+    fixture
+        .input
+        .load_str(r#"[["ui.focus-next", "Tab"], ["ui.activate", "Return"]]"#)
+        .unwrap();
+
+    let mut driver = Driver::new();
+    let mut pressed = Vec::new();
+
+    let mut frame = |driver: &mut Driver<Vec<&'static str>>,
+                     pressed: &mut Vec<&'static str>,
+                     keys: &[VirtualKeyCode]| {
+        fixture.input.begin_frame();
+        for &code in keys {
+            key(&mut fixture.input, code, ElementState::Pressed);
+            key(&mut fixture.input, code, ElementState::Released);
+        }
+
+        let mut encoder = fixture.encoder();
+        let ctx = fixture.ui_ctx(Rect::new(Vec2::zero(), Vec2::splat(400.0)), &mut encoder);
+
+        driver.run(buttons(), ctx, pressed);
+        driver.focus().focused()
+    };
+
+    assert_eq!(frame(&mut driver, &mut pressed, &[]), None);
+    assert_eq!(
+        frame(&mut driver, &mut pressed, &[VirtualKeyCode::Tab]),
+        Some(FocusId(0))
+    );
+    assert_eq!(
+        frame(&mut driver, &mut pressed, &[VirtualKeyCode::Tab]),
+        Some(FocusId(1))
+    );
+    assert!(pressed.is_empty());
+
+    frame(&mut driver, &mut pressed, &[VirtualKeyCode::Return]);
+    assert_eq!(pressed, ["second"]);
+
+    // focus wraps around after the last view
+    assert_eq!(
+        frame(&mut driver, &mut pressed, &[VirtualKeyCode::Tab]),
+        Some(FocusId(0))
+    );
+    frame(&mut driver, &mut pressed, &[VirtualKeyCode::Return]);
+    assert_eq!(pressed, ["second", "first"]);
+}
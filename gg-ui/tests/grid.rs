@@ -0,0 +1,70 @@
+mod common;
+
+use gg_math::{Rect, Vec2};
+use gg_ui::views::{self, GridConfig};
+use gg_ui::{Driver, SetChildren, View, ViewExt, ViewId};
+
+use self::common::Fixture;
+
+fn cell(id: u64, min_height: f32, max_width: f32) -> impl View<()> {
+    views::nothing()
+        .min_width(50.0)
+        .min_height(min_height)
+        .max_width(max_width)
+        .id(ViewId::new(id))
+}
+
+#[test]
+fn grid_child_bounds() {
+    let mut fixture = Fixture::new();
+    let mut encoder = fixture.encoder();
+
+    let config = GridConfig {
+        spacing: Vec2::new(10.0, 5.0),
+        ..GridConfig::new(3)
+    };
+
+    let inf = f32::INFINITY;
+    let view = views::grid(config).children((
+        cell(0, 20.0, inf),
+        cell(1, 20.0, inf),
+        cell(2, 20.0, inf),
+        cell(3, 20.0, inf),
+        cell(4, 30.0, inf),
+        cell(5, 20.0, inf),
+        cell(6, 20.0, 40.0),
+    ));
+
+    let mut driver = Driver::new();
+    let ctx = fixture.ui_ctx(
+        Rect::new(Vec2::zero(), Vec2::new(320.0, 200.0)),
+        &mut encoder,
+    );
+
+    driver.run(view, ctx, &mut ());
+
+    let rects = driver
+        .hit_map()
+        .entries()
+        .iter()
+        .map(|v| (v.id.0, v.rect))
+        .collect::<Vec<_>>();
+
+    // 300 pixels are shared by three columns, the second row is as tall as
+    // its tallest child
+    let expected = [
+        (0, [0.0, 0.0, 100.0, 20.0]),
+        (1, [110.0, 0.0, 100.0, 20.0]),
+        (2, [220.0, 0.0, 100.0, 20.0]),
+        (3, [0.0, 25.0, 100.0, 30.0]),
+        (4, [110.0, 25.0, 100.0, 30.0]),
+        (5, [220.0, 25.0, 100.0, 30.0]),
+        (6, [30.0, 60.0, 40.0, 20.0]),
+    ];
+
+    assert_eq!(rects.len(), expected.len());
+    for ((id, rect), (expected_id, expected_rect)) in rects.into_iter().zip(expected) {
+        assert_eq!(id, expected_id);
+        assert_eq!(rect, Rect::from(expected_rect), "child {}", id);
+    }
+}
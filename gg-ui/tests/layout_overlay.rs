@@ -0,0 +1,87 @@
+mod common;
+
+use gg_graphics::{Color, Command};
+use gg_math::{Rect, Vec2};
+use gg_ui::{views, AppendChild, Driver, LayoutOverlay, OverlayEntry, ViewExt};
+
+use self::common::Fixture;
+
+#[test]
+fn overlay_outlines_bounds() {
+    let mut fixture = Fixture::with_fonts();
+    let mut encoder = fixture.encoder();
+
+    let mut driver = Driver::new();
+    driver.set_layout_overlay(true);
+
+    let view = views::overlay::<()>()
+        .child(views::nothing().padding(10.0))
+        .child(views::nothing().max_width(40.0));
+
+    let ctx = fixture.ui_ctx(
+        Rect::new(Vec2::zero(), Vec2::new(100.0, 50.0)),
+        &mut encoder,
+    );
+
+    driver.run(view, ctx, &mut ());
+
+    let root = Rect::new(Vec2::zero(), Vec2::new(100.0, 50.0));
+    let content = Rect::from_min_max(Vec2::new(10.0, 10.0), Vec2::new(90.0, 40.0));
+    let narrow = Rect::new(Vec2::new(30.0, 0.0), Vec2::new(40.0, 50.0));
+
+    let entries = driver.layout_overlay().unwrap().entries();
+    assert_eq!(
+        entries,
+        [
+            OverlayEntry {
+                rect: root,
+                content: None,
+            },
+            OverlayEntry {
+                rect: root,
+                content: None,
+            },
+            OverlayEntry {
+                rect: root,
+                content: Some(content),
+            },
+            OverlayEntry {
+                rect: narrow,
+                content: None,
+            },
+        ]
+    );
+
+    let mut drawn = Vec::new();
+    let mut glyphs = Vec::new();
+    for cmd in encoder.finish().list {
+        match cmd {
+            Command::DrawRect(cmd) => drawn.push(cmd),
+            Command::DrawGlyph(cmd) => glyphs.push(cmd.pos),
+            _ => {}
+        }
+    }
+
+    for rect in [root, narrow] {
+        for edge in LayoutOverlay::outline(rect) {
+            assert!(drawn.iter().any(|cmd| cmd.rect == edge));
+        }
+    }
+
+    // the mouse rests at the origin, so the root view is hovered and its size
+    // is drawn in its top left corner
+    let hovered = driver.layout_overlay().unwrap().hovered(Vec2::zero());
+    assert_eq!(hovered.map(|v| v.label()).as_deref(), Some("100x50"));
+
+    let label = drawn
+        .iter()
+        .find(|cmd| cmd.fill.color == Color::new(0.0, 0.0, 0.0, 0.8))
+        .unwrap()
+        .rect;
+    assert_eq!(label.min, root.min);
+    assert_eq!(glyphs.len(), "100x50".len());
+    assert!(glyphs.iter().all(|&pos| label.contains(pos)));
+
+    driver.set_layout_overlay(false);
+    assert!(driver.layout_overlay().is_none());
+}
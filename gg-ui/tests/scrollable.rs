@@ -0,0 +1,177 @@
+mod common;
+
+use gg_input::{DragEvent, Event, MouseButton};
+use gg_math::{Rect, Vec2};
+use gg_ui::views::stack::Orientation;
+use gg_ui::views::ScrollbarPolicy;
+use gg_ui::{views, Bounds, Focus, UpdateCtx, View, ViewExt};
+
+use self::common::Fixture;
+
+#[test]
+fn minimap_indicator() {
+    let mut fixture = Fixture::new();
+    let mut ctx = fixture.layout_ctx();
+
+    let mut view = views::scrollable(views::nothing::<()>().min_height(1000.0))
+        .with_minimap([0.0, 0.5])
+        .scroll_to(Vec2::new(0.0, 375.0));
+
+    view.pre_layout(&mut ctx);
+    let size = view.layout(&mut ctx, Vec2::new(100.0, 250.0));
+    assert_eq!(size, Vec2::new(100.0, 250.0));
+
+    let outer = Rect::new(Vec2::new(10.0, 20.0), size);
+    let indicator = view.minimap_indicator(outer).unwrap();
+    assert_eq!(indicator.min.y, 20.0 + 93.75);
+    assert_eq!(indicator.size().y, 62.5);
+    assert_eq!(indicator.max.x, outer.max.x);
+
+    let view = views::scrollable(views::nothing::<()>());
+    assert!(view.minimap_indicator(outer).is_none());
+}
+
+fn layout_scrollable<V: View<()>>(view: &mut V, size: Vec2<f32>) -> Vec2<f32> {
+    let mut fixture = Fixture::new();
+    let mut ctx = fixture.layout_ctx();
+
+    view.pre_layout(&mut ctx);
+    view.layout(&mut ctx, size)
+}
+
+#[test]
+fn scrollbar_thumb_size() {
+    let mut view = views::scrollable(views::nothing::<()>().min_height(1000.0));
+    let size = layout_scrollable(&mut view, Vec2::new(100.0, 250.0));
+    let outer = Rect::new(Vec2::zero(), size);
+
+    let thumb = view.scrollbar_thumb(outer, Orientation::Vertical).unwrap();
+    assert_eq!(thumb.min.y, 0.0);
+    assert_eq!(thumb.size().y, 62.5);
+    assert_eq!(thumb.max.x, outer.max.x);
+    assert!(view
+        .scrollbar_thumb(outer, Orientation::Horizontal)
+        .is_none());
+
+    // horizontal overflow gets its own bar
+    let mut view = views::scrollable(views::nothing::<()>().min_width(400.0));
+    let size = layout_scrollable(&mut view, Vec2::new(100.0, 250.0));
+    let outer = Rect::new(Vec2::zero(), size);
+
+    let thumb = view
+        .scrollbar_thumb(outer, Orientation::Horizontal)
+        .unwrap();
+    assert_eq!(thumb.size().x, 25.0);
+    assert_eq!(thumb.max.y, outer.max.y);
+    assert!(view.scrollbar_thumb(outer, Orientation::Vertical).is_none());
+
+    let mut view = views::scrollable(views::nothing::<()>().min_height(1000.0))
+        .scrollbars(ScrollbarPolicy::Never);
+    let size = layout_scrollable(&mut view, Vec2::new(100.0, 250.0));
+    let outer = Rect::new(Vec2::zero(), size);
+    assert!(view.scrollbar_thumb(outer, Orientation::Vertical).is_none());
+}
+
+#[test]
+fn scrollbar_drag() {
+    let fixture = Fixture::new();
+    let mut focus = Focus::new();
+
+    let mut view = views::scrollable(views::nothing::<()>().min_height(1000.0));
+    let size = layout_scrollable(&mut view, Vec2::new(100.0, 250.0));
+    let bounds = Bounds::new(Rect::new(Vec2::zero(), size));
+
+    let mut ctx = UpdateCtx {
+        assets: &fixture.assets,
+        input: &fixture.input,
+        data: &mut (),
+        focus: &mut focus,
+        layer: 0,
+        dt: 0.0,
+        redraw_requested: &mut false,
+    };
+
+    let drag = |start: Vec2<f32>, delta: Vec2<f32>| {
+        Event::Drag(DragEvent {
+            button: MouseButton::Left,
+            start,
+            current: start + delta,
+            delta,
+        })
+    };
+
+    // the thumb travels 187.5 pixels while the content travels 750
+    let on_track = Vec2::new(97.0, 10.0);
+    assert!(view.handle(&mut ctx, bounds, drag(on_track, Vec2::new(0.0, 10.0))));
+    assert_eq!(view.scroll_position(), Vec2::new(0.0, 40.0));
+
+    let thumb = view
+        .scrollbar_thumb(bounds.rect, Orientation::Vertical)
+        .unwrap();
+    assert!((thumb.min.y - 10.0).abs() < 1e-4);
+
+    assert!(view.handle(&mut ctx, bounds, drag(on_track, Vec2::new(0.0, 500.0))));
+    assert_eq!(view.scroll_position(), Vec2::new(0.0, 750.0));
+
+    // drags elsewhere don't move the content
+    let inside = Vec2::new(50.0, 10.0);
+    assert!(!view.handle(&mut ctx, bounds, drag(inside, Vec2::new(0.0, -100.0))));
+    assert_eq!(view.scroll_position(), Vec2::new(0.0, 750.0));
+}
+
+#[test]
+fn scrollbar_next_to_minimap() {
+    let mut view = views::scrollable(views::nothing::<()>().min_height(1000.0)).with_minimap([]);
+    let size = layout_scrollable(&mut view, Vec2::new(100.0, 250.0));
+    let outer = Rect::new(Vec2::zero(), size);
+
+    let strip = view.minimap_rect(outer).unwrap();
+    let thumb = view.scrollbar_thumb(outer, Orientation::Vertical).unwrap();
+    assert_eq!(thumb.max.x, strip.min.x);
+    assert_eq!(thumb.size().y, 62.5);
+}
+
+#[test]
+fn scroll_to_applies_once() {
+    let fixture = Fixture::new();
+    let mut focus = Focus::new();
+
+    let build = |pos: f32| {
+        let mut view = views::scrollable(views::nothing::<()>().min_height(1000.0))
+            .scroll_to(Vec2::new(0.0, pos));
+        layout_scrollable(&mut view, Vec2::new(100.0, 250.0));
+        view
+    };
+
+    let mut old = build(100.0);
+    assert_eq!(old.scroll_position(), Vec2::new(0.0, 100.0));
+
+    let mut ctx = UpdateCtx {
+        assets: &fixture.assets,
+        input: &fixture.input,
+        data: &mut (),
+        focus: &mut focus,
+        layer: 0,
+        dt: 0.0,
+        redraw_requested: &mut false,
+    };
+
+    let bounds = Bounds::new(Rect::new(Vec2::zero(), Vec2::new(100.0, 250.0)));
+    let drag = Event::Drag(DragEvent {
+        button: MouseButton::Left,
+        start: Vec2::new(97.0, 30.0),
+        current: Vec2::new(97.0, 40.0),
+        delta: Vec2::new(0.0, 10.0),
+    });
+    assert!(old.handle(&mut ctx, bounds, drag));
+    assert_eq!(old.scroll_position(), Vec2::new(0.0, 140.0));
+
+    // rebuilding with the same target keeps where the user scrolled to
+    let mut view = build(100.0);
+    view.init(&mut old);
+    assert_eq!(view.scroll_position(), Vec2::new(0.0, 140.0));
+
+    let mut new = build(300.0);
+    new.init(&mut view);
+    assert_eq!(new.scroll_position(), Vec2::new(0.0, 300.0));
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gg_input::Binding;
+use gg_math::{Rect, Vec2};
+use gg_ui::{views, Driver, LayoutCtx, LayoutHints, View};
+
+use self::common::Fixture;
+
+gg_input::action! {
+    pub enum TestAction {
+        Save = "test.save",
+        Open = "test.open",
+    }
+}
+
+#[test]
+fn shortcut_hint_shows_binding() {
+    let mut fixture = Fixture::with_fonts();
+    fixture.input.register_action::<TestAction>();
+    fixture
+        .input
+        .bind(TestAction::Save, "Ctrl-S".parse::<Binding>().unwrap());
+
+    let mut ctx = fixture.layout_ctx();
+
+    let mut save = views::shortcut_hint::<()>(TestAction::Save);
+    save.pre_layout(&mut ctx);
+    assert_eq!(save.hint(), Some("(Ctrl+S)"));
+
+    let mut open = views::shortcut_hint::<()>(TestAction::Open);
+    open.pre_layout(&mut ctx);
+    assert_eq!(open.hint(), None);
+}
+
+#[test]
+fn shortcut_hint_follows_rebinding() {
+    let mut fixture = Fixture::with_fonts();
+    fixture.input.register_action::<TestAction>();
+    fixture
+        .input
+        .bind(TestAction::Save, "Ctrl-S".parse::<Binding>().unwrap());
+
+    let mut old = views::shortcut_hint::<()>(TestAction::Save);
+    old.pre_layout(&mut fixture.layout_ctx());
+
+    fixture.input.unbind(TestAction::Save);
+    fixture
+        .input
+        .bind(TestAction::Save, "Ctrl-Shift-S".parse::<Binding>().unwrap());
+
+    // the action is the same, so the cached text is reused until layout
+    let mut new = views::shortcut_hint::<()>(TestAction::Save);
+    assert!(!new.init(&mut old));
+    assert_eq!(new.hint(), Some("(Ctrl+S)"));
+
+    new.pre_layout(&mut fixture.layout_ctx());
+    assert_eq!(new.hint(), Some("(Ctrl+Shift+S)"));
+}
+
+struct CountLayouts(Rc<Cell<u32>>);
+
+impl View<()> for CountLayouts {
+    fn init(&mut self, _old: &mut Self) -> bool {
+        false
+    }
+
+    fn pre_layout(&mut self, _ctx: &mut LayoutCtx) -> LayoutHints {
+        self.0.set(self.0.get() + 1);
+        LayoutHints::default()
+    }
+}
+
+#[test]
+fn rebinding_triggers_layout() {
+    let mut fixture = Fixture::new();
+    fixture.input.register_action::<TestAction>();
+
+    let layouts = Rc::new(Cell::new(0));
+    let mut driver = Driver::new();
+
+    let mut frame = |fixture: &mut Fixture| {
+        let mut encoder = fixture.encoder();
+        let ctx = fixture.ui_ctx(Rect::new(Vec2::zero(), Vec2::splat(100.0)), &mut encoder);
+        driver.run(CountLayouts(layouts.clone()), ctx, &mut ());
+    };
+
+    frame(&mut fixture);
+    frame(&mut fixture);
+    assert_eq!(layouts.get(), 1);
+
+    fixture
+        .input
+        .bind(TestAction::Open, "Ctrl-O".parse::<Binding>().unwrap());
+    frame(&mut fixture);
+    assert_eq!(layouts.get(), 2);
+}
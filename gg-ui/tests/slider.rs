@@ -0,0 +1,73 @@
+mod common;
+
+use gg_input::{DragEvent, ElementState, Event, KeyboardEvent, MouseButton, VirtualKeyCode};
+use gg_math::{Rect, Vec2};
+use gg_ui::{views, Bounds, Focus, Hover, UpdateCtx, View};
+
+use self::common::Fixture;
+
+fn drag(start: Vec2<f32>, current: Vec2<f32>) -> Event {
+    Event::Drag(DragEvent {
+        button: MouseButton::Left,
+        start,
+        current,
+        delta: current - start,
+    })
+}
+
+fn key(code: VirtualKeyCode) -> Event {
+    Event::Keyboard(KeyboardEvent {
+        state: ElementState::Pressed,
+        code,
+    })
+}
+
+#[test]
+fn slider_drag() {
+    let fixture = Fixture::new();
+
+    let mut reported = Vec::new();
+    let mut view = views::slider(5.0, 2.0..=8.0, |reported: &mut Vec<f32>, v| {
+        reported.push(v);
+    })
+    .step(0.5);
+
+    let mut focus = Focus::new();
+    let mut ctx = UpdateCtx {
+        assets: &fixture.assets,
+        input: &fixture.input,
+        data: &mut reported,
+        focus: &mut focus,
+        layer: 0,
+        dt: 0.0,
+        redraw_requested: &mut false,
+    };
+
+    let bounds = Bounds {
+        hover: Hover::Direct,
+        ..Bounds::new(Rect::new(Vec2::new(10.0, 10.0), Vec2::new(200.0, 24.0)))
+    };
+
+    view.update(&mut ctx, bounds);
+
+    // drags starting elsewhere are ignored
+    let outside = Vec2::new(300.0, 20.0);
+    assert!(!view.handle(&mut ctx, bounds, drag(outside, Vec2::new(0.0, 20.0))));
+
+    let start = Vec2::new(10.0, 20.0);
+    for x in [100.0, 250.0, 400.0, 50.0, -100.0] {
+        assert!(view.handle(&mut ctx, bounds, drag(start, Vec2::new(x, 20.0))));
+    }
+
+    assert_eq!(ctx.data.len(), 4);
+    assert!(ctx.data[0] > 2.0 && ctx.data[0] < 8.0);
+    assert_eq!(ctx.data[1], 8.0);
+    assert!(ctx.data[2] > 2.0 && ctx.data[2] < 8.0);
+    assert_eq!(ctx.data[3], 2.0);
+
+    // the drag focused the slider, so arrow keys nudge the value
+    view.handle(&mut ctx, bounds, key(VirtualKeyCode::Right));
+    view.handle(&mut ctx, bounds, key(VirtualKeyCode::Left));
+    view.handle(&mut ctx, bounds, key(VirtualKeyCode::Left));
+    assert_eq!(&reported[4..], [2.5, 2.0]);
+}
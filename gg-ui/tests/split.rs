@@ -0,0 +1,58 @@
+mod common;
+
+use gg_math::Vec2;
+use gg_ui::views::stack::Orientation;
+use gg_ui::{views, View, ViewExt};
+
+use self::common::Fixture;
+
+fn layout(view: &mut impl View<()>, size: Vec2<f32>) {
+    let mut fixture = Fixture::new();
+    let mut ctx = fixture.layout_ctx();
+
+    view.pre_layout(&mut ctx);
+    view.layout(&mut ctx, size);
+}
+
+#[test]
+fn drag_handle() {
+    let size = Vec2::new(406.0, 100.0);
+    let mut view = views::split(
+        Orientation::Horizontal,
+        views::nothing::<()>(),
+        views::nothing::<()>(),
+        0.5,
+        |_: &mut (), _| {},
+    );
+
+    layout(&mut view, size);
+    assert_eq!(view.left_size(), Vec2::new(200.0, 100.0));
+
+    view.drag_by(50.0);
+    layout(&mut view, size);
+    assert_eq!(view.left_size(), Vec2::new(250.0, 100.0));
+    assert_eq!(view.right_size(), Vec2::new(150.0, 100.0));
+    assert_eq!(view.ratio(), 250.0 / 400.0);
+}
+
+#[test]
+fn drag_handle_clamped() {
+    let size = Vec2::new(406.0, 100.0);
+    let mut view = views::split(
+        Orientation::Horizontal,
+        views::nothing::<()>().max_width(230.0),
+        views::nothing::<()>().min_width(100.0),
+        0.5,
+        |_: &mut (), _| {},
+    );
+
+    layout(&mut view, size);
+    view.drag_by(50.0);
+    layout(&mut view, size);
+    assert_eq!(view.left_size().x, 230.0);
+
+    view.drag_by(-1000.0);
+    layout(&mut view, size);
+    assert_eq!(view.left_size().x, 0.0);
+    assert_eq!(view.right_size().x, 400.0);
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use gg_math::Vec2;
+use gg_ui::views::{self, ColumnWidth};
+use gg_ui::{View, ViewExt};
+
+use self::common::Fixture;
+
+#[test]
+fn table_columns_align() {
+    let mut fixture = Fixture::with_fonts();
+    let mut ctx = fixture.layout_ctx();
+
+    let columns = vec![
+        views::column::<()>("Name", ColumnWidth::Fixed(120.0)),
+        views::column::<()>("Value", ColumnWidth::Weight(1.0)).on_sort(|_| {}),
+    ];
+    let rows = [(40.0, 20.0), (80.0, 30.0), (60.0, 25.0)];
+
+    let mut table = views::table(columns, rows, |&(width, height), _| {
+        views::nothing().min_width(width).min_height(height)
+    });
+
+    table.pre_layout(&mut ctx);
+    let size = table.layout(&mut ctx, Vec2::new(400.0, 300.0));
+
+    assert_eq!(table.num_rows(), 3);
+    assert_eq!(table.num_columns(), 2);
+
+    for col in 0..2 {
+        let x = table.header_rect(col).min.x;
+        for row in 0..3 {
+            assert_eq!(table.cell_rect(row, col).min.x, x);
+        }
+    }
+
+    assert_eq!(table.header_rect(0).width(), 120.0);
+    assert_eq!(table.header_rect(1).min.x, 120.0);
+    assert_eq!(table.header_rect(1).max.x, 400.0);
+
+    assert!(table.header_rect(0).max.y > 0.0);
+    assert!(table.header_rect(0).max.y <= table.cell_rect(0, 0).min.y);
+    assert_eq!(table.cell_rect(1, 0).height(), 30.0);
+    assert_eq!(size.y, table.cell_rect(2, 1).max.y);
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use gg_input::{ActionEvent, ElementState, Event, KeyboardEvent, TextEvent, VirtualKeyCode};
+use gg_math::{Rect, Vec2};
+use gg_ui::{views, Bounds, Focus, Hover, UiAction, UpdateCtx, View};
+
+use self::common::Fixture;
+
+fn key(code: VirtualKeyCode) -> Event {
+    Event::Keyboard(KeyboardEvent {
+        state: ElementState::Pressed,
+        code,
+    })
+}
+
+fn text(s: &str) -> Vec<Event> {
+    s.chars().map(|ch| Event::Text(TextEvent { ch })).collect()
+}
+
+fn touch() -> Event {
+    Event::Action(ActionEvent {
+        state: ElementState::Pressed,
+        action: UiAction::Touch.into(),
+    })
+}
+
+struct Harness {
+    fixture: Fixture,
+    focus: Focus,
+    bounds: Bounds,
+}
+
+impl Harness {
+    fn send(&mut self, view: &mut impl View<String>, model: &mut String, events: Vec<Event>) {
+        let mut ctx = UpdateCtx {
+            assets: &self.fixture.assets,
+            input: &self.fixture.input,
+            data: model,
+            focus: &mut self.focus,
+            layer: 0,
+            dt: 0.0,
+            redraw_requested: &mut false,
+        };
+
+        ctx.focus.begin_frame();
+        view.update(&mut ctx, self.bounds);
+        ctx.focus.end_registration();
+
+        for event in events {
+            view.handle(&mut ctx, self.bounds, event);
+        }
+    }
+}
+
+#[test]
+fn text_input_edits() {
+    let mut harness = Harness {
+        fixture: Fixture::new(),
+        focus: Focus::new(),
+        bounds: Bounds {
+            hover: Hover::Direct,
+            ..Bounds::new(Rect::new(Vec2::zero(), Vec2::new(200.0, 40.0)))
+        },
+    };
+
+    let mut model = String::from("hi");
+    let mut view = views::text_input(model.clone(), |model: &mut String, text: &str| {
+        *model = text.to_owned();
+    });
+
+    // keys are ignored until the field is focused
+    harness.send(&mut view, &mut model, text("!"));
+    assert_eq!(model, "hi");
+
+    harness.send(&mut view, &mut model, vec![touch()]);
+    harness.send(&mut view, &mut model, text(" there"));
+    assert_eq!(model, "hi there");
+
+    harness.send(&mut view, &mut model, vec![key(VirtualKeyCode::Back); 3]);
+    assert_eq!(model, "hi th");
+
+    let mut events = vec![key(VirtualKeyCode::Home), key(VirtualKeyCode::Right)];
+    events.extend(text("é"));
+    harness.send(&mut view, &mut model, events);
+    assert_eq!(model, "héi th");
+
+    let events = vec![key(VirtualKeyCode::Left), key(VirtualKeyCode::Delete)];
+    harness.send(&mut view, &mut model, events);
+    assert_eq!(model, "hi th");
+
+    harness.send(&mut view, &mut model, vec![key(VirtualKeyCode::End)]);
+    harness.send(&mut view, &mut model, vec![key(VirtualKeyCode::Delete)]);
+    assert_eq!(model, "hi th");
+
+    // control characters come with their own key events and aren't inserted
+    harness.send(&mut view, &mut model, text("\u{8}\r"));
+    assert_eq!(model, "hi th");
+
+    // clicking elsewhere removes the focus
+    harness.bounds.hover = Hover::None;
+    harness.send(&mut view, &mut model, vec![touch()]);
+    harness.send(&mut view, &mut model, text("!"));
+    assert_eq!(model, "hi th");
+}